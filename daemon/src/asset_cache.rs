@@ -0,0 +1,204 @@
+//! Local asset cache
+//!
+//! Optional background task that downloads the icons referenced by the live
+//! game and finished matches, so match cards and overlays render offline and
+//! without per-image latency. The cache mirrors the CDN path layout: a URL
+//! `{DDRAGON_BASE}/{path}` is stored at `{cache_dir}/{path}`.
+
+use serde_json::Value;
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::{mpsc, Mutex};
+use tracing::{debug, info, warn};
+
+use crate::{AssetCacheSettings, Result, TaskSupervisor, DDRAGON_BASE};
+
+/// Supervisor task name for the download loop
+const ASSET_CACHE_TASK: &str = "asset_cache";
+
+/// Max URL batches queued for download
+const MAX_QUEUED_BATCHES: usize = 32;
+
+/// Default cache directory name under the OS temp dir
+const DEFAULT_CACHE_DIR: &str = "pack-league-assets";
+
+/// Handle to the background asset downloader.
+///
+/// Dropping the handle closes the queue and lets the task finish.
+pub struct AssetCache {
+    dir: PathBuf,
+    tx: mpsc::Sender<Vec<String>>,
+}
+
+impl AssetCache {
+    /// Start the download task under `supervisor`. Must be called from
+    /// within a tokio runtime.
+    pub fn start(settings: &AssetCacheSettings, supervisor: &TaskSupervisor) -> Self {
+        let dir = settings
+            .cache_dir
+            .as_ref()
+            .map(PathBuf::from)
+            .unwrap_or_else(default_cache_dir);
+
+        let (tx, rx) = mpsc::channel(MAX_QUEUED_BATCHES);
+        let rx = Arc::new(Mutex::new(rx));
+
+        info!("Asset cache enabled at {:?}", dir);
+
+        let task_dir = dir.clone();
+        supervisor.spawn(ASSET_CACHE_TASK, move || {
+            Self::run(task_dir.clone(), Arc::clone(&rx))
+        });
+
+        Self { dir, tx }
+    }
+
+    /// Cache directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Local path for a CDN URL, if it can be cached
+    pub fn local_path(&self, url: &str) -> Option<PathBuf> {
+        local_path(&self.dir, url)
+    }
+
+    /// Queue every asset URL found in `assets` for download.
+    ///
+    /// Never blocks; if the queue is full the batch is dropped and will be
+    /// picked up again the next time the same assets are referenced.
+    pub fn prefetch(&self, assets: &Value) {
+        let urls: Vec<String> = collect_urls(assets)
+            .into_iter()
+            .filter(|url| self.local_path(url).is_some_and(|p| !p.exists()))
+            .collect();
+
+        if urls.is_empty() {
+            return;
+        }
+
+        if self.tx.try_send(urls).is_err() {
+            debug!("Asset cache queue full, skipping batch");
+        }
+    }
+
+    /// Download loop, restarted by the supervisor if it panics
+    async fn run(dir: PathBuf, rx: Arc<Mutex<mpsc::Receiver<Vec<String>>>>) {
+        let client = reqwest::Client::new();
+        let mut failed: HashSet<String> = HashSet::new();
+        let mut rx = rx.lock().await;
+
+        while let Some(urls) = rx.recv().await {
+            for url in urls {
+                let Some(path) = local_path(&dir, &url) else {
+                    continue;
+                };
+                if path.exists() || failed.contains(&url) {
+                    continue;
+                }
+
+                match download(&client, &url, &path).await {
+                    Ok(()) => debug!("Cached {}", url),
+                    Err(e) => {
+                        warn!("Failed to cache {}: {}", url, e);
+                        // Don't retry missing assets for the rest of the run
+                        failed.insert(url);
+                    }
+                }
+            }
+        }
+
+        info!("Asset cache stopped");
+    }
+}
+
+/// Cache directory used when none is configured
+pub fn default_cache_dir() -> PathBuf {
+    std::env::temp_dir().join(DEFAULT_CACHE_DIR)
+}
+
+/// Download `url` to `path`, writing to a temp file first so a partial
+/// download is never mistaken for a cached asset.
+pub(crate) async fn download(client: &reqwest::Client, url: &str, path: &Path) -> Result<()> {
+    let bytes = client
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?;
+
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+
+    let tmp = path.with_extension("part");
+    tokio::fs::write(&tmp, &bytes).await?;
+    tokio::fs::rename(&tmp, path).await?;
+    Ok(())
+}
+
+/// Map a CDN URL to its location under `dir`
+fn local_path(dir: &Path, url: &str) -> Option<PathBuf> {
+    let relative = Path::new(url.strip_prefix(DDRAGON_BASE)?.trim_start_matches('/'));
+
+    // Only plain path segments - never escape the cache dir
+    if relative.as_os_str().is_empty()
+        || !relative.components().all(|c| matches!(c, Component::Normal(_)))
+    {
+        return None;
+    }
+
+    Some(dir.join(relative))
+}
+
+/// Collect every URL string in an asset JSON tree
+fn collect_urls(value: &Value) -> Vec<String> {
+    let mut urls = Vec::new();
+    let mut stack = vec![value];
+
+    while let Some(value) = stack.pop() {
+        match value {
+            Value::String(s) if s.starts_with("https://") => urls.push(s.clone()),
+            Value::Array(items) => stack.extend(items),
+            Value::Object(map) => stack.extend(map.values()),
+            _ => {}
+        }
+    }
+
+    urls.sort();
+    urls.dedup();
+    urls
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_local_path_mirrors_cdn_layout() {
+        let dir = Path::new("/cache");
+        assert_eq!(
+            local_path(dir, &format!("{}/14.24.1/img/item/3031.png", DDRAGON_BASE)),
+            Some(PathBuf::from("/cache/14.24.1/img/item/3031.png"))
+        );
+        assert_eq!(local_path(dir, "https://example.com/x.png"), None);
+        assert_eq!(local_path(dir, &format!("{}/../etc/passwd", DDRAGON_BASE)), None);
+    }
+
+    #[test]
+    fn test_collect_urls_dedups_nested() {
+        let assets = json!({
+            "version": "14.24.1",
+            "champion": "https://a/champion.png",
+            "items": ["https://a/1.png", null, "https://a/1.png"],
+            "participants": [{ "summonerName": "x", "champion": "https://a/champion.png" }],
+        });
+        assert_eq!(
+            collect_urls(&assets),
+            vec!["https://a/1.png".to_string(), "https://a/champion.png".to_string()]
+        );
+    }
+}
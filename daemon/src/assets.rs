@@ -0,0 +1,387 @@
+//! Asset URL builder
+//!
+//! Turns stored champion/item/spell/rune names and IDs into Data Dragon image
+//! URLs for the current patch, so frontends don't each carry their own CDN
+//! path logic.
+
+use serde_json::{json, Value};
+
+use crate::protocol::{IntroChampion, IntroLoadout, MatchIntro};
+use crate::{riot_ids_match, CreateMatch, LiveMatch, LivePlayer};
+
+/// Data Dragon CDN base
+pub const DDRAGON_BASE: &str = "https://ddragon.leagueoflegends.com/cdn";
+
+/// Version used until the client's patch is known
+pub const FALLBACK_DDRAGON_VERSION: &str = "14.24.1";
+
+/// Champion display names whose Data Dragon key can't be derived by stripping punctuation
+const CHAMPION_KEY_OVERRIDES: &[(&str, &str)] = &[
+    ("Wukong", "MonkeyKing"),
+    ("Nunu & Willump", "Nunu"),
+    ("Renata Glasc", "Renata"),
+    ("LeBlanc", "Leblanc"),
+    ("Kai'Sa", "Kaisa"),
+    ("Kha'Zix", "Khazix"),
+    ("Cho'Gath", "Chogath"),
+    ("ChoGath", "Chogath"),
+    ("Vel'Koz", "Velkoz"),
+    ("VelKoz", "Velkoz"),
+    ("Bel'Veth", "Belveth"),
+    ("BelVeth", "Belveth"),
+    ("K'Sante", "KSante"),
+    ("Jarvan", "JarvanIV"),
+];
+
+/// Summoner spell display names whose Data Dragon ID isn't `Summoner{Name}`
+const SPELL_ID_OVERRIDES: &[(&str, &str)] = &[
+    ("Cleanse", "SummonerBoost"),
+    ("Ghost", "SummonerHaste"),
+    ("Ignite", "SummonerDot"),
+    ("Clarity", "SummonerMana"),
+    ("Mark", "SummonerSnowball"),
+    ("Snowball", "SummonerSnowball"),
+];
+
+/// Keystones: (perk ID, display name, icon path under `cdn/img/`)
+const KEYSTONES: &[(i32, &str, &str)] = &[
+    (8005, "Press the Attack", "perk-images/Styles/Precision/PressTheAttack/PressTheAttack.png"),
+    (8008, "Lethal Tempo", "perk-images/Styles/Precision/LethalTempo/LethalTempoTemp.png"),
+    (8021, "Fleet Footwork", "perk-images/Styles/Precision/FleetFootwork/FleetFootwork.png"),
+    (8010, "Conqueror", "perk-images/Styles/Precision/Conqueror/Conqueror.png"),
+    (8112, "Electrocute", "perk-images/Styles/Domination/Electrocute/Electrocute.png"),
+    (8124, "Predator", "perk-images/Styles/Domination/Predator/Predator.png"),
+    (8128, "Dark Harvest", "perk-images/Styles/Domination/DarkHarvest/DarkHarvest.png"),
+    (9923, "Hail of Blades", "perk-images/Styles/Domination/HailOfBlades/HailOfBlades.png"),
+    (8214, "Summon Aery", "perk-images/Styles/Sorcery/SummonAery/SummonAery.png"),
+    (8229, "Arcane Comet", "perk-images/Styles/Sorcery/ArcaneComet/ArcaneComet.png"),
+    (8230, "Phase Rush", "perk-images/Styles/Sorcery/PhaseRush/PhaseRush.png"),
+    (8437, "Grasp of the Undying", "perk-images/Styles/Resolve/GraspOfTheUndying/GraspOfTheUndying.png"),
+    (8439, "Aftershock", "perk-images/Styles/Resolve/VeteranAftershock/VeteranAftershock.png"),
+    (8465, "Guardian", "perk-images/Styles/Resolve/Guardian/Guardian.png"),
+    (8351, "Glacial Augment", "perk-images/Styles/Inspiration/GlacialAugment/GlacialAugment.png"),
+    (8360, "Unsealed Spellbook", "perk-images/Styles/Inspiration/UnsealedSpellbook/UnsealedSpellbook.png"),
+    (8369, "First Strike", "perk-images/Styles/Inspiration/FirstStrike/FirstStrike.png"),
+];
+
+/// Rune trees: (style ID, display name, icon path under `cdn/img/`)
+const RUNE_TREES: &[(i32, &str, &str)] = &[
+    (8000, "Precision", "perk-images/Styles/7201_Precision.png"),
+    (8100, "Domination", "perk-images/Styles/7200_Domination.png"),
+    (8200, "Sorcery", "perk-images/Styles/7202_Sorcery.png"),
+    (8300, "Inspiration", "perk-images/Styles/7203_Whimsy.png"),
+    (8400, "Resolve", "perk-images/Styles/7204_Resolve.png"),
+];
+
+/// Normalize a name for loose matching ("DarkHarvest" == "Dark Harvest")
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Look up a rune entry by ID or (loosely matched) name
+fn find_rune<'a>(table: &'a [(i32, &str, &str)], name_or_id: &str) -> Option<&'a (i32, &'a str, &'a str)> {
+    if let Ok(id) = name_or_id.parse::<i32>() {
+        return table.iter().find(|(rid, _, _)| *rid == id);
+    }
+    let wanted = normalize(name_or_id);
+    table.iter().find(|(_, name, _)| normalize(name) == wanted)
+}
+
+/// Convert a client game version ("14.24.636.1234") to a Data Dragon version ("14.24.1")
+pub fn ddragon_version_from_game_version(game_version: &str) -> Option<String> {
+    let mut parts = game_version.split('.');
+    let major: u32 = parts.next()?.trim().parse().ok()?;
+    let minor: u32 = parts.next()?.trim().parse().ok()?;
+    Some(format!("{}.{}.1", major, minor))
+}
+
+/// Data Dragon champion key for a champion name
+pub fn champion_key(name: &str) -> String {
+    if let Some((_, key)) = CHAMPION_KEY_OVERRIDES.iter().find(|(n, _)| *n == name) {
+        return key.to_string();
+    }
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect()
+}
+
+/// Data Dragon spell ID for a summoner spell name ("Flash" -> "SummonerFlash")
+pub fn spell_id(name: &str) -> String {
+    if let Some((_, id)) = SPELL_ID_OVERRIDES.iter().find(|(n, _)| *n == name) {
+        return id.to_string();
+    }
+    if name.starts_with("Summoner") {
+        name.to_string()
+    } else {
+        format!("Summoner{}", name)
+    }
+}
+
+/// Builds CDN image URLs for a specific Data Dragon version
+#[derive(Debug, Clone)]
+pub struct AssetUrls {
+    version: String,
+}
+
+impl AssetUrls {
+    pub fn new(version: impl Into<String>) -> Self {
+        Self {
+            version: version.into(),
+        }
+    }
+
+    /// Build from the client's game version, falling back to the default patch
+    pub fn from_game_version(game_version: &str) -> Self {
+        Self::new(
+            ddragon_version_from_game_version(game_version)
+                .unwrap_or_else(|| FALLBACK_DDRAGON_VERSION.to_string()),
+        )
+    }
+
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    pub fn champion_icon(&self, champion: &str) -> Option<String> {
+        if champion.is_empty() {
+            return None;
+        }
+        Some(format!(
+            "{}/{}/img/champion/{}.png",
+            DDRAGON_BASE,
+            self.version,
+            champion_key(champion)
+        ))
+    }
+
+    pub fn item_icon(&self, item_id: i32) -> Option<String> {
+        if item_id <= 0 {
+            return None;
+        }
+        Some(format!("{}/{}/img/item/{}.png", DDRAGON_BASE, self.version, item_id))
+    }
+
+    pub fn spell_icon(&self, spell: &str) -> Option<String> {
+        if spell.is_empty() {
+            return None;
+        }
+        Some(format!(
+            "{}/{}/img/spell/{}.png",
+            DDRAGON_BASE,
+            self.version,
+            spell_id(spell)
+        ))
+    }
+
+    /// Keystone icon by perk ID or name (rune images aren't versioned)
+    pub fn keystone_icon(&self, keystone: &str) -> Option<String> {
+        find_rune(KEYSTONES, keystone).map(|(_, _, path)| format!("{}/img/{}", DDRAGON_BASE, path))
+    }
+
+    /// Rune tree icon by style ID or name
+    pub fn rune_tree_icon(&self, tree: &str) -> Option<String> {
+        find_rune(RUNE_TREES, tree).map(|(_, _, path)| format!("{}/img/{}", DDRAGON_BASE, path))
+    }
+
+    /// Asset URLs for a live match (player and participants)
+    pub fn for_live_match(&self, live: &LiveMatch) -> Value {
+        let participants: Vec<Value> = live
+            .participants
+            .iter()
+            .map(|p| {
+                json!({
+                    "summonerName": p.summoner_name,
+                    "champion": self.champion_icon(&p.champion),
+                })
+            })
+            .collect();
+
+        json!({
+            "version": self.version,
+            "champion": self.champion_icon(&live.champion),
+            "items": live.items.iter().map(|i| self.item_icon(i.item_id)).collect::<Vec<_>>(),
+            "trinket": live.trinket.as_ref().and_then(|t| self.item_icon(t.item_id)),
+            "spell1": live.spell1.as_ref().and_then(|s| self.spell_icon(&s.name)),
+            "spell2": live.spell2.as_ref().and_then(|s| self.spell_icon(&s.name)),
+            "keystone": live.runes.as_ref().and_then(|r| self.keystone_icon(&r.keystone_id.to_string())),
+            "secondaryTree": live.runes.as_ref().and_then(|r| self.rune_tree_icon(&r.secondary_tree_id.to_string())),
+            "participants": participants,
+        })
+    }
+
+    /// Matchup card for the start of a live game
+    pub fn match_intro(
+        &self,
+        live: &LiveMatch,
+        queue_name: Option<String>,
+        rank: Option<String>,
+    ) -> MatchIntro {
+        let intro_champion = |p: &LivePlayer| IntroChampion {
+            summoner_name: p.summoner_name.clone(),
+            champion: p.champion.clone(),
+            champion_icon: self.champion_icon(&p.champion),
+            role: p.position.to_lowercase(),
+        };
+
+        let local = live
+            .participants
+            .iter()
+            .find(|p| riot_ids_match(&p.summoner_name, &live.summoner_name))
+            .or_else(|| {
+                live.participants
+                    .iter()
+                    .find(|p| p.team == live.team && p.champion == live.champion)
+            });
+        let player = IntroChampion {
+            summoner_name: live.summoner_name.clone(),
+            champion: live.champion.clone(),
+            champion_icon: self.champion_icon(&live.champion),
+            role: local.map(|p| p.position.to_lowercase()).unwrap_or_default(),
+        };
+
+        let runes = live.runes.as_ref();
+        let loadout = IntroLoadout {
+            spell1: live.spell1.as_ref().map(|s| s.name.clone()),
+            spell1_icon: live.spell1.as_ref().and_then(|s| self.spell_icon(&s.name)),
+            spell2: live.spell2.as_ref().map(|s| s.name.clone()),
+            spell2_icon: live.spell2.as_ref().and_then(|s| self.spell_icon(&s.name)),
+            keystone: runes.map(|r| r.keystone_name.clone()),
+            keystone_icon: runes.and_then(|r| self.keystone_icon(&r.keystone_id.to_string())),
+            secondary_tree: runes.map(|r| r.secondary_tree_name.clone()),
+            secondary_tree_icon: runes
+                .and_then(|r| self.rune_tree_icon(&r.secondary_tree_id.to_string())),
+        };
+
+        let (allies, enemies): (Vec<&LivePlayer>, Vec<&LivePlayer>) =
+            live.participants.iter().partition(|p| p.team == live.team);
+
+        MatchIntro {
+            queue_name,
+            rank,
+            player,
+            loadout,
+            allies: allies.into_iter().map(intro_champion).collect(),
+            enemies: enemies.into_iter().map(intro_champion).collect(),
+        }
+    }
+
+    /// Asset URLs for a finalized match
+    pub fn for_match(&self, data: &CreateMatch) -> Value {
+        // Older records only have the IDs as item names
+        let item_icon = |item: &String| item.parse::<i32>().ok().and_then(|id| self.item_icon(id));
+        let items: Vec<Option<String>> = if data.item_ids.is_empty() {
+            data.items.iter().map(item_icon).collect()
+        } else {
+            data.item_ids.iter().map(|&id| self.item_icon(id)).collect()
+        };
+        let trinket = match data.trinket_id {
+            Some(id) => self.item_icon(id),
+            None => data.trinket.as_ref().and_then(item_icon),
+        };
+
+        let participants: Vec<Value> = data
+            .participants
+            .iter()
+            .map(|p| {
+                json!({
+                    "summonerName": p.summoner_name,
+                    "champion": self.champion_icon(&p.champion),
+                })
+            })
+            .collect();
+
+        json!({
+            "version": self.version,
+            "champion": self.champion_icon(&data.champion),
+            "items": items,
+            "trinket": trinket,
+            "spell1": self.spell_icon(&data.summoner_spell1),
+            "spell2": self.spell_icon(&data.summoner_spell2),
+            "keystone": self.keystone_icon(&data.keystone_rune),
+            "secondaryTree": self.rune_tree_icon(&data.secondary_tree),
+            "participants": participants,
+        })
+    }
+}
+
+impl Default for AssetUrls {
+    fn default() -> Self {
+        Self::new(FALLBACK_DDRAGON_VERSION)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ddragon_version() {
+        assert_eq!(
+            ddragon_version_from_game_version("14.24.636.1234"),
+            Some("14.24.1".to_string())
+        );
+        assert_eq!(ddragon_version_from_game_version("garbage"), None);
+    }
+
+    #[test]
+    fn test_champion_and_spell_urls() {
+        let urls = AssetUrls::new("14.24.1");
+        assert_eq!(
+            urls.champion_icon("Kai'Sa").unwrap(),
+            "https://ddragon.leagueoflegends.com/cdn/14.24.1/img/champion/Kaisa.png"
+        );
+        assert!(urls.champion_icon("Lee Sin").unwrap().ends_with("/LeeSin.png"));
+        assert!(urls.champion_icon("Wukong").unwrap().ends_with("/MonkeyKing.png"));
+        assert!(urls.spell_icon("Ignite").unwrap().ends_with("/SummonerDot.png"));
+        assert!(urls.spell_icon("Flash").unwrap().ends_with("/SummonerFlash.png"));
+        assert_eq!(urls.item_icon(0), None);
+    }
+
+    #[test]
+    fn test_rune_lookup_by_id_and_name() {
+        let urls = AssetUrls::default();
+        assert_eq!(urls.keystone_icon("8112"), urls.keystone_icon("Electrocute"));
+        assert_eq!(urls.keystone_icon("DarkHarvest"), urls.keystone_icon("Dark Harvest"));
+        assert!(urls.rune_tree_icon("Inspiration").unwrap().ends_with("7203_Whimsy.png"));
+        assert_eq!(urls.keystone_icon("Not A Rune"), None);
+    }
+
+    #[test]
+    fn test_match_intro_splits_teams() {
+        let player = |name: &str, champion: &str, team: &str, position: &str| {
+            json!({
+                "summonerName": name, "champion": champion, "team": team,
+                "kills": 0, "deaths": 0, "assists": 0, "cs": 0, "level": 1,
+                "isDead": false, "position": position,
+            })
+        };
+        let live: LiveMatch = serde_json::from_value(json!({
+            "summonerName": "Me", "champion": "Ahri", "level": 1,
+            "kills": 0, "deaths": 0, "assists": 0, "cs": 0, "currentGold": 500.0,
+            "gameTimeSecs": 0.0, "gameMode": "CLASSIC", "team": "blue",
+            "items": [], "trinket": null,
+            "spell1": {"name": "Flash"}, "spell2": {"name": "Ignite"}, "runes": null,
+            "participants": [
+                player("Me", "Ahri", "blue", "MIDDLE"),
+                player("Ally", "Lee Sin", "blue", "JUNGLE"),
+                player("Enemy", "Zed", "red", "MIDDLE"),
+            ],
+            "isDead": false,
+        }))
+        .unwrap();
+
+        let intro = AssetUrls::default().match_intro(
+            &live,
+            Some("Ranked Solo/Duo".to_string()),
+            Some("GOLD II".to_string()),
+        );
+        assert_eq!(intro.player.role, "middle");
+        assert_eq!(intro.allies.len(), 2);
+        assert_eq!(intro.enemies[0].champion, "Zed");
+        assert!(intro.loadout.spell2_icon.unwrap().ends_with("/SummonerDot.png"));
+        assert_eq!(intro.loadout.keystone, None);
+    }
+}
@@ -0,0 +1,129 @@
+//! Bounded in-memory buffers
+//!
+//! Long sessions can produce a lot of events and snapshots. These buffers cap
+//! memory use by dropping the oldest entries once full, and count how many
+//! entries were dropped so the loss is visible in metrics.
+
+use std::collections::VecDeque;
+
+pub use pack_league_types::protocol::BufferMetrics;
+
+/// Fixed-capacity FIFO buffer that evicts the oldest entry when full
+#[derive(Debug, Clone)]
+pub struct RingBuffer<T> {
+    items: VecDeque<T>,
+    capacity: usize,
+    dropped: u64,
+}
+
+impl<T> RingBuffer<T> {
+    /// Create a buffer holding at most `capacity` entries (minimum 1)
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            items: VecDeque::with_capacity(capacity.min(64)),
+            capacity,
+            dropped: 0,
+        }
+    }
+
+    /// Push an entry, evicting the oldest one if the buffer is full
+    pub fn push(&mut self, item: T) {
+        if self.items.len() >= self.capacity {
+            self.items.pop_front();
+            self.dropped += 1;
+        }
+        self.items.push_back(item);
+    }
+
+    /// Remove and return all buffered entries, oldest first
+    pub fn drain(&mut self) -> Vec<T> {
+        self.items.drain(..).collect()
+    }
+
+    /// Remove all entries (the drop counter is kept)
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+
+    /// Most recently pushed entry
+    pub fn latest(&self) -> Option<&T> {
+        self.items.back()
+    }
+
+    /// Iterate over buffered entries, oldest first
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.items.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Total number of entries evicted since creation
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Change the capacity, evicting the oldest entries if needed
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        while self.items.len() > self.capacity {
+            self.items.pop_front();
+            self.dropped += 1;
+        }
+    }
+
+    /// Snapshot of this buffer's fill level and drop count
+    pub fn metrics(&self) -> BufferMetrics {
+        BufferMetrics {
+            len: self.items.len(),
+            capacity: self.capacity,
+            dropped: self.dropped,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_buffer_evicts_oldest() {
+        let mut buf = RingBuffer::new(3);
+        for i in 0..5 {
+            buf.push(i);
+        }
+        assert_eq!(buf.len(), 3);
+        assert_eq!(buf.dropped(), 2);
+        assert_eq!(buf.drain(), vec![2, 3, 4]);
+        assert!(buf.is_empty());
+        assert_eq!(buf.dropped(), 2);
+    }
+
+    #[test]
+    fn test_ring_buffer_shrink() {
+        let mut buf = RingBuffer::new(4);
+        for i in 0..4 {
+            buf.push(i);
+        }
+        buf.set_capacity(2);
+        assert_eq!(buf.latest(), Some(&3));
+        assert_eq!(
+            buf.metrics(),
+            BufferMetrics {
+                len: 2,
+                capacity: 2,
+                dropped: 2
+            }
+        );
+    }
+}
@@ -0,0 +1,353 @@
+//! Champ select draft capture
+//!
+//! Follows `/lol-champ-select/v1/session` over the LCU WebSocket and records
+//! bans, picks, the assigned role and lock-in timing. The draft of the last
+//! completed champ select is handed to the finalizer so it ends up in
+//! `CreateMatch`; otherwise only the champion played is known after the game.
+//!
+//! Enemy bans of the champion the player was hovering are flagged in the
+//! draft and queued for the integration to count and announce.
+//!
+//! Updates missed while the socket was down are caught up on from the REST
+//! session once it is connected again.
+
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, info};
+
+use crate::{
+    lcu_connection, uris, BufferMetrics, ChampSelectDraft, ChampSelectSession, ConnectionState,
+    DraftBan, DraftPick, GameflowPhase, LcuEvent, LcuWebSocket, SocketMetrics, TaskSupervisor,
+};
+
+/// Supervisor task name
+const CHAMP_SELECT_TASK: &str = "champ_select";
+
+/// Supervisor task reading the capture's WebSocket
+const CHAMP_SELECT_SOCKET_TASK: &str = "champ_select_socket";
+
+/// Delay between connection attempts while the client isn't running
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Builds a draft from successive champ select session updates
+#[derive(Debug, Default)]
+pub struct ChampSelectRecorder {
+    started: Option<Instant>,
+    draft: ChampSelectDraft,
+    /// Completed action IDs already recorded
+    seen_actions: HashSet<i64>,
+    /// Champion the player last hovered before locking in (0 if none)
+    hovered: i32,
+    /// Hovered champions banned by the enemy, not yet taken
+    hover_bans: Vec<i32>,
+}
+
+impl ChampSelectRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply a session update
+    pub fn on_session(&mut self, session: &ChampSelectSession) {
+        self.on_session_at(session, Instant::now());
+    }
+
+    fn on_session_at(&mut self, session: &ChampSelectSession, now: Instant) {
+        let started = *self.started.get_or_insert(now);
+        let elapsed = now.saturating_duration_since(started).as_secs_f64();
+
+        let role_of = |cell_id: i64| -> String {
+            session
+                .my_team
+                .iter()
+                .chain(&session.their_team)
+                .find(|p| p.cell_id == cell_id)
+                .map(|p| p.assigned_position.clone())
+                .unwrap_or_default()
+        };
+
+        // The hover is usually cleared in the same update that bans it
+        let hovered = self.hovered;
+        if let Some(state) = session.local_state() {
+            if !state.locked && state.champion_id != 0 {
+                self.hovered = state.champion_id;
+            }
+            self.draft.assigned_role = state.role;
+            self.draft.champion_id = state.champion_id;
+        }
+
+        for action in session.actions.iter().flatten() {
+            if !action.completed || action.champion_id == 0 || !self.seen_actions.insert(action.id) {
+                continue;
+            }
+
+            match action.action_type.as_str() {
+                "ban" => {
+                    let hover_banned = !action.is_ally_action
+                        && (action.champion_id == hovered || action.champion_id == self.hovered);
+                    if hover_banned {
+                        self.hover_bans.push(action.champion_id);
+                    }
+                    self.draft.bans.push(DraftBan {
+                        champion_id: action.champion_id,
+                        champion: None,
+                        is_ally: action.is_ally_action,
+                        hover_banned,
+                    });
+                }
+                "pick" => {
+                    if action.actor_cell_id == session.local_player_cell_id {
+                        self.draft.lock_in_secs = Some(elapsed);
+                    }
+                    self.draft.picks.push(DraftPick {
+                        cell_id: action.actor_cell_id,
+                        champion_id: action.champion_id,
+                        champion: None,
+                        is_ally: action.is_ally_action,
+                        role: role_of(action.actor_cell_id),
+                        locked_at_secs: elapsed,
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        self.draft.duration_secs = elapsed;
+    }
+
+    /// Hovered champions banned by the enemy since the last call
+    pub fn take_hover_bans(&mut self) -> Vec<i32> {
+        std::mem::take(&mut self.hover_bans)
+    }
+
+    /// Champ select ended: return the draft and start over
+    pub fn finish(&mut self) -> Option<ChampSelectDraft> {
+        let started = self.started.is_some();
+        let draft = std::mem::take(self).draft;
+        started.then_some(draft)
+    }
+}
+
+#[derive(Debug, Default)]
+struct CaptureState {
+    recorder: ChampSelectRecorder,
+    /// Draft of the most recently finished champ select
+    last_draft: Option<ChampSelectDraft>,
+}
+
+/// Background champ select capture (a supervised WebSocket task)
+#[derive(Clone)]
+pub struct ChampSelectCapture {
+    state: Arc<Mutex<CaptureState>>,
+    /// Backlog of the capture's current (or last) WebSocket
+    socket: Arc<Mutex<Option<SocketMetrics>>>,
+}
+
+impl ChampSelectCapture {
+    /// Start following champ select on the supervisor
+    pub fn start(supervisor: &TaskSupervisor) -> Self {
+        let capture = Self {
+            state: Arc::new(Mutex::new(CaptureState::default())),
+            socket: Arc::new(Mutex::new(None)),
+        };
+
+        capture.spawn(supervisor);
+        capture
+    }
+
+    /// Open a new WebSocket, keeping the captured draft
+    pub fn reconnect(&self, supervisor: &TaskSupervisor) {
+        self.spawn(supervisor);
+    }
+
+    /// Spawning under the same name replaces a running capture task
+    fn spawn(&self, supervisor: &TaskSupervisor) {
+        let task = self.clone();
+        let sockets = supervisor.clone();
+        supervisor.spawn(CHAMP_SELECT_TASK, move || task.clone().run(sockets.clone()));
+    }
+
+    /// Take the draft of the last finished champ select
+    pub fn take_draft(&self) -> Option<ChampSelectDraft> {
+        self.state.lock().expect("Mutex poisoned").last_draft.take()
+    }
+
+    /// Hovered champions banned by the enemy since the last call
+    pub fn take_hover_bans(&self) -> Vec<i32> {
+        self.state.lock().expect("Mutex poisoned").recorder.take_hover_bans()
+    }
+
+    /// Forget the last draft (e.g. after a dodge)
+    pub fn clear(&self) {
+        self.state.lock().expect("Mutex poisoned").last_draft = None;
+    }
+
+    /// Backlog of the capture's WebSocket; `None` before it first connected
+    pub fn backlog_metrics(&self) -> Option<BufferMetrics> {
+        self.socket
+            .lock()
+            .expect("Mutex poisoned")
+            .as_ref()
+            .map(SocketMetrics::metrics)
+    }
+
+    async fn run(self, supervisor: TaskSupervisor) {
+        loop {
+            match LcuWebSocket::connect(&supervisor, CHAMP_SELECT_SOCKET_TASK).await {
+                Ok(mut ws) => {
+                    info!("Champ select capture connected");
+                    *self.socket.lock().expect("Mutex poisoned") = Some(ws.metrics_handle());
+                    self.refresh_session().await;
+                    self.follow(&mut ws).await;
+                    debug!("Champ select capture disconnected");
+                }
+                Err(e) => debug!("Champ select capture waiting for client: {}", e),
+            }
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    }
+
+    /// Handle events until the socket closes, catching up after it reconnects
+    async fn follow(&self, ws: &mut LcuWebSocket) {
+        let mut socket_state = ws.state_changes();
+        loop {
+            tokio::select! {
+                event = ws.recv() => match event {
+                    Some(event) => self.handle_event(&event),
+                    None => return,
+                },
+                changed = socket_state.changed() => {
+                    if changed.is_err() {
+                        return;
+                    }
+                    if *socket_state.borrow_and_update() == ConnectionState::Connected {
+                        self.refresh_session().await;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Catch up on updates missed while disconnected: record the current
+    /// session, or finish the draft if champ select ended in the meantime
+    async fn refresh_session(&self) {
+        let Some(client) = lcu_connection().client() else {
+            return;
+        };
+        match client.get_gameflow_phase().await {
+            Ok(GameflowPhase::ChampSelect) => {
+                if let Ok(session) = client.get_champ_select_session().await {
+                    self.state.lock().expect("Mutex poisoned").recorder.on_session(&session);
+                }
+            }
+            Ok(_) => self.finish_draft(),
+            Err(e) => debug!("Champ select capture could not refresh: {}", e),
+        }
+    }
+
+    fn handle_event(&self, event: &LcuEvent) {
+        if event.uri != uris::CHAMP_SELECT_SESSION {
+            return;
+        }
+
+        if event.event_type == "Delete" {
+            self.finish_draft();
+        } else if let Ok(session) = serde_json::from_value::<ChampSelectSession>(event.data.clone()) {
+            self.state.lock().expect("Mutex poisoned").recorder.on_session(&session);
+        }
+    }
+
+    /// Keep the draft of a champ select that just ended
+    fn finish_draft(&self) {
+        let mut state = self.state.lock().expect("Mutex poisoned");
+        if let Some(draft) = state.recorder.finish() {
+            info!(
+                "Champ select finished: {} picks, {} bans, role {:?}",
+                draft.picks.len(),
+                draft.bans.len(),
+                draft.assigned_role
+            );
+            state.last_draft = Some(draft);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(actions: serde_json::Value) -> ChampSelectSession {
+        serde_json::from_value(serde_json::json!({
+            "localPlayerCellId": 2,
+            "myTeam": [{"cellId": 2, "championId": 103, "assignedPosition": "middle"}],
+            "theirTeam": [{"cellId": 7, "championId": 238, "assignedPosition": ""}],
+            "actions": actions,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_recorder_tracks_bans_picks_and_lock_in() {
+        let mut recorder = ChampSelectRecorder::new();
+        let start = Instant::now();
+
+        recorder.on_session_at(
+            &session(serde_json::json!([[
+                {"id": 1, "actorCellId": 2, "championId": 55, "completed": true, "isAllyAction": true, "type": "ban"},
+                {"id": 2, "actorCellId": 7, "championId": 157, "completed": true, "isAllyAction": false, "type": "ban"}
+            ]])),
+            start,
+        );
+        let picks = serde_json::json!([
+            [{"id": 1, "actorCellId": 2, "championId": 55, "completed": true, "isAllyAction": true, "type": "ban"}],
+            [{"id": 3, "actorCellId": 7, "championId": 238, "completed": true, "isAllyAction": false, "type": "pick"}],
+            [{"id": 4, "actorCellId": 2, "championId": 103, "completed": true, "isAllyAction": true, "type": "pick"}]
+        ]);
+        recorder.on_session_at(&session(picks), start + Duration::from_secs(45));
+
+        let mut draft = recorder.finish().unwrap();
+        assert_eq!(draft.bans.len(), 2);
+        assert_eq!(draft.picks.len(), 2);
+        assert_eq!(draft.picks[1].role, "middle");
+        assert_eq!(draft.team_bans(false).map(|b| b.champion_id).collect::<Vec<_>>(), vec![157]);
+        assert_eq!(draft.team_picks(false).next().unwrap().champion_id, 238);
+
+        draft.name_champions(|id| (id == 238).then(|| "Zed".to_string()));
+        assert_eq!(draft.picks[0].champion.as_deref(), Some("Zed"));
+        assert_eq!(draft.picks[1].champion, None);
+        assert_eq!(draft.assigned_role, "middle");
+        assert_eq!(draft.champion_id, 103);
+        assert_eq!(draft.lock_in_secs, Some(45.0));
+
+        // Nothing recorded since
+        assert!(recorder.finish().is_none());
+    }
+
+    #[test]
+    fn test_enemy_ban_of_hovered_champion() {
+        let mut recorder = ChampSelectRecorder::new();
+        let with_hover = |hover: i32, actions: serde_json::Value| -> ChampSelectSession {
+            serde_json::from_value(serde_json::json!({
+                "localPlayerCellId": 2,
+                "myTeam": [{"cellId": 2, "championPickIntent": hover, "assignedPosition": "middle"}],
+                "actions": actions,
+            }))
+            .unwrap()
+        };
+
+        recorder.on_session(&with_hover(103, serde_json::json!([])));
+        // Banned by the enemy; the client clears the hover in the same update
+        recorder.on_session(&with_hover(0, serde_json::json!([[
+            {"id": 1, "actorCellId": 2, "championId": 55, "completed": true, "isAllyAction": true, "type": "ban"},
+            {"id": 2, "actorCellId": 7, "championId": 103, "completed": true, "isAllyAction": false, "type": "ban"}
+        ]])));
+
+        assert_eq!(recorder.take_hover_bans(), vec![103]);
+        assert!(recorder.take_hover_bans().is_empty());
+
+        let draft = recorder.finish().unwrap();
+        assert!(!draft.bans[0].hover_banned);
+        assert!(draft.bans[1].hover_banned);
+    }
+}
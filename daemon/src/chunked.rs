@@ -0,0 +1,146 @@
+//! Chunked responses for large results
+//!
+//! Some read commands (match events, champion games, arena matches) can
+//! return more data than the host wants on a single stdout line. Results that
+//! exceed `MAX_CHUNK_BYTES` are streamed as `ResponseChunk` notifications,
+//! each carrying a sequence number and a final flag, and the command itself
+//! answers with a `ChunkedResponse` header naming the stream. Small results
+//! are returned inline as before.
+//!
+//! Chunks go out on the channel the command came in on (`ReplyChannel`):
+//! stdout for the parent daemon, the client's connection for the debug
+//! socket.
+
+use serde::Serialize;
+use serde_json::Value;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::protocol::{ChunkedResponse, ImportProgress, ResponseChunk};
+use crate::NotificationWriter;
+
+/// Serialized item bytes per chunk
+pub const MAX_CHUNK_BYTES: usize = 256 * 1024;
+
+/// Channel a command came in on. Unsolicited parts of the reply (response
+/// chunks, progress updates) are written to it before the reply itself.
+pub trait ReplyChannel {
+    fn write_chunks(&self, chunks: &[ResponseChunk]);
+    fn write_import_progress(&self, progress: &ImportProgress);
+}
+
+/// The protocol channel (stdout)
+impl ReplyChannel for NotificationWriter {
+    fn write_chunks(&self, chunks: &[ResponseChunk]) {
+        NotificationWriter::write_chunks(self, chunks);
+    }
+
+    fn write_import_progress(&self, progress: &ImportProgress) {
+        NotificationWriter::write_import_progress(self, progress);
+    }
+}
+
+/// Response for a list result: the items inline when they fit in one chunk,
+/// otherwise a `ChunkedResponse` after the chunks have been written to
+/// `channel`
+pub fn list_response<T: Serialize>(items: &[T], channel: &dyn ReplyChannel) -> Value {
+    let values: Vec<Value> = items
+        .iter()
+        .filter_map(|item| match serde_json::to_value(item) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                warn!("Failed to serialize response item: {}", e);
+                None
+            }
+        })
+        .collect();
+
+    let stream_id = Uuid::new_v4().to_string();
+    let total_items = values.len();
+    let chunks = split_chunks(&stream_id, values, MAX_CHUNK_BYTES);
+    if chunks.len() <= 1 {
+        let items = chunks
+            .into_iter()
+            .next()
+            .map(|c| c.items)
+            .unwrap_or_default();
+        return Value::Array(items);
+    }
+
+    debug!(
+        "Streaming {} items as {} chunks ({})",
+        total_items,
+        chunks.len(),
+        stream_id
+    );
+    channel.write_chunks(&chunks);
+    serde_json::to_value(ChunkedResponse {
+        stream_id,
+        chunks: chunks.len() as u32,
+        total_items,
+    })
+    .unwrap_or_default()
+}
+
+/// Split items into chunks of at most `max_bytes` serialized bytes each;
+/// an item larger than that gets a chunk of its own
+pub fn split_chunks(stream_id: &str, items: Vec<Value>, max_bytes: usize) -> Vec<ResponseChunk> {
+    let mut chunks: Vec<ResponseChunk> = Vec::new();
+    let mut current = Vec::new();
+    let mut current_bytes = 0;
+
+    for item in items {
+        let size = item.to_string().len();
+        if !current.is_empty() && current_bytes + size > max_bytes {
+            chunks.push(chunk(stream_id, chunks.len(), std::mem::take(&mut current)));
+            current_bytes = 0;
+        }
+        current_bytes += size;
+        current.push(item);
+    }
+    if !current.is_empty() || chunks.is_empty() {
+        chunks.push(chunk(stream_id, chunks.len(), current));
+    }
+
+    if let Some(last) = chunks.last_mut() {
+        last.is_final = true;
+    }
+    chunks
+}
+
+fn chunk(stream_id: &str, sequence: usize, items: Vec<Value>) -> ResponseChunk {
+    ResponseChunk {
+        stream_id: stream_id.to_string(),
+        sequence: sequence as u32,
+        is_final: false,
+        items,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_split_chunks() {
+        let items: Vec<Value> = (0..5).map(|i| json!({ "id": i })).collect();
+        let size = items[0].to_string().len();
+
+        let chunks = split_chunks("s", items.clone(), size * 2);
+        let sizes: Vec<_> = chunks.iter().map(|c| c.items.len()).collect();
+        assert_eq!(sizes, vec![2, 2, 1]);
+        let sequences: Vec<_> = chunks.iter().map(|c| c.sequence).collect();
+        assert_eq!(sequences, vec![0, 1, 2]);
+        let finals: Vec<_> = chunks.iter().map(|c| c.is_final).collect();
+        assert_eq!(finals, vec![false, false, true]);
+
+        // Oversized items still go out, one per chunk
+        assert_eq!(split_chunks("s", items.clone(), 1).len(), 5);
+
+        let single = split_chunks("s", items, MAX_CHUNK_BYTES);
+        assert_eq!(single.len(), 1);
+        assert!(single[0].is_final);
+        assert_eq!(split_chunks("s", Vec::new(), 1).len(), 1);
+    }
+}
@@ -0,0 +1,228 @@
+//! Clock helpers
+//!
+//! - [`SleepDetector`] detects OS sleep/resume so cached connections can be
+//!   torn down and re-discovered after the machine wakes up.
+//! - [`SessionClock`] provides session-relative timing on a monotonic clock, so
+//!   offsets stay correct if the system clock changes mid-game.
+
+use chrono::{DateTime, Utc};
+use std::time::{Duration, Instant, SystemTime};
+
+pub use pack_league_types::protocol::SessionClockAnchor;
+
+/// Default time lost to a suspend that is treated as a sleep/resume
+pub const DEFAULT_RESUME_THRESHOLD: Duration = Duration::from_secs(30);
+
+/// Detects sleep/resume from the clocks disagreeing between checks.
+///
+/// Where the monotonic clock pauses during sleep (Linux, macOS), the wall
+/// clock jumping ahead of it gives the suspend away, however long the gap
+/// between checks. Where it keeps counting (Windows) both clocks advance
+/// together, so a long gap only means something to a caller that checks on
+/// a fixed interval (see `ticking`); a plain detector never treats one as a
+/// resume, since callers like `get_status` may simply not be called for a
+/// while.
+#[derive(Debug, Clone)]
+pub struct SleepDetector {
+    threshold: Duration,
+    /// Interval the caller checks on, if it checks on one
+    tick: Option<Duration>,
+    last_mono: Instant,
+    last_wall: SystemTime,
+}
+
+impl SleepDetector {
+    pub fn new(threshold: Duration) -> Self {
+        Self {
+            threshold,
+            tick: None,
+            last_mono: Instant::now(),
+            last_wall: SystemTime::now(),
+        }
+    }
+
+    /// Detector for a caller checking every `tick`; a check arriving more
+    /// than the threshold late is also treated as a resume
+    pub fn ticking(tick: Duration) -> Self {
+        Self {
+            tick: Some(tick),
+            ..Self::default()
+        }
+    }
+
+    /// Record a check. Returns the detected gap if the process appears to
+    /// have been suspended since the previous check.
+    pub fn check(&mut self) -> Option<Duration> {
+        self.check_at(Instant::now(), SystemTime::now())
+    }
+
+    fn check_at(&mut self, mono: Instant, wall: SystemTime) -> Option<Duration> {
+        let mono_elapsed = mono.saturating_duration_since(self.last_mono);
+        let wall_elapsed = wall.duration_since(self.last_wall).unwrap_or_default();
+
+        self.last_mono = mono;
+        self.last_wall = wall;
+
+        // Monotonic clock paused, wall clock didn't
+        let drift = wall_elapsed.saturating_sub(mono_elapsed);
+        if drift >= self.threshold {
+            return Some(drift);
+        }
+
+        // Monotonic clock kept counting through sleep: the check came late
+        let late = mono_elapsed.saturating_sub(self.tick?);
+        (late >= self.threshold).then_some(mono_elapsed)
+    }
+
+    /// Reset the reference point (e.g. after intentionally idling)
+    pub fn reset(&mut self) {
+        self.last_mono = Instant::now();
+        self.last_wall = SystemTime::now();
+    }
+}
+
+impl Default for SleepDetector {
+    fn default() -> Self {
+        Self::new(DEFAULT_RESUME_THRESHOLD)
+    }
+}
+
+/// Monotonic clock anchored at session start.
+///
+/// The wall-clock time is read once, at the anchor. Everything after that is
+/// derived from `Instant`, so NTP adjustments or manual clock changes during a
+/// game don't shift session offsets.
+#[derive(Debug, Clone)]
+pub struct SessionClock {
+    anchor: Instant,
+    started_at: DateTime<Utc>,
+    /// Session time minus game time, once live data has been seen
+    game_time_offset: Option<f64>,
+}
+
+impl SessionClock {
+    /// Anchor a new clock at the current instant
+    pub fn start() -> Self {
+        Self {
+            anchor: Instant::now(),
+            started_at: Utc::now(),
+            game_time_offset: None,
+        }
+    }
+
+    /// Wall-clock time at the anchor
+    pub fn started_at(&self) -> DateTime<Utc> {
+        self.started_at
+    }
+
+    /// Seconds since the session started (monotonic)
+    pub fn elapsed_secs(&self) -> f64 {
+        self.anchor.elapsed().as_secs_f64()
+    }
+
+    /// Current time derived from the anchor, unaffected by clock changes
+    pub fn now(&self) -> DateTime<Utc> {
+        let elapsed = chrono::Duration::from_std(self.anchor.elapsed()).unwrap_or_default();
+        self.started_at + elapsed
+    }
+
+    /// Record an observed in-game time to map game time onto session time.
+    ///
+    /// Network and polling delay only ever make the observed offset larger,
+    /// so the smallest offset seen is kept.
+    pub fn sync_game_time(&mut self, game_time_secs: f64) {
+        self.sync_game_time_at(self.elapsed_secs(), game_time_secs);
+    }
+
+    fn sync_game_time_at(&mut self, elapsed_secs: f64, game_time_secs: f64) {
+        let offset = (elapsed_secs - game_time_secs).max(0.0);
+        self.game_time_offset = Some(match self.game_time_offset {
+            Some(current) => current.min(offset),
+            None => offset,
+        });
+    }
+
+    /// Convert an in-game timestamp to seconds since session start
+    pub fn game_time_to_session_secs(&self, game_time_secs: f64) -> Option<f64> {
+        self.game_time_offset.map(|offset| offset + game_time_secs)
+    }
+
+    /// Estimated current game time, once live data has been seen
+    pub fn game_time_now(&self) -> Option<f64> {
+        self.game_time_offset.map(|offset| self.elapsed_secs() - offset)
+    }
+
+    /// Wall-clock time at game time 0, once live data has been seen
+    pub fn game_started_at(&self) -> Option<DateTime<Utc>> {
+        let offset = self.game_time_offset?;
+        Some(self.started_at + chrono::Duration::milliseconds((offset * 1000.0) as i64))
+    }
+
+    /// Serializable anchor for the session context
+    pub fn anchor(&self) -> SessionClockAnchor {
+        SessionClockAnchor {
+            started_at: self.started_at,
+            game_time_offset_secs: self.game_time_offset,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_clock_game_time_mapping() {
+        let mut clock = SessionClock::start();
+        assert_eq!(clock.game_time_to_session_secs(10.0), None);
+
+        // First observation after a 45s loading screen, polled late
+        clock.sync_game_time_at(47.0, 1.0);
+        // A later, tighter observation refines the offset
+        clock.sync_game_time_at(105.0, 60.0);
+
+        assert_eq!(clock.game_time_to_session_secs(0.0), Some(45.0));
+        assert_eq!(clock.game_time_to_session_secs(120.0), Some(165.0));
+        assert_eq!(clock.anchor().game_time_offset_secs, Some(45.0));
+        assert_eq!(
+            clock.game_started_at(),
+            Some(clock.started_at() + chrono::Duration::seconds(45))
+        );
+    }
+
+    #[test]
+    fn test_no_resume_for_short_gap() {
+        let mut detector = SleepDetector::new(Duration::from_secs(30));
+        let mono = detector.last_mono + Duration::from_secs(1);
+        let wall = detector.last_wall + Duration::from_secs(1);
+        assert_eq!(detector.check_at(mono, wall), None);
+    }
+
+    #[test]
+    fn test_long_gap_between_calls_is_not_a_resume() {
+        let mut detector = SleepDetector::new(Duration::from_secs(30));
+        let mono = detector.last_mono + Duration::from_secs(600);
+        let wall = detector.last_wall + Duration::from_secs(600);
+        assert_eq!(detector.check_at(mono, wall), None);
+    }
+
+    #[test]
+    fn test_resume_from_late_tick() {
+        let mut detector = SleepDetector::ticking(Duration::from_secs(5));
+        let mono = detector.last_mono + Duration::from_secs(20);
+        let wall = detector.last_wall + Duration::from_secs(20);
+        assert_eq!(detector.check_at(mono, wall), None);
+
+        let mono = mono + Duration::from_secs(600);
+        let wall = wall + Duration::from_secs(600);
+        assert_eq!(detector.check_at(mono, wall), Some(Duration::from_secs(600)));
+    }
+
+    #[test]
+    fn test_resume_from_wall_clock_drift() {
+        let mut detector = SleepDetector::new(Duration::from_secs(30));
+        let mono = detector.last_mono + Duration::from_secs(1);
+        let wall = detector.last_wall + Duration::from_secs(301);
+        assert_eq!(detector.check_at(mono, wall), Some(Duration::from_secs(300)));
+    }
+}
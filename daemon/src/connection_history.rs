@@ -0,0 +1,136 @@
+//! Uptime and reconnect history
+//!
+//! Flaky client connections are hard to diagnose from a single status
+//! snapshot. The integration records each connection status change with the
+//! reason it happened, and reports the most recent ones with its metrics.
+
+use std::time::Instant;
+
+use chrono::Utc;
+
+use crate::buffers::RingBuffer;
+use crate::protocol::{ConnectionStatus, ConnectionTransition};
+
+/// Transitions kept in the history
+pub const MAX_CONNECTION_TRANSITIONS: usize = 20;
+
+#[derive(Debug)]
+pub struct ConnectionHistory {
+    started: Instant,
+    connected_since: Option<Instant>,
+    /// Why the connection was dropped, reported with the next disconnect
+    pending_cause: Option<String>,
+    transitions: RingBuffer<ConnectionTransition>,
+}
+
+impl Default for ConnectionHistory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConnectionHistory {
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            connected_since: None,
+            pending_cause: None,
+            transitions: RingBuffer::new(MAX_CONNECTION_TRANSITIONS),
+        }
+    }
+
+    /// Remember why the connection is being dropped (e.g. a failed request)
+    pub fn note_cause(&mut self, cause: impl Into<String>) {
+        self.pending_cause = Some(cause.into());
+    }
+
+    /// Forget a noted cause once the connection proved healthy again
+    pub fn clear_cause(&mut self) {
+        self.pending_cause = None;
+    }
+
+    /// Record a status change; `cause` is used unless one was noted earlier
+    pub fn record(&mut self, from: ConnectionStatus, to: ConnectionStatus, cause: &str) {
+        if from == to {
+            return;
+        }
+
+        let cause = match to {
+            ConnectionStatus::Disconnected => self
+                .pending_cause
+                .take()
+                .unwrap_or_else(|| cause.to_string()),
+            _ => cause.to_string(),
+        };
+        match (from, to) {
+            (ConnectionStatus::Disconnected, _) => {
+                self.connected_since = Some(Instant::now());
+                self.pending_cause = None;
+            }
+            (_, ConnectionStatus::Disconnected) => self.connected_since = None,
+            _ => {}
+        }
+
+        self.transitions.push(ConnectionTransition {
+            at: Utc::now(),
+            from,
+            to,
+            cause,
+        });
+    }
+
+    pub fn uptime_secs(&self) -> u64 {
+        self.started.elapsed().as_secs()
+    }
+
+    /// Seconds since the client last connected
+    pub fn connected_secs(&self) -> Option<u64> {
+        self.connected_since.map(|since| since.elapsed().as_secs())
+    }
+
+    /// Recent transitions, oldest first
+    pub fn transitions(&self) -> Vec<ConnectionTransition> {
+        self.transitions.iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_records_causes() {
+        let mut history = ConnectionHistory::new();
+        assert_eq!(history.connected_secs(), None);
+
+        history.record(
+            ConnectionStatus::Disconnected,
+            ConnectionStatus::Connected,
+            "client found",
+        );
+        history.record(
+            ConnectionStatus::Connected,
+            ConnectionStatus::Connected,
+            "ignored",
+        );
+        assert_eq!(history.connected_secs(), Some(0));
+
+        let (connected, disconnected) =
+            (ConnectionStatus::Connected, ConnectionStatus::Disconnected);
+        history.note_cause("system resumed");
+        history.record(connected, disconnected, "client not found");
+        history.record(connected, disconnected, "client not found");
+
+        let causes: Vec<_> = history.transitions().into_iter().map(|t| t.cause).collect();
+        assert_eq!(
+            causes,
+            vec!["client found", "system resumed", "client not found"]
+        );
+        assert_eq!(history.connected_secs(), None);
+
+        for _ in 0..MAX_CONNECTION_TRANSITIONS {
+            history.record(ConnectionStatus::InGame, disconnected, "client not found");
+        }
+        assert_eq!(history.transitions().len(), MAX_CONNECTION_TRANSITIONS);
+    }
+}
@@ -0,0 +1,342 @@
+//! Data Dragon asset resolver
+//!
+//! End-of-game stats only carry numeric IDs for items, champions, summoner
+//! spells and runes. The resolver downloads the current patch's static data
+//! (`item.json`, `champion.json`, `summoner.json`, `runesReforged.json`),
+//! caches it on disk next to the cached icons, and maps IDs to English names
+//! and icon URLs. Until the data is loaded every lookup returns `None` and
+//! callers fall back to their built-in tables or the raw ID.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use tracing::{debug, info, warn};
+
+use crate::asset_cache::download;
+use crate::supervisor::spawn_background;
+use crate::{ChampionProfile, LiveMatch, Result, DDRAGON_BASE};
+
+/// Locale of the cached static data
+const DDRAGON_LOCALE: &str = "en_US";
+
+/// Background task loading a patch's static data
+const DDRAGON_LOAD_TASK: &str = "ddragon_load";
+
+/// Something with a Data Dragon name and an image reference (a file ID such
+/// as "SummonerFlash", or a path under `cdn/img/` for runes)
+#[derive(Debug, Clone, PartialEq)]
+struct Entry {
+    image: String,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct DataFile<T> {
+    data: HashMap<String, T>,
+}
+
+#[derive(Deserialize)]
+struct ItemData {
+    name: String,
+}
+
+/// Champion or summoner spell: `key` is the numeric ID as a string
+#[derive(Deserialize)]
+struct KeyedData {
+    id: String,
+    key: String,
+    name: String,
+    /// Champion class tags
+    #[serde(default)]
+    tags: Vec<String>,
+    /// Champion ratings
+    #[serde(default)]
+    info: Option<ChampionInfo>,
+}
+
+#[derive(Deserialize)]
+struct ChampionInfo {
+    attack: u8,
+    magic: u8,
+}
+
+#[derive(Deserialize)]
+struct RuneTreeData {
+    id: i32,
+    icon: String,
+    name: String,
+    slots: Vec<RuneSlotData>,
+}
+
+#[derive(Deserialize)]
+struct RuneSlotData {
+    runes: Vec<RuneData>,
+}
+
+#[derive(Deserialize)]
+struct RuneData {
+    id: i32,
+    icon: String,
+    name: String,
+}
+
+/// Static data of one patch
+#[derive(Debug, Default)]
+pub struct DdragonData {
+    version: String,
+    items: HashMap<i32, String>,
+    champions: HashMap<i32, Entry>,
+    champion_profiles: HashMap<i32, ChampionProfile>,
+    spells: HashMap<i32, Entry>,
+    /// Rune trees and individual runes
+    runes: HashMap<i32, Entry>,
+}
+
+impl DdragonData {
+    /// Parse the four static data files of `version`
+    pub fn from_json(
+        version: &str,
+        items: &[u8],
+        champions: &[u8],
+        spells: &[u8],
+        runes: &[u8],
+    ) -> Result<Self> {
+        let items: DataFile<ItemData> = serde_json::from_slice(items)?;
+        let champions: DataFile<KeyedData> = serde_json::from_slice(champions)?;
+        let spells: DataFile<KeyedData> = serde_json::from_slice(spells)?;
+        let trees: Vec<RuneTreeData> = serde_json::from_slice(runes)?;
+
+        let champion_profiles = champions
+            .data
+            .values()
+            .filter_map(|d| {
+                let info = d.info.as_ref()?;
+                let profile = ChampionProfile {
+                    tags: d.tags.clone(),
+                    attack: info.attack,
+                    magic: info.magic,
+                };
+                Some((d.key.parse().ok()?, profile))
+            })
+            .collect();
+
+        let by_key = |file: DataFile<KeyedData>| -> HashMap<i32, Entry> {
+            file.data
+                .into_values()
+                .filter_map(|d| {
+                    let key = d.key.parse().ok()?;
+                    Some((key, Entry { image: d.id, name: d.name }))
+                })
+                .collect()
+        };
+
+        let mut rune_entries = HashMap::new();
+        for tree in trees {
+            for rune in tree.slots.into_iter().flat_map(|s| s.runes) {
+                rune_entries.insert(rune.id, Entry { image: rune.icon, name: rune.name });
+            }
+            rune_entries.insert(tree.id, Entry { image: tree.icon, name: tree.name });
+        }
+
+        Ok(Self {
+            version: version.to_string(),
+            items: items
+                .data
+                .into_iter()
+                .filter_map(|(id, item)| Some((id.parse().ok()?, item.name)))
+                .collect(),
+            champions: by_key(champions),
+            champion_profiles,
+            spells: by_key(spells),
+            runes: rune_entries,
+        })
+    }
+
+    /// Download (or read from `cache_dir`) the static data of `version`
+    pub async fn load(version: &str, cache_dir: &Path) -> Result<Self> {
+        let client = reqwest::Client::new();
+        let items = fetch(&client, cache_dir, version, "item.json").await?;
+        let champions = fetch(&client, cache_dir, version, "champion.json").await?;
+        let spells = fetch(&client, cache_dir, version, "summoner.json").await?;
+        let runes = fetch(&client, cache_dir, version, "runesReforged.json").await?;
+        Self::from_json(version, &items, &champions, &spells, &runes)
+    }
+}
+
+/// Read a static data file from the cache, downloading it first if missing.
+/// Cached files mirror the CDN layout like the icon cache.
+async fn fetch(client: &reqwest::Client, cache_dir: &Path, version: &str, file: &str) -> Result<Vec<u8>> {
+    let relative = format!("{}/data/{}/{}", version, DDRAGON_LOCALE, file);
+    let path = cache_dir.join(&relative);
+
+    if !path.exists() {
+        let url = format!("{}/{}", DDRAGON_BASE, relative);
+        debug!("Downloading {}", url);
+        download(client, &url, &path).await?;
+    }
+    Ok(tokio::fs::read(&path).await?)
+}
+
+/// Shared, lazily loaded Data Dragon lookups
+#[derive(Debug, Clone, Default)]
+pub struct DdragonResolver {
+    data: Arc<RwLock<Option<Arc<DdragonData>>>>,
+}
+
+impl DdragonResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolver over already loaded data
+    pub fn with_data(data: DdragonData) -> Self {
+        Self {
+            data: Arc::new(RwLock::new(Some(Arc::new(data)))),
+        }
+    }
+
+    /// Patch of the loaded data
+    pub fn version(&self) -> Option<String> {
+        self.data().map(|d| d.version.clone())
+    }
+
+    /// Load `version` in the background unless it is already loaded. Must
+    /// be called from within a tokio runtime.
+    pub fn load(&self, version: &str, cache_dir: PathBuf) {
+        if self.version().as_deref() == Some(version) {
+            return;
+        }
+
+        let resolver = self.clone();
+        let version = version.to_string();
+        spawn_background(DDRAGON_LOAD_TASK, async move {
+            if let Err(e) = resolver.load_now(&version, &cache_dir).await {
+                warn!("Failed to load Data Dragon {}: {}", version, e);
+            }
+        });
+    }
+
+    /// Load `version` and wait for it, for callers that need names right away
+    pub async fn load_now(&self, version: &str, cache_dir: &Path) -> Result<()> {
+        let data = DdragonData::load(version, cache_dir).await?;
+        info!(
+            "Loaded Data Dragon {} ({} items, {} champions)",
+            version,
+            data.items.len(),
+            data.champions.len()
+        );
+        *self.data.write().expect("RwLock poisoned") = Some(Arc::new(data));
+        Ok(())
+    }
+
+    fn data(&self) -> Option<Arc<DdragonData>> {
+        self.data.read().expect("RwLock poisoned").clone()
+    }
+
+    pub fn item_name(&self, id: i32) -> Option<String> {
+        self.data()?.items.get(&id).cloned()
+    }
+
+    pub fn champion_name(&self, id: i32) -> Option<String> {
+        self.data()?.champions.get(&id).map(|c| c.name.clone())
+    }
+
+    /// Champion ID by display name ("Lee Sin") or Data Dragon ID ("LeeSin")
+    pub fn champion_id(&self, name: &str) -> Option<i32> {
+        self.data()?
+            .champions
+            .iter()
+            .find(|(_, c)| c.name.eq_ignore_ascii_case(name) || c.image.eq_ignore_ascii_case(name))
+            .map(|(&id, _)| id)
+    }
+
+    /// Class tags and damage ratings of a champion
+    pub fn champion_profile(&self, id: i32) -> Option<ChampionProfile> {
+        self.data()?.champion_profiles.get(&id).cloned()
+    }
+
+    pub fn spell_name(&self, id: i32) -> Option<String> {
+        self.data()?.spells.get(&id).map(|s| s.name.clone())
+    }
+
+    /// Name of a rune or rune tree
+    pub fn rune_name(&self, id: i32) -> Option<String> {
+        self.data()?.runes.get(&id).map(|r| r.name.clone())
+    }
+
+    pub fn item_icon(&self, id: i32) -> Option<String> {
+        let data = self.data()?;
+        data.items
+            .contains_key(&id)
+            .then(|| format!("{}/{}/img/item/{}.png", DDRAGON_BASE, data.version, id))
+    }
+
+    pub fn champion_icon(&self, id: i32) -> Option<String> {
+        let data = self.data()?;
+        let champion = data.champions.get(&id)?;
+        Some(format!("{}/{}/img/champion/{}.png", DDRAGON_BASE, data.version, champion.image))
+    }
+
+    pub fn spell_icon(&self, id: i32) -> Option<String> {
+        let data = self.data()?;
+        let spell = data.spells.get(&id)?;
+        Some(format!("{}/{}/img/spell/{}.png", DDRAGON_BASE, data.version, spell.image))
+    }
+
+    /// Rune or rune tree icon (rune images aren't versioned)
+    pub fn rune_icon(&self, id: i32) -> Option<String> {
+        let rune = self.data()?.runes.get(&id)?.clone();
+        Some(format!("{}/img/{}", DDRAGON_BASE, rune.image))
+    }
+
+    /// Fill in item names the Live Client API left empty
+    pub fn fill_live_match(&self, live: &mut LiveMatch) {
+        for item in live.items.iter_mut().chain(live.trinket.as_mut()) {
+            if item.name.is_empty() {
+                if let Some(name) = self.item_name(item.item_id) {
+                    item.name = name;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolver() -> DdragonResolver {
+        let items = br#"{"data": {"3031": {"name": "Infinity Edge"}, "3340": {"name": "Stealth Ward"}}}"#;
+        let champions = br#"{"data": {"MonkeyKing": {"id": "MonkeyKing", "key": "62", "name": "Wukong",
+            "tags": ["Fighter", "Tank"], "info": {"attack": 8, "defense": 5, "magic": 2, "difficulty": 3}}}}"#;
+        let spells = br#"{"data": {"SummonerFlash": {"id": "SummonerFlash", "key": "4", "name": "Flash"}}}"#;
+        let runes = br#"[{"id": 8100, "icon": "perk-images/Styles/7200_Domination.png", "name": "Domination",
+            "slots": [{"runes": [{"id": 8112, "icon": "perk-images/Styles/Domination/Electrocute/Electrocute.png", "name": "Electrocute"}]}]}]"#;
+
+        DdragonResolver::with_data(
+            DdragonData::from_json("14.24.1", items, champions, spells, runes).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_resolves_names_and_icons() {
+        let resolver = resolver();
+        assert_eq!(resolver.item_name(3031).as_deref(), Some("Infinity Edge"));
+        assert_eq!(resolver.champion_name(62).as_deref(), Some("Wukong"));
+        assert_eq!(resolver.champion_profile(62).unwrap().tags, vec!["Fighter", "Tank"]);
+        assert_eq!(resolver.spell_name(4).as_deref(), Some("Flash"));
+        assert_eq!(resolver.rune_name(8112).as_deref(), Some("Electrocute"));
+        assert_eq!(resolver.rune_name(8100).as_deref(), Some("Domination"));
+        assert_eq!(
+            resolver.champion_icon(62).unwrap(),
+            "https://ddragon.leagueoflegends.com/cdn/14.24.1/img/champion/MonkeyKing.png"
+        );
+        assert_eq!(
+            resolver.spell_icon(4).unwrap(),
+            "https://ddragon.leagueoflegends.com/cdn/14.24.1/img/spell/SummonerFlash.png"
+        );
+        assert!(resolver.item_name(1).is_none());
+        assert!(DdragonResolver::new().item_name(3031).is_none());
+    }
+}
@@ -0,0 +1,236 @@
+//! Active player death tracking
+//!
+//! Records each of the player's deaths with who was involved and an estimate
+//! of the gold missed while waiting to respawn, so death-review tools get
+//! structured data instead of raw kill events.
+//!
+//! The Live Client API has no damage log, so the active player's health is
+//! sampled while polling; the health lost in the seconds before a death is
+//! reported as the damage taken.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+use crate::{riot_ids_match, LiveMatch, Player};
+
+/// Seconds of health samples kept for the damage taken before a death
+const DAMAGE_WINDOW_SECS: f64 = 10.0;
+
+/// Base respawn time in seconds by champion level (1-18). Death timers also
+/// grow with game time past 15 minutes; that increase is ignored here.
+const BASE_RESPAWN_SECS: [f64; 18] = [
+    6.0, 6.0, 8.0, 8.0, 10.0, 12.0, 16.0, 20.0, 25.0, 28.0, 32.5, 35.0, 37.5, 40.0, 42.5, 45.0,
+    47.5, 50.0,
+];
+
+/// One death of the active player
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeathRecord {
+    pub game_time_secs: f64,
+    /// Killer as reported by the event (player, turret or minion name)
+    pub killer_name: String,
+    /// Killer's champion, if the killer was a player
+    pub killer_champion: Option<String>,
+    /// Assisting champions (player names when the champion isn't known)
+    pub assisters: Vec<String>,
+    /// Estimated time spent dead
+    pub respawn_secs: f64,
+    /// Gold the player would have earned at their average rate while dead
+    pub gold_lost_estimate: i32,
+    /// Killer's items, most expensive first
+    #[serde(default)]
+    pub killer_items: Vec<String>,
+    /// Health lost in the last `DAMAGE_WINDOW_SECS` before the death
+    #[serde(default)]
+    pub recent_damage_taken: Option<f64>,
+}
+
+/// Collects the active player's deaths for the current game
+#[derive(Debug, Clone, Default)]
+pub struct DeathTracker {
+    deaths: Vec<DeathRecord>,
+    /// Recent (game time, current health) samples of the active player
+    health: VecDeque<(f64, f64)>,
+}
+
+impl DeathTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sample the active player's health
+    pub fn sample_health(&mut self, game_time_secs: f64, current_health: f64) {
+        while self
+            .health
+            .front()
+            .is_some_and(|&(at, _)| game_time_secs - at > DAMAGE_WINDOW_SECS)
+        {
+            self.health.pop_front();
+        }
+        self.health.push_back((game_time_secs, current_health));
+    }
+
+    /// Record a death. `live` is the latest snapshot, used to resolve
+    /// champions and estimate income; `players` (from `allgamedata`) gives
+    /// the killer's items.
+    pub fn record(
+        &mut self,
+        game_time_secs: f64,
+        killer_name: &str,
+        assisters: &[String],
+        live: Option<&LiveMatch>,
+        players: &[Player],
+    ) -> &DeathRecord {
+        let champion_of = |name: &str| -> Option<String> {
+            live?.participants
+                .iter()
+                .find(|p| riot_ids_match(&p.summoner_name, name))
+                .map(|p| p.champion.clone())
+        };
+
+        let level = live.map_or(1, |l| l.level).clamp(1, 18) as usize;
+        let respawn_secs = BASE_RESPAWN_SECS[level - 1];
+
+        // Average income so far: held items plus unspent gold over game time
+        let gold_per_sec = live
+            .filter(|l| l.game_time_secs > 0.0)
+            .map(|l| {
+                let item_gold = l
+                    .participants
+                    .iter()
+                    .find(|p| riot_ids_match(&p.summoner_name, &l.summoner_name))
+                    .map_or(0, |p| p.item_gold);
+                (item_gold as f64 + l.current_gold) / l.game_time_secs
+            })
+            .unwrap_or(0.0);
+
+        let mut killer_items: Vec<_> = players
+            .iter()
+            .find(|p| riot_ids_match(&p.summoner_name, killer_name))
+            .map(|p| p.items.iter().collect())
+            .unwrap_or_default();
+        killer_items.sort_by(|a, b| b.price.cmp(&a.price));
+
+        // Health drops to zero, so the peak of the window is what was lost
+        let recent_damage_taken = self
+            .health
+            .iter()
+            .filter(|&&(at, _)| at <= game_time_secs && game_time_secs - at <= DAMAGE_WINDOW_SECS)
+            .map(|&(_, health)| health)
+            .reduce(f64::max);
+        self.health.clear();
+
+        self.deaths.push(DeathRecord {
+            game_time_secs,
+            killer_name: killer_name.to_string(),
+            killer_champion: champion_of(killer_name),
+            assisters: assisters
+                .iter()
+                .map(|name| champion_of(name).unwrap_or_else(|| name.clone()))
+                .collect(),
+            respawn_secs,
+            gold_lost_estimate: (gold_per_sec * respawn_secs).round() as i32,
+            killer_items: killer_items.into_iter().map(|i| i.display_name.clone()).collect(),
+            recent_damage_taken,
+        });
+        self.deaths.last().expect("just pushed")
+    }
+
+    /// Deaths recorded so far, in game order
+    pub fn deaths(&self) -> &[DeathRecord] {
+        &self.deaths
+    }
+
+    /// Forget all deaths (e.g. at session start)
+    pub fn reset(&mut self) {
+        self.deaths.clear();
+        self.health.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Item, LivePlayer, Team};
+
+    fn player(name: &str, champion: &str, team: Team, item_gold: i32) -> LivePlayer {
+        LivePlayer {
+            summoner_name: name.to_string(),
+            champion: champion.to_string(),
+            team,
+            kills: 0,
+            deaths: 0,
+            assists: 0,
+            cs: 0,
+            level: 1,
+            is_dead: false,
+            item_gold,
+            position: String::new(),
+            items: Vec::new(),
+            trinket: None,
+            spell1: None,
+            spell2: None,
+            runes: None,
+        }
+    }
+
+    #[test]
+    fn test_death_resolves_champions_and_estimates_gold() {
+        let mut live: LiveMatch = serde_json::from_value(serde_json::json!({
+            "summonerName": "Me#EUW",
+            "champion": "Ahri",
+            "level": 6,
+            "kills": 0, "deaths": 1, "assists": 0, "cs": 40,
+            "currentGold": 600.0,
+            "gameTimeSecs": 600.0,
+            "gameMode": "CLASSIC",
+            "team": "blue",
+            "items": [],
+            "trinket": null,
+            "spell1": null,
+            "spell2": null,
+            "runes": null,
+            "participants": [],
+            "isDead": true
+        }))
+        .unwrap();
+        live.participants = vec![
+            player("Me#EUW", "Ahri", Team::Blue, 2400),
+            player("Them#EUW", "Zed", Team::Red, 3000),
+            player("Jungler#EUW", "Lee Sin", Team::Red, 2000),
+        ];
+
+        let item = |name: &str, price| Item {
+            display_name: name.to_string(),
+            price,
+            ..Item::default()
+        };
+        let killer = Player {
+            summoner_name: "Them#EUW".to_string(),
+            items: vec![item("Long Sword", 350), item("Duskblade of Draktharr", 2900)],
+            ..Player::default()
+        };
+
+        let mut tracker = DeathTracker::new();
+        tracker.sample_health(580.0, 1200.0);
+        tracker.sample_health(590.0, 900.0);
+        tracker.sample_health(596.0, 300.0);
+        let death = tracker.record(
+            598.0,
+            "Them",
+            &["Jungler".to_string(), "Turret_T2_C_05_A".to_string()],
+            Some(&live),
+            &[killer],
+        );
+
+        assert_eq!(death.killer_champion.as_deref(), Some("Zed"));
+        assert_eq!(death.assisters, vec!["Lee Sin", "Turret_T2_C_05_A"]);
+        assert_eq!(death.respawn_secs, 12.0);
+        // (2400 + 600) gold over 600s = 5 gold/s, for 12s
+        assert_eq!(death.gold_lost_estimate, 60);
+        assert_eq!(death.killer_items, vec!["Duskblade of Draktharr", "Long Sword"]);
+        assert_eq!(death.recent_damage_taken, Some(900.0));
+        assert_eq!(tracker.deaths().len(), 1);
+    }
+}
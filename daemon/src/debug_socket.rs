@@ -0,0 +1,286 @@
+//! Debug protocol socket
+//!
+//! The parent daemon owns stdin/stdout, so nothing else can talk to a running
+//! pack. With `LEAGUE_DEBUG_SOCKET` set, the pack also listens on a Unix
+//! socket at that path (a named pipe such as `\\.\pipe\pack-league` on
+//! Windows) speaking the pack's NDJSON commands: one `LeagueCommand` per
+//! line, answered by one line (preceded by any response chunks or progress
+//! lines the command produced). A developer can attach a REPL (`socat`,
+//! `nc -U`) to a running pack without disturbing the parent connection.
+//! Several clients can be attached at once, each on its own thread.
+
+use std::cell::RefCell;
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+use crate::protocol::{ImportProgress, LeagueCommand, ResponseChunk};
+use crate::supervisor::mark_background_thread;
+#[cfg(windows)]
+use crate::supervisor::spawn_background;
+use crate::{LeagueError, ReplyChannel};
+
+/// Environment variable holding the socket path
+const DEBUG_SOCKET_ENV: &str = "LEAGUE_DEBUG_SOCKET";
+
+/// Background thread accepting debug clients
+const DEBUG_SOCKET_TASK: &str = "debug_socket";
+
+/// Background thread or task serving one debug client (a panic in a
+/// command drops the client, not the pack)
+const DEBUG_CLIENT_TASK: &str = "debug_client";
+
+/// Runs a command against the integration and returns its reply; chunks and
+/// progress updates go to the given channel
+pub type CommandDispatch =
+    Arc<dyn Fn(LeagueCommand, &dyn ReplyChannel) -> serde_json::Value + Send + Sync>;
+
+/// Lines written ahead of a debug client's reply
+#[derive(Default)]
+struct ClientLines(RefCell<String>);
+
+impl ClientLines {
+    fn push<T: serde::Serialize>(&self, message: &T) {
+        match serde_json::to_string(message) {
+            Ok(line) => {
+                let mut lines = self.0.borrow_mut();
+                lines.push_str(&line);
+                lines.push('\n');
+            }
+            Err(e) => warn!("Failed to serialize debug client line: {}", e),
+        }
+    }
+}
+
+impl ReplyChannel for ClientLines {
+    fn write_chunks(&self, chunks: &[ResponseChunk]) {
+        chunks.iter().for_each(|chunk| self.push(chunk));
+    }
+
+    fn write_import_progress(&self, progress: &ImportProgress) {
+        self.push(progress);
+    }
+}
+
+/// Socket path from the environment, if the debug socket is enabled
+pub fn debug_socket_path() -> Option<PathBuf> {
+    std::env::var_os(DEBUG_SOCKET_ENV)
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Listen on `path` in the background; commands are run by `dispatch`
+pub fn start_debug_socket(path: PathBuf, dispatch: CommandDispatch) {
+    let spawned = std::thread::Builder::new()
+        .name("debug-socket".to_string())
+        .spawn(move || {
+            mark_background_thread(DEBUG_SOCKET_TASK);
+            info!("Debug socket listening on {:?}", path);
+            if let Err(e) = listen(&path, dispatch) {
+                warn!("Debug socket on {:?} stopped: {}", path, e);
+            }
+        });
+    if let Err(e) = spawned {
+        warn!("Failed to start debug socket: {}", e);
+    }
+}
+
+/// Reply to one command line: chunk and progress lines, then the reply line
+/// (without the final newline)
+fn reply_to(line: &str, dispatch: &CommandDispatch) -> String {
+    let lines = ClientLines::default();
+    let reply = match serde_json::from_str::<LeagueCommand>(line) {
+        Ok(command) => dispatch(command, &lines),
+        Err(e) => LeagueError::InvalidRequest(e.to_string()).response(),
+    };
+    let mut lines = lines.0.into_inner();
+    lines.push_str(&reply.to_string());
+    lines
+}
+
+/// Answer command lines until the client disconnects
+fn serve_lines(
+    reader: impl BufRead,
+    mut writer: impl Write,
+    dispatch: &CommandDispatch,
+) -> std::io::Result<()> {
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        writeln!(writer, "{}", reply_to(&line, dispatch))?;
+        writer.flush()?;
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn listen(path: &std::path::Path, dispatch: CommandDispatch) -> std::io::Result<()> {
+    use std::io::BufReader;
+    use std::os::unix::fs::FileTypeExt;
+    use std::os::unix::net::UnixListener;
+
+    // A socket left behind by a previous run would fail the bind; anything
+    // else at the path is not ours to delete
+    match std::fs::symlink_metadata(path) {
+        Ok(meta) if meta.file_type().is_socket() => std::fs::remove_file(path)?,
+        Ok(_) => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                format!("{} exists and is not a socket", path.display()),
+            ))
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+    let listener = UnixListener::bind(path)?;
+
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let dispatch = Arc::clone(&dispatch);
+        std::thread::spawn(move || {
+            mark_background_thread(DEBUG_CLIENT_TASK);
+            debug!("Debug client attached");
+            let result = stream
+                .try_clone()
+                .and_then(|reader| serve_lines(BufReader::new(reader), stream, &dispatch));
+            if let Err(e) = result {
+                debug!("Debug client error: {}", e);
+            }
+            debug!("Debug client detached");
+        });
+    }
+    Ok(())
+}
+
+#[cfg(windows)]
+fn listen(path: &std::path::Path, dispatch: CommandDispatch) -> std::io::Result<()> {
+    // Pipe I/O runs on its own runtime; commands run on plain threads, since
+    // the dispatch blocks on the pack's runtime
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_io()
+        .build()?;
+    runtime.block_on(accept_pipe_clients(path, dispatch))
+}
+
+#[cfg(windows)]
+async fn accept_pipe_clients(
+    path: &std::path::Path,
+    dispatch: CommandDispatch,
+) -> std::io::Result<()> {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::windows::named_pipe::ServerOptions;
+
+    let mut server = ServerOptions::new()
+        .first_pipe_instance(true)
+        .create(path)?;
+    loop {
+        server.connect().await?;
+        let client = server;
+        server = ServerOptions::new().create(path)?;
+
+        let dispatch = Arc::clone(&dispatch);
+        spawn_background(DEBUG_CLIENT_TASK, async move {
+            debug!("Debug client attached");
+            let (reader, mut writer) = tokio::io::split(client);
+            let mut lines = BufReader::new(reader).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let (tx, rx) = tokio::sync::oneshot::channel();
+                let dispatch = Arc::clone(&dispatch);
+                std::thread::spawn(move || {
+                    mark_background_thread(DEBUG_CLIENT_TASK);
+                    let _ = tx.send(reply_to(&line, &dispatch));
+                });
+                let Ok(reply) = rx.await else {
+                    break;
+                };
+                if writer
+                    .write_all(format!("{}\n", reply).as_bytes())
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+            }
+            debug!("Debug client detached");
+        });
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn listen(_path: &std::path::Path, _dispatch: CommandDispatch) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "no debug socket on this platform",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_each_command_line_gets_a_reply_line() {
+        let dispatch: CommandDispatch = Arc::new(|command, _| match command {
+            LeagueCommand::GetEventCatalog => json!({ "ok": true }),
+            _ => serde_json::Value::Null,
+        });
+        let input = "{\"type\":\"GetEventCatalog\"}\n\nnot json\n";
+
+        let mut output = Vec::new();
+        serve_lines(input.as_bytes(), &mut output, &dispatch).unwrap();
+
+        let replies: Vec<serde_json::Value> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(replies.len(), 2);
+        assert_eq!(replies[0], json!({ "ok": true }));
+        assert_eq!(replies[1]["error"]["code"], "INVALID_REQUEST");
+    }
+
+    #[test]
+    fn test_chunks_go_to_the_client_before_the_reply() {
+        let dispatch: CommandDispatch = Arc::new(|_, channel| {
+            let items = vec![json!({ "id": 1 }), json!({ "id": 2 })];
+            channel.write_chunks(&crate::split_chunks("s", items, 1));
+            json!({ "stream_id": "s", "chunks": 2 })
+        });
+
+        let mut output = Vec::new();
+        serve_lines("{\"type\":\"GetArenaMatches\"}\n".as_bytes(), &mut output, &dispatch)
+            .unwrap();
+
+        let lines: Vec<serde_json::Value> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["sequence"], 0);
+        assert_eq!(lines[1]["is_final"], true);
+        assert_eq!(lines[2]["chunks"], 2);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_listen_leaves_non_socket_files_alone() {
+        let path = std::env::temp_dir()
+            .join(format!("league_debug_socket_test_{}", std::process::id()));
+        std::fs::write(&path, "keep me").unwrap();
+
+        let dispatch: CommandDispatch = Arc::new(|_, _| serde_json::Value::Null);
+        let err = listen(&path, dispatch).unwrap_err();
+
+        assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "keep me");
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -1,7 +1,10 @@
 //! Error types for League integration
 
+use serde_json::{json, Value};
 use thiserror::Error;
 
+use crate::protocol::{CommandError, LeagueErrorCode};
+
 pub type Result<T> = std::result::Result<T, LeagueError>;
 
 #[derive(Debug, Error)]
@@ -12,6 +15,27 @@ pub enum LeagueError {
     #[error("LCU connection failed: {0}")]
     LcuConnectionFailed(String),
 
+    #[error("Invalid lockfile: {0}")]
+    LockfileParse(String),
+
+    #[error("Live Client API timed out")]
+    LiveClientTimeout,
+
+    #[error("Live Client API unavailable: {0}")]
+    LiveClientUnavailable(String),
+
+    #[error("End-of-game stats unavailable: {0}")]
+    EndOfGameUnavailable(String),
+
+    #[error("Replay API unavailable: {0}")]
+    ReplayUnavailable(String),
+
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+
     #[error("HTTP request failed: {0}")]
     HttpError(#[from] reqwest::Error),
 
@@ -30,9 +54,114 @@ pub enum LeagueError {
     #[error("League of Legends is not running")]
     LeagueNotRunning,
 
+    #[error("Match history import failed: {0}")]
+    ImportFailed(Box<LeagueError>),
+
+    #[error("Reprocessing failed: {0}")]
+    ReprocessFailed(String),
+
+    #[error("Replay request failed: {0}")]
+    ReplayRequestFailed(Box<LeagueError>),
+
+    #[error("Storage command failed: {0}")]
+    StorageFailed(Box<LeagueError>),
+
     #[error("{0}")]
     Other(String),
 }
 
+impl LeagueError {
+    /// Live Client API request error, telling timeouts apart
+    pub fn live_client(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            LeagueError::LiveClientTimeout
+        } else {
+            LeagueError::LiveClientUnavailable(e.to_string())
+        }
+    }
+
+    /// Code reported to the daemon for this error
+    pub fn code(&self) -> LeagueErrorCode {
+        match self {
+            LeagueError::LcuNotFound(_) | LeagueError::LeagueNotRunning => {
+                LeagueErrorCode::LcuNotRunning
+            }
+            LeagueError::LcuConnectionFailed(_) => LeagueErrorCode::LcuConnectionFailed,
+            LeagueError::LockfileParse(_) => LeagueErrorCode::LockfileParse,
+            LeagueError::LiveClientTimeout => LeagueErrorCode::LiveClientTimeout,
+            LeagueError::LiveClientUnavailable(_) => LeagueErrorCode::LiveClientUnavailable,
+            LeagueError::EndOfGameUnavailable(_) => LeagueErrorCode::EogUnavailable,
+            LeagueError::ReplayUnavailable(_) => LeagueErrorCode::ReplayUnavailable,
+            LeagueError::NotFound(_) => LeagueErrorCode::NotFound,
+            LeagueError::InvalidRequest(_) => LeagueErrorCode::InvalidRequest,
+            LeagueError::HttpError(e) if e.is_connect() || e.is_timeout() => {
+                LeagueErrorCode::LcuConnectionFailed
+            }
+            LeagueError::WebSocketError(_) => LeagueErrorCode::WebsocketFailed,
+            LeagueError::IoError(_) => LeagueErrorCode::IoFailed,
+            LeagueError::JsonError(_) | LeagueError::ParseError(_) => LeagueErrorCode::ParseFailed,
+            LeagueError::HttpError(_) | LeagueError::Other(_) => LeagueErrorCode::Internal,
+            LeagueError::ImportFailed(_) => LeagueErrorCode::ImportFailed,
+            LeagueError::ReprocessFailed(_) => LeagueErrorCode::ReprocessFailed,
+            LeagueError::ReplayRequestFailed(_) => LeagueErrorCode::ReplayRequestFailed,
+            LeagueError::StorageFailed(_) => LeagueErrorCode::StorageFailed,
+        }
+    }
+
+    /// Error a command failed with, for command-specific errors
+    pub fn cause(&self) -> Option<&LeagueError> {
+        match self {
+            LeagueError::ImportFailed(cause)
+            | LeagueError::ReplayRequestFailed(cause)
+            | LeagueError::StorageFailed(cause) => Some(cause),
+            _ => None,
+        }
+    }
+
+    pub fn command_error(&self) -> CommandError {
+        let cause = self.cause().map(LeagueError::code);
+        CommandError {
+            code: self.code(),
+            message: self.to_string(),
+            retryable: cause.unwrap_or(self.code()).is_retryable(),
+            cause,
+        }
+    }
+
+    /// Command reply for this error
+    pub fn response(&self) -> Value {
+        json!({ "error": self.command_error() })
+    }
+}
+
 // Alias for compatibility with code that uses AppError
 pub type AppError = LeagueError;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_response_carries_code() {
+        let response = LeagueError::LeagueNotRunning.response();
+        assert_eq!(response["error"]["code"], "LCU_NOT_RUNNING");
+        assert_eq!(response["error"]["retryable"], true);
+
+        let response = LeagueError::NotFound("No raw data for match 42".into()).response();
+        assert_eq!(response["error"]["code"], "NOT_FOUND");
+        assert_eq!(response["error"]["retryable"], false);
+        assert!(response["error"].get("cause").is_none());
+    }
+
+    #[test]
+    fn test_command_error_names_the_command_and_its_cause() {
+        let error = LeagueError::ImportFailed(Box::new(LeagueError::LeagueNotRunning));
+        let response = error.response();
+        assert_eq!(response["error"]["code"], "IMPORT_FAILED");
+        assert_eq!(response["error"]["cause"], "LCU_NOT_RUNNING");
+        assert_eq!(response["error"]["retryable"], true);
+
+        let error = LeagueError::StorageFailed(Box::new(LeagueError::ParseError("bad".into())));
+        assert_eq!(error.response()["error"]["retryable"], false);
+    }
+}
@@ -0,0 +1,117 @@
+//! Catalog of emitted event types
+//!
+//! Lists every event type the pack can emit with an English name, the game
+//! modes it occurs in and its default clip capture, so the main daemon and
+//! UI can build trigger configuration screens without League knowledge.
+//! Event types without a trigger rule are reported with the default capture
+//! window and never clip by default.
+
+use crate::protocol::{CatalogMode, EventCatalogEntry};
+use crate::{trigger_kind, LeagueEventType, TriggerConfig, TriggerKind};
+
+/// Pack-level events that aren't Live Client event types, none of which
+/// has a trigger rule: (event type, name, modes)
+const PACK_EVENTS: &[(&str, &str, &[CatalogMode])] = &[
+    (
+        "HighlightWindow",
+        "Highlight",
+        &[CatalogMode::League, CatalogMode::Arena],
+    ),
+    ("GoldLeadChange", "Gold lead change", &[CatalogMode::League]),
+    ("GoldSwing", "Gold swing", &[CatalogMode::League]),
+    (
+        "Bookmark",
+        "Bookmarked moment",
+        &[CatalogMode::League, CatalogMode::Tft, CatalogMode::Arena],
+    ),
+    ("TftRoundStart", "Round start", &[CatalogMode::Tft]),
+    ("TftRoundEnd", "Round end", &[CatalogMode::Tft]),
+    ("TftFightWon", "Fight won", &[CatalogMode::Tft]),
+    ("TftFightLost", "Fight lost", &[CatalogMode::Tft]),
+    ("TftCarousel", "Carousel", &[CatalogMode::Tft]),
+    (
+        "TftPlayerEliminated",
+        "Player eliminated",
+        &[CatalogMode::Tft],
+    ),
+    ("TftTopFour", "Top four", &[CatalogMode::Tft]),
+];
+
+/// Modes a Live Client event type occurs in; Arena has no structures,
+/// epic monsters or wards
+fn modes_for(event_type: &LeagueEventType) -> Vec<CatalogMode> {
+    match event_type {
+        LeagueEventType::TurretKilled
+        | LeagueEventType::InhibKilled
+        | LeagueEventType::DragonKill
+        | LeagueEventType::HeraldKill
+        | LeagueEventType::BaronKill
+        | LeagueEventType::InhibRespawningSoon
+        | LeagueEventType::InhibRespawned
+        | LeagueEventType::ObjectiveContested
+        | LeagueEventType::ObjectiveSteal
+        | LeagueEventType::WardPlaced
+        | LeagueEventType::ControlWardBought
+        | LeagueEventType::Ace => vec![CatalogMode::League],
+        _ => vec![CatalogMode::League, CatalogMode::Arena],
+    }
+}
+
+fn entry(
+    event_type: &str,
+    name: &str,
+    modes: Vec<CatalogMode>,
+    trigger: Option<TriggerKind>,
+    defaults: &TriggerConfig,
+) -> EventCatalogEntry {
+    let rule = trigger.map(|kind| defaults.rule(kind)).unwrap_or_default();
+    EventCatalogEntry {
+        event_type: event_type.to_string(),
+        name: name.to_string(),
+        modes,
+        trigger,
+        enabled_by_default: rule.enabled,
+        pre_secs: rule.pre_secs,
+        post_secs: rule.post_secs,
+    }
+}
+
+/// Every event type the pack can emit, Live Client types first
+pub fn event_catalog() -> Vec<EventCatalogEntry> {
+    let defaults = TriggerConfig::default();
+    let live = LeagueEventType::ALL.iter().map(|event_type| {
+        entry(
+            event_type.as_str(),
+            event_type.display_name(),
+            modes_for(event_type),
+            trigger_kind(event_type),
+            &defaults,
+        )
+    });
+    let pack = PACK_EVENTS
+        .iter()
+        .map(|(event_type, name, modes)| entry(event_type, name, modes.to_vec(), None, &defaults));
+    live.chain(pack).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalog_covers_modes_with_default_capture() {
+        let catalog = event_catalog();
+        let find = |event_type: &str| catalog.iter().find(|e| e.event_type == event_type).unwrap();
+
+        let baron = find("BaronKill");
+        assert_eq!(baron.trigger, Some(TriggerKind::Baron));
+        assert!(baron.enabled_by_default);
+        assert_eq!(baron.pre_secs, crate::DEFAULT_PRE_SECS);
+        assert_eq!(baron.modes, vec![CatalogMode::League]);
+
+        assert!(!find("TurretKilled").enabled_by_default);
+        assert!(find("ChampionKill").modes.contains(&CatalogMode::Arena));
+        assert_eq!(find("TftTopFour").modes, vec![CatalogMode::Tft]);
+        assert!(catalog.iter().all(|e| e.event_type != "Unknown"));
+    }
+}
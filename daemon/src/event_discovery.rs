@@ -0,0 +1,155 @@
+//! Raw event payload retention
+//!
+//! Live Client events are parsed into a fixed set of fields, so whatever
+//! else Riot adds to an event (or a new event type altogether) is lost.
+//! Depending on `StatsSettings::raw_event_retention`, raw payloads are
+//! appended to the `league_event_discovery` log (JSON lines next to the stats
+//! file), so new formats can be analyzed and added to `LeagueEventType`.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+use crate::{LeagueEventType, RawEventRetention, Result, StatsSettings};
+
+/// File name of the discovery log inside the data directory
+pub(crate) const DISCOVERY_FILE: &str = "league_event_discovery.jsonl";
+
+/// A raw event payload as received from the Live Client API
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscoveredEvent {
+    pub event_name: String,
+    /// External match ID, when the match was known
+    pub match_id: Option<String>,
+    pub recorded_at: DateTime<Utc>,
+    pub payload: Value,
+}
+
+/// Append-only store of raw event payloads
+#[derive(Debug, Default)]
+pub struct EventDiscovery {
+    /// `None` keeps nothing (retention off or no data directory)
+    path: Option<PathBuf>,
+    retention: RawEventRetention,
+}
+
+impl EventDiscovery {
+    /// Open the log in the data directory from the given settings
+    pub fn open(settings: &StatsSettings) -> Self {
+        if settings.raw_event_retention == RawEventRetention::None {
+            return Self::default();
+        }
+
+        match settings.data_dir() {
+            Some(dir) => Self::open_at(dir.join(DISCOVERY_FILE), settings.raw_event_retention),
+            None => {
+                warn!("No data directory available, raw events will not be persisted");
+                Self::default()
+            }
+        }
+    }
+
+    /// Open the log at an explicit file path
+    pub fn open_at(path: PathBuf, retention: RawEventRetention) -> Self {
+        debug!("Event discovery log at {:?} ({:?})", path, retention);
+        Self {
+            path: Some(path),
+            retention,
+        }
+    }
+
+    /// Whether the payload of an event type is kept
+    pub fn retains(&self, event_name: &str) -> bool {
+        match self.retention {
+            RawEventRetention::None => false,
+            RawEventRetention::Unknown => {
+                LeagueEventType::from(event_name) == LeagueEventType::Unknown
+            }
+            RawEventRetention::All => true,
+        }
+    }
+
+    /// Keep a raw payload if the retention policy asks for it
+    pub fn record(&self, match_id: Option<&str>, event_name: &str, payload: &Value) {
+        let Some(ref path) = self.path else {
+            return;
+        };
+        if !self.retains(event_name) {
+            return;
+        }
+
+        let event = DiscoveredEvent {
+            event_name: event_name.to_string(),
+            match_id: match_id.map(String::from),
+            recorded_at: Utc::now(),
+            payload: payload.clone(),
+        };
+        if let Err(e) = append_event(path, &event) {
+            warn!("Failed to save raw event to {:?}: {}", path, e);
+        }
+    }
+
+    /// All kept payloads, oldest first
+    pub fn events(&self) -> Result<Vec<DiscoveredEvent>> {
+        let Some(ref path) = self.path else {
+            return Ok(Vec::new());
+        };
+        let file = match std::fs::File::open(path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut events = Vec::new();
+        for line in BufReader::new(file).lines() {
+            // Skip lines torn by a crash mid-write
+            match serde_json::from_str::<DiscoveredEvent>(&line?) {
+                Ok(event) => events.push(event),
+                Err(e) => debug!("Skipping unreadable discovery line: {}", e),
+            }
+        }
+        Ok(events)
+    }
+}
+
+fn append_event(path: &Path, event: &DiscoveredEvent) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut line = serde_json::to_vec(event)?;
+    line.push(b'\n');
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(&line)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+    use serde_json::json;
+
+    #[test]
+    fn test_only_unknown_event_types_are_kept() {
+        let dir = TempDir::new("discovery");
+        let discovery =
+            EventDiscovery::open_at(dir.join(DISCOVERY_FILE), RawEventRetention::Unknown);
+
+        let kill = json!({ "EventID": 3, "EventName": "ChampionKill", "EventTime": 300.0 });
+        let feat = json!({ "EventID": 4, "EventName": "FeatUpdate", "EventTime": 310.0 });
+        discovery.record(Some("42"), "ChampionKill", &kill);
+        discovery.record(Some("42"), "FeatUpdate", &feat);
+
+        let events = discovery.events().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_name, "FeatUpdate");
+        assert_eq!(events[0].payload, feat);
+    }
+}
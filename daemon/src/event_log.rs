@@ -0,0 +1,227 @@
+//! Persisted game event timeline
+//!
+//! Every event polled during a match is appended to that match's log in the
+//! `league_game_events` directory (one JSON-lines file per match, next to
+//! the stats file) so the UI can render a full match timeline. Appending
+//! keeps writes cheap while a game is running, and reading a timeline only
+//! touches its own match's file.
+//!
+//! Older versions kept every match in a single `league_game_events.jsonl`;
+//! it is split into per-match logs the first time the log is opened.
+
+use std::collections::BTreeMap;
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, warn};
+
+use crate::protocol::GameEvent;
+use crate::{Result, StatsSettings, StoredGameEvent};
+
+/// Directory of per-match event logs inside the data directory
+pub(crate) const EVENTS_DIR: &str = "league_game_events";
+
+/// Single log of all matches written by older versions
+const LEGACY_EVENTS_FILE: &str = "league_game_events.jsonl";
+
+/// Append-only store of game events, one log per match
+#[derive(Debug, Default)]
+pub struct EventLog {
+    /// `None` keeps nothing (no data directory)
+    dir: Option<PathBuf>,
+}
+
+impl EventLog {
+    /// Open the log in the data directory from the given settings
+    pub fn open(settings: &StatsSettings) -> Self {
+        match settings.data_dir() {
+            Some(dir) => {
+                let log = Self::open_at(dir.join(EVENTS_DIR));
+                log.split_legacy_log(&dir.join(LEGACY_EVENTS_FILE));
+                log
+            }
+            None => {
+                warn!("No data directory available, game events will not be persisted");
+                Self::default()
+            }
+        }
+    }
+
+    /// Open the log in an explicit directory
+    pub fn open_at(dir: PathBuf) -> Self {
+        debug!("Event log at {:?}", dir);
+        Self { dir: Some(dir) }
+    }
+
+    /// Append a match's events
+    pub fn append(&self, match_id: &str, events: &[GameEvent]) {
+        let Some(path) = self.path_for(match_id) else {
+            return;
+        };
+        let stored: Vec<_> = events.iter().map(|e| to_stored(match_id, e)).collect();
+        if let Err(e) = append_events(&path, &stored) {
+            warn!("Failed to save game events to {:?}: {}", path, e);
+        }
+    }
+
+    /// All stored events of a match, in the order they were polled
+    pub fn events_for(&self, match_id: &str) -> Result<Vec<StoredGameEvent>> {
+        let Some(path) = self.path_for(match_id) else {
+            return Ok(Vec::new());
+        };
+        let file = match std::fs::File::open(&path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut events = Vec::new();
+        for line in BufReader::new(file).lines() {
+            // Skip lines torn by a crash mid-write
+            match serde_json::from_str::<StoredGameEvent>(&line?) {
+                Ok(event) => events.push(event),
+                Err(e) => debug!("Skipping unreadable event line: {}", e),
+            }
+        }
+        Ok(events)
+    }
+
+    /// Log of a match; `None` without a data directory or for IDs that
+    /// aren't safe as a file name
+    fn path_for(&self, match_id: &str) -> Option<PathBuf> {
+        let safe = !match_id.is_empty()
+            && match_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !safe {
+            debug!("Not persisting events of match {:?}", match_id);
+            return None;
+        }
+        Some(self.dir.as_ref()?.join(format!("{}.jsonl", match_id)))
+    }
+
+    /// Move the events of the single log older versions wrote into the
+    /// per-match logs, then remove it
+    fn split_legacy_log(&self, legacy: &Path) {
+        let file = match std::fs::File::open(legacy) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return,
+            Err(e) => {
+                warn!("Failed to open legacy event log {:?}: {}", legacy, e);
+                return;
+            }
+        };
+
+        let mut by_match: BTreeMap<String, Vec<StoredGameEvent>> = BTreeMap::new();
+        for line in BufReader::new(file).lines().map_while(|line| line.ok()) {
+            if let Ok(event) = serde_json::from_str::<StoredGameEvent>(&line) {
+                by_match.entry(event.match_id.clone()).or_default().push(event);
+            }
+        }
+
+        for (match_id, events) in &by_match {
+            let Some(path) = self.path_for(match_id) else {
+                continue;
+            };
+            if let Err(e) = append_events(&path, events) {
+                // Keep the legacy log so nothing is lost; retried next start
+                warn!("Failed to split legacy event log into {:?}: {}", path, e);
+                return;
+            }
+        }
+        match std::fs::remove_file(legacy) {
+            Ok(()) => info!("Split legacy event log into {} match logs", by_match.len()),
+            Err(e) => warn!("Failed to remove legacy event log {:?}: {}", legacy, e),
+        }
+    }
+}
+
+/// Event as stored: game time, killer/victim/assisters and the rest of the
+/// event data, flagged when it triggered a clip
+fn to_stored(match_id: &str, event: &GameEvent) -> StoredGameEvent {
+    let has_clip = event
+        .data
+        .pointer("/trigger_decision/triggered")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    StoredGameEvent {
+        id: uuid::Uuid::new_v4().to_string(),
+        match_id: match_id.to_string(),
+        event_type: event.event_type.clone(),
+        event_time_secs: event.timestamp_secs,
+        data: event.data.clone(),
+        has_clip,
+    }
+}
+
+fn append_events(path: &Path, events: &[StoredGameEvent]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut lines = Vec::new();
+    for event in events {
+        serde_json::to_writer(&mut lines, event)?;
+        lines.push(b'\n');
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(&lines)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+    use serde_json::json;
+
+    #[test]
+    fn test_events_round_trip_per_match() {
+        let dir = TempDir::new("events");
+        let log = EventLog::open_at(dir.join(EVENTS_DIR));
+
+        let kill = GameEvent::new(
+            "ChampionKill".to_string(),
+            312.5,
+            json!({
+                "killer_name": "Me",
+                "victim_name": "Them",
+                "assisters": ["Duo"],
+                "trigger_decision": { "triggered": true },
+            }),
+        );
+        let turret = GameEvent::new("TurretKilled".to_string(), 700.0, json!({}));
+        log.append("match-1", &[kill]);
+        log.append("match-2", &[turret]);
+
+        let events = log.events_for("match-1").unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, "ChampionKill");
+        assert_eq!(events[0].event_time_secs, 312.5);
+        assert_eq!(events[0].data["assisters"], json!(["Duo"]));
+        assert!(events[0].has_clip);
+        assert!(log.events_for("match-3").unwrap().is_empty());
+        assert!(dir.join(EVENTS_DIR).join("match-2.jsonl").exists());
+    }
+
+    #[test]
+    fn test_legacy_log_is_split_per_match() {
+        let dir = TempDir::new("events_legacy");
+        let stored = |match_id: &str| {
+            let event = GameEvent::new("TurretKilled".to_string(), 700.0, json!({}));
+            serde_json::to_string(&to_stored(match_id, &event)).unwrap()
+        };
+        let legacy = dir.join(LEGACY_EVENTS_FILE);
+        let lines = format!("{}\n{}\n{}\n{{\"matchI", stored("m1"), stored("m2"), stored("m1"));
+        std::fs::write(&legacy, lines).unwrap();
+
+        let log = EventLog::open_at(dir.join(EVENTS_DIR));
+        log.split_legacy_log(&legacy);
+
+        assert!(!legacy.exists());
+        assert_eq!(log.events_for("m1").unwrap().len(), 2);
+        assert_eq!(log.events_for("m2").unwrap().len(), 1);
+    }
+}
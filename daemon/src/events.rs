@@ -1,4 +1,20 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::riot_ids_match;
+
+/// Kills without dying for a killing spree
+const SPREE_KILLS: u32 = 3;
+
+/// Kills within this many seconds belong to the same multikill (a double
+/// kill escalating to a triple is reported as two `Multikill` events)
+const MULTIKILL_KILL_WINDOW_SECS: f64 = 10.0;
+
+/// Max seconds between multikills for them to count as one chain
+const MULTIKILL_CHAIN_WINDOW_SECS: f64 = 30.0;
+
+/// Seconds of lead-in for a clip of a single synthesized kill event
+const CLIP_LEAD_SECS: f64 = 10.0;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "PascalCase")]
@@ -16,6 +32,22 @@ pub enum LeagueEventType {
     BaronKill,
     InhibRespawningSoon,
     InhibRespawned,
+    /// Synthesized: 3+ kills without dying
+    KillingSpree,
+    /// Synthesized: a player on a killing spree was killed
+    Shutdown,
+    /// Synthesized: assist on the game's first kill
+    FirstBloodAssist,
+    /// Synthesized: back-to-back multikills by one player
+    MultikillChain,
+    /// Synthesized: an epic monster taken in a fight (or stolen)
+    ObjectiveContested,
+    /// Synthesized: a dragon or Baron stolen from the team fighting for it
+    ObjectiveSteal,
+    /// Synthesized: the player placed a control ward (inventory heuristic)
+    WardPlaced,
+    /// Synthesized: the player bought a control ward
+    ControlWardBought,
     #[serde(other)]
     Unknown,
 }
@@ -36,11 +68,102 @@ impl From<&str> for LeagueEventType {
             "BaronKill" => LeagueEventType::BaronKill,
             "InhibRespawningSoon" => LeagueEventType::InhibRespawningSoon,
             "InhibRespawned" => LeagueEventType::InhibRespawned,
+            "KillingSpree" => LeagueEventType::KillingSpree,
+            "Shutdown" => LeagueEventType::Shutdown,
+            "FirstBloodAssist" => LeagueEventType::FirstBloodAssist,
+            "MultikillChain" => LeagueEventType::MultikillChain,
+            "ObjectiveContested" => LeagueEventType::ObjectiveContested,
+            "ObjectiveSteal" => LeagueEventType::ObjectiveSteal,
+            "WardPlaced" => LeagueEventType::WardPlaced,
+            "ControlWardBought" => LeagueEventType::ControlWardBought,
             _ => LeagueEventType::Unknown,
         }
     }
 }
 
+impl LeagueEventType {
+    /// All known event types
+    pub const ALL: [LeagueEventType; 21] = [
+        LeagueEventType::GameStart,
+        LeagueEventType::GameEnd,
+        LeagueEventType::ChampionKill,
+        LeagueEventType::Multikill,
+        LeagueEventType::Ace,
+        LeagueEventType::FirstBlood,
+        LeagueEventType::TurretKilled,
+        LeagueEventType::InhibKilled,
+        LeagueEventType::DragonKill,
+        LeagueEventType::HeraldKill,
+        LeagueEventType::BaronKill,
+        LeagueEventType::InhibRespawningSoon,
+        LeagueEventType::InhibRespawned,
+        LeagueEventType::KillingSpree,
+        LeagueEventType::Shutdown,
+        LeagueEventType::FirstBloodAssist,
+        LeagueEventType::MultikillChain,
+        LeagueEventType::ObjectiveContested,
+        LeagueEventType::ObjectiveSteal,
+        LeagueEventType::WardPlaced,
+        LeagueEventType::ControlWardBought,
+    ];
+
+    /// English name for settings screens
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            LeagueEventType::GameStart => "Game start",
+            LeagueEventType::GameEnd => "Game end",
+            LeagueEventType::ChampionKill => "Champion kill",
+            LeagueEventType::Multikill => "Multikill",
+            LeagueEventType::Ace => "Ace",
+            LeagueEventType::FirstBlood => "First blood",
+            LeagueEventType::TurretKilled => "Turret destroyed",
+            LeagueEventType::InhibKilled => "Inhibitor destroyed",
+            LeagueEventType::DragonKill => "Dragon slain",
+            LeagueEventType::HeraldKill => "Rift Herald slain",
+            LeagueEventType::BaronKill => "Baron slain",
+            LeagueEventType::InhibRespawningSoon => "Inhibitor respawning soon",
+            LeagueEventType::InhibRespawned => "Inhibitor respawned",
+            LeagueEventType::KillingSpree => "Killing spree",
+            LeagueEventType::Shutdown => "Shutdown",
+            LeagueEventType::FirstBloodAssist => "First blood assist",
+            LeagueEventType::MultikillChain => "Multikill chain",
+            LeagueEventType::ObjectiveContested => "Contested objective",
+            LeagueEventType::ObjectiveSteal => "Objective stolen",
+            LeagueEventType::WardPlaced => "Control ward placed",
+            LeagueEventType::ControlWardBought => "Control ward bought",
+            LeagueEventType::Unknown => "Unknown event",
+        }
+    }
+
+    /// Event name as used by the Live Client API (and for synthesized events)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LeagueEventType::GameStart => "GameStart",
+            LeagueEventType::GameEnd => "GameEnd",
+            LeagueEventType::ChampionKill => "ChampionKill",
+            LeagueEventType::Multikill => "Multikill",
+            LeagueEventType::Ace => "Ace",
+            LeagueEventType::FirstBlood => "FirstBlood",
+            LeagueEventType::TurretKilled => "TurretKilled",
+            LeagueEventType::InhibKilled => "InhibKilled",
+            LeagueEventType::DragonKill => "DragonKill",
+            LeagueEventType::HeraldKill => "HeraldKill",
+            LeagueEventType::BaronKill => "BaronKill",
+            LeagueEventType::InhibRespawningSoon => "InhibRespawningSoon",
+            LeagueEventType::InhibRespawned => "InhibRespawned",
+            LeagueEventType::KillingSpree => "KillingSpree",
+            LeagueEventType::Shutdown => "Shutdown",
+            LeagueEventType::FirstBloodAssist => "FirstBloodAssist",
+            LeagueEventType::MultikillChain => "MultikillChain",
+            LeagueEventType::ObjectiveContested => "ObjectiveContested",
+            LeagueEventType::ObjectiveSteal => "ObjectiveSteal",
+            LeagueEventType::WardPlaced => "WardPlaced",
+            LeagueEventType::ControlWardBought => "ControlWardBought",
+            LeagueEventType::Unknown => "Unknown",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ParsedGameEvent {
@@ -50,4 +173,202 @@ pub struct ParsedGameEvent {
     pub victim_name: Option<String>,
     pub assisters: Vec<String>,
     pub is_player_involved: bool,
+    /// Multikill size, or spree length, shut-down streak or chain length of
+    /// synthesized events
+    #[serde(default)]
+    pub count: Option<u32>,
+    /// Where a clip of this event should start (synthesized events only)
+    #[serde(default)]
+    pub clip_start_secs: Option<f64>,
+}
+
+/// A player's run of multikills
+#[derive(Debug, Clone)]
+struct MultikillChain {
+    killer: String,
+    started_at: f64,
+    last_at: f64,
+    length: u32,
+}
+
+/// Derives streak events the Live Client API doesn't report (killing
+/// sprees, shutdowns, first blood assists, multikill chains) from the raw
+/// event stream
+#[derive(Debug, Clone, Default)]
+pub struct StreakTracker {
+    /// Kills since last death, per player
+    streaks: HashMap<String, u32>,
+    /// When each player's current streak started
+    streak_started: HashMap<String, f64>,
+    first_kill_seen: bool,
+    chain: Option<MultikillChain>,
+}
+
+impl StreakTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed a raw event; returns the events it completes
+    pub fn observe(&mut self, event: &ParsedGameEvent, player_name: &str) -> Vec<ParsedGameEvent> {
+        let is_player = |name: Option<&String>| {
+            !player_name.is_empty() && name.is_some_and(|n| riot_ids_match(n, player_name))
+        };
+        let synthesized = |event_type, count, clip_start_secs: f64, involved| ParsedGameEvent {
+            event_type,
+            event_time: event.event_time,
+            killer_name: event.killer_name.clone(),
+            victim_name: event.victim_name.clone(),
+            assisters: event.assisters.clone(),
+            is_player_involved: involved,
+            count: Some(count),
+            clip_start_secs: Some(clip_start_secs.max(0.0)),
+        };
+        let mut out = Vec::new();
+
+        match event.event_type {
+            LeagueEventType::ChampionKill => {
+                let lead_in = event.event_time - CLIP_LEAD_SECS;
+
+                if !self.first_kill_seen {
+                    self.first_kill_seen = true;
+                    if event.assisters.iter().any(|a| is_player(Some(a))) {
+                        out.push(synthesized(LeagueEventType::FirstBloodAssist, 1, lead_in, true));
+                    }
+                }
+
+                if let Some(ref victim) = event.victim_name {
+                    let ended = self.streaks.remove(victim).unwrap_or(0);
+                    self.streak_started.remove(victim);
+                    if ended >= SPREE_KILLS {
+                        let involved = is_player(event.killer_name.as_ref())
+                            || is_player(Some(victim))
+                            || event.assisters.iter().any(|a| is_player(Some(a)));
+                        out.push(synthesized(LeagueEventType::Shutdown, ended, lead_in, involved));
+                    }
+                }
+
+                if let Some(ref killer) = event.killer_name {
+                    let started = *self
+                        .streak_started
+                        .entry(killer.clone())
+                        .or_insert(event.event_time);
+                    let streak = self.streaks.entry(killer.clone()).or_insert(0);
+                    *streak += 1;
+                    if *streak >= SPREE_KILLS {
+                        let count = *streak;
+                        // The clip covers the whole spree
+                        let involved = is_player(Some(killer));
+                        out.push(synthesized(LeagueEventType::KillingSpree, count, started - CLIP_LEAD_SECS, involved));
+                    }
+                }
+            }
+            LeagueEventType::Multikill => {
+                let Some(ref killer) = event.killer_name else {
+                    return out;
+                };
+
+                let chain = match self.chain.take() {
+                    // The same multikill escalating
+                    Some(mut chain)
+                        if chain.killer == *killer
+                            && event.event_time - chain.last_at <= MULTIKILL_KILL_WINDOW_SECS =>
+                    {
+                        chain.last_at = event.event_time;
+                        self.chain = Some(chain);
+                        return out;
+                    }
+                    Some(mut chain)
+                        if chain.killer == *killer
+                            && event.event_time - chain.last_at <= MULTIKILL_CHAIN_WINDOW_SECS =>
+                    {
+                        chain.last_at = event.event_time;
+                        chain.length += 1;
+                        chain
+                    }
+                    _ => MultikillChain {
+                        killer: killer.clone(),
+                        started_at: event.event_time,
+                        last_at: event.event_time,
+                        length: 1,
+                    },
+                };
+
+                if chain.length >= 2 {
+                    out.push(synthesized(
+                        LeagueEventType::MultikillChain,
+                        chain.length,
+                        chain.started_at - CLIP_LEAD_SECS,
+                        is_player(Some(killer)),
+                    ));
+                }
+                self.chain = Some(chain);
+            }
+            _ => {}
+        }
+
+        out
+    }
+
+    /// Forget all streaks (new game)
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(event_type: LeagueEventType, time: f64, killer: &str, victim: &str, assisters: &[&str]) -> ParsedGameEvent {
+        ParsedGameEvent {
+            event_type,
+            event_time: time,
+            killer_name: (!killer.is_empty()).then(|| killer.to_string()),
+            victim_name: (!victim.is_empty()).then(|| victim.to_string()),
+            assisters: assisters.iter().map(|a| a.to_string()).collect(),
+            is_player_involved: false,
+            count: None,
+            clip_start_secs: None,
+        }
+    }
+
+    fn kinds(events: &[ParsedGameEvent]) -> Vec<(LeagueEventType, Option<u32>)> {
+        events.iter().map(|e| (e.event_type.clone(), e.count)).collect()
+    }
+
+    #[test]
+    fn test_spree_shutdown_and_first_blood_assist() {
+        let mut tracker = StreakTracker::new();
+        let kill = LeagueEventType::ChampionKill;
+
+        let out = tracker.observe(&event(kill.clone(), 100.0, "Zed", "Ahri", &["Me"]), "Me");
+        assert_eq!(kinds(&out), vec![(LeagueEventType::FirstBloodAssist, Some(1))]);
+
+        assert!(tracker.observe(&event(kill.clone(), 150.0, "Zed", "Lux", &[]), "Me").is_empty());
+        let out = tracker.observe(&event(kill.clone(), 200.0, "Zed", "Jinx", &[]), "Me");
+        assert_eq!(kinds(&out), vec![(LeagueEventType::KillingSpree, Some(3))]);
+        assert_eq!(out[0].clip_start_secs, Some(90.0));
+        assert!(!out[0].is_player_involved);
+
+        let out = tracker.observe(&event(kill, 260.0, "Me", "Zed", &[]), "Me");
+        assert_eq!(kinds(&out), vec![(LeagueEventType::Shutdown, Some(3))]);
+        assert!(out[0].is_player_involved);
+    }
+
+    #[test]
+    fn test_multikill_chain() {
+        let mut tracker = StreakTracker::new();
+        let multi = LeagueEventType::Multikill;
+
+        assert!(tracker.observe(&event(multi.clone(), 600.0, "Me", "", &[]), "Me").is_empty());
+        // Double kill turning into a triple is still one multikill
+        assert!(tracker.observe(&event(multi.clone(), 604.0, "Me", "", &[]), "Me").is_empty());
+        let out = tracker.observe(&event(multi.clone(), 624.0, "Me", "", &[]), "Me");
+        assert_eq!(kinds(&out), vec![(LeagueEventType::MultikillChain, Some(2))]);
+        assert_eq!(out[0].clip_start_secs, Some(590.0));
+
+        // Too late to continue the chain
+        assert!(tracker.observe(&event(multi, 700.0, "Me", "", &[]), "Me").is_empty());
+    }
 }
@@ -0,0 +1,107 @@
+//! Client FPS annotations for the match timeline
+//!
+//! Neither the Live Client API nor the LCU exposes the game's frame rate, so
+//! samples are reported by the daemon (which can read it from its capture
+//! pipeline) through `LeagueCommand::ReportFps`. Samples are thinned out to
+//! one timeline event per interval, except frame drops, which are always
+//! kept so clip review can line them up with deaths.
+
+use std::collections::VecDeque;
+
+/// Game seconds between two regular samples in the timeline
+pub const FPS_SAMPLE_INTERVAL_SECS: f64 = 5.0;
+
+/// A sample below this fraction of the recent average is a frame drop
+const FRAME_DROP_RATIO: f32 = 0.6;
+
+/// Recent samples the average is taken over
+const BASELINE_SAMPLES: usize = 12;
+
+/// Samples needed before frame drops are detected
+const MIN_BASELINE_SAMPLES: usize = 3;
+
+/// FPS sample to add to the timeline
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FpsSample {
+    pub game_time_secs: f64,
+    pub fps: f32,
+    /// Recent average the sample was compared against
+    pub baseline_fps: f32,
+    pub frame_drop: bool,
+}
+
+/// Reported FPS of the current game
+#[derive(Debug, Default)]
+pub struct FpsSampler {
+    recent: VecDeque<f32>,
+    last_emitted: Option<f64>,
+}
+
+impl FpsSampler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn reset(&mut self) {
+        self.recent.clear();
+        self.last_emitted = None;
+    }
+
+    /// Take a reported sample; returns it if it belongs in the timeline
+    pub fn record(&mut self, game_time_secs: f64, fps: f32) -> Option<FpsSample> {
+        if !fps.is_finite() || fps < 0.0 {
+            return None;
+        }
+
+        let baseline_fps = if self.recent.is_empty() {
+            fps
+        } else {
+            self.recent.iter().sum::<f32>() / self.recent.len() as f32
+        };
+        let frame_drop =
+            self.recent.len() >= MIN_BASELINE_SAMPLES && fps < baseline_fps * FRAME_DROP_RATIO;
+
+        if self.recent.len() == BASELINE_SAMPLES {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(fps);
+
+        let due = match self.last_emitted {
+            Some(last) => game_time_secs - last >= FPS_SAMPLE_INTERVAL_SECS,
+            None => true,
+        };
+        if !due && !frame_drop {
+            return None;
+        }
+        self.last_emitted = Some(game_time_secs);
+
+        Some(FpsSample {
+            game_time_secs,
+            fps,
+            baseline_fps,
+            frame_drop,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_samples_are_thinned_but_drops_kept() {
+        let mut sampler = FpsSampler::new();
+        assert!(sampler.record(1.0, 144.0).is_some());
+        assert!(sampler.record(2.0, 140.0).is_none());
+        assert!(sampler.record(3.0, 146.0).is_none());
+
+        let drop = sampler.record(4.0, 40.0).unwrap();
+        assert!(drop.frame_drop);
+        assert!((drop.baseline_fps - 143.3).abs() < 0.1);
+
+        assert!(sampler.record(5.0, 138.0).is_none());
+        let sample = sampler.record(9.0, 141.0).unwrap();
+        assert!(!sample.frame_drop);
+        assert!(sampler.record(10.0, f32::NAN).is_none());
+    }
+}
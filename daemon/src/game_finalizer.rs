@@ -1,9 +1,11 @@
 use anyhow::Result;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
 use tracing::{info, warn};
 
-use crate::{CreateMatch, LiveMatch, MatchResult, Participant, Team};
-use crate::{EndOfGameStats, LcuClient, LocalPlayerStats, RankedEntry};
+use crate::{normalize_role, riot_ids_match, ArenaMatchDetails, ArenaPartner, Badge, BadgeThresholds, ChampSelectDraft, CreateMatch, GameMode, GameModeContext, LiveMatch, MatchResult, MatchRunes, Participant, RankChange, Team};
+use crate::{lcu_connection, is_eog_leaver, DdragonResolver, EndOfGameStats, LcuFeature, LocalPlayerStats, RankTracker, RankedEntry, RawMatch, SourceDiff};
 
 /// Convert summoner spell ID to name
 fn spell_id_to_name(id: i32) -> String {
@@ -67,112 +69,427 @@ fn rune_tree_id_to_name(id: i32) -> String {
     .to_string()
 }
 
+/// `part` as a share of `total`, if the total is known
+fn share(part: i64, total: i64) -> Option<f64> {
+    (total > 0).then(|| part as f64 / total as f64)
+}
+
+/// A player's share of their team's champion damage
+fn team_damage_share(eog: &EndOfGameStats, team_id: i32, damage: i64) -> Option<f64> {
+    let team = eog.teams.iter().find(|t| t.team_id == team_id)?;
+    let total: i64 = team
+        .players
+        .iter()
+        .map(|p| p.stats.total_damage_dealt_to_champions)
+        .sum();
+    share(damage, total)
+}
+
+/// Placement, duo partner and augments of an Arena game (`None` for other modes)
+fn arena_details(eog: &EndOfGameStats, local: &LocalPlayerStats) -> Option<ArenaMatchDetails> {
+    if crate::from_api_key(&eog.game_mode) != &crate::ARENA {
+        return None;
+    }
+
+    let stats = &local.stats;
+    let placement = u8::try_from(stats.player_subteam_placement).ok().filter(|&p| p > 0)?;
+
+    // The partner is the other player in the same duo
+    let partner = eog
+        .teams
+        .iter()
+        .flat_map(|t| &t.players)
+        .find(|p| {
+            p.stats.player_subteam == stats.player_subteam
+                && !riot_ids_match(&p.summoner_name, &local.summoner_name)
+        })
+        .map(|p| ArenaPartner {
+            summoner_name: p.summoner_name.clone(),
+            champion: p.champion_name.clone(),
+        });
+
+    Some(ArenaMatchDetails {
+        game_id: eog.game_id,
+        placement,
+        champion: local.champion_name.clone(),
+        partner,
+        augment_ids: stats.augment_ids(),
+        played_at: Utc::now(),
+    })
+}
+
+/// The session's game mode, else the mode the game reported
+fn match_mode(game_mode: Option<&GameModeContext>, api_key: &str) -> &'static GameMode {
+    match game_mode {
+        Some(ctx) => ctx.game_mode(),
+        None => crate::from_api_key(api_key),
+    }
+}
+
+/// Badge thresholds for the session's game mode, else the end-of-game stats' mode
+fn badge_thresholds(game_mode: Option<&GameModeContext>, eog: &EndOfGameStats) -> BadgeThresholds {
+    BadgeThresholds::for_mode(match_mode(game_mode, &eog.game_mode))
+}
+
+/// Data fetched from the client when a game ends
+struct EndOfGameData {
+    /// End-of-game stats as received
+    payload: Option<Value>,
+    /// Parsed `payload` (`None` if it didn't parse)
+    stats: Option<EndOfGameStats>,
+    post_game_rank: Option<RankedEntry>,
+    /// Whether the gameflow session says the game was remade
+    remade: bool,
+}
+
+impl EndOfGameData {
+    async fn fetch() -> Self {
+        // Try to get end of game stats from LCU
+        let lcu = lcu_connection().client();
+        if lcu.is_none() {
+            warn!("Failed to connect to LCU");
+        }
+        let capabilities = lcu_connection().capabilities();
+        // Without the end-of-game endpoint the match comes from live data
+        let payload = match lcu {
+            Some(ref lcu) if capabilities.supports(LcuFeature::EndOfGame) => match lcu.get_end_of_game_payload().await {
+                Ok(payload) => Some(payload),
+                Err(e) => {
+                    warn!("Failed to get end of game stats: {}", e);
+                    None
+                }
+            },
+            _ => None,
+        };
+        let stats = payload.as_ref().and_then(|payload| {
+            serde_json::from_value::<EndOfGameStats>(payload.clone())
+                .map_err(|e| warn!("Failed to parse end of game stats: {}", e))
+                .ok()
+        });
+
+        // Get post-game rank for LP calculation
+        let post_game_rank = match lcu {
+            Some(ref lcu) if capabilities.supports(LcuFeature::Ranked) => lcu
+                .get_ranked_stats()
+                .await
+                .ok()
+                .and_then(|ranks| ranks.into_iter().find(|r| r.queue_type == "RANKED_SOLO_5x5")),
+            _ => None,
+        };
+
+        // The termination reason catches remakes the stats don't flag
+        let remade = match lcu {
+            Some(ref lcu) => lcu
+                .get_gameflow_session()
+                .await
+                .is_ok_and(|session| session.ended_in_remake()),
+            None => false,
+        };
+
+        Self {
+            payload,
+            stats,
+            post_game_rank,
+            remade,
+        }
+    }
+}
+
+/// What finalizing the game now would produce, without finalizing it
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FinalizationPreview {
+    /// The match that would be saved (`None` means it would come out empty)
+    pub game: Option<CreateMatch>,
+    /// "endOfGame" or "liveData"; `None` when neither was available
+    pub source: Option<&'static str>,
+    /// Whether the client returned end-of-game stats
+    pub end_of_game_received: bool,
+    /// Whether those stats parsed
+    pub end_of_game_parsed: bool,
+    pub live_match_cached: bool,
+    pub pre_game_rank_captured: bool,
+    pub rune_page_captured: bool,
+    pub draft_captured: bool,
+    /// Where the live snapshot disagrees with the end-of-game stats
+    pub source_diff: Option<SourceDiff>,
+}
+
 /// Service that finalizes game data when a match ends and saves it to the database
 pub struct GameFinalizer {
-    pre_game_rank: Option<RankedEntry>,
+    rank: RankTracker,
+    rune_page: Option<MatchRunes>,
+    draft: Option<ChampSelectDraft>,
+    /// Names for item, spell and rune IDs
+    resolver: DdragonResolver,
+    /// End-of-game payload of the last finalized game, for storing
+    raw_match: Option<RawMatch>,
 }
 
 impl GameFinalizer {
     pub fn new() -> Self {
         Self {
-            pre_game_rank: None,
+            rank: RankTracker::new(),
+            rune_page: None,
+            draft: None,
+            resolver: DdragonResolver::new(),
+            raw_match: None,
+        }
+    }
+
+    /// Use `resolver` for item, spell and rune names
+    pub fn set_resolver(&mut self, resolver: DdragonResolver) {
+        self.resolver = resolver;
+    }
+
+    /// Item name, or the ID as a string if Data Dragon isn't loaded
+    fn item_name(&self, id: i32) -> String {
+        self.resolver.item_name(id).unwrap_or_else(|| id.to_string())
+    }
+
+    /// Store the rune page the player locked in with
+    pub fn capture_rune_page(&mut self, runes: MatchRunes) {
+        if self.rune_page.as_ref() != Some(&runes) {
+            info!("Captured rune page: {}", runes.page_name);
+            self.rune_page = Some(runes);
         }
     }
 
+    /// Forget the captured rune page (e.g. after a dodge)
+    pub fn clear_rune_page(&mut self) {
+        self.rune_page = None;
+    }
+
+    /// Store the champ select draft of the current game
+    pub fn capture_draft(&mut self, draft: ChampSelectDraft) {
+        self.draft = Some(draft);
+    }
+
+    /// Draft of the current game, if one was captured
+    pub fn draft_mut(&mut self) -> Option<&mut ChampSelectDraft> {
+        self.draft.as_mut()
+    }
+
+    /// Drop everything captured for a game that will not be finalized
+    pub fn discard(&mut self) {
+        self.rank.clear();
+        self.rune_page = None;
+        self.draft = None;
+    }
+
+    /// Captured draft with champion names for its bans and picks
+    fn named_draft(&self) -> Option<ChampSelectDraft> {
+        let mut draft = self.draft.clone()?;
+        draft.name_champions(|id| self.resolver.champion_name(id));
+        Some(draft)
+    }
+
+    /// Role from the captured draft, else the first known `fallbacks` entry
+    fn role(&self, fallbacks: &[&str]) -> Option<String> {
+        self.draft
+            .as_ref()
+            .and_then(|d| normalize_role(&d.assigned_role))
+            .or_else(|| fallbacks.iter().find_map(|role| normalize_role(role)))
+    }
+
     /// Store the player's rank at the start of the game for LP calculation
     pub async fn capture_pre_game_rank(&mut self) {
-        if let Ok(lcu) = LcuClient::new() {
+        if !lcu_connection().supports(LcuFeature::Ranked) {
+            return;
+        }
+        if let Some(lcu) = lcu_connection().client() {
             if let Ok(ranks) = lcu.get_ranked_stats().await {
                 // Get Solo/Duo queue rank (RANKED_SOLO_5x5)
-                self.pre_game_rank = ranks
-                    .into_iter()
-                    .find(|r| r.queue_type == "RANKED_SOLO_5x5");
-
-                if let Some(ref rank) = self.pre_game_rank {
-                    info!(
-                        "Captured pre-game rank: {} {} ({}LP)",
-                        rank.tier, rank.division, rank.league_points
-                    );
-                }
+                self.rank.capture(
+                    ranks
+                        .into_iter()
+                        .find(|r| r.queue_type == "RANKED_SOLO_5x5"),
+                );
             }
         }
     }
 
     /// Finalize the game and return match data for saving
     /// Note: The caller (daemon actor) is responsible for saving to database
+    ///
+    /// `game_mode` selects the badge thresholds; without it the mode is taken
+    /// from the end-of-game stats. `game_started_at` dates the match; without
+    /// it the game is taken to have just ended.
     pub async fn finalize_game(
         &mut self,
         last_live_match: Option<LiveMatch>,
+        game_mode: Option<&GameModeContext>,
+        game_started_at: Option<DateTime<Utc>>,
     ) -> Result<Option<CreateMatch>> {
         info!("Finalizing game...");
 
-        // Try to get end of game stats from LCU
-        let eog_stats = match LcuClient::new() {
-            Ok(lcu) => match lcu.get_end_of_game_stats().await {
-                Ok(stats) => Some(stats),
-                Err(e) => {
-                    warn!("Failed to get end of game stats: {}", e);
-                    None
-                }
-            },
-            Err(e) => {
-                warn!("Failed to connect to LCU: {}", e);
-                None
-            }
-        };
+        let eog = EndOfGameData::fetch().await;
 
-        // Get post-game rank for LP calculation
-        let post_game_rank = if let Ok(lcu) = LcuClient::new() {
-            lcu.get_ranked_stats()
-                .await
-                .ok()
-                .and_then(|ranks| ranks.into_iter().find(|r| r.queue_type == "RANKED_SOLO_5x5"))
-        } else {
-            None
+        // LP change across promotions and demotions
+        let rank_change = self.rank.finish(eog.post_game_rank.as_ref());
+
+        // Create match record from available data
+        self.raw_match = None;
+        let Some((create_match, raw_match)) =
+            self.build_match(eog, rank_change, last_live_match, game_mode, game_started_at)
+        else {
+            warn!("No game data available to finalize");
+            return Ok(None);
         };
+        self.raw_match = raw_match;
 
-        // Calculate LP change
-        let lp_change = match (&self.pre_game_rank, &post_game_rank) {
-            (Some(pre), Some(post)) => {
-                // Simple LP diff - doesn't account for promotion/demotion
-                Some(post.league_points - pre.league_points)
-            }
-            _ => None,
+        // Clear everything captured before the game
+        self.discard();
+
+        Ok(create_match)
+    }
+
+    /// Run finalization against the data available now, without
+    /// forgetting anything captured for the game (for debugging empty
+    /// matches)
+    pub async fn preview_game(
+        &self,
+        last_live_match: Option<LiveMatch>,
+        game_mode: Option<&GameModeContext>,
+        game_started_at: Option<DateTime<Utc>>,
+    ) -> FinalizationPreview {
+        let eog = EndOfGameData::fetch().await;
+        let rank_change = self.rank.change(eog.post_game_rank.as_ref());
+
+        let mut preview = FinalizationPreview {
+            game: None,
+            source: None,
+            end_of_game_received: eog.payload.is_some(),
+            end_of_game_parsed: eog.stats.is_some(),
+            live_match_cached: last_live_match.is_some(),
+            pre_game_rank_captured: self.rank.is_captured(),
+            rune_page_captured: self.rune_page.is_some(),
+            draft_captured: self.draft.is_some(),
+            source_diff: None,
         };
+        let built = self.build_match(eog, rank_change, last_live_match, game_mode, game_started_at);
+        if let Some((game, raw)) = built {
+            preview.game = game;
+            preview.source_diff = raw.and_then(|raw| raw.source_diff);
+            preview.source = Some(if preview.end_of_game_parsed { "endOfGame" } else { "liveData" });
+        }
+        preview
+    }
 
+    /// Match record (and the payload to store with it) from end-of-game
+    /// stats, else from the last live snapshot; `None` without either.
+    /// With both, the snapshot is compared against the stats.
+    fn build_match(
+        &self,
+        eog: EndOfGameData,
+        rank_change: Option<RankChange>,
+        last_live_match: Option<LiveMatch>,
+        game_mode: Option<&GameModeContext>,
+        game_started_at: Option<DateTime<Utc>>,
+    ) -> Option<(Option<CreateMatch>, Option<RawMatch>)> {
         // Get current rank string
-        let rank_str = post_game_rank
+        let rank_str = eog
+            .post_game_rank
             .as_ref()
             .map(|r| format!("{} {}", r.tier, r.division));
+        let remade = eog.remade;
+        let annotate = |mut parsed: CreateMatch| {
+            if remade {
+                parsed.mark_remake();
+            }
+            if match_mode(game_mode, &parsed.game_mode).is_pve {
+                parsed.mark_pve();
+            }
+            parsed.queue = game_mode.cloned();
+            parsed.set_game_times(game_started_at);
+            parsed
+        };
 
-        // Create match record from available data
-        let create_match = if let Some(eog) = eog_stats {
-            self.create_match_from_eog(eog, lp_change, rank_str)
-        } else if let Some(live) = last_live_match {
-            self.create_match_from_live(live, lp_change, rank_str)
+        if let Some(stats) = eog.stats {
+            let thresholds = badge_thresholds(game_mode, &stats);
+            let create_match = self
+                .create_match_from_eog(stats, rank_change, rank_str, thresholds)
+                .map(annotate);
+            let raw_match = match (&create_match, eog.payload) {
+                (Some(parsed), Some(payload)) => {
+                    let mut raw = RawMatch::new(parsed, payload, game_mode);
+                    raw.source_diff = last_live_match.as_ref().map(|live| {
+                        let diff = SourceDiff::compare(live, parsed);
+                        diff.log();
+                        diff
+                    });
+                    Some(raw)
+                }
+                _ => None,
+            };
+            Some((create_match, raw_match))
         } else {
-            warn!("No game data available to finalize");
-            return Ok(None);
-        };
+            last_live_match.map(|live| {
+                let parsed = self.create_match_from_live(live, rank_change, rank_str).map(annotate);
+                (parsed, None)
+            })
+        }
+    }
 
-        // Clear pre-game rank
-        self.pre_game_rank = None;
+    /// End-of-game payload of the last finalized game (`None` if the match
+    /// came from live data)
+    pub fn take_raw_match(&mut self) -> Option<RawMatch> {
+        self.raw_match.take()
+    }
 
-        Ok(create_match)
+    /// Parse a stored end-of-game payload again, with the rune page, draft
+    /// and rank it was first parsed with
+    pub fn reprocess(&self, raw: &RawMatch) -> Result<Option<CreateMatch>> {
+        let eog: EndOfGameStats = serde_json::from_value(raw.payload.clone())?;
+        let thresholds = badge_thresholds(raw.game_mode.as_ref(), &eog);
+
+        let finalizer = GameFinalizer {
+            rune_page: raw.runes.clone(),
+            draft: raw.draft.clone(),
+            resolver: self.resolver.clone(),
+            ..GameFinalizer::new()
+        };
+        let parsed = finalizer
+            .create_match_from_eog(eog, raw.rank_change.clone(), raw.rank.clone(), thresholds)
+            .map(|parsed| {
+                let mut parsed = CreateMatch {
+                    played_at: raw.played_at,
+                    puuid: raw.puuid.clone(),
+                    queue: raw.game_mode.clone(),
+                    ..parsed
+                };
+                if match_mode(raw.game_mode.as_ref(), &parsed.game_mode).is_pve {
+                    parsed.mark_pve();
+                }
+                parsed.set_game_times(None);
+                parsed
+            });
+        Ok(parsed)
     }
 
     /// Create match from end-of-game stats (most complete data)
     fn create_match_from_eog(
         &self,
         eog: EndOfGameStats,
-        lp_change: Option<i32>,
+        rank_change: Option<RankChange>,
         rank: Option<String>,
+        thresholds: BadgeThresholds,
     ) -> Option<CreateMatch> {
         let local = eog.local_player.as_ref()?;
         let stats = &local.stats;
-
-        // Determine win/loss
-        let result = if stats.win {
+        let arena = arena_details(&eog, local);
+        let mode = crate::from_api_key(&eog.game_mode);
+
+        // Determine win/loss (Arena: top two duos win)
+        let result = if eog.is_remake() {
+            MatchResult::Remake
+        } else if let Some(ref arena) = arena {
+            if arena.is_win() {
+                MatchResult::Win
+            } else {
+                MatchResult::Loss
+            }
+        } else if stats.win {
             MatchResult::Win
         } else {
             MatchResult::Loss
@@ -195,31 +512,62 @@ impl GameFinalizer {
             .map(|t| t.players.iter().map(|p| p.stats.champions_killed).sum())
             .unwrap_or(0);
 
-        let kill_participation = if team_kills > 0 {
+        // Modes without champion kills (Swarm) have none to take part in
+        let kill_participation = if team_kills > 0 && mode.has_kda {
             ((stats.champions_killed + stats.assists) as f64 / team_kills as f64 * 100.0) as i32
         } else {
             0
         };
 
+        let damage_share =
+            team_damage_share(&eog, local.team_id, stats.total_damage_dealt_to_champions);
+
         // Build participants list
+        let game_length = eog.game_length;
+        // Remakes don't move LP
+        let rank_change = rank_change.filter(|_| result != MatchResult::Remake);
         let participants: Vec<Participant> = eog
             .teams
             .iter()
             .flat_map(|t| {
                 let team = if t.team_id == 100 { Team::Blue } else { Team::Red };
+                let team_damage: i64 = t
+                    .players
+                    .iter()
+                    .map(|p| p.stats.total_damage_dealt_to_champions)
+                    .sum();
+                let team_gold: i32 = t.players.iter().map(|p| p.stats.gold_earned).sum();
+                let teammates = (t.players.len() as f64 - 1.0).max(1.0);
                 t.players.iter().map(move |p| Participant {
-                    summoner_name: p.summoner_name.clone(),
+                    summoner_name: if riot_ids_match(&p.summoner_name, &local.summoner_name) {
+                        p.summoner_name.clone()
+                    } else {
+                        p.riot_id()
+                    },
                     champion: p.champion_name.clone(),
-                    team: team.clone(),
+                    team,
+                    damage_share: share(p.stats.total_damage_dealt_to_champions, team_damage),
+                    kills: p.stats.champions_killed,
+                    deaths: p.stats.num_deaths,
+                    assists: p.stats.assists,
+                    left_game: is_eog_leaver(
+                        &p.stats,
+                        (team_gold - p.stats.gold_earned) as f64 / teammates,
+                        game_length,
+                    ),
+                    item_ids: Vec::new(),
                 })
             })
             .collect();
 
         // Compute badges from stats
-        let badges = self.compute_badges(local, &eog);
+        let earned = self.compute_badges(local, &eog, thresholds);
+        let badges = earned.iter().map(|b| b.label().to_string()).collect();
+        let badge_keys = earned.iter().map(|b| b.string_key().to_string()).collect();
 
         Some(CreateMatch {
             game_id: eog.game_id,
+            puuid: None,
             summoner_name: local.summoner_name.clone(),
             champion: local.champion_name.clone(),
             champion_level: stats.level,
@@ -232,19 +580,53 @@ impl GameFinalizer {
             vision_score: stats.vision_score,
             kill_participation,
             damage_dealt: stats.total_damage_dealt_to_champions,
+            damage_share,
             game_mode: eog.game_mode.clone(),
+            queue: None,
             played_at: Utc::now(),
+            started_at: None,
+            ended_at: None,
             duration_secs: eog.game_length,
-            lp_change,
+            lp_change: rank_change.as_ref().map(|c| c.lp_change),
             rank,
-            summoner_spell1: spell_id_to_name(local.spell1_id),
-            summoner_spell2: spell_id_to_name(local.spell2_id),
-            keystone_rune: keystone_id_to_name(local.perk0),
-            secondary_tree: rune_tree_id_to_name(local.perk_sub_style),
-            items: local.items.iter().take(6).map(|i| format!("{}", i)).collect(),
-            trinket: local.items.get(6).map(|i| format!("{}", i)),
+            rank_change,
+            side: Team::from_team_id(local.team_id),
+            latency: None,
+            objective_participation: None,
+            season_id: None,
+            split_id: None,
+            summoner_spell1: self
+                .resolver
+                .spell_name(local.spell1_id)
+                .unwrap_or_else(|| spell_id_to_name(local.spell1_id)),
+            summoner_spell2: self
+                .resolver
+                .spell_name(local.spell2_id)
+                .unwrap_or_else(|| spell_id_to_name(local.spell2_id)),
+            keystone_rune: self
+                .resolver
+                .rune_name(local.perk0)
+                .unwrap_or_else(|| keystone_id_to_name(local.perk0)),
+            secondary_tree: self
+                .resolver
+                .rune_name(local.perk_sub_style)
+                .unwrap_or_else(|| rune_tree_id_to_name(local.perk_sub_style)),
+            runes: self.rune_page.clone(),
+            role: self.role(&[&local.selected_position, &local.detected_team_position]),
+            draft: self.named_draft(),
+            arena,
+            items: local.items.iter().take(6).map(|&i| self.item_name(i)).collect(),
+            trinket: local.items.get(6).map(|&i| self.item_name(i)),
+            item_ids: local.items.iter().take(6).copied().collect(),
+            trinket_id: local.items.get(6).copied(),
+            leavers: participants
+                .iter()
+                .filter(|p| p.left_game)
+                .map(|p| p.summoner_name.clone())
+                .collect(),
             participants,
             badges,
+            badge_keys,
         })
     }
 
@@ -252,7 +634,7 @@ impl GameFinalizer {
     fn create_match_from_live(
         &self,
         live: LiveMatch,
-        lp_change: Option<i32>,
+        rank_change: Option<RankChange>,
         rank: Option<String>,
     ) -> Option<CreateMatch> {
         // We can't determine win/loss from live data alone
@@ -274,24 +656,50 @@ impl GameFinalizer {
             .map(|p| p.kills)
             .sum();
 
-        let kill_participation = if team_kills > 0 {
+        let kill_participation = if team_kills > 0 && crate::from_api_key(&live.game_mode).has_kda {
             ((live.kills + live.assists) as f64 / team_kills as f64 * 100.0) as i32
         } else {
             0
         };
 
+        let position = live
+            .participants
+            .iter()
+            .find(|p| riot_ids_match(&p.summoner_name, &live.summoner_name))
+            .map(|p| p.position.clone())
+            .unwrap_or_default();
+
         let participants: Vec<Participant> = live
             .participants
             .iter()
             .map(|p| Participant {
                 summoner_name: p.summoner_name.clone(),
                 champion: p.champion.clone(),
-                team: p.team.clone(),
+                team: p.team,
+                damage_share: None, // Not available from live data
+                kills: p.kills,
+                deaths: p.deaths,
+                assists: p.assists,
+                left_game: false, // Flagged by the integration's leaver tracker
+                item_ids: p.items.iter().map(|i| i.item_id).collect(),
             })
             .collect();
 
+        // Prefer the rune page captured at lock-in over the live client's runes
+        let (keystone_rune, secondary_tree) = match self.rune_page {
+            Some(ref page) => (
+                page.keystone_id().map(keystone_id_to_name).unwrap_or_default(),
+                rune_tree_id_to_name(page.secondary_tree_id),
+            ),
+            None => (
+                live.runes.as_ref().map(|r| r.keystone_name.clone()).unwrap_or_default(),
+                live.runes.as_ref().map(|r| r.secondary_tree_name.clone()).unwrap_or_default(),
+            ),
+        };
+
         Some(CreateMatch {
             game_id: 0, // Unknown from live data
+            puuid: None,
             summoner_name: live.summoner_name,
             champion: live.champion,
             champion_level: live.level,
@@ -304,41 +712,65 @@ impl GameFinalizer {
             vision_score: 0, // Not available from live data
             kill_participation,
             damage_dealt: 0, // Not available from live data
+            damage_share: None,
             game_mode: live.game_mode,
+            queue: None,
             played_at: Utc::now(),
+            started_at: None,
+            ended_at: None,
             duration_secs: live.game_time_secs as i32,
-            lp_change,
+            lp_change: rank_change.as_ref().map(|c| c.lp_change),
             rank,
+            rank_change,
+            side: Some(live.team),
+            latency: None,
+            objective_participation: None,
+            season_id: None,
+            split_id: None,
             summoner_spell1: live.spell1.map(|s| s.name).unwrap_or_default(),
             summoner_spell2: live.spell2.map(|s| s.name).unwrap_or_default(),
-            keystone_rune: live.runes.as_ref().map(|r| r.keystone_name.clone()).unwrap_or_default(),
-            secondary_tree: live.runes.as_ref().map(|r| r.secondary_tree_name.clone()).unwrap_or_default(),
+            keystone_rune,
+            secondary_tree,
+            runes: self.rune_page.clone(),
+            role: self.role(&[&position]),
+            draft: self.named_draft(),
+            arena: None, // Placement is only in end-of-game stats
             items: live.items.iter().map(|i| i.name.clone()).collect(),
+            item_ids: live.items.iter().map(|i| i.item_id).collect(),
+            trinket_id: live.trinket.as_ref().map(|t| t.item_id),
             trinket: live.trinket.map(|t| t.name),
             participants,
+            leavers: vec![],
             badges: vec![],
+            badge_keys: vec![],
         })
     }
 
     /// Compute achievement badges from end of game stats
-    fn compute_badges(&self, local: &LocalPlayerStats, eog: &EndOfGameStats) -> Vec<String> {
+    fn compute_badges(
+        &self,
+        local: &LocalPlayerStats,
+        eog: &EndOfGameStats,
+        thresholds: BadgeThresholds,
+    ) -> Vec<Badge> {
         let mut badges = Vec::new();
         let stats = &local.stats;
+        let has_kda = crate::from_api_key(&eog.game_mode).has_kda;
 
         // Perfect game (no deaths)
-        if stats.num_deaths == 0 && (stats.champions_killed > 0 || stats.assists > 0) {
-            badges.push("Perfect".to_string());
+        if has_kda && stats.num_deaths == 0 && (stats.champions_killed > 0 || stats.assists > 0) {
+            badges.push(Badge::Perfect);
         }
 
-        // Legendary KDA (5+ KDA)
+        // Legendary KDA
         let kda = if stats.num_deaths > 0 {
             (stats.champions_killed + stats.assists) as f64 / stats.num_deaths as f64
         } else {
             (stats.champions_killed + stats.assists) as f64
         };
 
-        if kda >= 5.0 && stats.num_deaths > 0 {
-            badges.push("Legendary".to_string());
+        if has_kda && kda >= thresholds.legendary_kda && stats.num_deaths > 0 {
+            badges.push(Badge::Legendary);
         }
 
         // MVP candidate (most kills on winning team)
@@ -347,16 +779,25 @@ impl GameFinalizer {
             if let Some(t) = team {
                 let max_kills = t.players.iter().map(|p| p.stats.champions_killed).max().unwrap_or(0);
                 if stats.champions_killed == max_kills && max_kills > 0 {
-                    badges.push("MVP".to_string());
+                    badges.push(Badge::Mvp);
                 }
             }
         }
 
+        // Carry (large share of the team's champion damage)
+        let damage_share =
+            team_damage_share(eog, local.team_id, stats.total_damage_dealt_to_champions);
+        if damage_share.is_some_and(|s| s >= thresholds.carry_damage_share) {
+            badges.push(Badge::Carry);
+        }
+
         // High CS
         let game_mins = eog.game_length as f64 / 60.0;
         let total_cs = stats.minions_killed + stats.neutral_minions_killed;
-        if game_mins > 0.0 && total_cs as f64 / game_mins >= 8.0 {
-            badges.push("Farm Master".to_string());
+        if let Some(min_cs_per_min) = thresholds.farm_master_cs_per_min {
+            if game_mins > 0.0 && total_cs as f64 / game_mins >= min_cs_per_min {
+                badges.push(Badge::FarmMaster);
+            }
         }
 
         badges
@@ -368,3 +809,197 @@ impl Default for GameFinalizer {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(name: &str, champion: &str, subteam: i32, placement: i32) -> serde_json::Value {
+        serde_json::json!({
+            "championName": champion,
+            "summonerName": name,
+            "stats": {
+                "assists": 0, "championsKilled": 0, "numDeaths": 0, "minionsKilled": 0,
+                "neutralMinionsKilled": 0, "visionScore": 0, "totalDamageDealtToChampions": 0,
+                "goldEarned": 0, "level": 18,
+                "playerSubteam": subteam, "playerSubteamPlacement": placement,
+                "playerAugment1": 1205, "playerAugment2": 0, "playerAugment3": 1120
+            }
+        })
+    }
+
+    /// `player` as the end-of-game `localPlayer`, on `team_id`
+    fn local_player(mut player: serde_json::Value, team_id: i32) -> serde_json::Value {
+        player.as_object_mut().unwrap().extend(
+            serde_json::json!({
+                "spell1Id": 4, "spell2Id": 14, "teamId": team_id,
+                "items": [], "perk0": 0, "perkSubStyle": 0
+            })
+            .as_object()
+            .unwrap()
+            .clone(),
+        );
+        player
+    }
+
+    #[test]
+    fn test_arena_details_placement_partner_and_augments() {
+        let me = player("Me", "Sett", 3, 2);
+        let local = local_player(me.clone(), 300);
+
+        let eog: EndOfGameStats = serde_json::from_value(serde_json::json!({
+            "gameId": 42,
+            "gameMode": "CHERRY",
+            "gameLength": 1100,
+            "gameType": "MATCHED_GAME",
+            "localPlayer": local,
+            "teams": [
+                {"teamId": 100, "isWinningTeam": false, "players": [me, player("Duo", "Zyra", 3, 2)]},
+                {"teamId": 200, "isWinningTeam": true, "players": [player("Other", "Jax", 1, 1)]}
+            ]
+        }))
+        .unwrap();
+
+        let arena = arena_details(&eog, eog.local_player.as_ref().unwrap()).unwrap();
+        assert_eq!(arena.placement, 2);
+        assert!(arena.is_win());
+        assert_eq!(arena.partner.unwrap().champion, "Zyra");
+        assert_eq!(arena.augment_ids, vec![1205, 1120]);
+
+        let classic = EndOfGameStats {
+            game_mode: "CLASSIC".to_string(),
+            ..eog.clone()
+        };
+        assert!(arena_details(&classic, classic.local_player.as_ref().unwrap()).is_none());
+    }
+
+    #[test]
+    fn test_reprocess_keeps_stored_context() {
+        let local = local_player(player("Me", "Ahri", 0, 0), 100);
+        let played_at = Utc::now() - chrono::Duration::days(3);
+        let raw = RawMatch {
+            game_id: 7,
+            played_at,
+            payload: serde_json::json!({
+                "gameId": 7,
+                "gameMode": "CLASSIC",
+                "gameLength": 1800,
+                "gameType": "MATCHED_GAME",
+                "localPlayer": local,
+                "teams": [
+                    {"teamId": 100, "isWinningTeam": true, "players": [player("Me", "Ahri", 0, 0)]}
+                ]
+            }),
+            game_mode: None,
+            rank: Some("GOLD II".to_string()),
+            rank_change: None,
+            runes: None,
+            draft: Some(ChampSelectDraft {
+                assigned_role: "middle".to_string(),
+                ..Default::default()
+            }),
+            puuid: Some("me".to_string()),
+            source_diff: None,
+        };
+
+        let parsed = GameFinalizer::new().reprocess(&raw).unwrap().unwrap();
+        assert_eq!(parsed.game_id, 7);
+        assert_eq!(parsed.played_at, played_at);
+        assert_eq!(parsed.rank.as_deref(), Some("GOLD II"));
+        assert_eq!(parsed.role.as_deref(), Some("MIDDLE"));
+        assert_eq!(parsed.puuid.as_deref(), Some("me"));
+    }
+
+    #[test]
+    fn test_early_surrender_is_a_remake_without_lp() {
+        let local = local_player(player("Me", "Ahri", 0, 0), 100);
+        let eog = |game_length: i32| -> EndOfGameStats {
+            serde_json::from_value(serde_json::json!({
+                "gameId": 9,
+                "gameMode": "CLASSIC",
+                "gameLength": game_length,
+                "gameType": "MATCHED_GAME",
+                "gameEndedInEarlySurrender": true,
+                "localPlayer": local,
+                "teams": [
+                    {"teamId": 100, "isWinningTeam": false, "players": [player("Me", "Ahri", 0, 0)]}
+                ]
+            }))
+            .unwrap()
+        };
+        let rank_change = RankChange {
+            lp_change: -18,
+            promoted: false,
+            demoted: false,
+            series_progress: None,
+        };
+        let finalizer = GameFinalizer::new();
+        let thresholds = BadgeThresholds::CLASSIC;
+
+        let remake = finalizer
+            .create_match_from_eog(eog(200), Some(rank_change.clone()), None, thresholds)
+            .unwrap();
+        assert_eq!(remake.result, MatchResult::Remake);
+        assert_eq!(remake.lp_change, None);
+
+        let surrender = finalizer
+            .create_match_from_eog(eog(1200), Some(rank_change), None, thresholds)
+            .unwrap();
+        assert_eq!(surrender.result, MatchResult::Loss);
+        assert_eq!(surrender.lp_change, Some(-18));
+    }
+
+    #[test]
+    fn test_swarm_has_no_enemy_team_or_lp() {
+        let mut local = local_player(player("Me", "Seraphine", 0, 0), 100);
+        local["stats"]["win"] = serde_json::json!(true);
+        let stats: EndOfGameStats = serde_json::from_value(serde_json::json!({
+            "gameId": 11,
+            "gameMode": "STRAWBERRY",
+            "gameLength": 900,
+            "gameType": "MATCHED_GAME",
+            "localPlayer": local,
+            "teams": [
+                {"teamId": 100, "isWinningTeam": true, "players": [
+                    player("Me", "Seraphine", 0, 0), player("Ally", "Jinx", 0, 0)
+                ]},
+                {"teamId": 200, "isWinningTeam": false, "players": [player("Bot", "Briar", 0, 0)]}
+            ]
+        }))
+        .unwrap();
+        let eog = EndOfGameData {
+            payload: None,
+            stats: Some(stats),
+            post_game_rank: None,
+            remade: false,
+        };
+        let rank_change = RankChange {
+            lp_change: 0,
+            promoted: false,
+            demoted: false,
+            series_progress: None,
+        };
+
+        let (game, _) = GameFinalizer::new()
+            .build_match(eog, Some(rank_change), None, None, None)
+            .unwrap();
+        let game = game.unwrap();
+        assert_eq!(game.result, MatchResult::Win);
+        assert_eq!(game.lp_change, None);
+        assert_eq!(game.side, None);
+        assert_eq!(game.participants.len(), 2);
+    }
+
+    #[test]
+    fn test_role_prefers_draft_then_fallbacks() {
+        let mut finalizer = GameFinalizer::new();
+        assert_eq!(finalizer.role(&["", "MID"]), Some("MIDDLE".to_string()));
+        assert_eq!(finalizer.role(&["NONE"]), None);
+
+        finalizer.capture_draft(ChampSelectDraft {
+            assigned_role: "utility".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(finalizer.role(&["JUNGLE"]), Some("UTILITY".to_string()));
+    }
+}
@@ -16,7 +16,7 @@
 //!          ▼               ▼
 //! ┌─────────────────┐  ┌─────────────────┐
 //! │ WebSocket Mode  │  │  Polling Mode   │
-//! │ (real-time)     │  │  (1s interval)  │
+//! │ (real-time)     │  │ (configurable)  │
 //! └────────┬────────┘  └────────┬────────┘
 //!          │                    │
 //!          └────────┬───────────┘
@@ -30,8 +30,12 @@
 //! ```
 
 use anyhow::Result;
-use crate::{GameflowPhase, LcuClient, LcuWebSocket, LcuEvent, uris};
+use crate::{
+    lcu_connection, polling_config, uris, BufferMetrics, ConnectionState, GameflowPhase, LcuEvent,
+    LcuWebSocket, SleepDetector, SocketMetrics, TaskSupervisor,
+};
 use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tokio::sync::{broadcast, mpsc};
 use tracing::{debug, info, warn};
@@ -88,8 +92,10 @@ pub struct StageChangeEvent {
 pub struct GameflowChangeEvent {
     /// The new gameflow phase
     pub phase: String,
-    /// Display name for the phase
+    /// Display name for the phase (English)
     pub display_name: String,
+    /// Locale-independent string key for the phase (e.g. "phase.in_game")
+    pub display_key: String,
     /// Whether we're in game
     pub is_in_game: bool,
     /// Whether we're in the client
@@ -116,24 +122,30 @@ enum MonitorMode {
 
 /// Monitor for League client gameflow phase changes
 pub struct GameflowMonitor {
-    poll_interval: Duration,
     shutdown_tx: Option<broadcast::Sender<()>>,
+    supervisor: TaskSupervisor,
+    /// Backlog of the monitor's current (or last) WebSocket
+    socket: Arc<Mutex<Option<SocketMetrics>>>,
 }
 
+/// Supervisor task name for the monitor loop
+const MONITOR_TASK: &str = "gameflow_monitor";
+
+/// Supervisor task name for the monitor's WebSocket reader
+const MONITOR_SOCKET_TASK: &str = "gameflow_monitor_socket";
+
 impl GameflowMonitor {
-    /// Create a new gameflow monitor
-    pub fn new(poll_interval_ms: u64) -> Self {
+    /// Create a new gameflow monitor, polling at the configured fallback
+    /// interval (see `polling_config`) when the WebSocket is unavailable.
+    /// The monitor loop and its socket run on `supervisor`.
+    pub fn new(supervisor: TaskSupervisor) -> Self {
         Self {
-            poll_interval: Duration::from_millis(poll_interval_ms),
             shutdown_tx: None,
+            supervisor,
+            socket: Arc::new(Mutex::new(None)),
         }
     }
 
-    /// Create with default 1-second polling interval (only used as fallback)
-    pub fn default() -> Self {
-        Self::new(1000)
-    }
-
     /// Start monitoring gameflow changes
     ///
     /// Prefers WebSocket for real-time events, falls back to polling if unavailable.
@@ -147,23 +159,26 @@ impl GameflowMonitor {
             return Ok(());
         }
 
-        let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
-        self.shutdown_tx = Some(shutdown_tx);
+        let (shutdown_tx, _) = broadcast::channel(1);
+        self.shutdown_tx = Some(shutdown_tx.clone());
 
-        let poll_interval = self.poll_interval;
-
-        tokio::spawn(async move {
-            run_monitor_loop(event_tx, poll_interval, shutdown_rx).await;
+        // Supervised so a panic in the loop restarts it instead of silently stopping
+        let supervisor = self.supervisor.clone();
+        let socket = Arc::clone(&self.socket);
+        self.supervisor.spawn(MONITOR_TASK, move || {
+            let shutdown_rx = shutdown_tx.subscribe();
+            run_monitor_loop(supervisor.clone(), Arc::clone(&socket), event_tx.clone(), shutdown_rx)
         });
 
         info!("Gameflow monitor started (WebSocket preferred, {}ms polling fallback)",
-              self.poll_interval.as_millis());
+              polling_config().gameflow_fallback_ms);
         Ok(())
     }
 
     /// Stop monitoring
     pub fn stop(&mut self) {
         if let Some(tx) = self.shutdown_tx.take() {
+            self.supervisor.stop(MONITOR_TASK);
             let _ = tx.send(());
             info!("Gameflow monitor stopped");
         }
@@ -173,6 +188,15 @@ impl GameflowMonitor {
     pub fn is_running(&self) -> bool {
         self.shutdown_tx.is_some()
     }
+
+    /// Backlog of the monitor's WebSocket; `None` before it first connected
+    pub fn backlog_metrics(&self) -> Option<BufferMetrics> {
+        self.socket
+            .lock()
+            .expect("Mutex poisoned")
+            .as_ref()
+            .map(SocketMetrics::metrics)
+    }
 }
 
 impl Drop for GameflowMonitor {
@@ -183,8 +207,9 @@ impl Drop for GameflowMonitor {
 
 /// Main monitoring loop - tries WebSocket first, falls back to polling
 async fn run_monitor_loop(
+    supervisor: TaskSupervisor,
+    socket: Arc<Mutex<Option<SocketMetrics>>>,
     event_tx: mpsc::Sender<GameflowEvent>,
-    poll_interval: Duration,
     mut shutdown_rx: broadcast::Receiver<()>,
 ) {
     let mut last_phase = GameflowPhase::None;
@@ -200,7 +225,15 @@ async fn run_monitor_loop(
         }
 
         // Try to use WebSocket mode
-        match try_websocket_mode(&event_tx, &mut last_phase, &mut last_layout, &mut shutdown_rx).await {
+        let websocket = try_websocket_mode(
+            &supervisor,
+            &socket,
+            &event_tx,
+            &mut last_phase,
+            &mut last_layout,
+            &mut shutdown_rx,
+        );
+        match websocket.await {
             Ok(()) => {
                 // WebSocket closed gracefully, try to reconnect
                 info!("WebSocket disconnected, will reconnect...");
@@ -221,7 +254,6 @@ async fn run_monitor_loop(
             &event_tx,
             &mut last_phase,
             &mut last_layout,
-            poll_interval,
             &mut shutdown_rx,
         ).await;
 
@@ -248,18 +280,55 @@ enum PollResult {
     ClientDisconnected,
 }
 
+/// How often the WebSocket loop checks for a sleep/resume
+const RESUME_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
 /// Try to monitor via WebSocket (real-time events)
 async fn try_websocket_mode(
+    supervisor: &TaskSupervisor,
+    socket: &Mutex<Option<SocketMetrics>>,
     event_tx: &mpsc::Sender<GameflowEvent>,
     last_phase: &mut GameflowPhase,
     last_layout: &mut TargetLayout,
     shutdown_rx: &mut broadcast::Receiver<()>,
 ) -> Result<()> {
-    let mut ws = LcuWebSocket::connect().await?;
+    let mut ws = LcuWebSocket::connect(supervisor, MONITOR_SOCKET_TASK).await?;
+    *socket.lock().expect("Mutex poisoned") = Some(ws.metrics_handle());
     info!("Gameflow monitor using WebSocket mode (real-time events)");
 
+    // The phase may have changed while we were disconnected (client restart,
+    // resume from sleep) - fetch it once so it is re-emitted if needed
+    refresh_phase(event_tx, last_phase, last_layout).await;
+
+    let mut socket_state = ws.state_changes();
+    let mut sleep_detector = SleepDetector::ticking(RESUME_CHECK_INTERVAL);
+    let mut resume_check = tokio::time::interval(RESUME_CHECK_INTERVAL);
+
     loop {
         tokio::select! {
+            _ = resume_check.tick() => {
+                if let Some(gap) = sleep_detector.check() {
+                    // The socket is almost certainly dead after a suspend. Report the
+                    // client as gone and reconnect; the current phase is re-emitted
+                    // once the connection is re-established.
+                    warn!("System resume detected ({}s gap), reconnecting WebSocket", gap.as_secs());
+                    handle_phase_change(event_tx, GameflowPhase::None, last_phase, last_layout).await;
+                    return Ok(());
+                }
+            }
+
+            changed = socket_state.changed() => {
+                if changed.is_err() {
+                    return Ok(());
+                }
+                let state = *socket_state.borrow_and_update();
+                debug!("Gameflow WebSocket {:?}", state);
+                if state == ConnectionState::Connected {
+                    // The socket reconnected by itself; catch up on missed changes
+                    refresh_phase(event_tx, last_phase, last_layout).await;
+                }
+            }
+
             event = ws.recv() => {
                 match event {
                     Some(event) => {
@@ -268,7 +337,7 @@ async fn try_websocket_mode(
                         }
                     }
                     None => {
-                        // WebSocket closed
+                        // WebSocket closed and gave up reconnecting
                         return Ok(());
                     }
                 }
@@ -281,21 +350,38 @@ async fn try_websocket_mode(
     }
 }
 
+/// Fetch the current phase over REST and emit it if it changed
+async fn refresh_phase(
+    event_tx: &mpsc::Sender<GameflowEvent>,
+    last_phase: &mut GameflowPhase,
+    last_layout: &mut TargetLayout,
+) {
+    if let Some(client) = lcu_connection().client() {
+        if let Ok(phase) = client.get_gameflow_phase().await {
+            handle_phase_change(event_tx, phase, last_phase, last_layout).await;
+        }
+    }
+}
+
 /// Try to monitor via REST API polling (fallback)
 async fn try_polling_mode(
     event_tx: &mpsc::Sender<GameflowEvent>,
     last_phase: &mut GameflowPhase,
     last_layout: &mut TargetLayout,
-    poll_interval: Duration,
     shutdown_rx: &mut broadcast::Receiver<()>,
 ) -> PollResult {
     let mut consecutive_failures = 0;
 
     loop {
+        // Idle in the lobby or without a phase; queue, champ select and the
+        // game poll at full speed
+        let active = !matches!(*last_phase, GameflowPhase::None | GameflowPhase::Lobby);
+        let config = polling_config();
+        let poll_interval = config.interval(config.gameflow_fallback_ms, active);
         tokio::select! {
             _ = tokio::time::sleep(poll_interval) => {
-                match LcuClient::new() {
-                    Ok(client) => {
+                match lcu_connection().client() {
+                    Some(client) => {
                         match client.get_gameflow_phase().await {
                             Ok(phase) => {
                                 if consecutive_failures > 0 {
@@ -308,6 +394,7 @@ async fn try_polling_mode(
                                 return PollResult::ClientConnected;
                             }
                             Err(e) => {
+                                lcu_connection().invalidate();
                                 consecutive_failures += 1;
                                 if consecutive_failures == 1 {
                                     debug!("Failed to get gameflow phase: {}", e);
@@ -315,7 +402,7 @@ async fn try_polling_mode(
                             }
                         }
                     }
-                    Err(_) => {
+                    None => {
                         consecutive_failures += 1;
                         if consecutive_failures == 1 {
                             debug!("League client not running");
@@ -366,6 +453,7 @@ async fn handle_phase_change(
         let _ = event_tx.send(GameflowEvent::PhaseChanged(GameflowChangeEvent {
             phase: format!("{:?}", phase),
             display_name: phase.display_name().to_string(),
+            display_key: phase.string_key().to_string(),
             is_in_game: phase.is_in_game(),
             is_in_client: phase.is_in_client(),
         })).await;
@@ -0,0 +1,244 @@
+//! Match history import
+//!
+//! Games played before the companion was installed are missing from the
+//! stored game results. The importer pages through the client's match
+//! history (newest first) and turns every game not stored yet into a game
+//! result, reporting progress after each page. Only what the history list
+//! carries is imported (result, KDA, champion, side, role); badges, latency
+//! and the other end-of-game details stay with games recorded live.
+//!
+//! A backfill takes many requests, so it runs as an `ImportJob` detached from
+//! the integration: the integration is locked only to set the job up and to
+//! store its results, and status and event polls go on in between.
+
+use chrono::{DateTime, Local, Timelike, Utc};
+use std::collections::HashSet;
+use tokio::sync::RwLock;
+use tracing::{debug, info};
+
+use crate::protocol::ImportProgress;
+use crate::{
+    normalize_role, DdragonResolver, GameResultRecord, LcuClient, LeagueError, LeagueIntegration,
+    MatchHistoryGame, ReplyChannel, Result, Team,
+};
+
+/// Games requested per page
+const PAGE_SIZE: u32 = 20;
+
+/// Games read unless a limit is given
+pub const DEFAULT_IMPORT_LIMIT: u32 = 200;
+
+/// Shorter games are remakes
+const MIN_GAME_SECS: i32 = 300;
+
+/// Import the match history into `integration`, writing progress to
+/// `channel`. The lock is held only around the set-up and the store.
+pub async fn import_match_history<C: ReplyChannel + ?Sized>(
+    integration: &RwLock<LeagueIntegration>,
+    limit: Option<u32>,
+    channel: &C,
+) -> Result<ImportProgress> {
+    let failed = |e| LeagueError::ImportFailed(Box::new(e));
+    let job = integration.write().await.prepare_import(limit).await.map_err(failed)?;
+    let (progress, records) = job.run(channel).await.map_err(failed)?;
+    integration.write().await.finish_import(records);
+    Ok(progress)
+}
+
+/// Everything an import needs, taken from the integration
+pub struct ImportJob {
+    client: LcuClient,
+    ddragon: DdragonResolver,
+    /// Games already stored
+    known: HashSet<i64>,
+    limit: Option<u32>,
+}
+
+impl ImportJob {
+    pub fn new(
+        client: LcuClient,
+        ddragon: DdragonResolver,
+        known: HashSet<i64>,
+        limit: Option<u32>,
+    ) -> Self {
+        Self {
+            client,
+            ddragon,
+            known,
+            limit,
+        }
+    }
+
+    /// Read the match history, writing progress to `channel` after every
+    /// page. Returns the final progress and the new game results.
+    pub async fn run<C: ReplyChannel + ?Sized>(
+        &self,
+        channel: &C,
+    ) -> Result<(ImportProgress, Vec<GameResultRecord>)> {
+        let mut last = ImportProgress::default();
+        let records = MatchHistoryImporter::new(&self.ddragon, self.limit)
+            .run(&self.client, &self.known, |progress| {
+                channel.write_import_progress(progress);
+                last = progress.clone();
+            })
+            .await?;
+        Ok((last, records))
+    }
+}
+
+/// Reads the current summoner's match history into game results
+pub struct MatchHistoryImporter<'a> {
+    ddragon: &'a DdragonResolver,
+    /// Most games to read
+    limit: u32,
+}
+
+impl<'a> MatchHistoryImporter<'a> {
+    pub fn new(ddragon: &'a DdragonResolver, limit: Option<u32>) -> Self {
+        Self {
+            ddragon,
+            limit: limit.unwrap_or(DEFAULT_IMPORT_LIMIT),
+        }
+    }
+
+    /// Game results for the games not in `known`, oldest first.
+    /// `on_progress` is called after every page and once when done.
+    pub async fn run(
+        &self,
+        client: &LcuClient,
+        known: &HashSet<i64>,
+        mut on_progress: impl FnMut(&ImportProgress),
+    ) -> Result<Vec<GameResultRecord>> {
+        let mut progress = ImportProgress::default();
+        let mut records = Vec::new();
+        let mut seen = HashSet::new();
+
+        while progress.scanned < self.limit {
+            let beg = progress.scanned;
+            let end = (beg + PAGE_SIZE).min(self.limit) - 1;
+            let page = client.get_match_history(beg, end).await?.games;
+            progress.total = page.game_count;
+            if page.games.is_empty() {
+                break;
+            }
+
+            for game in &page.games {
+                progress.scanned += 1;
+                // Pages shift when a game finishes during the import
+                if !seen.insert(game.game_id) || known.contains(&game.game_id) {
+                    progress.skipped += 1;
+                    continue;
+                }
+                match self.record(game) {
+                    Some(record) => {
+                        progress.imported += 1;
+                        records.push(record);
+                    }
+                    None => progress.skipped += 1,
+                }
+            }
+            debug!("Match history import: {:?}", progress);
+            on_progress(&progress);
+
+            if page.games.len() < (end - beg + 1) as usize {
+                break;
+            }
+        }
+
+        progress.done = true;
+        on_progress(&progress);
+        info!(
+            "Match history import read {} games, {} new",
+            progress.scanned, progress.imported
+        );
+
+        records.sort_by_key(|r| r.played_at);
+        Ok(records)
+    }
+
+    /// Game result of a history game; `None` for remakes and games without
+    /// the player's stats
+    pub fn record(&self, game: &MatchHistoryGame) -> Option<GameResultRecord> {
+        let player = game.participants.first()?;
+        if player.stats.game_ended_in_early_surrender || game.game_duration < MIN_GAME_SECS {
+            return None;
+        }
+
+        let started_at = DateTime::<Utc>::from_timestamp_millis(game.game_creation)?;
+        let played_at = started_at + chrono::Duration::seconds(game.game_duration as i64);
+        let role = if player.timeline.role.contains("SUPPORT") {
+            normalize_role("SUPPORT")
+        } else {
+            normalize_role(&player.timeline.lane)
+        };
+
+        Some(GameResultRecord {
+            game_id: game.game_id,
+            won: player.stats.win,
+            kills: player.stats.kills,
+            deaths: player.stats.deaths,
+            assists: player.stats.assists,
+            duration_secs: game.game_duration,
+            played_at,
+            local_hour: played_at.with_timezone(&Local).hour(),
+            side: Team::from_team_id(player.team_id),
+            role,
+            champion: self
+                .ddragon
+                .champion_name(player.champion_id)
+                .unwrap_or_default(),
+            game_mode: game.game_mode.clone(),
+            had_leaver: false,
+            objectives_taken: 0,
+            objectives_participated: 0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_game_becomes_game_result() {
+        let game: MatchHistoryGame = serde_json::from_value(serde_json::json!({
+            "gameId": 7001, "gameCreation": 1_700_000_000_000i64, "gameDuration": 1800,
+            "gameMode": "CLASSIC", "queueId": 420,
+            "participants": [{
+                "participantId": 1, "teamId": 200, "championId": 103,
+                "stats": { "win": true, "kills": 8, "deaths": 2, "assists": 5 },
+                "timeline": { "lane": "BOTTOM", "role": "DUO_SUPPORT" }
+            }]
+        }))
+        .unwrap();
+
+        let ddragon = DdragonResolver::new();
+        let importer = MatchHistoryImporter::new(&ddragon, None);
+        let record = importer.record(&game).unwrap();
+        assert!(record.won);
+        assert_eq!(record.side, Some(Team::Red));
+        assert_eq!(record.role.as_deref(), Some("UTILITY"));
+        assert_eq!(record.played_at.timestamp(), 1_700_000_000 + 1800);
+
+        let remake = MatchHistoryGame {
+            game_duration: 200,
+            ..game
+        };
+        assert!(importer.record(&remake).is_none());
+    }
+
+    #[test]
+    fn test_failed_import_reports_its_error() {
+        let progress = serde_json::to_value(ImportProgress::default()).unwrap();
+        assert!(progress.get("error").is_none());
+
+        let failed = ImportProgress {
+            done: true,
+            error: Some("League client not running".to_string()),
+            ..Default::default()
+        };
+        let line = serde_json::to_value(&failed).unwrap();
+        assert_eq!(line["type"], "ImportProgress");
+        assert_eq!(line["error"], "League client not running");
+    }
+}
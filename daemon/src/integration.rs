@@ -3,19 +3,43 @@
 //! This module provides the League integration logic for the standalone gamepack.
 //! It communicates with the main daemon via IPC protocol.
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
 
-use crate::game_finalizer::GameFinalizer;
+use crate::asset_cache::{default_cache_dir, AssetCache};
+use crate::game_finalizer::{FinalizationPreview, GameFinalizer};
+use crate::buffers::RingBuffer;
+use crate::clock::{SessionClock, SleepDetector};
 use crate::protocol::{
-    ConnectionStatus, IntegrationStatus, LiveMatchData, MatchData, MatchResult, SessionContext,
+    ConnectionStatus, ImportProgress, IntegrationMetrics, IntegrationStatus, LiveMatchData,
+    MatchData, MatchResult, LeagueCommand, ProfileMetadata, RunningStatus, SessionContext,
+    SimulatedEventResult, SpectatedPlayer,
+};
+use crate::stats_store::{DodgeTracker, QueueInfo, QueueTimer, StatsStore};
+use crate::strings::StringTable;
+use crate::supervisor::TaskSupervisor;
+use crate::types::{
+    AssetCacheSettings, Badge, BufferSettings, GameModeContext, NotificationSettings,
+    RemoteHostSettings, SpectatorSettings, StatsSettings, TriggerConfig,
+};
+use crate::{
+    analyze_draft, combine_backlog_metrics, event_catalog, is_game_process_running, kill_importance,
+    lcu_connection, list_response, mark_leavers, measure_latency, notification_writer,
+    polling_config, recording_hint, riot_ids_match, set_lcu_rate_limit, set_polling_config,
+    set_ws_backlog, signature_for_kill, AssetUrls, ChampSelectCapture, ConnectionHistory,
+    DataStorage, DdragonResolver, DeathTracker, DraftComposition, EventDiscovery, EventLog,
+    EventPush, FpsSampler, GameResultRecord, GameflowPhase, ImportJob, LatencyTracker,
+    LcuCapabilities, LcuClient, LcuFeature, LeagueEventType, LeaverTracker, LiveClientApi,
+    LiveField, LiveMatch, MomentumTracker, NonGameKind, NonGameRecord, ObjectiveTracker,
+    ParsedGameEvent, RankedEntry, RawMatchStore, ReplayApi, ReplayPlaybackUpdate, ReplyChannel,
+    SnapshotRecorder, SnapshotStore, StoredGameEvent, StreakTracker, TftLiveBoard, TftTracker,
+    TriggerEvaluator, VisionRecap, VisionTracker, LEAGUE_GAME_ID, LEAGUE_SLUG,
 };
-use crate::types::GameModeContext;
-use crate::{GameflowPhase, LiveClientApi, LiveMatch, RankedEntry, LEAGUE_GAME_ID, LEAGUE_SLUG};
 
 // Use shared types from the gamepack runtime
 use gamepack_runtime::{
@@ -44,14 +68,18 @@ pub struct LeagueIntegration {
     connection_status: ConnectionStatus,
     /// Previous connection status (for change detection)
     prev_connection_status: ConnectionStatus,
+    /// Uptime and recent connection status changes
+    connection_history: ConnectionHistory,
     /// Current game phase
     current_phase: Option<String>,
     /// Previous game phase (for change detection)
     prev_phase: Option<String>,
     /// Whether we're currently in game
     is_in_game: bool,
-    /// Pending events to be polled
-    pending_events: Vec<GameEvent>,
+    /// Pending events to be polled (bounded, oldest dropped first)
+    pending_events: RingBuffer<GameEvent>,
+    /// Recent live match snapshots for the current session (bounded)
+    live_snapshots: RingBuffer<LiveMatch>,
     /// Session context (set when session starts)
     session_context: Option<SessionContext>,
     /// Last processed event ID (to avoid duplicates)
@@ -62,39 +90,841 @@ pub struct LeagueIntegration {
     active_player_name: Option<String>,
     /// Current match's external ID (game_id from LCU)
     external_match_id: Option<String>,
+    /// Wall-clock end of the game, from the first post-game phase
+    game_ended_at: Option<DateTime<Utc>>,
     /// Current subpack index (0 for League, 1 for TFT)
     current_subpack: u8,
     /// Last emitted stats (for delta detection)
     last_emitted_stats: Option<HashMap<String, serde_json::Value>>,
+    /// Capacities for the in-memory buffers
+    buffer_settings: BufferSettings,
+    /// Supervisor for background tasks (health is reported in the status)
+    supervisor: TaskSupervisor,
+    /// Detects OS sleep/resume between status checks
+    sleep_detector: SleepDetector,
+    /// Monotonic clock for the current session (set when session starts)
+    session_clock: Option<SessionClock>,
+    /// Display strings for the user's locale
+    strings: StringTable,
+    /// CDN image URLs for the client's patch
+    asset_urls: AssetUrls,
+    /// Local icon cache settings
+    asset_cache_settings: AssetCacheSettings,
+    /// Background icon downloader (started on first use when enabled)
+    asset_cache: Option<AssetCache>,
+    /// Data Dragon names for the current patch (shared with the finalizer)
+    ddragon: DdragonResolver,
+    /// Whether the current game's live assets were queued for caching
+    live_assets_prefetched: bool,
+    /// Whether the current game's `MatchIntro` was emitted
+    intro_sent: bool,
+    /// Game server latency samples of the current game
+    latency: LatencyTracker,
+    /// FPS reported by the daemon for the current game
+    fps: FpsSampler,
+    /// Dragon soul, spawn timers and turret plates of the current game
+    objectives: ObjectiveTracker,
+    /// Replay API client (created on first use)
+    replay: Option<ReplayApi>,
+    /// Spectated game recording settings
+    spectator_settings: SpectatorSettings,
+    /// Whether the client is spectating a game that should be recorded
+    is_spectating: bool,
+    /// Whether the current session is a spectated game
+    session_spectated: bool,
+    /// Clip trigger evaluation (used for simulated events)
+    triggers: TriggerEvaluator,
+    /// Gold timeline, momentum markers and highlight windows for the current game
+    momentum: MomentumTracker,
+    /// The active player's deaths in the current game
+    deaths: DeathTracker,
+    /// Stage/round progression of the current TFT game
+    tft: TftTracker,
+    /// Most recent TFT board served as live data
+    tft_board: Option<TftLiveBoard>,
+    /// Killing sprees and multikill chains in the current game
+    streaks: StreakTracker,
+    /// Control ward purchases and placements in the current game
+    vision: VisionTracker,
+    /// Participants whose live stats stopped changing (AFK or left)
+    leavers: LeaverTracker,
+    /// Riot IDs of the player's party members in the current game
+    premades: Vec<String>,
+    /// PUUID of the account the current game is played on
+    account_puuid: Option<String>,
+    /// Profile header of the account, fetched when a session starts
+    profile: Option<ProfileMetadata>,
+    /// Topics pushed to the daemon without polling
+    event_push: EventPush,
+    /// Statistics persisted across sessions
+    stats: StatsStore,
+    /// Persisted per-match event timeline
+    event_log: EventLog,
+    /// Persisted end-of-game payloads, for reprocessing
+    raw_matches: RawMatchStore,
+    /// Persisted raw event payloads, for new event formats
+    event_discovery: EventDiscovery,
+    /// Samples the scoreboard during the current game
+    snapshots: SnapshotRecorder,
+    /// Persisted scoreboard snapshots per match
+    match_snapshots: SnapshotStore,
+    /// Maintenance over the data directory's stores
+    storage: DataStorage,
+    /// Times the current matchmaking search
+    queue_timer: QueueTimer,
+    /// Detects dodged champ selects
+    dodge_tracker: DodgeTracker,
+    /// Set when the game failed to launch or crashed during the session
+    abort_reason: Option<&'static str>,
+    /// Whether sessions follow the gameflow (`GameSessionController`)
+    gameflow_sessions: bool,
+    /// Champ select draft capture (started once the client is found)
+    champ_select: Option<ChampSelectCapture>,
+    /// Data directory, snapshot interval and retention of the stores below
+    stats_settings: StatsSettings,
 }
 
 impl LeagueIntegration {
-    /// Create a new League integration
-    pub fn new() -> Self {
+    /// Create a new League integration persisting to the data directory
+    /// from `stats_settings`
+    pub fn new(stats_settings: StatsSettings) -> Self {
+        let buffer_settings = BufferSettings::default();
+        let ddragon = DdragonResolver::new();
+        let mut finalizer = GameFinalizer::new();
+        finalizer.set_resolver(ddragon.clone());
         Self {
-            finalizer: GameFinalizer::new(),
+            finalizer,
             live_client: LiveClientApi::new().ok(),
             last_live_match: Arc::new(RwLock::new(None)),
             pre_game_rank: None,
             connection_status: ConnectionStatus::Disconnected,
             prev_connection_status: ConnectionStatus::Disconnected,
+            connection_history: ConnectionHistory::new(),
             current_phase: None,
             prev_phase: None,
             is_in_game: false,
-            pending_events: Vec::new(),
+            pending_events: RingBuffer::new(buffer_settings.max_pending_events),
+            live_snapshots: RingBuffer::new(buffer_settings.max_live_snapshots),
             session_context: None,
             last_event_id: -1,
             game_mode_context: None,
             active_player_name: None,
             external_match_id: None,
+            game_ended_at: None,
             current_subpack: SUBPACK_LEAGUE,
             last_emitted_stats: None,
+            buffer_settings,
+            supervisor: TaskSupervisor::new(),
+            sleep_detector: SleepDetector::default(),
+            session_clock: None,
+            strings: StringTable::english(),
+            asset_urls: AssetUrls::default(),
+            asset_cache_settings: AssetCacheSettings::default(),
+            asset_cache: None,
+            ddragon,
+            live_assets_prefetched: false,
+            intro_sent: false,
+            latency: LatencyTracker::new(),
+            fps: FpsSampler::new(),
+            objectives: ObjectiveTracker::new(),
+            replay: None,
+            spectator_settings: SpectatorSettings::default(),
+            is_spectating: false,
+            session_spectated: false,
+            triggers: TriggerEvaluator::new(TriggerConfig::default()),
+            momentum: MomentumTracker::new(),
+            deaths: DeathTracker::new(),
+            tft: TftTracker::new(),
+            tft_board: None,
+            streaks: StreakTracker::new(),
+            vision: VisionTracker::new(),
+            leavers: LeaverTracker::new(),
+            premades: Vec::new(),
+            account_puuid: None,
+            profile: None,
+            event_push: EventPush::new(),
+            stats: StatsStore::open(&stats_settings),
+            event_log: EventLog::open(&stats_settings),
+            raw_matches: RawMatchStore::open(&stats_settings),
+            event_discovery: EventDiscovery::open(&stats_settings),
+            snapshots: SnapshotRecorder::new(&stats_settings),
+            match_snapshots: SnapshotStore::open(&stats_settings),
+            storage: DataStorage::open(&stats_settings),
+            queue_timer: QueueTimer::new(),
+            dodge_tracker: DodgeTracker::new(),
+            abort_reason: None,
+            gameflow_sessions: false,
+            champ_select: None,
+            stats_settings,
         }
     }
 
+    /// Set the locale table used for translated labels in emitted events
+    pub fn set_string_table(&mut self, strings: StringTable) {
+        info!("Display strings locale: {}", strings.locale());
+        self.strings = strings;
+    }
+
+    /// Supervisor that background services should be started under
+    pub fn supervisor(&self) -> &TaskSupervisor {
+        &self.supervisor
+    }
+
+    /// Update buffer capacities (excess entries are dropped oldest-first;
+    /// the WebSocket backlog applies to sockets opened afterwards)
+    pub fn set_buffer_settings(&mut self, settings: BufferSettings) {
+        self.pending_events.set_capacity(settings.max_pending_events);
+        self.live_snapshots.set_capacity(settings.max_live_snapshots);
+        set_ws_backlog(settings.max_ws_backlog);
+        self.buffer_settings = settings;
+    }
+
+    /// Current buffer settings
+    pub fn buffer_settings(&self) -> &BufferSettings {
+        &self.buffer_settings
+    }
+
+    /// Update the local icon cache settings (the downloader restarts lazily)
+    pub fn set_asset_cache_settings(&mut self, settings: AssetCacheSettings) {
+        self.asset_cache = None;
+        self.asset_cache_settings = settings;
+    }
+
+    /// Update clip trigger rules
+    pub fn set_trigger_config(&mut self, config: TriggerConfig) {
+        self.triggers.update_config(config);
+    }
+
+    /// Handle a League-specific IPC command
+    ///
+    /// Response chunks and progress updates go to `channel`, the channel the
+    /// command came in on.
+    pub async fn handle_command(
+        &mut self,
+        command: LeagueCommand,
+        channel: &dyn ReplyChannel,
+    ) -> Value {
+        match command {
+            LeagueCommand::SimulateEvent { event_type, data } => {
+                let result = self.simulate_event(&event_type, &data);
+                serde_json::to_value(&result).unwrap_or(Value::Null)
+            }
+            LeagueCommand::GetLiveData { fields } => self
+                .get_live_data_fields(fields.as_deref())
+                .await
+                .map(|data| data.data)
+                .unwrap_or(Value::Null),
+            LeagueCommand::GetStatus => {
+                serde_json::to_value(self.get_status().await).unwrap_or(Value::Null)
+            }
+            LeagueCommand::GetQueueStats => {
+                serde_json::to_value(self.stats.stats().queue_time_summary()).unwrap_or(Value::Null)
+            }
+            LeagueCommand::GetNonGameStats => {
+                serde_json::to_value(self.stats.stats().non_game_summary()).unwrap_or(Value::Null)
+            }
+            LeagueCommand::GetHoverBans => {
+                serde_json::to_value(&self.stats.stats().hover_bans).unwrap_or(Value::Null)
+            }
+            LeagueCommand::GetArenaMatches => {
+                list_response(&self.stats.stats().arena_match_details, channel)
+            }
+            LeagueCommand::Subscribe { ref topics } => {
+                self.event_push.subscribe(topics, &self.supervisor).await;
+                json!({ "topics": self.event_push.topics() })
+            }
+            LeagueCommand::Unsubscribe { ref topics } => {
+                self.event_push.unsubscribe(topics, &self.supervisor);
+                json!({ "topics": self.event_push.topics() })
+            }
+            LeagueCommand::GetPlaytime { days } => {
+                serde_json::to_value(self.stats.playtime_summary(days.unwrap_or(7)))
+                    .unwrap_or(Value::Null)
+            }
+            LeagueCommand::GetRankedSplits => {
+                let stats = self.stats.stats();
+                json!({
+                    "current": stats.ranked_split_at(Utc::now()),
+                    "splits": stats.ranked_splits,
+                })
+            }
+            LeagueCommand::ReportFps {
+                fps,
+                game_time_secs,
+            } => json!({ "recorded": self.report_fps(fps, game_time_secs) }),
+            LeagueCommand::GetPerformanceAnalytics => {
+                serde_json::to_value(self.stats.stats().performance_analytics())
+                    .unwrap_or(Value::Null)
+            }
+            LeagueCommand::GetChampionGames {
+                ref champion,
+                ref game_mode,
+                since,
+                until,
+                exclude_leavers,
+            } => {
+                let mut games =
+                    self.stats.champion_games(champion, game_mode.as_deref(), since, until);
+                if exclude_leavers {
+                    games.retain(|game| !game.had_leaver);
+                }
+                list_response(&games, channel)
+            }
+            LeagueCommand::GetDuoStats => {
+                serde_json::to_value(self.stats.stats().duo_summary()).unwrap_or(Value::Null)
+            }
+            LeagueCommand::GetRivals => {
+                serde_json::to_value(self.stats.stats().rivalries.repeat_opponents())
+                    .unwrap_or(Value::Null)
+            }
+            LeagueCommand::GetReplayState => match self.replay_state().await {
+                Ok(state) => state,
+                Err(e) => {
+                    debug!("No replay loaded: {}", e);
+                    e.response()
+                }
+            },
+            LeagueCommand::SetReplayPlayback {
+                time,
+                paused,
+                speed,
+            } => {
+                let update = ReplayPlaybackUpdate { time, paused, speed };
+                match self.replay_client() {
+                    Ok(replay) => Self::replay_response(replay.set_playback(&update).await),
+                    Err(e) => e.response(),
+                }
+            }
+            LeagueCommand::SetReplayRender { ref render } => match self.replay_client() {
+                Ok(replay) => Self::replay_response(replay.set_render(render).await),
+                Err(e) => e.response(),
+            },
+            LeagueCommand::RecordReplay { ref recording } => match self.replay_client() {
+                Ok(replay) => {
+                    info!(
+                        "Recording replay {:.1}s-{:.1}s",
+                        recording.start_time, recording.end_time
+                    );
+                    Self::replay_response(replay.record_range(recording).await)
+                }
+                Err(e) => e.response(),
+            },
+            LeagueCommand::ListConnections => {
+                json!({ "connections": lcu_connection().list_connections().await })
+            }
+            LeagueCommand::SelectConnection { ref puuid } => {
+                self.select_connection(puuid.as_deref()).await
+            }
+            LeagueCommand::Reconnect { ref request_id } => self.reconnect(request_id).await,
+            LeagueCommand::Configure {
+                polling,
+                notifications,
+                remote_host,
+                asset_cache,
+                spectator,
+                triggers,
+                buffers,
+                lcu_rate_limit,
+                strings,
+                stats,
+                daily_playtime_limit_mins,
+                gameflow_sessions,
+            } => {
+                if let Some(polling) = polling {
+                    set_polling_config(polling);
+                }
+                if let Some(notifications) = notifications {
+                    self.set_notification_settings(notifications);
+                }
+                if let Some(remote_host) = remote_host {
+                    self.set_remote_host(&remote_host);
+                }
+                if let Some(asset_cache) = asset_cache {
+                    self.set_asset_cache_settings(asset_cache);
+                }
+                if let Some(spectator) = spectator {
+                    self.set_spectator_settings(spectator);
+                }
+                if let Some(triggers) = triggers {
+                    self.set_trigger_config(triggers);
+                }
+                if let Some(buffers) = buffers {
+                    self.set_buffer_settings(buffers);
+                }
+                if let Some(lcu_rate_limit) = lcu_rate_limit {
+                    set_lcu_rate_limit(lcu_rate_limit);
+                }
+                if let Some(strings) = strings {
+                    self.set_string_table(StringTable::from_locale(strings));
+                }
+                if let Some(stats) = stats {
+                    self.set_stats_settings(stats);
+                }
+                if let Some(daily_playtime_limit_mins) = daily_playtime_limit_mins {
+                    self.set_daily_playtime_limit(daily_playtime_limit_mins);
+                }
+                if let Some(gameflow_sessions) = gameflow_sessions {
+                    self.set_gameflow_sessions(gameflow_sessions);
+                }
+                self.settings_in_effect()
+            }
+            LeagueCommand::GetMatchEvents { ref match_id } => match self.get_match_events(match_id) {
+                Ok(events) => list_response(&events, channel),
+                Err(e) => {
+                    warn!("Failed to read events for match {}: {}", match_id, e);
+                    e.response()
+                }
+            },
+            LeagueCommand::ReprocessMatch { ref match_id } => match self.reprocess_match(match_id) {
+                Ok(data) => serde_json::to_value(data).unwrap_or(Value::Null),
+                Err(e) => {
+                    warn!("Failed to reprocess match {}: {}", match_id, e);
+                    e.response()
+                }
+            },
+            LeagueCommand::PreviewFinalization => {
+                serde_json::to_value(self.preview_finalization().await).unwrap_or(Value::Null)
+            }
+            LeagueCommand::GetMatchSnapshots { ref match_id } => {
+                match self.match_snapshots.load(match_id) {
+                    Ok(snapshots) => list_response(&snapshots, channel),
+                    Err(e) => {
+                        warn!("Failed to read snapshots for match {}: {}", match_id, e);
+                        e.response()
+                    }
+                }
+            }
+            LeagueCommand::ImportMatchHistory { limit } => {
+                match self.import_match_history(limit, channel).await {
+                    Ok(progress) => serde_json::to_value(progress).unwrap_or(Value::Null),
+                    Err(e) => {
+                        warn!("Match history import failed: {}", e);
+                        e.response()
+                    }
+                }
+            }
+            LeagueCommand::GetEventCatalog => {
+                serde_json::to_value(event_catalog()).unwrap_or(Value::Null)
+            }
+            LeagueCommand::GetDiscoveredEvents => match self.event_discovery.events() {
+                Ok(events) => list_response(&events, channel),
+                Err(e) => {
+                    warn!("Failed to read discovered events: {}", e);
+                    e.response()
+                }
+            },
+            LeagueCommand::GetStorageReport => match self.storage.report() {
+                Ok(report) => serde_json::to_value(report).unwrap_or(Value::Null),
+                Err(e) => {
+                    warn!("Failed to report storage: {}", e);
+                    crate::LeagueError::StorageFailed(Box::new(e)).response()
+                }
+            },
+            LeagueCommand::CheckStorageIntegrity => match self.storage.check_integrity() {
+                Ok(report) => serde_json::to_value(report).unwrap_or(Value::Null),
+                Err(e) => {
+                    warn!("Storage integrity check failed: {}", e);
+                    crate::LeagueError::StorageFailed(Box::new(e)).response()
+                }
+            },
+            LeagueCommand::CompactStorage => match self.storage.compact() {
+                Ok(report) => serde_json::to_value(report).unwrap_or(Value::Null),
+                Err(e) => {
+                    warn!("Storage compaction failed: {}", e);
+                    crate::LeagueError::StorageFailed(Box::new(e)).response()
+                }
+            },
+            LeagueCommand::BookmarkMoment {
+                ref request_id,
+                ref note,
+            } => {
+                let game_time = self.bookmark_moment(request_id, note.as_deref()).await;
+                json!({
+                    "requestId": request_id,
+                    "recorded": game_time.is_some(),
+                    "gameTimeSecs": game_time,
+                })
+            }
+        }
+    }
+
+    /// Add a reported FPS sample to the timeline; whether it was kept
+    fn report_fps(&mut self, fps: f32, game_time_secs: Option<f64>) -> bool {
+        if !self.is_in_game {
+            return false;
+        }
+        let game_time = game_time_secs
+            .or_else(|| self.live_snapshots.latest().map(|m| m.game_time_secs))
+            .unwrap_or(0.0);
+        let Some(sample) = self.fps.record(game_time, fps) else {
+            return false;
+        };
+
+        if sample.frame_drop {
+            debug!(
+                "Frame drop at {:.1}s: {:.0} FPS (average {:.0})",
+                game_time, sample.fps, sample.baseline_fps
+            );
+        }
+        self.pending_events.push(GameEvent::new(
+            "FpsSample".to_string(),
+            game_time,
+            json!({
+                "fps": sample.fps,
+                "baseline_fps": sample.baseline_fps,
+                "frame_drop": sample.frame_drop,
+            }),
+        ));
+        true
+    }
+
+    /// Bookmark the current game time; the game time, or `None` outside a game
+    async fn bookmark_moment(&mut self, request_id: &str, note: Option<&str>) -> Option<f64> {
+        if !self.is_in_game {
+            return None;
+        }
+
+        // Live snapshots are only as fresh as the last poll
+        self.ensure_live_client();
+        let game_time = match self.live_client {
+            Some(ref live_client) => live_client.get_game_stats().await.ok().map(|s| s.game_time),
+            None => None,
+        };
+        let game_time = game_time
+            .or_else(|| self.live_snapshots.latest().map(|m| m.game_time_secs))
+            .unwrap_or(0.0);
+
+        self.record_bookmark(request_id, note, game_time);
+        Some(game_time)
+    }
+
+    /// Queue a bookmark event with the latest live snapshot
+    fn record_bookmark(&mut self, request_id: &str, note: Option<&str>, game_time: f64) {
+        info!("Bookmarked moment at {:.1}s ({})", game_time, request_id);
+        self.pending_events.push(GameEvent::new(
+            "Bookmark".to_string(),
+            game_time,
+            json!({
+                "request_id": request_id,
+                "note": note,
+                "is_player_involved": true,
+                "snapshot": self.live_snapshots.latest(),
+            }),
+        ));
+    }
+
+    /// What `session_end` would save now, with the same annotations
+    async fn preview_finalization(&mut self) -> FinalizationPreview {
+        let last_match = self.last_live_match.read().await.clone();
+        self.analyze_composition();
+
+        let mut preview = self
+            .finalizer
+            .preview_game(
+                last_match,
+                self.game_mode_context.as_ref(),
+                self.session_clock.as_ref().and_then(|c| c.game_started_at()),
+            )
+            .await;
+        if let Some(ref mut data) = preview.game {
+            if let Some(split) = self.stats.stats().ranked_split_at(data.played_at) {
+                data.season_id = Some(split.season_id);
+                data.split_id = Some(split.split_id);
+            }
+            data.latency = self.latency.summary();
+            data.objective_participation = Some(self.objectives.participation().clone());
+            mark_leavers(data, &self.leavers.idle_players());
+            data.puuid = self.account_puuid.clone();
+        }
+        info!(
+            "Finalization preview: {} (source: {:?})",
+            if preview.game.is_some() { "match" } else { "empty" },
+            preview.source
+        );
+        preview
+    }
+
+    /// Backfill game results from the client's match history
+    async fn import_match_history(
+        &mut self,
+        limit: Option<u32>,
+        channel: &dyn ReplyChannel,
+    ) -> crate::Result<ImportProgress> {
+        let failed = |e| crate::LeagueError::ImportFailed(Box::new(e));
+        let job = self.prepare_import(limit).await.map_err(failed)?;
+        let (progress, records) = job.run(channel).await.map_err(failed)?;
+        self.finish_import(records);
+        Ok(progress)
+    }
+
+    /// Set up a match history import that runs without the integration
+    /// (see `import_match_history` in the import module)
+    pub async fn prepare_import(&mut self, limit: Option<u32>) -> crate::Result<ImportJob> {
+        let client = self.try_lcu_client().ok_or(crate::LeagueError::LeagueNotRunning)?;
+
+        // Champion names need Data Dragon, which otherwise loads with the first game
+        if self.ddragon.version().is_none() {
+            let version = client.get_game_version().await?;
+            self.asset_urls = AssetUrls::from_game_version(&version);
+            let cache_dir = self
+                .asset_cache
+                .as_ref()
+                .map(|c| c.dir().to_path_buf())
+                .unwrap_or_else(default_cache_dir);
+            if let Err(e) = self.ddragon.load_now(self.asset_urls.version(), &cache_dir).await {
+                warn!("Importing without champion names: {}", e);
+            }
+        }
+
+        let known = self.stats.game_result_ids();
+        Ok(ImportJob::new(client, self.ddragon.clone(), known, limit))
+    }
+
+    /// Store the game results of a finished import
+    pub fn finish_import(&mut self, records: Vec<GameResultRecord>) {
+        self.stats.import_game_results(records);
+    }
+
+    /// Stored timeline of a match (event type, game time, killer/victim/assisters)
+    pub fn get_match_events(&self, match_id: &str) -> crate::Result<Vec<StoredGameEvent>> {
+        self.event_log.events_for(match_id)
+    }
+
+    /// Parse a match's stored end-of-game payload again.
+    ///
+    /// Only what the payload and its stored context determine is rebuilt;
+    /// latency and badges for records broken at the time aren't included.
+    pub fn reprocess_match(&self, match_id: &str) -> crate::Result<Option<crate::CreateMatch>> {
+        let raw = self.raw_matches.load(match_id)?;
+        let mut parsed = self
+            .finalizer
+            .reprocess(&raw)
+            .map_err(|e| crate::LeagueError::ReprocessFailed(e.to_string()))?;
+
+        if let Some(ref mut data) = parsed {
+            if let Some(split) = self.stats.stats().ranked_split_at(data.played_at) {
+                data.season_id = Some(split.season_id);
+                data.split_id = Some(split.split_id);
+            }
+            info!("Reprocessed match {}", match_id);
+        }
+        Ok(parsed)
+    }
+
+    /// Run a synthetic event through the trigger evaluator and queue it for
+    /// the next poll, exactly like an event from the Live Client API.
+    ///
+    /// `data` may set `event_time`, `killer_name`, `victim_name`, `assisters`,
+    /// `count`, `clip_start_secs` and `is_player_involved` (defaults to true
+    /// so player triggers fire).
+    pub fn simulate_event(&mut self, event_type: &str, data: &Value) -> SimulatedEventResult {
+        let str_field = |key: &str| data.get(key).and_then(|v| v.as_str()).map(String::from);
+
+        let event_time = data.get("event_time").and_then(|v| v.as_f64()).unwrap_or_else(|| {
+            self.live_snapshots.latest().map(|m| m.game_time_secs).unwrap_or(0.0)
+        });
+
+        let parsed = ParsedGameEvent {
+            event_type: LeagueEventType::from(event_type),
+            event_time,
+            killer_name: str_field("killer_name"),
+            victim_name: str_field("victim_name"),
+            assisters: data
+                .get("assisters")
+                .and_then(|v| serde_json::from_value(v.clone()).ok())
+                .unwrap_or_default(),
+            is_player_involved: data
+                .get("is_player_involved")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true),
+            count: data.get("count").and_then(|v| v.as_u64()).map(|c| c as u32),
+            clip_start_secs: data.get("clip_start_secs").and_then(|v| v.as_f64()),
+        };
+
+        let decision = self.triggers.evaluate(&parsed);
+        let triggered = decision.triggered;
+        let trigger = decision.trigger.clone();
+
+        info!(
+            "Simulated event: {} at {:.1}s (trigger: {:?})",
+            event_type, event_time, trigger
+        );
+
+        let event = GameEvent::new(
+            event_type.to_string(),
+            event_time,
+            json!({
+                "killer_name": parsed.killer_name,
+                "victim_name": parsed.victim_name,
+                "assisters": parsed.assisters,
+                "is_player_involved": parsed.is_player_involved,
+                "trigger": trigger,
+                "trigger_decision": decision,
+                "simulated": true,
+            }),
+        );
+        self.pending_events.push(event.clone());
+
+        SimulatedEventResult {
+            event,
+            triggered,
+            trigger,
+        }
+    }
+
+    /// Update where statistics are persisted (reloads from the new location)
+    pub fn set_stats_settings(&mut self, settings: StatsSettings) {
+        self.stats = StatsStore::open(&settings);
+        self.event_log = EventLog::open(&settings);
+        self.raw_matches = RawMatchStore::open(&settings);
+        self.event_discovery = EventDiscovery::open(&settings);
+        self.snapshots = SnapshotRecorder::new(&settings);
+        self.match_snapshots = SnapshotStore::open(&settings);
+        self.storage = DataStorage::open(&settings);
+        self.stats_settings = settings;
+    }
+
+    /// Update spectated game recording settings
+    pub fn set_spectator_settings(&mut self, settings: SpectatorSettings) {
+        self.spectator_settings = settings;
+    }
+
+    /// Update batching of pushed notifications
+    pub fn set_notification_settings(&self, settings: NotificationSettings) {
+        notification_writer().set_batch_window(Duration::from_millis(settings.batch_window_ms));
+    }
+
+    /// Read League on another PC (or the local machine again); the cached
+    /// client connection is dropped so the next request goes to the new host
+    pub fn set_remote_host(&self, settings: &RemoteHostSettings) {
+        crate::set_remote_host(settings);
+        lcu_connection().invalidate();
+    }
+
+    /// Change the soft daily playtime limit without reopening the stores
+    pub fn set_daily_playtime_limit(&mut self, mins: u32) {
+        self.stats_settings.daily_playtime_limit_mins = Some(mins).filter(|&mins| mins > 0);
+        self.stats.set_daily_limit(self.stats_settings.daily_playtime_limit_mins);
+    }
+
+    /// Let the pack drive sessions from the gameflow; the daemon's handler
+    /// starts or stops its `GameSessionController` to match
+    pub fn set_gameflow_sessions(&mut self, enabled: bool) {
+        self.gameflow_sessions = enabled;
+    }
+
+    /// Whether sessions follow the gameflow instead of the daemon
+    pub fn gameflow_sessions(&self) -> bool {
+        self.gameflow_sessions
+    }
+
+    /// Settings changeable with `Configure`, as currently in effect
+    fn settings_in_effect(&self) -> Value {
+        let notifications = NotificationSettings {
+            batch_window_ms: notification_writer().batch_window().as_millis() as u64,
+        };
+        json!({
+            "polling": polling_config(),
+            "notifications": notifications,
+            // The lockfile holds the client password and isn't echoed
+            "remote_host": { "host": crate::remote::remote_host() },
+            "asset_cache": self.asset_cache_settings,
+            "spectator": self.spectator_settings,
+            "triggers": self.triggers.config,
+            "buffers": self.buffer_settings,
+            "lcu_rate_limit": crate::rate_limit::lcu_rate_limit(),
+            "strings": { "locale": self.strings.locale() },
+            "stats": self.stats_settings,
+            "gameflow_sessions": self.gameflow_sessions,
+        })
+    }
+
+    /// Asset URLs with the local cache directory attached, queuing any
+    /// uncached icons for download when `prefetch` is set
+    fn assets_json(&mut self, mut assets: Value, prefetch: bool) -> Value {
+        if !self.asset_cache_settings.enabled {
+            return assets;
+        }
+
+        let cache = self.asset_cache.get_or_insert_with(|| {
+            AssetCache::start(&self.asset_cache_settings, &self.supervisor)
+        });
+        if prefetch {
+            cache.prefetch(&assets);
+        }
+        if let Value::Object(ref mut map) = assets {
+            map.insert("cacheDir".to_string(), json!(cache.dir()));
+        }
+        assets
+    }
+
+    /// Fill levels and drop counters for the in-memory buffers
+    pub fn metrics(&self) -> IntegrationMetrics {
+        IntegrationMetrics {
+            pending_events: self.pending_events.metrics(),
+            live_snapshots: self.live_snapshots.metrics(),
+            ws_backlog: combine_backlog_metrics(
+                [
+                    self.event_push.backlog_metrics(),
+                    self.champ_select.as_ref().and_then(ChampSelectCapture::backlog_metrics),
+                ]
+                .into_iter()
+                .flatten(),
+            ),
+            uptime_secs: self.connection_history.uptime_secs(),
+            connected_secs: self.connection_history.connected_secs(),
+            connection_history: self.connection_history.transitions(),
+        }
+    }
+
+    /// Live match snapshots recorded during the current session, oldest first
+    pub fn live_snapshots(&self) -> impl Iterator<Item = &LiveMatch> {
+        self.live_snapshots.iter()
+    }
+
+    /// Create the Live Client API if it isn't available yet
+    /// (e.g. Riot's certificate couldn't be located at startup)
+    fn ensure_live_client(&mut self) {
+        if self.live_client.is_none() {
+            self.live_client = LiveClientApi::new().ok();
+        }
+    }
+
+    fn replay_client(&mut self) -> crate::Result<&ReplayApi> {
+        let replay = match self.replay.take() {
+            Some(replay) => replay,
+            None => ReplayApi::new()?,
+        };
+        Ok(self.replay.insert(replay))
+    }
+
+    /// Command response for a Replay API call
+    fn replay_response<T: serde::Serialize>(result: crate::Result<T>) -> Value {
+        match result {
+            Ok(value) => serde_json::to_value(value).unwrap_or(Value::Null),
+            Err(e) => {
+                warn!("Replay API request failed: {}", e);
+                crate::LeagueError::ReplayRequestFailed(Box::new(e)).response()
+            }
+        }
+    }
+
+    /// Playback, camera/render and recording state of the loaded replay
+    async fn replay_state(&mut self) -> crate::Result<Value> {
+        let replay = self.replay_client()?;
+        let playback = replay.get_playback().await?;
+        let render = replay.get_render().await.ok();
+        let recording = replay.get_recording().await.ok();
+        Ok(json!({
+            "playback": playback,
+            "render": render,
+            "recording": recording,
+        }))
+    }
+
     /// Try to get the LCU client connection
-    fn try_lcu_client(&self) -> Option<crate::LcuClient> {
-        crate::LcuClient::new().ok()
+    fn try_lcu_client(&self) -> Option<LcuClient> {
+        lcu_connection().client()
     }
 
     /// Get current subpack index based on game mode
@@ -110,13 +940,240 @@ impl LeagueIntegration {
             .unwrap_or(false)
     }
 
-    /// Detect if League client is running
-    pub async fn detect_running(&self) -> bool {
-        self.try_lcu_client().is_some()
+    /// Whether a different game than `external_match_id` is being played,
+    /// which means that match is over. `false` when unknown.
+    pub fn is_playing_other_match(&self, external_match_id: &str) -> bool {
+        self.is_in_game
+            && self
+                .external_match_id
+                .as_deref()
+                .is_some_and(|id| id != external_match_id)
+    }
+
+    /// Detect which parts of League are running (client, game, or both).
+    ///
+    /// The game counts as running if the Live Client API answers or the game
+    /// process exists, so a crashed client mid-game still reports running.
+    pub async fn detect_running(&self) -> RunningStatus {
+        let client = self.try_lcu_client().is_some();
+        RunningStatus::from_parts(client, self.is_game_running().await)
+    }
+
+    /// Whether the Live Client API answers or the game process exists
+    async fn is_game_running(&self) -> bool {
+        let live_reachable = match self.live_client {
+            Some(ref live_client) => live_client.is_reachable().await,
+            None => match LiveClientApi::new() {
+                Ok(live_client) => live_client.is_reachable().await,
+                Err(_) => false,
+            },
+        };
+        live_reachable || is_game_process_running()
+    }
+
+    /// Store the rune page once the player has locked in. Refreshed on every
+    /// status check until the game loads, since runes can still be edited.
+    async fn capture_locked_rune_page(&mut self, client: &LcuClient) {
+        let locked = client
+            .get_champ_select_session()
+            .await
+            .ok()
+            .and_then(|session| session.local_state())
+            .is_some_and(|state| state.locked);
+
+        if locked && lcu_connection().supports(LcuFeature::Runes) {
+            if let Ok(page) = client.get_current_rune_page().await {
+                self.finalizer.capture_rune_page(page.into());
+            }
+        }
+    }
+
+    /// The local player's ward placements and clears from the game timeline.
+    ///
+    /// Best effort: the timeline isn't always available right after the game.
+    async fn fetch_vision_recap(&self, game_id: i64) -> Option<VisionRecap> {
+        if !lcu_connection().supports(LcuFeature::Timelines) {
+            return None;
+        }
+        let client = self.try_lcu_client()?;
+        let puuid = client.get_current_summoner().await.ok()?.puuid;
+
+        let participant_id = client
+            .get_match_history_game(game_id)
+            .await
+            .ok()?
+            .participant_id(&puuid)?;
+
+        match client.get_game_timeline(game_id).await {
+            Ok(timeline) => {
+                let recap = VisionRecap::from_timeline(&timeline, participant_id);
+                info!(
+                    "Vision recap: {} placed ({} control), {} cleared",
+                    recap.wards_placed, recap.control_wards_placed, recap.wards_cleared
+                );
+                Some(recap)
+            }
+            Err(e) => {
+                debug!("Game timeline not available for {}: {}", game_id, e);
+                None
+            }
+        }
+    }
+
+    /// Mastery of the champion played and the player's profile, for the
+    /// match card
+    async fn fetch_mastery(&self, data: &crate::CreateMatch) -> Option<Value> {
+        let client = self.try_lcu_client()?;
+        let summoner = client.get_current_summoner().await.ok()?;
+
+        let champion_id = data
+            .draft
+            .as_ref()
+            .map(|d| d.champion_id)
+            .filter(|&id| id != 0)
+            .or_else(|| self.ddragon.champion_id(&data.champion))?;
+
+        let mastery = if lcu_connection().supports(LcuFeature::Mastery) {
+            match client.get_champion_mastery(summoner.summoner_id).await {
+                Ok(masteries) => masteries.into_iter().find(|m| m.champion_id == champion_id),
+                Err(e) => {
+                    debug!("Champion mastery not available: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        Some(json!({
+            "mastery": mastery,
+            "mastery_label": mastery.as_ref().map(|m| m.label()),
+            "profile": {
+                "summoner_level": summoner.summoner_level,
+                "profile_icon_id": summoner.profile_icon_id,
+            },
+        }))
+    }
+
+    /// Follow the client logged in to `puuid`, or the lockfile's client for `None`
+    async fn select_connection(&mut self, puuid: Option<&str>) -> Value {
+        let port = match puuid {
+            Some(puuid) => {
+                let connections = lcu_connection().list_connections().await;
+                match connections.iter().find(|c| !c.puuid.is_empty() && c.puuid == puuid) {
+                    Some(connection) => Some(connection.port),
+                    None => return json!({ "selected": false }),
+                }
+            }
+            None => None,
+        };
+
+        lcu_connection().select(port);
+        self.prev_phase = None;
+        json!({ "selected": true })
+    }
+
+    /// Re-establish every connection to the client and game on request.
+    ///
+    /// Like a resume, the phase is forgotten so the next status check
+    /// re-emits it.
+    async fn reconnect(&mut self, request_id: &str) -> Value {
+        info!("Reconnect requested ({})", request_id);
+        self.connection_history.note_cause("reconnect requested");
+        self.prev_phase = None;
+
+        let client = lcu_connection().reconnect();
+        if let Some(ref capture) = self.champ_select {
+            capture.reconnect(&self.supervisor);
+        }
+        self.event_push.reconnect(&self.supervisor).await;
+
+        self.live_client = None;
+        self.ensure_live_client();
+        let live_client = match self.live_client {
+            Some(ref live_client) => live_client.is_reachable().await,
+            None => false,
+        };
+
+        json!({
+            "requestId": request_id,
+            "lcuConnected": client.is_some(),
+            "liveClientReachable": live_client,
+        })
+    }
+
+    /// Tear down cached connections after the machine wakes from sleep.
+    ///
+    /// Connections are re-discovered lazily, and the phase is forgotten so the
+    /// next status check re-emits it.
+    fn handle_resume(&mut self, gap: std::time::Duration) {
+        warn!(
+            "System resume detected ({}s gap), re-discovering League connections",
+            gap.as_secs()
+        );
+
+        self.live_client = None;
+        lcu_connection().invalidate();
+        self.connection_history.note_cause("system resumed");
+        self.prev_phase = None;
+        self.pending_events.push(GameEvent::new(
+            "SystemResumed".to_string(),
+            0.0,
+            json!({ "gap_secs": gap.as_secs() }),
+        ));
+    }
+
+    /// Mark the end of the session's game on the wall clock, so the recorder
+    /// can trim the post-game footage without waiting for the process to exit
+    fn mark_game_ended(&mut self, phase: GameflowPhase) {
+        let Some(ref clock) = self.session_clock else {
+            return;
+        };
+        let ended_at = clock.now();
+        let session_secs = clock.elapsed_secs();
+        info!(
+            "Game ended at {} ({:.1}s into the session, {})",
+            ended_at,
+            session_secs,
+            phase.display_name()
+        );
+        self.game_ended_at = Some(ended_at);
+        self.pending_events.push(GameEvent::new(
+            "GameEndedAt".to_string(),
+            clock.game_time_now().unwrap_or(0.0),
+            json!({
+                "ended_at": ended_at,
+                "session_secs": session_secs,
+                "phase": phase.display_name(),
+            }),
+        ));
+    }
+
+    /// Count and announce enemy bans of the champion the player was hovering
+    fn record_hover_bans(&mut self) {
+        let Some(ref capture) = self.champ_select else {
+            return;
+        };
+        for champion_id in capture.take_hover_bans() {
+            let champion = self
+                .ddragon
+                .champion_name(champion_id)
+                .unwrap_or_else(|| champion_id.to_string());
+            self.stats.record_hover_ban(&champion);
+            self.pending_events.push(GameEvent::new(
+                "HoverBanned".to_string(),
+                0.0,
+                json!({ "champion_id": champion_id, "champion": champion }),
+            ));
+        }
     }
 
     /// Get current integration status
     pub async fn get_status(&mut self) -> IntegrationStatus {
+        if let Some(gap) = self.sleep_detector.check() {
+            self.handle_resume(gap);
+        }
+
         // Try to connect to LCU
         if let Some(client) = self.try_lcu_client() {
             let new_status = ConnectionStatus::Connected;
@@ -135,10 +1192,19 @@ impl LeagueIntegration {
 
             self.connection_status = new_status;
 
+            if self.champ_select.is_none() {
+                self.champ_select = Some(ChampSelectCapture::start(&self.supervisor));
+            }
+            self.record_hover_bans();
+            lcu_connection().start_health_check(&self.supervisor);
+
             // Get current gameflow phase
             match client.get_gameflow_phase().await {
                 Ok(phase) => {
-                    let is_in_game = phase.is_in_game();
+                    self.connection_history.clear_cause();
+                    let is_spectating =
+                        phase.is_spectating() && self.spectator_settings.record_spectated_games;
+                    let is_in_game = phase.is_in_game() || is_spectating;
                     let new_phase = Some(phase.display_name().to_string());
 
                     // Emit PhaseChanged event if phase changed
@@ -154,13 +1220,79 @@ impl LeagueIntegration {
                                 "from": self.prev_phase,
                                 "to": new_phase,
                                 "phase": phase.display_name(),
+                                "phase_key": phase.string_key(),
+                                "phase_label": self.strings.get(phase.string_key()),
                             }),
                         ));
+                        if phase.is_post_game() && self.game_ended_at.is_none() {
+                            self.mark_game_ended(phase);
+                        }
+
+                        // Mode and map decide the expected length and quality
+                        let (game_mode, map_id) = match client.get_gameflow_session().await {
+                            Ok(session) => (session.game_mode().to_string(), session.map.id),
+                            Err(_) => (String::new(), 0),
+                        };
+                        let hint = recording_hint(phase, &game_mode, map_id);
+                        debug!("Recording hint: {:?}", hint);
+                        self.pending_events.push(GameEvent::new(
+                            "RecordingHint".to_string(),
+                            0.0,
+                            serde_json::to_value(&hint).unwrap_or(Value::Null),
+                        ));
                         self.prev_phase = new_phase.clone();
                     }
 
                     self.current_phase = new_phase;
                     self.is_in_game = is_in_game;
+                    self.is_spectating = is_spectating;
+
+                    // No end-of-game stats will follow a failed launch or crash
+                    if let Some(reason) = phase.abort_reason() {
+                        if self.session_context.is_some() && self.abort_reason.is_none() {
+                            warn!("Game session aborted: {}", reason);
+                            self.abort_reason = Some(reason);
+                            self.pending_events.push(GameEvent::new(
+                                "SessionAborted".to_string(),
+                                0.0,
+                                json!({
+                                    "reason": reason,
+                                    "phase": phase.display_name(),
+                                }),
+                            ));
+                        }
+                    }
+
+                    // Queue type is looked up once, when matchmaking or champ select starts
+                    let queue = if (phase == GameflowPhase::Matchmaking
+                        && !self.queue_timer.is_timing())
+                        || (phase == GameflowPhase::ChampSelect
+                            && !self.dodge_tracker.in_champ_select())
+                    {
+                        client
+                            .get_gameflow_session()
+                            .await
+                            .map(|s| QueueInfo {
+                                queue_id: s.game_data.queue.id,
+                                queue_name: s.game_data.queue.name,
+                            })
+                            .unwrap_or_default()
+                    } else {
+                        QueueInfo::default()
+                    };
+                    if let Some(record) = self.dodge_tracker.on_phase(phase, queue.queue_id) {
+                        self.finalizer.clear_rune_page();
+                        if let Some(ref capture) = self.champ_select {
+                            capture.clear();
+                        }
+                        self.stats.record_non_game(record);
+                    }
+                    if self.dodge_tracker.in_champ_select() {
+                        self.capture_locked_rune_page(&client).await;
+                    }
+                    if let Some(record) = self.queue_timer.on_phase(phase, queue) {
+                        self.stats.record_queue_time(record);
+                    }
 
                     if is_in_game {
                         self.connection_status = ConnectionStatus::InGame;
@@ -168,6 +1300,9 @@ impl LeagueIntegration {
                 }
                 Err(e) => {
                     debug!("Failed to get gameflow phase: {}", e);
+                    lcu_connection().invalidate();
+                    self.connection_history
+                        .note_cause(format!("gameflow request failed: {}", e));
                 }
             }
         } else {
@@ -186,18 +1321,49 @@ impl LeagueIntegration {
             self.connection_status = ConnectionStatus::Disconnected;
             self.current_phase = None;
             self.prev_phase = None;
+            self.profile = None;
             self.is_in_game = false;
+            self.is_spectating = false;
         }
 
+        let cause = match (self.prev_connection_status, self.connection_status) {
+            (ConnectionStatus::Disconnected, _) => "client found",
+            (_, ConnectionStatus::InGame) => "game started",
+            (ConnectionStatus::InGame, ConnectionStatus::Connected) => "game ended",
+            _ => "client not found",
+        };
+        self.connection_history
+            .record(self.prev_connection_status, self.connection_status, cause);
+
         // Update previous status for next comparison
         self.prev_connection_status = self.connection_status;
 
+        // The queue timer only runs while matchmaking
+        let queue_estimate = self
+            .stats
+            .queue_estimate(&self.queue_timer)
+            .filter(|_| self.connection_status != ConnectionStatus::Disconnected);
+
+        // A game in progress needs no probe; otherwise the client may have
+        // crashed with the game still running
+        let game_running = self.is_in_game || self.is_game_running().await;
+        let running = RunningStatus::from_parts(
+            self.connection_status != ConnectionStatus::Disconnected,
+            game_running,
+        );
+
         IntegrationStatus {
             game_slug: LEAGUE_SLUG.to_string(),
             connected: self.connection_status != ConnectionStatus::Disconnected,
             connection_status: self.connection_status,
             game_phase: self.current_phase.clone(),
             is_in_game: self.is_in_game,
+            is_spectating: self.is_spectating,
+            metrics: self.metrics(),
+            tasks: self.supervisor.health(),
+            profile: self.profile.clone(),
+            queue_estimate,
+            running,
         }
     }
 
@@ -206,13 +1372,17 @@ impl LeagueIntegration {
         // Check LCU status first - this emits ClientConnected/Disconnected/PhaseChanged events
         let _ = self.get_status().await;
 
-        let mut events = std::mem::take(&mut self.pending_events);
+        let mut events = self.pending_events.drain();
+        // Everything past the queued lifecycle events is pushed as game events
+        let first_game_event = events.len();
+        let mut game_started = false;
+        self.ensure_live_client();
 
         // Only poll if we have a live client and are in game
         if let Some(ref live_client) = self.live_client {
             // Try to get events from the Live Client API
-            match live_client.get_events().await {
-                Ok(game_events) => {
+            match live_client.get_events_raw().await {
+                Ok((game_events, raw_events)) => {
                     // Use cached player name, or try to fetch it if not cached
                     let player_name = if let Some(ref name) = self.active_player_name {
                         name.clone()
@@ -231,23 +1401,105 @@ impl LeagueIntegration {
                         }
                     };
 
-                    for event in game_events.events {
+                    // Health samples, for the damage taken before a death
+                    if let Some(game_time) = self.session_clock.as_ref().and_then(|c| c.game_time_now()) {
+                        if let Ok(player) = live_client.get_active_player().await {
+                            self.deaths.sample_health(game_time, player.champion_stats.current_health);
+                        }
+                    }
+
+                    for (event, raw) in game_events.into_iter().zip(raw_events) {
                         // Skip already processed events
                         if event.event_id <= self.last_event_id {
                             continue;
                         }
                         self.last_event_id = event.event_id;
+                        self.event_discovery.record(
+                            self.external_match_id.as_deref(),
+                            &event.event_name,
+                            &raw,
+                        );
+                        game_started |= event.event_name == "GameStart";
 
                         // Check if player is involved in this event (only if we have a valid player name)
+                        let is_player = |name: &String| riot_ids_match(name, &player_name);
                         let is_player_involved = !player_name.is_empty() && (
-                            event.killer_name.as_ref() == Some(&player_name)
-                            || event.victim_name.as_ref() == Some(&player_name)
-                            || event.assisters.contains(&player_name)
+                            event.killer_name.as_ref().is_some_and(is_player)
+                            || event.victim_name.as_ref().is_some_and(is_player)
+                            || event.assisters.iter().any(is_player)
+                        );
+
+                        info!(
+                            "Game event: {} at {:.1}s (player_involved: {})",
+                            event.event_name, event.event_time, is_player_involved
+                        );
+
+                        let mut death_context = None;
+                        if event.event_name == "ChampionKill"
+                            && !player_name.is_empty()
+                            && event.victim_name.as_ref().is_some_and(is_player)
+                        {
+                            // Everyone's items, for what the killer had
+                            let players = match live_client.get_all_game_data().await {
+                                Ok(game_data) => game_data.all_players,
+                                Err(e) => {
+                                    debug!("Failed to get game data for death context: {}", e);
+                                    Vec::new()
+                                }
+                            };
+                            let death = self.deaths.record(
+                                event.event_time,
+                                event.killer_name.as_deref().unwrap_or_default(),
+                                &event.assisters,
+                                self.live_snapshots.latest(),
+                                &players,
+                            );
+                            debug!("Recorded death: {:?}", death);
+                            death_context = Some(death.clone());
+                        }
+
+                        // Kills on a signature champion rank above ordinary kills
+                        let mut signature = None;
+                        let mut importance = None;
+                        if event.event_name == "ChampionKill"
+                            && !player_name.is_empty()
+                            && event.killer_name.as_ref().is_some_and(is_player)
+                        {
+                            signature = self.live_snapshots.latest().and_then(signature_for_kill);
+                            importance = Some(kill_importance(signature));
+                        }
+
+                        self.momentum.observe_event(&event.event_name, event.event_time, is_player_involved);
+                        let contested = self.objectives.observe(
+                            &event,
+                            self.live_snapshots.latest(),
+                            &player_name,
                         );
 
-                        // Create game event using protocol types
+                        // Offset from session start on the monotonic clock (for clip alignment)
+                        let session_offset_secs = self
+                            .session_clock
+                            .as_ref()
+                            .and_then(|c| c.game_time_to_session_secs(event.event_time));
+
+                        let parsed = ParsedGameEvent {
+                            event_type: LeagueEventType::from(event.event_name.as_str()),
+                            event_time: event.event_time,
+                            killer_name: event.killer_name.clone(),
+                            victim_name: event.victim_name.clone(),
+                            assisters: event.assisters.clone(),
+                            is_player_involved,
+                            count: event.kill_streak,
+                            clip_start_secs: None,
+                        };
+
+                        // Record the clip trigger decision with the stored event
+                        let decision = self.triggers.evaluate(&parsed);
+                        let streak_events = self.streaks.observe(&parsed, &player_name);
+
+                        // Create game event using protocol types (fields are moved, not cloned)
                         let game_event = GameEvent::new(
-                            event.event_name.clone(),
+                            event.event_name,
                             event.event_time,
                             serde_json::json!({
                                 "event_id": event.event_id,
@@ -255,15 +1507,82 @@ impl LeagueIntegration {
                                 "victim_name": event.victim_name,
                                 "assisters": event.assisters,
                                 "is_player_involved": is_player_involved,
+                                "session_offset_secs": session_offset_secs,
+                                "trigger_decision": decision,
+                                "death_context": death_context,
+                                "signature_moment": signature,
+                                "importance": importance,
                             }),
                         );
 
-                        info!(
-                            "Game event: {} at {:.1}s (player_involved: {})",
-                            event.event_name, event.event_time, is_player_involved
-                        );
-
                         events.push(game_event);
+
+                        if let Some(contested) = contested {
+                            let decision = self.triggers.evaluate(&contested.event);
+                            info!(
+                                "Contested {} at {:.1}s ({:?} kills, stolen: {})",
+                                contested.objective,
+                                contested.event.event_time,
+                                contested.event.count,
+                                contested.stolen
+                            );
+                            events.push(GameEvent::new(
+                                contested.event.event_type.as_str().to_string(),
+                                contested.event.event_time,
+                                json!({
+                                    "objective": contested.objective,
+                                    "stolen": contested.stolen,
+                                    "killer_name": contested.event.killer_name,
+                                    "involved": contested.event.assisters,
+                                    "kills": contested.event.count,
+                                    "clip_start_secs": contested.event.clip_start_secs,
+                                    "is_player_involved": contested.event.is_player_involved,
+                                    "trigger_decision": decision,
+                                }),
+                            ));
+
+                            if let Some(steal) = contested.steal_event() {
+                                let decision = self.triggers.evaluate(&steal);
+                                info!(
+                                    "{} stolen by {:?} at {:.1}s",
+                                    contested.objective, steal.killer_name, steal.event_time
+                                );
+                                events.push(GameEvent::new(
+                                    steal.event_type.as_str().to_string(),
+                                    steal.event_time,
+                                    json!({
+                                        "objective": contested.objective,
+                                        "killer_name": steal.killer_name,
+                                        "clip_start_secs": steal.clip_start_secs,
+                                        "is_player_involved": steal.is_player_involved,
+                                        "trigger_decision": decision,
+                                    }),
+                                ));
+                            }
+                        }
+
+                        // Sprees, shutdowns and multikill chains derived from the raw events
+                        for streak in streak_events {
+                            let decision = self.triggers.evaluate(&streak);
+                            info!(
+                                "Streak event: {} ({:?}) at {:.1}s",
+                                streak.event_type.as_str(), streak.count, streak.event_time
+                            );
+                            events.push(GameEvent::new(
+                                streak.event_type.as_str().to_string(),
+                                streak.event_time,
+                                json!({
+                                    "killer_name": streak.killer_name,
+                                    "victim_name": streak.victim_name,
+                                    "assisters": streak.assisters,
+                                    "count": streak.count,
+                                    "clip_start_secs": streak.clip_start_secs,
+                                    "is_player_involved": streak.is_player_involved,
+                                    "session_offset_secs": session_offset_secs,
+                                    "trigger_decision": decision,
+                                }),
+                            ));
+                        }
                     }
                 }
                 Err(e) => {
@@ -273,9 +1592,44 @@ impl LeagueIntegration {
             }
         }
 
+        if game_started && !self.intro_sent && !self.is_tft() {
+            events.extend(self.match_intro().await);
+        }
+
+        if self.is_tft() && self.is_in_game {
+            events.extend(self.poll_tft_events().await);
+        }
+
+        if self.is_in_game {
+            events.extend(self.sample_latency().await);
+        }
+
+        // Teamfights and comebacks, from polled events and live snapshots
+        for window in self.momentum.take_highlights() {
+            info!(
+                "Highlight window: {:?} {:.1}s-{:.1}s (peak {:.1})",
+                window.kind, window.start_secs, window.end_secs, window.peak_score
+            );
+            events.push(GameEvent::new(
+                window.event_name().to_string(),
+                window.end_secs,
+                json!({
+                    "kind": window.kind,
+                    "clip_start_secs": window.start_secs,
+                    "end_secs": window.end_secs,
+                    "peak_score": window.peak_score,
+                    "kills": window.kills,
+                    "is_player_involved": window.player_involved,
+                }),
+            ));
+        }
+
+        self.event_push.publish_game_events(&events[first_game_event..]);
+
         // Emit events to daemon for timeline storage
         if !events.is_empty() {
             if let Some(ref external_id) = self.external_match_id {
+                self.event_log.append(external_id, &events);
                 emit_game_events(self.current_subpack, external_id.clone(), events.clone());
                 debug!(
                     "Emitted {} game events for match {}",
@@ -295,6 +1649,86 @@ impl LeagueIntegration {
         events
     }
 
+    /// Matchup card for overlays, sent once when the game starts
+    async fn match_intro(&mut self) -> Option<GameEvent> {
+        let live_client = self.live_client.as_ref()?;
+        let live_match = match live_client.get_all_game_data().await {
+            Ok(game_data) => game_data.to_live_match()?,
+            Err(e) => {
+                debug!("Failed to get game data for match intro: {}", e);
+                return None;
+            }
+        };
+        self.intro_sent = true;
+
+        let queue_name = self.game_mode_context.as_ref().map(|c| c.queue_name.clone());
+        let rank = self
+            .pre_game_rank
+            .as_ref()
+            .filter(|r| !r.tier.is_empty())
+            .map(|r| format!("{} {}", r.tier, r.division));
+        let intro = self.asset_urls.match_intro(&live_match, queue_name, rank);
+        info!(
+            "Match intro: {} ({}) with {} allies and {} enemies",
+            intro.player.champion,
+            intro.player.role,
+            intro.allies.len(),
+            intro.enemies.len()
+        );
+
+        Some(GameEvent::new(
+            "MatchIntro".to_string(),
+            live_match.game_time_secs,
+            serde_json::to_value(&intro).unwrap_or(Value::Null),
+        ))
+    }
+
+    /// Round, carousel and elimination events from the LCU TFT game state
+    async fn poll_tft_events(&mut self) -> Vec<GameEvent> {
+        if !lcu_connection().supports(LcuFeature::Tft) {
+            return Vec::new();
+        }
+        let Some(client) = self.try_lcu_client() else {
+            return Vec::new();
+        };
+        let state = match client.get_tft_game_state().await {
+            Ok(state) => state,
+            Err(e) => {
+                debug!("Failed to get TFT game state: {}", e);
+                return Vec::new();
+            }
+        };
+
+        let game_time = self
+            .live_snapshots
+            .latest()
+            .map(|m| m.game_time_secs)
+            .or_else(|| self.tft_board.as_ref().map(|b| b.game_time_secs))
+            .unwrap_or(0.0);
+        let events = self.tft.update(&state, game_time);
+        for event in &events {
+            info!("TFT event: {} at {:.1}s", event.event_type, game_time);
+        }
+        events
+    }
+
+    /// Probe the game server when a latency sample is due
+    async fn sample_latency(&mut self) -> Option<GameEvent> {
+        let game_time = self.live_snapshots.latest()?.game_time_secs;
+        let server = self.latency.due(game_time)?;
+        let round_trip = measure_latency(server).await;
+        let Some(ping_ms) = self.latency.record(game_time, round_trip) else {
+            debug!("Game server {} did not answer the latency probe", server);
+            return None;
+        };
+
+        Some(GameEvent::new(
+            "PingSample".to_string(),
+            game_time,
+            json!({ "ping_ms": ping_ms }),
+        ))
+    }
+
     /// Detect recordable moments from game events.
     ///
     /// Moments are things that might be worth recording as clips.
@@ -318,7 +1752,7 @@ impl LeagueIntegration {
 
             match event_type.as_str() {
                 // Player death
-                "ChampionKill" if victim == Some(player_name) => {
+                "ChampionKill" if victim.is_some_and(|v| riot_ids_match(v, player_name)) => {
                     moments.push(Moment::new(
                         "death",
                         game_time,
@@ -330,7 +1764,7 @@ impl LeagueIntegration {
                 }
 
                 // Player kill
-                "ChampionKill" if killer == Some(player_name) => {
+                "ChampionKill" if killer.is_some_and(|k| riot_ids_match(k, player_name)) => {
                     moments.push(Moment::new(
                         "kill",
                         game_time,
@@ -366,6 +1800,49 @@ impl LeagueIntegration {
                     ));
                 }
 
+                // Teamfights and comebacks from the excitement score
+                "HighlightWindow" => {
+                    moments.push(Moment::new(
+                        "highlight_window",
+                        game_time,
+                        json!({
+                            "kind": event.data.get("kind"),
+                            "clip_start_secs": event.data.get("clip_start_secs"),
+                            "peak_score": event.data.get("peak_score"),
+                            "is_player_involved": is_player_involved,
+                        }),
+                    ));
+                }
+
+                // Sprees, shutdowns and chains derived by the streak tracker
+                "KillingSpree" | "Shutdown" | "MultikillChain" if is_player_involved => {
+                    let moment_id = match event_type.as_str() {
+                        "KillingSpree" => "killing_spree",
+                        "Shutdown" => "shutdown",
+                        _ => "multikill_chain",
+                    };
+
+                    moments.push(Moment::new(
+                        moment_id,
+                        game_time,
+                        json!({
+                            "count": event.data.get("count"),
+                            "clip_start_secs": event.data.get("clip_start_secs"),
+                        }),
+                    ));
+                }
+
+                // Moments marked by hand
+                "Bookmark" => {
+                    moments.push(Moment::new(
+                        "bookmark",
+                        game_time,
+                        json!({
+                            "note": event.data.get("note"),
+                        }),
+                    ));
+                }
+
                 // First blood
                 "FirstBlood" if is_player_involved => {
                     moments.push(Moment::new(
@@ -389,28 +1866,72 @@ impl LeagueIntegration {
                     ));
                 }
 
-                // Baron kills
-                "BaronKill" if is_player_involved => {
+                // Baron kills
+                "BaronKill" if is_player_involved => {
+                    moments.push(Moment::new(
+                        "baron_kill",
+                        game_time,
+                        json!({}),
+                    ));
+                }
+
+                // Elder dragon
+                "ElderDragonKill" if is_player_involved => {
+                    moments.push(Moment::new(
+                        "elder_dragon_kill",
+                        game_time,
+                        json!({}),
+                    ));
+                }
+
+                // Dragon or Baron stolen by the player
+                "ObjectiveSteal" if is_player_involved => {
+                    moments.push(Moment::new(
+                        "objective_steal",
+                        game_time,
+                        json!({
+                            "objective": event.data.get("objective"),
+                        }),
+                    ));
+                }
+
+                // Rift Herald
+                "HeraldKill" if is_player_involved => {
                     moments.push(Moment::new(
-                        "baron_kill",
+                        "herald_kill",
                         game_time,
                         json!({}),
                     ));
                 }
 
-                // Elder dragon
-                "ElderDragonKill" if is_player_involved => {
+                // TFT: the player was knocked out
+                "TftPlayerEliminated" if is_player_involved => {
                     moments.push(Moment::new(
-                        "elder_dragon_kill",
+                        "tft_eliminated",
                         game_time,
-                        json!({}),
+                        json!({
+                            "placement": event.data.get("placement"),
+                        }),
                     ));
                 }
 
-                // Rift Herald
-                "HeraldKill" if is_player_involved => {
+                // TFT: the player won a fight against another player
+                "TftFightWon" => {
                     moments.push(Moment::new(
-                        "herald_kill",
+                        "tft_fight_won",
+                        game_time,
+                        json!({
+                            "stage": event.data.get("stage"),
+                            "round": event.data.get("round"),
+                            "label": event.data.get("label"),
+                        }),
+                    ));
+                }
+
+                // TFT: the player made it into the top four
+                "TftTopFour" => {
+                    moments.push(Moment::new(
+                        "tft_top_four",
                         game_time,
                         json!({}),
                     ));
@@ -434,17 +1955,118 @@ impl LeagueIntegration {
 
     /// Get live match data
     pub async fn get_live_data(&mut self) -> Option<LiveMatchData> {
+        self.get_live_data_fields(None).await
+    }
+
+    /// Pre-game live data while in champ select: the hovered/locked champion,
+    /// role, spells and current rune page.
+    async fn get_champ_select_data(&self) -> Option<LiveMatchData> {
+        let capabilities = lcu_connection().capabilities();
+        if !capabilities.supports(LcuFeature::ChampSelect) {
+            return None;
+        }
+        let client = self.try_lcu_client()?;
+        let mut state = client.get_champ_select_session().await.ok()?.local_state()?;
+
+        let page = if capabilities.supports(LcuFeature::Runes) {
+            client.get_current_rune_page().await.ok()
+        } else {
+            None
+        };
+        if let Some(page) = page {
+            state.keystone_id = page.selected_perk_ids.first().copied();
+            state.primary_tree_id = Some(page.primary_style_id);
+            state.secondary_tree_id = Some(page.sub_style_id);
+        }
+
+        Some(LiveMatchData {
+            game_id: 0,
+            game_time_secs: 0.0,
+            data: json!({
+                "phase": "champSelect",
+                "champSelect": state,
+            }),
+        })
+    }
+
+    /// Get live match data restricted to the requested field groups.
+    ///
+    /// Masks that only need game time and scores are served from the small
+    /// `gamestats` / `playerscores` endpoints instead of `allgamedata`. An
+    /// empty mask returns every field, like no mask.
+    pub async fn get_live_data_fields(&mut self, fields: Option<&[LiveField]>) -> Option<LiveMatchData> {
+        let fields = LiveField::mask(fields);
         if !self.is_in_game {
+            if self.dodge_tracker.in_champ_select() {
+                return self.get_champ_select_data().await;
+            }
             return None;
         }
 
+        if self.is_tft() {
+            return self.get_tft_live_data().await;
+        }
+
+        if let Some(fields) = fields.filter(|f| LiveField::is_lightweight(f)) {
+            return self.get_lightweight_live_data(fields).await;
+        }
+
         // Try to get live data from live client API
+        self.ensure_live_client();
         if let Some(ref live_client) = self.live_client {
             match live_client.get_all_game_data().await {
                 Ok(game_data) => {
-                    if let Some(live_match) = LiveMatch::from_game_data(&game_data) {
+                    if let Some(live_match) = game_data.to_live_match() {
+                        if let Some(ref mut clock) = self.session_clock {
+                            clock.sync_game_time(live_match.game_time_secs);
+                        }
+
                         // Store for session end
                         *self.last_live_match.write().await = Some(live_match.clone());
+                        self.live_snapshots.push(live_match.clone());
+                        self.triggers.set_teammates(
+                            live_match
+                                .participants
+                                .iter()
+                                .filter(|p| p.team == live_match.team)
+                                .map(|p| p.summoner_name.clone()),
+                        );
+
+                        // Momentum shifts go out with the next poll, like any other game event
+                        for marker in self.momentum.record(&live_match) {
+                            info!("Momentum marker: {:?}", marker);
+                            self.pending_events.push(GameEvent::new(
+                                marker.event_name().to_string(),
+                                live_match.game_time_secs,
+                                json!({
+                                    "marker": marker,
+                                    "player_team": live_match.team,
+                                }),
+                            ));
+                        }
+
+                        self.leavers.observe(&live_match);
+
+                        // Control wards, guessed from the inventory
+                        let game_time = live_match.game_time_secs;
+                        for ward in self.vision.observe(&live_match.items, game_time) {
+                            let decision = self.triggers.evaluate(&ward);
+                            info!(
+                                "Vision event: {} at {:.1}s",
+                                ward.event_type.as_str(),
+                                ward.event_time
+                            );
+                            self.pending_events.push(GameEvent::new(
+                                ward.event_type.as_str().to_string(),
+                                ward.event_time,
+                                json!({
+                                    "ward_type": "CONTROL_WARD",
+                                    "count": ward.count,
+                                    "is_player_involved": true,
+                                    "trigger_decision": decision,
+                                }),
+                            ));
+                        }
 
                         // Emit statistics to daemon (with delta detection)
                         if let Some(ref external_id) = self.external_match_id {
@@ -464,10 +2086,29 @@ impl LeagueIntegration {
                             }
                         }
 
+                        // The asset set doesn't change mid-game, so only queue it once
+                        let prefetch = !self.live_assets_prefetched;
+                        self.live_assets_prefetched = true;
+                        let assets = self.asset_urls.for_live_match(&live_match);
+                        let assets = self.assets_json(assets, prefetch);
+
+                        let objectives = self.objectives.timers(live_match.game_time_secs);
+                        let mut data = serde_json::to_value(&live_match).unwrap_or(Value::Null);
+                        if let Value::Object(ref mut map) = data {
+                            map.insert("assets".to_string(), assets);
+                            map.insert(
+                                "objectives".to_string(),
+                                serde_json::to_value(objectives).unwrap_or(Value::Null),
+                            );
+                        }
+                        if let Some(fields) = fields {
+                            data = LiveField::select(fields, data);
+                        }
+
                         return Some(LiveMatchData {
                             game_id: LEAGUE_GAME_ID,
                             game_time_secs: live_match.game_time_secs,
-                            data: serde_json::to_value(&live_match).unwrap_or(Value::Null),
+                            data,
                         });
                     }
                 }
@@ -480,19 +2121,146 @@ impl LeagueIntegration {
         None
     }
 
+    /// The player's board during TFT games, which have no Summoner's Rift
+    /// players to build a `LiveMatch` from. Field masks don't apply.
+    async fn get_tft_live_data(&mut self) -> Option<LiveMatchData> {
+        if !lcu_connection().supports(LcuFeature::Tft) {
+            return None;
+        }
+        let client = self.try_lcu_client()?;
+        let state = match client.get_tft_game_state().await {
+            Ok(state) => state,
+            Err(e) => {
+                debug!("Failed to get TFT game state: {}", e);
+                return None;
+            }
+        };
+
+        // Gold, level and game time are all the Live Client reports in TFT
+        self.ensure_live_client();
+        let (active, game_time) = match self.live_client {
+            Some(ref live_client) => (
+                live_client.get_active_player().await.ok(),
+                live_client.get_game_stats().await.ok().map(|s| s.game_time),
+            ),
+            None => (None, None),
+        };
+        if let (Some(game_time), Some(clock)) = (game_time, self.session_clock.as_mut()) {
+            clock.sync_game_time(game_time);
+        }
+
+        let board = TftLiveBoard::new(&state, active.as_ref(), game_time.unwrap_or(0.0))?;
+        self.tft_board = Some(board.clone());
+
+        let mut data = serde_json::to_value(&board).unwrap_or(Value::Null);
+        if let Value::Object(ref mut map) = data {
+            map.insert("phase".to_string(), json!("tft"));
+        }
+        Some(LiveMatchData {
+            game_id: LEAGUE_GAME_ID,
+            game_time_secs: board.game_time_secs,
+            data,
+        })
+    }
+
+    /// Game time and/or scores without fetching the full game snapshot
+    async fn get_lightweight_live_data(&mut self, fields: &[LiveField]) -> Option<LiveMatchData> {
+        self.ensure_live_client();
+        let live_client = self.live_client.as_ref()?;
+
+        let stats = match live_client.get_game_stats().await {
+            Ok(stats) => stats,
+            Err(e) => {
+                debug!("Failed to get game stats: {}", e);
+                return None;
+            }
+        };
+
+        let mut data = json!({ "gameTimeSecs": stats.game_time });
+
+        if fields.contains(&LiveField::Scores) {
+            let name = match self.active_player_name.clone() {
+                Some(name) => name,
+                None => live_client.get_active_player_name().await.ok()?,
+            };
+            let scores = live_client.get_player_scores(&name).await.ok()?;
+            data = json!({
+                "summonerName": name,
+                "gameTimeSecs": stats.game_time,
+                "kills": scores.kills,
+                "deaths": scores.deaths,
+                "assists": scores.assists,
+                "cs": scores.creep_score,
+            });
+        }
+
+        if fields.contains(&LiveField::Objectives) {
+            if let Value::Object(ref mut map) = data {
+                let objectives = self.objectives.timers(stats.game_time);
+                map.insert(
+                    "objectives".to_string(),
+                    serde_json::to_value(objectives).unwrap_or(Value::Null),
+                );
+            }
+        }
+
+        if let Some(ref mut clock) = self.session_clock {
+            clock.sync_game_time(stats.game_time);
+        }
+
+        Some(LiveMatchData {
+            game_id: LEAGUE_GAME_ID,
+            game_time_secs: stats.game_time,
+            data: LiveField::select(fields, data),
+        })
+    }
+
     /// Start a game session
     pub async fn session_start(&mut self) -> Option<Value> {
         info!("League session starting");
 
+        // Anchor session timing on the monotonic clock
+        let session_clock = SessionClock::start();
+
         // Reset event tracking for new session
         self.last_event_id = -1;
         self.is_in_game = true;
         self.active_player_name = None;
+        self.abort_reason = None;
         self.external_match_id = None;
+        self.game_ended_at = None;
         self.current_subpack = SUBPACK_LEAGUE;
         self.last_emitted_stats = None;
+        self.live_snapshots.clear();
+        self.live_assets_prefetched = false;
+        self.intro_sent = false;
+        self.latency.start(None);
+        self.fps.reset();
+        self.objectives.reset();
+        self.session_spectated = false;
+        self.momentum.reset();
+        self.deaths.reset();
+        self.tft.reset();
+        self.tft_board = None;
+        self.streaks.reset();
+        self.vision.reset();
+        self.leavers.reset();
+        self.triggers.reset();
+        self.premades.clear();
+        self.account_puuid = None;
+
+        if let Some(draft) = self.champ_select.as_ref().and_then(|c| c.take_draft()) {
+            self.finalizer.capture_draft(draft);
+        }
+
+        if self.is_spectating {
+            return self.spectated_session_start(session_clock).await;
+        }
+
+        self.snapshots.start(&self.supervisor);
 
         // Try to pre-fetch active player name from Live Client API
+        self.ensure_live_client();
         if let Some(ref live_client) = self.live_client {
             if let Ok(player) = live_client.get_active_player().await {
                 info!("Active player name: {}", player.summoner_name);
@@ -505,6 +2273,49 @@ impl LeagueIntegration {
 
         // Get pre-game rank and game mode context
         if let Some(client) = self.try_lcu_client() {
+            // Patch version for asset URLs
+            if let Ok(version) = client.get_game_version().await {
+                self.asset_urls = AssetUrls::from_game_version(&version);
+                debug!("Asset version: {}", self.asset_urls.version());
+
+                let cache_dir = self
+                    .asset_cache
+                    .as_ref()
+                    .map(|c| c.dir().to_path_buf())
+                    .unwrap_or_else(default_cache_dir);
+                self.ddragon.load(self.asset_urls.version(), cache_dir);
+            }
+
+            // Account the game is played on, to tell several accounts apart
+            let summoner = client.get_current_summoner().await.ok();
+            self.account_puuid = summoner.as_ref().map(|s| s.puuid.clone());
+            let capabilities = lcu_connection().capabilities();
+
+            // Profile header (level, ranked border, honor), once per session
+            if let Some(ref summoner) = summoner {
+                self.profile = Some(Self::fetch_profile(&client, summoner, &capabilities).await);
+            }
+
+            // Party members, for duo stats once the game is over
+            self.premades = match summoner.filter(|_| capabilities.supports(LcuFeature::Lobby)) {
+                Some(summoner) => match client.get_lobby().await {
+                    Ok(lobby) => lobby.premades(&summoner.puuid),
+                    Err(_) => Vec::new(),
+                },
+                None => Vec::new(),
+            };
+            if !self.premades.is_empty() {
+                debug!("Premade with {:?}", self.premades);
+            }
+
+            // Split boundaries, so games can be grouped per split
+            if capabilities.supports(LcuFeature::RankedSplits) {
+                match client.get_splits_config().await {
+                    Ok(config) => self.stats.record_ranked_splits(config.ranked_splits()),
+                    Err(e) => debug!("Failed to get splits config: {}", e),
+                }
+            }
+
             // Get game mode from gameflow session first (needed to determine which rank to fetch)
             if let Ok(session) = client.get_gameflow_session().await {
                 let game_mode = session.game_mode();
@@ -517,11 +2328,14 @@ impl LeagueIntegration {
                     info!("Match external ID: {}", game_id);
                 }
 
+                self.latency.start(session.game_client.server_addr());
+
                 self.game_mode_context = Some(GameModeContext::from_session(
                     game_mode,
                     queue.id,
                     &queue.name,
                     queue.is_ranked,
+                    session.map.id,
                 ));
 
                 // Determine subpack based on game mode
@@ -529,16 +2343,22 @@ impl LeagueIntegration {
                 self.current_subpack = if is_tft { SUBPACK_TFT } else { SUBPACK_LEAGUE };
 
                 info!(
-                    "Game mode detected: {} (queue: {}, ranked: {}, subpack: {})",
+                    "Game mode detected: {} (queue: {}, ranked: {}, map: {}, subpack: {})",
                     self.game_mode_context.as_ref().map(|c| c.display_name.as_str()).unwrap_or("unknown"),
                     queue.name,
                     queue.is_ranked,
+                    session.map.id,
                     self.current_subpack
                 );
             }
 
             // Get ranked stats - select appropriate queue based on game mode
-            if let Ok(ranks) = client.get_ranked_stats().await {
+            let ranks = if capabilities.supports(LcuFeature::Ranked) {
+                client.get_ranked_stats().await.ok()
+            } else {
+                None
+            };
+            if let Some(ranks) = ranks {
                 let is_tft = self.game_mode_context.as_ref().map(|c| c.is_tft()).unwrap_or(false);
 
                 self.pre_game_rank = if is_tft {
@@ -554,41 +2374,340 @@ impl LeagueIntegration {
         }
 
         // Create session context with game mode info
-        let context = SessionContext::new(json!({
-            "pre_game_rank": self.pre_game_rank,
-            "game_mode": self.game_mode_context,
-            "subpack": self.current_subpack,
-            "external_match_id": self.external_match_id,
-        }));
+        let composition = self.analyze_composition();
+        let context = SessionContext::anchored(
+            json!({
+                "pre_game_rank": self.pre_game_rank,
+                "game_mode": self.game_mode_context,
+                "subpack": self.current_subpack,
+                "external_match_id": self.external_match_id,
+                "puuid": self.account_puuid,
+                "profile": self.profile,
+                "composition": composition,
+            }),
+            session_clock.anchor(),
+        );
+        self.session_clock = Some(session_clock);
+
+        self.session_context = Some(context.clone());
+
+        Some(serde_json::to_value(&context).unwrap_or(Value::Null))
+    }
+
+    /// Level, ranked border and honor level of the logged-in account.
+    /// Regalia and honor are skipped when the client lacks them.
+    async fn fetch_profile(
+        client: &LcuClient,
+        summoner: &crate::Summoner,
+        capabilities: &LcuCapabilities,
+    ) -> ProfileMetadata {
+        let ranked_border = if capabilities.supports(LcuFeature::Regalia) {
+            match client.get_regalia().await {
+                Ok(regalia) => regalia.ranked_border(),
+                Err(e) => {
+                    debug!("Regalia not available: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        let honor_level = if capabilities.supports(LcuFeature::Honor) {
+            match client.get_honor_profile().await {
+                Ok(honor) => Some(honor.honor_level),
+                Err(e) => {
+                    debug!("Honor level not available: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        ProfileMetadata {
+            summoner_level: summoner.summoner_level,
+            profile_icon_id: summoner.profile_icon_id,
+            ranked_border,
+            honor_level,
+        }
+    }
+
+    /// Team compositions of the captured draft, analyzed once Data Dragon
+    /// is loaded
+    fn analyze_composition(&mut self) -> Option<DraftComposition> {
+        let draft = self.finalizer.draft_mut()?;
+        if draft.composition.is_none() {
+            draft.composition = analyze_draft(draft, &self.ddragon);
+        }
+        draft.composition.clone()
+    }
+
+    /// Remember the player's rank before the game (e.g. from champ select)
+    pub async fn capture_pre_game_rank(&mut self) {
+        self.finalizer.capture_pre_game_rank().await;
+    }
+
+    /// End the current session without waiting for end-of-game stats
+    /// (the game failed to launch or crashed)
+    pub fn mark_aborted(&mut self, reason: &'static str) {
+        if self.abort_reason.is_none() {
+            warn!("Game session aborted: {}", reason);
+            self.abort_reason = Some(reason);
+        }
+    }
+
+    /// Start a session for a spectated game.
+    ///
+    /// No rank or external match ID is captured, so nothing from the game
+    /// reaches the user's personal stats or match timeline.
+    async fn spectated_session_start(&mut self, session_clock: SessionClock) -> Option<Value> {
+        self.session_spectated = true;
+
+        let mut spectated_game_id = None;
+        let mut spectated_player = None;
+
+        if let Some(client) = self.try_lcu_client() {
+            if let Ok(session) = client.get_gameflow_session().await {
+                let queue = &session.game_data.queue;
+                self.game_mode_context = Some(GameModeContext::from_session(
+                    session.game_mode(),
+                    queue.id,
+                    &queue.name,
+                    queue.is_ranked,
+                    session.map.id,
+                ));
+
+                if session.game_data.game_id != 0 {
+                    spectated_game_id = Some(session.game_data.game_id.to_string());
+                }
+
+                spectated_player = Self::find_spectated_player(&client, &session).await;
+            }
+        }
+
+        info!(
+            "Spectated session starting (player: {})",
+            spectated_player.as_ref().map(|p| p.riot_id.as_str()).unwrap_or("unknown")
+        );
+
+        let context = SessionContext::anchored(
+            json!({
+                "spectated": true,
+                "spectated_player": spectated_player,
+                "spectated_game_id": spectated_game_id,
+                "game_mode": self.game_mode_context,
+                "subpack": self.current_subpack,
+            }),
+            session_clock.anchor(),
+        );
+        self.session_clock = Some(session_clock);
 
         self.session_context = Some(context.clone());
 
         Some(serde_json::to_value(&context).unwrap_or(Value::Null))
     }
 
+    /// Identify the spectated player as the friend playing in the game.
+    /// Returns `None` for games without a friend in them (e.g. pro play).
+    async fn find_spectated_player(
+        client: &LcuClient,
+        session: &crate::GameflowSession,
+    ) -> Option<SpectatedPlayer> {
+        if !lcu_connection().supports(LcuFeature::Friends) {
+            return None;
+        }
+        let friends = match client.get_friends().await {
+            Ok(friends) => friends,
+            Err(e) => {
+                debug!("Failed to get friends list: {}", e);
+                return None;
+            }
+        };
+
+        session.players().find_map(|player| {
+            friends
+                .iter()
+                .find(|f| !f.puuid.is_empty() && f.puuid == player.puuid)
+                .map(|friend| SpectatedPlayer {
+                    puuid: player.puuid.clone(),
+                    riot_id: friend.riot_id(),
+                    champion_id: player.champion_id,
+                })
+        })
+    }
+
     /// End a game session and return match data
     pub async fn session_end(&mut self, _context: Value) -> Option<MatchData> {
         info!("League session ending");
 
+        if std::mem::take(&mut self.session_spectated) {
+            return Some(self.spectated_session_end().await);
+        }
+
+        if let Some(reason) = self.abort_reason.take() {
+            return self.abort_session(reason).await;
+        }
+
         // Get the last live match data
         let last_match = self.last_live_match.read().await.clone();
 
+        // Data Dragon may have loaded only after the session started
+        self.analyze_composition();
+
         // Get post-game data from finalizer
-        let match_data = self.finalizer.finalize_game(last_match).await.ok().flatten();
+        let mut match_data = self
+            .finalizer
+            .finalize_game(
+                last_match,
+                self.game_mode_context.as_ref(),
+                self.session_clock.as_ref().and_then(|c| c.game_started_at()),
+            )
+            .await
+            .ok()
+            .flatten();
+
+        if let Some(ref mut data) = match_data {
+            if let Some(split) = self.stats.stats().ranked_split_at(data.played_at) {
+                data.season_id = Some(split.season_id);
+                data.split_id = Some(split.split_id);
+            }
+            data.latency = self.latency.summary();
+            data.objective_participation = Some(self.objectives.participation().clone());
+            mark_leavers(data, &self.leavers.idle_players());
+            if !data.leavers.is_empty() {
+                info!("Leavers in this game: {:?}", data.leavers);
+            }
+            data.puuid = self.account_puuid.take();
+        }
+
+        // Keep the payload as received, so the match can be parsed again later
+        let mut source_diff = None;
+        if let (Some(mut raw), Some(ref data)) = (self.finalizer.take_raw_match(), &match_data) {
+            raw.puuid = data.puuid.clone();
+            self.raw_matches.save(&raw.game_id.to_string(), &raw);
+            source_diff = raw.source_diff;
+        }
+
+        // Scoreboard timeline, for gold graphs after the game
+        let snapshots = self.snapshots.finish(&self.supervisor);
+        if let Some(ref data) = match_data {
+            if !snapshots.is_empty() {
+                self.match_snapshots.save(&data.game_id.to_string(), &snapshots);
+            }
+        }
+
+        // First game on a champion (remakes don't count as having played it)
+        let first_time = match match_data {
+            Some(ref mut data) if data.result != crate::MatchResult::Remake => {
+                let first_time = self.stats.record_champion_play(&data.champion);
+                if first_time {
+                    data.badges.push(Badge::FirstTime.label().to_string());
+                    data.badge_keys.push(Badge::FirstTime.string_key().to_string());
+                }
+                first_time
+            }
+            _ => false,
+        };
+
+        // Records are compared against all previously finished games
+        let records_broken = match match_data {
+            Some(ref mut data) => {
+                let broken = self.stats.record_personal_bests(data);
+                for record in &broken {
+                    data.badges.push(record.badge().label().to_string());
+                    data.badge_keys.push(record.badge().string_key().to_string());
+                }
+                broken
+            }
+            None => Vec::new(),
+        };
+
+        // Opponents the player has faced before
+        let rivals = match match_data {
+            Some(ref data) => self.stats.record_opponents(data),
+            None => Vec::new(),
+        };
+
+        let premades = std::mem::take(&mut self.premades);
+        if let Some(ref data) = match_data {
+            self.stats.record_duo_games(data, &premades);
+            self.stats.record_game_result(data);
+        }
+
+        let mastery = match match_data {
+            Some(ref data) => self.fetch_mastery(data).await,
+            None => None,
+        };
+
+        // Ward activity needs the game ID, which only the end-of-game stats have
+        let vision = match match_data {
+            Some(ref data) if data.game_id != 0 => self.fetch_vision_recap(data.game_id).await,
+            _ => None,
+        };
+
+        // Time in game today, with a heads-up once the soft limit is crossed
+        if let Some(ref clock) = self.session_clock {
+            if self.stats.record_playtime(clock.started_at(), clock.now()) {
+                let summary = self.stats.playtime_summary(1);
+                info!("Daily playtime limit reached ({}s today)", summary.today_secs);
+                self.pending_events.push(GameEvent::new(
+                    "PlaytimeLimitReached".to_string(),
+                    0.0,
+                    json!({
+                        "today_secs": summary.today_secs,
+                        "limit_secs": summary.daily_limit_secs,
+                    }),
+                ));
+            }
+        }
 
         // Capture values before resetting
         let game_mode_ctx = self.game_mode_context.take();
         let subpack = self.current_subpack;
         let external_match_id = self.external_match_id.take();
+        let game_ended_at = self.game_ended_at.take();
 
         // Reset session state
         self.session_context = None;
+        self.session_clock = None;
         self.active_player_name = None;
         self.last_emitted_stats = None;
+        self.live_snapshots.clear();
         *self.last_live_match.write().await = None;
 
         // If we have an external match ID, emit SetComplete to the daemon
         if let Some(ref external_id) = external_match_id {
+            // Ward events are only known after the game, so they go out just before completion
+            if let (Some(recap), Some(data)) = (&vision, &match_data) {
+                emit_game_events(
+                    subpack,
+                    external_id.clone(),
+                    recap.to_game_events(data.duration_secs as f64),
+                );
+            }
+
+            if let (true, Some(data)) = (first_time, &match_data) {
+                let game_end = data.duration_secs as f64;
+                emit_game_events(
+                    subpack,
+                    external_id.clone(),
+                    vec![GameEvent::new(
+                        "FirstTimeChampion".to_string(),
+                        game_end,
+                        json!({ "champion": data.champion, "is_player_involved": true }),
+                    )],
+                );
+                emit_moments(
+                    subpack,
+                    external_id.clone(),
+                    vec![Moment::new(
+                        "first_time_champion",
+                        game_end,
+                        json!({ "champion": data.champion }),
+                    )],
+                );
+            }
+
             // Build final stats from the match data
             let final_stats = match_data.as_ref().map(|data| {
                 self.build_stats_map(data, &game_mode_ctx)
@@ -613,33 +2732,207 @@ impl LeagueIntegration {
             );
         }
 
+        if let Some(ref data) = match_data {
+            if data.result == crate::MatchResult::Remake {
+                self.stats.record_non_game(NonGameRecord {
+                    kind: NonGameKind::Remake,
+                    queue_id: game_mode_ctx.as_ref().map_or(0, |ctx| ctx.queue_id),
+                    occurred_at: Utc::now(),
+                    lost_secs: data.duration_secs as f64,
+                });
+            }
+            if let Some(ref arena) = data.arena {
+                self.stats.record_arena_match(arena.clone());
+            }
+        }
+
+        let momentum = std::mem::take(&mut self.momentum);
+        let deaths = std::mem::take(&mut self.deaths);
+        let match_assets = match_data
+            .as_ref()
+            .map(|data| self.asset_urls.for_match(data))
+            .map(|assets| self.assets_json(assets, true));
+
         // Convert to protocol MatchData (for backwards compat)
         match_data.map(|data| {
             let result = match data.result {
                 crate::MatchResult::Win => MatchResult::Win,
                 crate::MatchResult::Loss => MatchResult::Loss,
-                crate::MatchResult::Remake => MatchResult::Loss,
+                crate::MatchResult::Remake => MatchResult::Remake,
             };
 
-            // Include game mode in details
+            // Include game mode, asset URLs and the gold timeline in details
             let mut details = serde_json::to_value(&data).unwrap_or(Value::Null);
-            if let Some(ref mode_ctx) = game_mode_ctx {
-                if let Value::Object(ref mut map) = details {
+            if let Value::Object(ref mut map) = details {
+                if let Some(ref mode_ctx) = game_mode_ctx {
                     map.insert("game_mode".to_string(), serde_json::to_value(mode_ctx).unwrap_or(Value::Null));
                 }
+                if let Some(ended_at) = game_ended_at {
+                    map.insert("game_ended_at".to_string(), json!(ended_at));
+                }
+                if let Some(assets) = match_assets {
+                    map.insert("assets".to_string(), assets);
+                }
+                // Placement-based like TFT: 1-4 in Arena
+                if let Some(ref arena) = data.arena {
+                    map.insert("placement".to_string(), json!(arena.placement));
+                }
+                if let Some(Value::Object(mastery)) = mastery {
+                    map.extend(mastery);
+                }
+                if let Some(vision) = vision {
+                    map.insert(
+                        "vision".to_string(),
+                        serde_json::to_value(vision).unwrap_or(Value::Null),
+                    );
+                }
+                if let Some(diff) = source_diff.filter(|d| !d.discrepancies.is_empty()) {
+                    map.insert(
+                        "source_diff".to_string(),
+                        serde_json::to_value(diff).unwrap_or(Value::Null),
+                    );
+                }
+                map.insert(
+                    "deaths".to_string(),
+                    serde_json::to_value(deaths.deaths()).unwrap_or(Value::Null),
+                );
+                map.insert(
+                    "records_broken".to_string(),
+                    serde_json::to_value(&records_broken).unwrap_or(Value::Null),
+                );
+                map.insert(
+                    "rivals".to_string(),
+                    serde_json::to_value(&rivals).unwrap_or(Value::Null),
+                );
+                map.insert(
+                    "momentum".to_string(),
+                    json!({
+                        "goldTimeline": momentum.samples(),
+                        "markers": momentum.markers(),
+                        "highlights": momentum.highlights(),
+                    }),
+                );
             }
 
             MatchData {
                 game_slug: LEAGUE_SLUG.to_string(),
                 game_id: LEAGUE_GAME_ID,
-                played_at: Utc::now(),
+                played_at: data.played_at,
+                started_at: data.started_at,
+                ended_at: data.ended_at,
                 duration_secs: data.duration_secs,
                 result,
+                spectated: false,
                 details,
             }
         })
     }
 
+    /// End a spectated session. The match data is marked `spectated` and
+    /// names the spectated player, so it never counts as the user's game.
+    async fn spectated_session_end(&mut self) -> MatchData {
+        info!("Spectated session ended");
+
+        let last_match = self.last_live_match.write().await.take();
+        let duration_secs = last_match.map_or(0, |m| m.game_time_secs as i32);
+        let (started_at, ended_at) = self.clock_game_times(duration_secs);
+        let context = self.session_context.take().map_or(Value::Null, |c| c.data);
+
+        self.snapshots.finish(&self.supervisor);
+        self.game_mode_context = None;
+        self.session_clock = None;
+        self.game_ended_at = None;
+        self.live_snapshots.clear();
+
+        MatchData {
+            game_slug: LEAGUE_SLUG.to_string(),
+            game_id: LEAGUE_GAME_ID,
+            played_at: ended_at,
+            started_at,
+            ended_at: Some(ended_at),
+            duration_secs,
+            result: MatchResult::Spectated,
+            spectated: true,
+            details: json!({
+                "spectated": true,
+                "spectated_player": context["spectated_player"],
+                "spectated_game_id": context["spectated_game_id"],
+                "game_mode": context["game_mode"],
+            }),
+        }
+    }
+
+    /// Start and end of the current game from the session clock, for games
+    /// without end-of-game stats. A game that never started its clock ends
+    /// now.
+    fn clock_game_times(&self, duration_secs: i32) -> (Option<DateTime<Utc>>, DateTime<Utc>) {
+        let started_at = self.session_clock.as_ref().and_then(|c| c.game_started_at());
+        let ended_at = started_at
+            .map(|start| start + chrono::Duration::seconds(duration_secs as i64))
+            .unwrap_or_else(Utc::now);
+        (started_at, ended_at)
+    }
+
+    /// End a session whose game failed to launch or crashed, without
+    /// waiting for end-of-game stats that will never come
+    async fn abort_session(&mut self, reason: &'static str) -> Option<MatchData> {
+        info!("Ending aborted session ({})", reason);
+
+        let last_match = self.last_live_match.write().await.take();
+        let game_mode_ctx = self.game_mode_context.take();
+        let external_match_id = self.external_match_id.take();
+        let duration_secs = last_match.map_or(0, |m| m.game_time_secs as i32);
+        let (started_at, ended_at) = self.clock_game_times(duration_secs);
+
+        self.finalizer.discard();
+        self.snapshots.finish(&self.supervisor);
+        self.momentum.reset();
+        self.deaths.reset();
+        self.tft.reset();
+        self.tft_board = None;
+        self.streaks.reset();
+        self.vision.reset();
+        self.leavers.reset();
+        self.triggers.reset();
+        self.latency.start(None);
+        self.fps.reset();
+        self.objectives.reset();
+        self.premades.clear();
+        self.account_puuid = None;
+        self.session_context = None;
+        self.session_clock = None;
+        self.game_ended_at = None;
+        self.active_player_name = None;
+        self.last_emitted_stats = None;
+        self.live_snapshots.clear();
+
+        if let Some(external_id) = external_match_id {
+            emit_match_data(MatchDataMessage::SetComplete {
+                subpack: self.current_subpack,
+                external_match_id: external_id.clone(),
+                summary_source: SummarySource::LiveFallback,
+                final_stats: None,
+            });
+            info!("Emitted SetComplete for aborted match {}", external_id);
+        }
+
+        Some(MatchData {
+            game_slug: LEAGUE_SLUG.to_string(),
+            game_id: LEAGUE_GAME_ID,
+            played_at: ended_at,
+            started_at,
+            ended_at: Some(ended_at),
+            duration_secs,
+            result: MatchResult::Aborted,
+            spectated: false,
+            details: json!({
+                "aborted": true,
+                "abortReason": reason,
+                "game_mode": game_mode_ctx,
+            }),
+        })
+    }
+
     /// Build a stats HashMap from match data for the current subpack
     fn build_stats_map(
         &self,
@@ -673,6 +2966,12 @@ impl LeagueIntegration {
             stats.insert("trinket".to_string(), json!(data.trinket));
             stats.insert("participants_json".to_string(), json!(data.participants));
             stats.insert("badges_json".to_string(), json!(data.badges));
+
+            if let Some(ref arena) = data.arena {
+                stats.insert("placement".to_string(), json!(arena.placement));
+                stats.insert("arena_partner".to_string(), json!(arena.partner));
+                stats.insert("augments_json".to_string(), json!(arena.augment_ids));
+            }
         }
         // TFT stats would be different - to be implemented when TFT support is added
 
@@ -683,9 +2982,49 @@ impl LeagueIntegration {
         if let Some(lp) = data.lp_change {
             stats.insert("lp_change".to_string(), json!(lp));
         }
+        if let Some(ref change) = data.rank_change {
+            stats.insert("promoted".to_string(), json!(change.promoted));
+            stats.insert("demoted".to_string(), json!(change.demoted));
+            if let Some(ref progress) = change.series_progress {
+                stats.insert("series_progress".to_string(), json!(progress));
+            }
+        }
         if let Some(ref rank) = data.rank {
             stats.insert("rank".to_string(), json!(rank));
         }
+        if let Some(side) = data.side {
+            stats.insert("side".to_string(), json!(side));
+        }
+        if let Some(ref role) = data.role {
+            stats.insert("role".to_string(), json!(role));
+        }
+        if let (Some(season_id), Some(split_id)) = (data.season_id, data.split_id) {
+            stats.insert("season_id".to_string(), json!(season_id));
+            stats.insert("split_id".to_string(), json!(split_id));
+        }
+        if let Some(ref latency) = data.latency {
+            stats.insert("avg_ping_ms".to_string(), json!(latency.average_ms));
+            stats.insert("max_ping_ms".to_string(), json!(latency.max_ms));
+        }
+        if let Some(ref draft) = data.draft {
+            let bans = |is_ally: bool| -> Vec<&String> {
+                draft.team_bans(is_ally).filter_map(|b| b.champion.as_ref()).collect()
+            };
+            let enemy_picks: Vec<&String> = draft
+                .team_picks(false)
+                .filter_map(|p| p.champion.as_ref())
+                .collect();
+            stats.insert("ally_bans".to_string(), json!(bans(true)));
+            stats.insert("enemy_bans".to_string(), json!(bans(false)));
+            stats.insert("enemy_picks".to_string(), json!(enemy_picks));
+        }
+        if let Some(percent) = data
+            .objective_participation
+            .as_ref()
+            .and_then(|p| p.percent())
+        {
+            stats.insert("objective_participation".to_string(), json!(percent));
+        }
 
         stats
     }
@@ -761,8 +3100,210 @@ impl LeagueIntegration {
     }
 }
 
-impl Default for LeagueIntegration {
-    fn default() -> Self {
-        Self::new()
+impl Drop for LeagueIntegration {
+    fn drop(&mut self) {
+        self.supervisor.shutdown();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    /// Integration persisting into `dir` instead of the user's data dir
+    fn integration(dir: &TempDir) -> LeagueIntegration {
+        LeagueIntegration::new(StatsSettings {
+            data_dir: Some(dir.path().to_string_lossy().into_owned()),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_simulate_event_runs_triggers_and_queues_event() {
+        let dir = TempDir::new("integration");
+        let mut integration = integration(&dir);
+
+        let result = integration.simulate_event(
+            "ChampionKill",
+            &json!({ "killer_name": "Me", "victim_name": "Them", "event_time": 120.0 }),
+        );
+        assert!(result.triggered);
+        assert_eq!(result.trigger.as_deref(), Some("kill"));
+        assert_eq!(result.event.timestamp_secs, 120.0);
+        assert_eq!(result.event.data["simulated"], true);
+
+        assert_eq!(result.event.data["trigger_decision"]["rule"], "kill");
+
+        // Disabled trigger still emits the event, but doesn't fire
+        let result = integration.simulate_event("TurretKilled", &json!({}));
+        assert!(!result.triggered);
+        assert_eq!(result.event.data["trigger_decision"]["rule"], "towerKill");
+        assert_eq!(result.event.data["trigger_decision"]["reason"], "rule_disabled");
+        assert_eq!(result.event.data["trigger_decision"]["ruleConfig"]["enabled"], false);
+
+        let queued = integration.pending_events.drain();
+        assert_eq!(queued.len(), 2);
+        assert_eq!(queued[0].event_type, "ChampionKill");
+    }
+
+    #[test]
+    fn test_bookmark_is_queued_as_moment() {
+        let dir = TempDir::new("integration");
+        let mut integration = integration(&dir);
+        integration.record_bookmark("req-1", Some("outplay"), 754.0);
+
+        let queued = integration.pending_events.drain();
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].timestamp_secs, 754.0);
+        assert_eq!(queued[0].data["request_id"], "req-1");
+
+        let moments = integration.detect_moments(&queued);
+        assert_eq!(moments.len(), 1);
+    }
+
+    #[test]
+    fn test_moments_match_the_player_across_name_forms() {
+        let dir = TempDir::new("integration");
+        let mut integration = integration(&dir);
+        integration.active_player_name = Some("Zoë#EUW".to_string());
+
+        let kill = |killer: &str, victim: &str| {
+            GameEvent::new(
+                "ChampionKill".to_string(),
+                300.0,
+                json!({ "killer_name": killer, "victim_name": victim }),
+            )
+        };
+        assert_eq!(integration.detect_moments(&[kill("Them", "ZOE\u{308}#euw")]).len(), 1);
+        assert_eq!(integration.detect_moments(&[kill("zoë", "Them")]).len(), 1);
+        assert!(integration.detect_moments(&[kill("Them", "Zoe")]).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_status_reports_task_health() {
+        let dir = TempDir::new("integration");
+        let mut integration = integration(&dir);
+        integration.supervisor.spawn("test_task", || async {
+            std::future::pending::<()>().await
+        });
+
+        let reply = integration
+            .handle_command(LeagueCommand::GetStatus, notification_writer())
+            .await;
+        let tasks = reply["tasks"].as_array().unwrap();
+        assert!(tasks.iter().any(|t| t["name"] == "test_task"));
+        integration.supervisor.shutdown();
+    }
+
+    #[tokio::test]
+    async fn test_aborted_session_is_dated_by_the_session_clock() {
+        let dir = TempDir::new("integration");
+        let mut integration = integration(&dir);
+        let mut clock = SessionClock::start();
+        clock.sync_game_time(0.0);
+        let game_started_at = clock.game_started_at();
+        integration.session_clock = Some(clock);
+        integration.mark_aborted("crashed");
+
+        let data = integration.session_end(Value::Null).await.unwrap();
+        assert_eq!(data.result, MatchResult::Aborted);
+        assert_eq!(data.started_at, game_started_at);
+        assert_eq!(data.ended_at, Some(data.played_at));
+        assert!(data.played_at >= data.started_at.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_spectated_session_ends_with_spectated_match_data() {
+        let dir = TempDir::new("integration");
+        let mut integration = integration(&dir);
+        integration.session_spectated = true;
+        integration.session_context = Some(SessionContext::new(json!({
+            "spectated": true,
+            "spectated_player": { "puuid": "p-1", "riot_id": "Friend#EUW", "champion_id": 103 },
+            "spectated_game_id": "7001",
+        })));
+
+        let data = integration.session_end(Value::Null).await.unwrap();
+        assert!(data.spectated);
+        assert_eq!(data.result, MatchResult::Spectated);
+        assert_eq!(data.details["spectated_player"]["riot_id"], "Friend#EUW");
+        assert_eq!(data.details["spectated_game_id"], "7001");
+        assert!(integration.session_context.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_status_details_carry_what_game_status_drops() {
+        let dir = TempDir::new("integration");
+        let mut integration = integration(&dir);
+        let status = integration.get_status().await;
+
+        let details = serde_json::to_value(crate::protocol::StatusDetails::from(&status)).unwrap();
+        assert_eq!(details["type"], "StatusDetails");
+        assert_eq!(
+            details["metrics"]["pending_events"]["capacity"],
+            status.metrics.pending_events.capacity
+        );
+        assert!(details["metrics"]["ws_backlog"]["dropped"].is_u64());
+        assert_eq!(details["running"], serde_json::to_value(status.running).unwrap());
+        assert!(details["profile"].is_null());
+        // Not matchmaking, so there is nothing to estimate
+        assert!(details["queue_estimate"].is_null());
+    }
+
+    #[tokio::test]
+    async fn test_configured_remote_host_is_used() {
+        let _remote = crate::remote::RemoteHostGuard::acquire();
+        let dir = TempDir::new("integration");
+        let mut integration = integration(&dir);
+        let configure = |host: &str| {
+            serde_json::from_value::<LeagueCommand>(json!({
+                "type": "Configure",
+                "remote_host": { "host": host, "lockfile": "LeagueClient:1:54321:secret:https" },
+            }))
+            .unwrap()
+        };
+
+        let reply = integration
+            .handle_command(configure("192.168.1.20"), notification_writer())
+            .await;
+        assert_eq!(reply["remote_host"]["host"], "192.168.1.20");
+        let connection = crate::LcuConnection::from_lockfile().unwrap();
+        assert_eq!(connection.base_url(), "https://192.168.1.20:54321");
+
+        let reply = integration.handle_command(configure(""), notification_writer()).await;
+        assert!(reply["remote_host"]["host"].is_null());
+    }
+
+    #[tokio::test]
+    async fn test_configure_daily_playtime_limit() {
+        let dir = TempDir::new("integration");
+        let mut integration = integration(&dir);
+        let configure = |mins: u32| {
+            serde_json::from_value::<LeagueCommand>(json!({
+                "type": "Configure",
+                "daily_playtime_limit_mins": mins,
+            }))
+            .unwrap()
+        };
+
+        let reply = integration.handle_command(configure(90), notification_writer()).await;
+        assert_eq!(reply["stats"]["dailyPlaytimeLimitMins"], 90);
+        assert_eq!(integration.stats.playtime_summary(1).daily_limit_secs, Some(5400));
+
+        let reply = integration.handle_command(configure(0), notification_writer()).await;
+        assert!(reply["stats"]["dailyPlaytimeLimitMins"].is_null());
+        assert_eq!(integration.stats.playtime_summary(1).daily_limit_secs, None);
+    }
+
+    #[test]
+    fn test_league_command_deserialize() {
+        let command: LeagueCommand =
+            serde_json::from_value(json!({ "type": "SimulateEvent", "event_type": "BaronKill" }))
+                .unwrap();
+        assert!(matches!(
+            command,
+            LeagueCommand::SimulateEvent { ref event_type, .. } if event_type == "BaronKill"
+        ));
     }
 }
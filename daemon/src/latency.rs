@@ -0,0 +1,128 @@
+//! Game server latency sampling
+//!
+//! Neither the Live Client API nor the LCU reports the in-game ping, so it
+//! is measured as the TCP connect round trip to the game server address from
+//! the gameflow session. The server only speaks UDP, but a refused
+//! connection still takes exactly one round trip. Samples go into the match
+//! timeline and are summarized per match.
+
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+
+use crate::LatencySummary;
+
+/// Game seconds between two samples
+pub const LATENCY_SAMPLE_INTERVAL_SECS: f64 = 10.0;
+
+/// Give up on a probe after this long (firewalled servers never answer)
+const PROBE_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Stop probing a server after this many unanswered probes in a row
+const MAX_FAILED_PROBES: u32 = 3;
+
+/// Round trip to `addr`, `None` if the server didn't answer in time
+pub async fn measure_latency(addr: SocketAddr) -> Option<Duration> {
+    let started = Instant::now();
+    match tokio::time::timeout(PROBE_TIMEOUT, TcpStream::connect(addr)).await {
+        Ok(Ok(_)) => Some(started.elapsed()),
+        Ok(Err(e)) if e.kind() == std::io::ErrorKind::ConnectionRefused => Some(started.elapsed()),
+        _ => None,
+    }
+}
+
+/// Latency samples of the current game
+#[derive(Debug, Default)]
+pub struct LatencyTracker {
+    server: Option<SocketAddr>,
+    /// (game time, round trip in ms)
+    samples: Vec<(f64, u32)>,
+    /// Game time of the last probe, answered or not
+    last_probe: Option<f64>,
+    failed_probes: u32,
+}
+
+impl LatencyTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// New game on `server`
+    pub fn start(&mut self, server: Option<SocketAddr>) {
+        *self = Self {
+            server,
+            ..Self::default()
+        };
+    }
+
+    /// Server to probe if a sample is due at `game_time` (counts as probed)
+    pub fn due(&mut self, game_time: f64) -> Option<SocketAddr> {
+        if self.failed_probes >= MAX_FAILED_PROBES {
+            return None;
+        }
+        if let Some(last) = self.last_probe {
+            if game_time - last < LATENCY_SAMPLE_INTERVAL_SECS {
+                return None;
+            }
+        }
+        let server = self.server?;
+        self.last_probe = Some(game_time);
+        Some(server)
+    }
+
+    /// Store the result of a probe; the round trip in ms if it was answered
+    pub fn record(&mut self, game_time: f64, round_trip: Option<Duration>) -> Option<u32> {
+        let Some(round_trip) = round_trip else {
+            self.failed_probes += 1;
+            return None;
+        };
+        let ms = round_trip.as_millis().min(u32::MAX as u128) as u32;
+        self.failed_probes = 0;
+        self.samples.push((game_time, ms));
+        Some(ms)
+    }
+
+    /// Average and max over the game, `None` without samples
+    pub fn summary(&self) -> Option<LatencySummary> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let total: u64 = self.samples.iter().map(|&(_, ms)| ms as u64).sum();
+        Some(LatencySummary {
+            average_ms: (total / self.samples.len() as u64) as u32,
+            max_ms: self.samples.iter().map(|&(_, ms)| ms).max().unwrap_or(0),
+            samples: self.samples.len(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_samples_are_spaced_and_summarized() {
+        let mut tracker = LatencyTracker::new();
+        assert!(tracker.due(0.0).is_none());
+
+        let server: SocketAddr = "192.0.2.10:5100".parse().unwrap();
+        tracker.start(Some(server));
+        assert_eq!(tracker.due(5.0), Some(server));
+        tracker.record(5.0, Some(Duration::from_millis(30)));
+        assert!(tracker.due(12.0).is_none());
+        assert_eq!(tracker.due(15.0), Some(server));
+        tracker.record(15.0, Some(Duration::from_millis(90)));
+
+        let summary = tracker.summary().unwrap();
+        assert_eq!(summary.average_ms, 60);
+        assert_eq!(summary.max_ms, 90);
+        assert_eq!(summary.samples, 2);
+
+        // An unreachable server is given up on
+        for time in [25.0, 35.0, 45.0] {
+            assert!(tracker.due(time).is_some());
+            assert_eq!(tracker.record(time, None), None);
+        }
+        assert!(tracker.due(55.0).is_none());
+    }
+}
@@ -1,32 +1,84 @@
 use crate::{AppError, Result};
-use crate::GameflowPhase;
+use crate::{ChampSelectState, GameflowPhase, MatchRunes, RankedSplit};
+use crate::rate_limit::lcu_limiter;
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use regex::Regex;
+use reqwest::header::HeaderValue;
 use reqwest::Client;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 use std::path::PathBuf;
 use std::process::Command;
 use tracing::{debug, info, warn};
+use zeroize::Zeroizing;
+
+/// The LCU lockfile password.
+///
+/// Wiped from memory on drop, and redacted from `Debug` and serde output so it
+/// can't leak into logs or status payloads.
+#[derive(Clone)]
+pub struct AuthToken(Zeroizing<String>);
+
+impl AuthToken {
+    pub fn new(token: impl Into<String>) -> Self {
+        Self(Zeroizing::new(token.into()))
+    }
+
+    /// Access the raw token (only for building the auth header)
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Debug for AuthToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("AuthToken(<redacted>)")
+    }
+}
+
+impl Serialize for AuthToken {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str("<redacted>")
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct LcuConnection {
+    /// Host the client is reached at (loopback unless a remote PC is configured)
+    pub host: String,
     pub port: u16,
-    pub auth_token: String,
+    pub auth_token: AuthToken,
     pub protocol: String,
 }
 
 impl LcuConnection {
+    /// League install directory of the running client
+    pub(crate) fn install_directory() -> Result<PathBuf> {
+        Self::find_install_directory()
+    }
+
     /// Find the League of Legends install directory by looking at the running process.
     /// This works regardless of where League is installed.
     fn find_install_directory() -> Result<PathBuf> {
+        let lines = Self::client_command_lines()?;
+        Self::extract_install_directory(lines.first().map_or("", String::as_str))
+    }
+
+    /// Command lines of all running `LeagueClientUx` processes
+    fn client_command_lines() -> Result<Vec<String>> {
+        let output = Self::process_list()?;
+        Ok(Self::client_lines(&output))
+    }
+
+    /// Command lines of running processes, as printed by the platform's tool
+    fn process_list() -> Result<String> {
         #[cfg(target_os = "windows")]
         {
-            Self::find_install_directory_windows()
+            Self::process_list_windows()
         }
 
         #[cfg(target_os = "macos")]
         {
-            Self::find_install_directory_macos()
+            Self::process_list_macos()
         }
 
         #[cfg(not(any(target_os = "macos", target_os = "windows")))]
@@ -36,10 +88,10 @@ impl LcuConnection {
     }
 
     #[cfg(target_os = "windows")]
-    fn find_install_directory_windows() -> Result<PathBuf> {
+    fn process_list_windows() -> Result<String> {
         use std::os::windows::process::CommandExt;
 
-        // Use WMIC to find the LeagueClientUx.exe process and get its command line
+        // Use WMIC to find the LeagueClientUx.exe processes and get their command lines
         // CREATE_NO_WINDOW (0x08000000) prevents a console window from appearing
         let output = Command::new("WMIC")
             .args(["PROCESS", "WHERE", "name='LeagueClientUx.exe'", "GET", "commandline"])
@@ -47,12 +99,11 @@ impl LcuConnection {
             .output()
             .map_err(|e| AppError::Other(format!("Failed to run WMIC: {}", e)))?;
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        Self::extract_install_directory(&stdout)
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
     }
 
     #[cfg(target_os = "macos")]
-    fn find_install_directory_macos() -> Result<PathBuf> {
+    fn process_list_macos() -> Result<String> {
         // Use ps to get all process args, then filter in Rust
         // NOTE: Previously used `ps | grep` which caused zombie processes because
         // the ps Child was dropped without calling .wait(). Using .output() waits
@@ -62,15 +113,53 @@ impl LcuConnection {
             .output()
             .map_err(|e| AppError::Other(format!("Failed to run ps: {}", e)))?;
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
 
-        // Find the line containing LeagueClientUx (filter in Rust instead of grep)
-        let league_line = stdout
+    /// Lines belonging to LeagueClientUx (filter in Rust instead of grep).
+    /// Skips the WMIC header and the client's renderer helper processes.
+    fn client_lines(output: &str) -> Vec<String> {
+        output
             .lines()
-            .find(|line| line.contains("LeagueClientUx"))
-            .unwrap_or("");
+            .filter(|line| line.contains("LeagueClientUx") && line.contains("--app-port="))
+            .map(|line| line.trim().to_string())
+            .collect()
+    }
+
+    /// Connections of every running client.
+    ///
+    /// The lockfile only describes one client per install, but players who
+    /// are logged in on several accounts run one `LeagueClientUx` per account,
+    /// each with its own port and token on its command line.
+    pub fn discover_all() -> Result<Vec<Self>> {
+        if crate::remote::is_remote() {
+            return Ok(vec![Self::from_lockfile()?]);
+        }
+
+        let connections: Vec<Self> = Self::client_command_lines()?
+            .iter()
+            .filter_map(|line| Self::from_command_line(line))
+            .collect();
+        if connections.is_empty() {
+            return Err(AppError::LeagueNotRunning);
+        }
+        Ok(connections)
+    }
 
-        Self::extract_install_directory(league_line)
+    /// Parse `--app-port` and `--remoting-auth-token` from a client command line
+    fn from_command_line(cmdline: &str) -> Option<Self> {
+        let port_re = Regex::new(r"--app-port=(\d+)").ok()?;
+        let token_re = Regex::new(r"--remoting-auth-token=([\w-]+)").ok()?;
+
+        let port = port_re.captures(cmdline)?.get(1)?.as_str().parse().ok()?;
+        let token = token_re.captures(cmdline)?.get(1)?.as_str();
+
+        Some(Self {
+            host: crate::remote::api_host(),
+            port,
+            auth_token: AuthToken::new(token),
+            protocol: "https".to_string(),
+        })
     }
 
     /// Extract the install directory from the process command line.
@@ -134,6 +223,14 @@ impl LcuConnection {
     /// Connect to the LCU by finding and parsing the lockfile.
     /// Works on both macOS and Windows, regardless of install location.
     pub fn from_lockfile() -> Result<Self> {
+        // A remote client's lockfile can't be discovered, the user supplies it
+        if crate::remote::is_remote() {
+            let content = crate::remote::remote_lockfile().ok_or_else(|| {
+                AppError::LockfileParse("remote host configured without lockfile credentials".into())
+            })?;
+            return Self::parse_lockfile_content(&content);
+        }
+
         let install_dir = Self::find_install_directory()?;
         let lockfile_path = install_dir.join("lockfile");
 
@@ -141,7 +238,8 @@ impl LcuConnection {
             return Err(AppError::LeagueNotRunning);
         }
 
-        let content = std::fs::read_to_string(&lockfile_path)?;
+        // The lockfile contains the password - wipe our copy once parsed
+        let content = Zeroizing::new(std::fs::read_to_string(&lockfile_path)?);
         Self::parse_lockfile_content(&content)
     }
 
@@ -151,37 +249,45 @@ impl LcuConnection {
         let parts: Vec<&str> = content.trim().split(':').collect();
 
         if parts.len() < 5 {
-            return Err(AppError::Other(format!(
-                "Invalid lockfile format: expected 5 parts, got {}",
+            return Err(AppError::LockfileParse(format!(
+                "expected 5 parts, got {}",
                 parts.len()
             )));
         }
 
         let port = parts[2].parse().map_err(|_| {
-            AppError::Other(format!("Invalid port in lockfile: {}", parts[2]))
+            AppError::LockfileParse(format!("invalid port {}", parts[2]))
         })?;
 
         info!("LCU connection: port={}, protocol={}", port, parts[4]);
 
         Ok(Self {
+            host: crate::remote::api_host(),
             port,
-            auth_token: parts[3].to_string(),
+            auth_token: AuthToken::new(parts[3]),
             protocol: parts[4].to_string(),
         })
     }
 
     pub fn base_url(&self) -> String {
-        format!("{}://127.0.0.1:{}", self.protocol, self.port)
+        format!("{}://{}:{}", self.protocol, self.host, self.port)
     }
 
-    /// Create Basic auth header value for LCU API
-    pub fn auth_header(&self) -> String {
-        let credentials = format!("riot:{}", self.auth_token);
-        format!("Basic {}", BASE64.encode(credentials.as_bytes()))
+    /// Create Basic auth header value for LCU API.
+    ///
+    /// The value is marked sensitive so HTTP clients don't log it.
+    pub fn auth_header(&self) -> HeaderValue {
+        let credentials = Zeroizing::new(format!("riot:{}", self.auth_token.expose()));
+        let encoded = Zeroizing::new(format!("Basic {}", BASE64.encode(credentials.as_bytes())));
+        let mut value = HeaderValue::from_str(&encoded)
+            .expect("base64 auth header is always a valid header value");
+        value.set_sensitive(true);
+        value
     }
 }
 
 /// LCU API client for communicating with the League Client
+#[derive(Clone)]
 pub struct LcuClient {
     connection: LcuConnection,
     client: Client,
@@ -192,19 +298,28 @@ impl LcuClient {
     pub fn new() -> Result<Self> {
         let connection = LcuConnection::from_lockfile()?;
 
-        // LCU uses self-signed certs, so we need to disable cert verification
-        let client = Client::builder()
-            .danger_accept_invalid_certs(true)
-            .build()
-            .map_err(|e| AppError::Other(format!("Failed to create HTTP client: {}", e)))?;
+        Self::from_connection(connection)
+    }
 
-        Ok(Self { connection, client })
+    /// Wait for the shared rate limiter before sending a request
+    async fn throttle(&self) {
+        lcu_limiter().until_ready().await;
+    }
+
+    /// Port of the client this talks to (identifies the client instance)
+    pub fn port(&self) -> u16 {
+        self.connection.port
+    }
+
+    pub fn connection(&self) -> &LcuConnection {
+        &self.connection
     }
 
     /// Create from an existing connection
     pub fn from_connection(connection: LcuConnection) -> Result<Self> {
+        // LCU certs are signed by Riot's own root, verified in `tls`
         let client = Client::builder()
-            .danger_accept_invalid_certs(true)
+            .use_preconfigured_tls(crate::tls::lcu_tls_config()?)
             .build()
             .map_err(|e| AppError::Other(format!("Failed to create HTTP client: {}", e)))?;
 
@@ -218,6 +333,7 @@ impl LcuClient {
             self.connection.base_url()
         );
 
+        self.throttle().await;
         let response = self
             .client
             .get(&url)
@@ -250,6 +366,7 @@ impl LcuClient {
             self.connection.base_url()
         );
 
+        self.throttle().await;
         let response = self
             .client
             .get(&url)
@@ -276,24 +393,56 @@ impl LcuClient {
         self.get_gameflow_phase().await.is_ok()
     }
 
+    /// Whether this client version serves `path`.
+    ///
+    /// An endpoint without data (no lobby, no game) answers 404 with an RPC
+    /// error; only a route the client doesn't know answers
+    /// `RESOURCE_NOT_FOUND`.
+    pub async fn has_endpoint(&self, path: &str) -> Result<bool> {
+        let url = format!("{}{}", self.connection.base_url(), path);
+
+        self.throttle().await;
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", self.connection.auth_header())
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to probe {}: {}", path, e)))?;
+
+        if response.status() != reqwest::StatusCode::NOT_FOUND {
+            return Ok(true);
+        }
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        Ok(body.get("errorCode").and_then(|c| c.as_str()) != Some("RESOURCE_NOT_FOUND"))
+    }
+
     /// Get end of game stats from LCU
     pub async fn get_end_of_game_stats(&self) -> Result<EndOfGameStats> {
+        let payload = self.get_end_of_game_payload().await?;
+        serde_json::from_value(payload)
+            .map_err(|e| AppError::EndOfGameUnavailable(format!("failed to parse: {}", e)))
+    }
+
+    /// Get the end of game stats block unparsed, for storing as received
+    pub async fn get_end_of_game_payload(&self) -> Result<serde_json::Value> {
         let url = format!(
             "{}/lol-end-of-game/v1/eog-stats-block",
             self.connection.base_url()
         );
 
+        self.throttle().await;
         let response = self
             .client
             .get(&url)
             .header("Authorization", self.connection.auth_header())
             .send()
             .await
-            .map_err(|e| AppError::Other(format!("Failed to get EOG stats: {}", e)))?;
+            .map_err(|e| AppError::EndOfGameUnavailable(format!("request failed: {}", e)))?;
 
         if !response.status().is_success() {
-            return Err(AppError::Other(format!(
-                "EOG stats request failed: {}",
+            return Err(AppError::EndOfGameUnavailable(format!(
+                "request returned {}",
                 response.status()
             )));
         }
@@ -301,7 +450,7 @@ impl LcuClient {
         response
             .json()
             .await
-            .map_err(|e| AppError::Other(format!("Failed to parse EOG stats: {}", e)))
+            .map_err(|e| AppError::EndOfGameUnavailable(format!("failed to read: {}", e)))
     }
 
     /// Get the current gameflow session (contains game mode, queue info, etc.)
@@ -311,6 +460,7 @@ impl LcuClient {
             self.connection.base_url()
         );
 
+        self.throttle().await;
         let response = self
             .client
             .get(&url)
@@ -341,6 +491,7 @@ impl LcuClient {
             summoner.account_id
         );
 
+        self.throttle().await;
         let response = self
             .client
             .get(&url)
@@ -363,163 +514,1020 @@ impl LcuClient {
 
         Ok(stats.queues)
     }
-}
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Summoner {
-    pub account_id: i64,
-    #[serde(default)]
-    pub display_name: String,
-    #[serde(default)]
-    pub game_name: String,
-    #[serde(default)]
-    pub tag_line: String,
-    pub summoner_level: i32,
-    pub profile_icon_id: i32,
-}
+    /// Get the current ranked season and its split boundaries
+    pub async fn get_splits_config(&self) -> Result<SplitsConfig> {
+        let url = format!("{}/lol-ranked/v1/splits-config", self.connection.base_url());
 
-impl Summoner {
-    /// Get the player's display name (Riot ID format: GameName#TagLine)
-    pub fn riot_id(&self) -> String {
-        if !self.game_name.is_empty() {
-            if !self.tag_line.is_empty() {
-                format!("{}#{}", self.game_name, self.tag_line)
-            } else {
-                self.game_name.clone()
-            }
-        } else {
-            self.display_name.clone()
+        self.throttle().await;
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", self.connection.auth_header())
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to get splits config: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Other(format!(
+                "Splits config request failed: {}",
+                response.status()
+            )));
         }
-    }
-}
 
-/// End of game statistics from LCU
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct EndOfGameStats {
-    pub game_id: i64,
-    pub game_mode: String,
-    pub game_length: i32,
-    pub game_type: String,
-    pub local_player: Option<LocalPlayerStats>,
-    pub teams: Vec<TeamStats>,
-}
+        response
+            .json()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to parse splits config: {}", e)))
+    }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct LocalPlayerStats {
-    pub champion_name: String,
-    pub summoner_name: String,
-    pub stats: PlayerStats,
-    pub spell1_id: i32,
-    pub spell2_id: i32,
-    pub team_id: i32,
-    pub items: Vec<i32>,
-    pub perk0: i32,
-    pub perk_sub_style: i32,
-}
+    /// Get the client's game version (e.g. "14.24.636.1234")
+    pub async fn get_game_version(&self) -> Result<String> {
+        let url = format!("{}/lol-patch/v1/game-version", self.connection.base_url());
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct PlayerStats {
-    pub assists: i32,
-    pub champions_killed: i32,
-    pub num_deaths: i32,
-    pub minions_killed: i32,
-    pub neutral_minions_killed: i32,
-    pub vision_score: i32,
-    pub total_damage_dealt_to_champions: i64,
-    pub gold_earned: i32,
-    pub level: i32,
-    #[serde(default)]
-    pub win: bool,
-}
+        self.throttle().await;
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", self.connection.auth_header())
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to get game version: {}", e)))?;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct TeamStats {
-    pub team_id: i32,
-    pub is_winning_team: bool,
-    pub players: Vec<TeamPlayerStats>,
-}
+        if !response.status().is_success() {
+            return Err(AppError::Other(format!(
+                "Game version request failed: {}",
+                response.status()
+            )));
+        }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct TeamPlayerStats {
-    pub champion_name: String,
-    pub summoner_name: String,
-    pub stats: PlayerStats,
-}
+        response
+            .json()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to parse game version: {}", e)))
+    }
 
-/// Gameflow session info from LCU
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct GameflowSession {
-    pub phase: String,
-    #[serde(default)]
-    pub game_data: GameflowGameData,
-}
+    /// Get the user's friends list
+    pub async fn get_friends(&self) -> Result<Vec<Friend>> {
+        let url = format!("{}/lol-chat/v1/friends", self.connection.base_url());
 
-/// Game data within gameflow session
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct GameflowGameData {
-    #[serde(default)]
-    pub game_id: i64,
-    #[serde(default)]
-    pub game_mode: String,
-    #[serde(default)]
-    pub game_type: String,
-    #[serde(default)]
-    pub queue: GameflowQueue,
-}
+        self.throttle().await;
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", self.connection.auth_header())
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to get friends: {}", e)))?;
 
-/// Queue info within gameflow session
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct GameflowQueue {
-    #[serde(default)]
-    pub id: i32,
-    #[serde(default)]
-    pub name: String,
-    #[serde(default)]
-    pub game_mode: String,
-    #[serde(default, rename = "type")]
-    pub queue_type: String,
-    #[serde(default)]
-    pub is_ranked: bool,
-}
+        if !response.status().is_success() {
+            return Err(AppError::Other(format!(
+                "Friends request failed: {}",
+                response.status()
+            )));
+        }
 
-impl GameflowSession {
-    /// Check if this is a TFT game
-    pub fn is_tft(&self) -> bool {
-        let mode = self.game_data.game_mode.to_uppercase();
-        let queue_mode = self.game_data.queue.game_mode.to_uppercase();
-        mode == "TFT" || queue_mode == "TFT"
+        response
+            .json()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to parse friends: {}", e)))
     }
 
-    /// Get the effective game mode (normalized)
-    pub fn game_mode(&self) -> &str {
-        if !self.game_data.game_mode.is_empty() {
-            &self.game_data.game_mode
-        } else if !self.game_data.queue.game_mode.is_empty() {
-            &self.game_data.queue.game_mode
-        } else {
-            "CLASSIC"
+    /// Get the mastery of every champion the summoner has played
+    pub async fn get_champion_mastery(&self, summoner_id: i64) -> Result<Vec<ChampionMastery>> {
+        let url = format!(
+            "{}/lol-collections/v1/inventories/{}/champion-mastery",
+            self.connection.base_url(),
+            summoner_id
+        );
+
+        self.throttle().await;
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", self.connection.auth_header())
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to get champion mastery: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Other(format!(
+                "Champion mastery request failed: {}",
+                response.status()
+            )));
         }
-    }
 
-    /// Check if this is a ranked game
-    pub fn is_ranked(&self) -> bool {
-        self.game_data.queue.is_ranked
+        response
+            .json()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to parse champion mastery: {}", e)))
     }
-}
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct RankedStats {
-    pub queues: Vec<RankedEntry>,
+    /// Get the current lobby (party members stay listed while the game runs)
+    pub async fn get_lobby(&self) -> Result<Lobby> {
+        let url = format!("{}/lol-lobby/v2/lobby", self.connection.base_url());
+
+        self.throttle().await;
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", self.connection.auth_header())
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to get lobby: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Other(format!(
+                "Lobby request failed: {}",
+                response.status()
+            )));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to parse lobby: {}", e)))
+    }
+
+    /// Get the current champ select session
+    pub async fn get_champ_select_session(&self) -> Result<ChampSelectSession> {
+        let url = format!(
+            "{}/lol-champ-select/v1/session",
+            self.connection.base_url()
+        );
+
+        self.throttle().await;
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", self.connection.auth_header())
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to get champ select session: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Other(format!(
+                "Champ select session request failed: {}",
+                response.status()
+            )));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to parse champ select session: {}", e)))
+    }
+
+    /// Get a finished game from match history (participant identities etc.)
+    pub async fn get_match_history_game(&self, game_id: i64) -> Result<MatchHistoryGame> {
+        let url = format!(
+            "{}/lol-match-history/v1/games/{}",
+            self.connection.base_url(),
+            game_id
+        );
+
+        self.throttle().await;
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", self.connection.auth_header())
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to get match history game: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Other(format!(
+                "Match history game request failed: {}",
+                response.status()
+            )));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to parse match history game: {}", e)))
+    }
+
+    /// Get games `beg_index..=end_index` of the current summoner's match
+    /// history (0 is the newest)
+    pub async fn get_match_history(&self, beg_index: u32, end_index: u32) -> Result<MatchHistoryPage> {
+        let url = format!(
+            "{}/lol-match-history/v1/products/lol/current-summoner/matches?begIndex={}&endIndex={}",
+            self.connection.base_url(),
+            beg_index,
+            end_index
+        );
+
+        self.throttle().await;
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", self.connection.auth_header())
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to get match history: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Other(format!(
+                "Match history request failed: {}",
+                response.status()
+            )));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to parse match history: {}", e)))
+    }
+
+    /// Get the Match-V5 style event timeline of a finished game
+    pub async fn get_game_timeline(&self, game_id: i64) -> Result<GameTimeline> {
+        let url = format!(
+            "{}/lol-match-history/v1/game-timelines/{}",
+            self.connection.base_url(),
+            game_id
+        );
+
+        self.throttle().await;
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", self.connection.auth_header())
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to get game timeline: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Other(format!(
+                "Game timeline request failed: {}",
+                response.status()
+            )));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to parse game timeline: {}", e)))
+    }
+
+    /// Get the currently selected rune page
+    pub async fn get_current_rune_page(&self) -> Result<RunePage> {
+        let url = format!("{}/lol-perks/v1/currentpage", self.connection.base_url());
+
+        self.throttle().await;
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", self.connection.auth_header())
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to get rune page: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Other(format!(
+                "Rune page request failed: {}",
+                response.status()
+            )));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to parse rune page: {}", e)))
+    }
+
+    /// Get the in-game TFT state (stage, round and player health)
+    pub async fn get_tft_game_state(&self) -> Result<TftGameState> {
+        let url = format!("{}/lol-tft/v1/game-state", self.connection.base_url());
+
+        self.throttle().await;
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", self.connection.auth_header())
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to get TFT game state: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Other(format!(
+                "TFT game state request failed: {}",
+                response.status()
+            )));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to parse TFT game state: {}", e)))
+    }
+
+    /// Get the current summoner's regalia (ranked border and banner)
+    pub async fn get_regalia(&self) -> Result<Regalia> {
+        let url = format!(
+            "{}/lol-regalia/v2/current-summoner/regalia",
+            self.connection.base_url()
+        );
+
+        self.throttle().await;
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", self.connection.auth_header())
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to get regalia: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Other(format!(
+                "Regalia request failed: {}",
+                response.status()
+            )));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to parse regalia: {}", e)))
+    }
+
+    /// Get the current summoner's honor level
+    pub async fn get_honor_profile(&self) -> Result<HonorProfile> {
+        let url = format!("{}/lol-honor-v2/v1/profile", self.connection.base_url());
+
+        self.throttle().await;
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", self.connection.auth_header())
+            .send()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to get honor profile: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::Other(format!(
+                "Honor profile request failed: {}",
+                response.status()
+            )));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| AppError::Other(format!("Failed to parse honor profile: {}", e)))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Summoner {
+    pub account_id: i64,
+    #[serde(default)]
+    pub summoner_id: i64,
+    #[serde(default)]
+    pub puuid: String,
+    #[serde(default)]
+    pub display_name: String,
+    #[serde(default)]
+    pub game_name: String,
+    #[serde(default)]
+    pub tag_line: String,
+    pub summoner_level: i32,
+    pub profile_icon_id: i32,
+}
+
+impl Summoner {
+    /// Get the player's display name (Riot ID format: GameName#TagLine)
+    pub fn riot_id(&self) -> String {
+        if !self.game_name.is_empty() {
+            if !self.tag_line.is_empty() {
+                format!("{}#{}", self.game_name, self.tag_line)
+            } else {
+                self.game_name.clone()
+            }
+        } else {
+            self.display_name.clone()
+        }
+    }
+}
+
+/// Mastery of one champion from the LCU collections API
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ChampionMastery {
+    pub champion_id: i32,
+    pub champion_level: i32,
+    pub champion_points: i64,
+    pub champion_points_since_last_level: i64,
+    pub champion_points_until_next_level: i64,
+    pub tokens_earned: i32,
+}
+
+impl ChampionMastery {
+    /// Short label for match cards, e.g. "Mastery 7, 350k points"
+    pub fn label(&self) -> String {
+        let points = if self.champion_points >= 1_000_000 {
+            format!("{:.1}M", self.champion_points as f64 / 1_000_000.0)
+        } else if self.champion_points >= 1_000 {
+            format!("{}k", self.champion_points / 1_000)
+        } else {
+            self.champion_points.to_string()
+        };
+        format!("Mastery {}, {} points", self.champion_level, points)
+    }
+}
+
+/// End of game statistics from LCU
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EndOfGameStats {
+    pub game_id: i64,
+    pub game_mode: String,
+    pub game_length: i32,
+    pub game_type: String,
+    /// Game ended in an early surrender vote (remake)
+    #[serde(default)]
+    pub game_ended_in_early_surrender: bool,
+    pub local_player: Option<LocalPlayerStats>,
+    pub teams: Vec<TeamStats>,
+}
+
+/// Longest game a remake vote can end
+const REMAKE_MAX_GAME_SECS: i32 = 300;
+
+impl EndOfGameStats {
+    /// Whether the game was remade: an early surrender within the first
+    /// minutes (later surrenders aren't remakes)
+    pub fn is_remake(&self) -> bool {
+        self.game_ended_in_early_surrender && self.game_length < REMAKE_MAX_GAME_SECS
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalPlayerStats {
+    pub champion_name: String,
+    pub summoner_name: String,
+    pub stats: PlayerStats,
+    pub spell1_id: i32,
+    pub spell2_id: i32,
+    pub team_id: i32,
+    pub items: Vec<i32>,
+    pub perk0: i32,
+    pub perk_sub_style: i32,
+    /// Position the game detected from where the player went
+    #[serde(default)]
+    pub detected_team_position: String,
+    /// Position picked in champ select (role queues only)
+    #[serde(default)]
+    pub selected_position: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerStats {
+    pub assists: i32,
+    pub champions_killed: i32,
+    pub num_deaths: i32,
+    pub minions_killed: i32,
+    pub neutral_minions_killed: i32,
+    pub vision_score: i32,
+    pub total_damage_dealt_to_champions: i64,
+    pub gold_earned: i32,
+    pub level: i32,
+    #[serde(default)]
+    pub win: bool,
+    /// Arena duo the player belongs to
+    #[serde(default)]
+    pub player_subteam: i32,
+    /// Arena placement of the player's duo (1-4)
+    #[serde(default)]
+    pub player_subteam_placement: i32,
+    #[serde(default)]
+    pub player_augment1: i32,
+    #[serde(default)]
+    pub player_augment2: i32,
+    #[serde(default)]
+    pub player_augment3: i32,
+    #[serde(default)]
+    pub player_augment4: i32,
+    #[serde(default)]
+    pub player_augment5: i32,
+    #[serde(default)]
+    pub player_augment6: i32,
+}
+
+impl PlayerStats {
+    /// Arena augments picked, in pick order (empty outside Arena)
+    pub fn augment_ids(&self) -> Vec<i32> {
+        [
+            self.player_augment1,
+            self.player_augment2,
+            self.player_augment3,
+            self.player_augment4,
+            self.player_augment5,
+            self.player_augment6,
+        ]
+        .into_iter()
+        .filter(|&id| id != 0)
+        .collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamStats {
+    pub team_id: i32,
+    pub is_winning_team: bool,
+    pub players: Vec<TeamPlayerStats>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamPlayerStats {
+    pub champion_name: String,
+    pub summoner_name: String,
+    #[serde(default)]
+    pub riot_id_game_name: String,
+    #[serde(default)]
+    pub riot_id_tag_line: String,
+    pub stats: PlayerStats,
+}
+
+impl TeamPlayerStats {
+    /// Riot ID ("name#tag") if available, otherwise the summoner name
+    pub fn riot_id(&self) -> String {
+        if self.riot_id_game_name.is_empty() {
+            self.summoner_name.clone()
+        } else {
+            format!("{}#{}", self.riot_id_game_name, self.riot_id_tag_line)
+        }
+    }
+}
+
+/// Gameflow session info from LCU
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameflowSession {
+    pub phase: String,
+    #[serde(default)]
+    pub game_data: GameflowGameData,
+    #[serde(default)]
+    pub map: GameflowMap,
+    #[serde(default)]
+    pub game_client: GameflowGameClient,
+    /// Why the game ended (e.g. "EarlySurrender" after a remake vote);
+    /// empty while it's running
+    #[serde(default)]
+    pub termination_reason: String,
+}
+
+/// Game server the client connects to
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GameflowGameClient {
+    pub server_ip: String,
+    pub server_port: u16,
+}
+
+impl GameflowGameClient {
+    /// Server address, if the client is connected to a game
+    pub fn server_addr(&self) -> Option<std::net::SocketAddr> {
+        let ip = self.server_ip.parse().ok()?;
+        (self.server_port != 0).then(|| std::net::SocketAddr::new(ip, self.server_port))
+    }
+}
+
+/// Map of the current game (11 = Summoner's Rift, 12 = Howling Abyss, ...)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GameflowMap {
+    pub id: i32,
+}
+
+/// Game data within gameflow session
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameflowGameData {
+    #[serde(default)]
+    pub game_id: i64,
+    #[serde(default)]
+    pub game_mode: String,
+    #[serde(default)]
+    pub game_type: String,
+    #[serde(default)]
+    pub queue: GameflowQueue,
+    #[serde(default)]
+    pub team_one: Vec<GameflowPlayer>,
+    #[serde(default)]
+    pub team_two: Vec<GameflowPlayer>,
+}
+
+/// Player entry in a gameflow session team
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GameflowPlayer {
+    pub puuid: String,
+    pub summoner_name: String,
+    pub champion_id: i32,
+}
+
+/// Friend entry from the LCU chat API
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Friend {
+    pub puuid: String,
+    pub game_name: String,
+    pub game_tag: String,
+    pub name: String,
+}
+
+impl Friend {
+    /// Riot ID ("name#tag") if available, otherwise the chat display name
+    pub fn riot_id(&self) -> String {
+        if self.game_name.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{}#{}", self.game_name, self.game_tag)
+        }
+    }
+}
+
+/// Lobby (party) from the LCU
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Lobby {
+    pub members: Vec<LobbyMember>,
+}
+
+/// Party member in a lobby
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LobbyMember {
+    pub puuid: String,
+    pub summoner_name: String,
+    pub game_name: String,
+    pub game_tag: String,
+}
+
+impl LobbyMember {
+    /// Riot ID ("name#tag") if available, otherwise the summoner name
+    pub fn riot_id(&self) -> String {
+        if self.game_name.is_empty() {
+            self.summoner_name.clone()
+        } else {
+            format!("{}#{}", self.game_name, self.game_tag)
+        }
+    }
+}
+
+impl Lobby {
+    /// Riot IDs of the other party members
+    pub fn premades(&self, local_puuid: &str) -> Vec<String> {
+        self.members
+            .iter()
+            .filter(|m| m.puuid != local_puuid)
+            .map(LobbyMember::riot_id)
+            .filter(|id| !id.is_empty())
+            .collect()
+    }
+}
+
+/// Champ select session from the LCU
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ChampSelectSession {
+    pub local_player_cell_id: i64,
+    pub my_team: Vec<ChampSelectPlayer>,
+    pub their_team: Vec<ChampSelectPlayer>,
+    /// Pick/ban turns, grouped by phase
+    pub actions: Vec<Vec<ChampSelectAction>>,
+}
+
+/// Player in champ select
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ChampSelectPlayer {
+    pub cell_id: i64,
+    /// Selected champion (0 while only hovering)
+    pub champion_id: i32,
+    /// Hovered champion
+    pub champion_pick_intent: i32,
+    pub assigned_position: String,
+    pub spell1_id: u64,
+    pub spell2_id: u64,
+}
+
+/// A pick or ban turn in champ select
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ChampSelectAction {
+    pub id: i64,
+    pub actor_cell_id: i64,
+    pub champion_id: i32,
+    pub completed: bool,
+    pub is_ally_action: bool,
+    #[serde(rename = "type")]
+    pub action_type: String,
+}
+
+impl ChampSelectSession {
+    /// The local player's hovered/locked champion, role and spells
+    pub fn local_state(&self) -> Option<ChampSelectState> {
+        let me = self
+            .my_team
+            .iter()
+            .find(|p| p.cell_id == self.local_player_cell_id)?;
+
+        let locked = self.actions.iter().flatten().any(|a| {
+            a.actor_cell_id == me.cell_id && a.action_type == "pick" && a.completed
+        });
+        let champion_id = if me.champion_id != 0 {
+            me.champion_id
+        } else {
+            me.champion_pick_intent
+        };
+
+        Some(ChampSelectState {
+            champion_id,
+            locked,
+            role: me.assigned_position.clone(),
+            spell1_id: me.spell1_id,
+            spell2_id: me.spell2_id,
+            ..Default::default()
+        })
+    }
+}
+
+/// In-game TFT state from the LCU
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TftGameState {
+    pub stage: u32,
+    pub round: u32,
+    /// "PVE", "PVP", "CAROUSEL" or "AUGMENT"
+    pub round_type: String,
+    pub players: Vec<TftPlayerState>,
+}
+
+/// One player in a TFT lobby
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TftPlayerState {
+    pub summoner_name: String,
+    /// Remaining health (0 once eliminated)
+    pub health: i32,
+    pub is_local_player: bool,
+    /// Champions on the board (only reported for the local player)
+    pub board: Vec<TftUnit>,
+}
+
+/// A champion on a TFT board
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TftUnit {
+    /// Unit key (e.g. "TFT13_Jinx")
+    pub character_id: String,
+    /// 1 to 4
+    pub star_level: u32,
+    pub item_ids: Vec<i32>,
+}
+
+/// Profile decorations from the LCU regalia API
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Regalia {
+    /// Highest rank this season, which decides the ranked border
+    pub highest_ranked_entry: Option<RegaliaRankedEntry>,
+    /// Highest rank last season (e.g. "PLATINUM")
+    pub last_season_highest_rank: Option<String>,
+}
+
+impl Regalia {
+    /// Tier shown as the ranked border (`None` for unranked accounts)
+    pub fn ranked_border(&self) -> Option<String> {
+        self.highest_ranked_entry
+            .as_ref()
+            .map(|entry| entry.tier.as_str())
+            .filter(|tier| !tier.is_empty() && *tier != "NONE" && *tier != "UNRANKED")
+            .map(String::from)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RegaliaRankedEntry {
+    pub queue_type: String,
+    pub tier: String,
+    pub division: String,
+}
+
+/// Honor level from the LCU honor API
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HonorProfile {
+    pub honor_level: i32,
+    pub checkpoint: i32,
+}
+
+/// Rune page from the LCU perks API
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RunePage {
+    pub name: String,
+    pub primary_style_id: i32,
+    pub sub_style_id: i32,
+    /// Selected perks, keystone first
+    pub selected_perk_ids: Vec<i32>,
+}
+
+impl From<RunePage> for MatchRunes {
+    fn from(page: RunePage) -> Self {
+        MatchRunes {
+            page_name: page.name,
+            primary_tree_id: page.primary_style_id,
+            secondary_tree_id: page.sub_style_id,
+            perk_ids: page.selected_perk_ids,
+        }
+    }
+}
+
+/// Finished game from the LCU match history
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MatchHistoryGame {
+    pub game_id: i64,
+    /// Start of the game (Unix milliseconds)
+    pub game_creation: i64,
+    /// Seconds
+    pub game_duration: i32,
+    pub game_mode: String,
+    pub queue_id: i32,
+    /// Everyone for a single game; only the player in history pages
+    pub participants: Vec<HistoryParticipant>,
+    pub participant_identities: Vec<ParticipantIdentity>,
+}
+
+/// A participant's champion and result in a match history game
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HistoryParticipant {
+    pub participant_id: i32,
+    /// 100 blue, 200 red
+    pub team_id: i32,
+    pub champion_id: i32,
+    pub stats: HistoryParticipantStats,
+    pub timeline: HistoryParticipantTimeline,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HistoryParticipantStats {
+    pub win: bool,
+    pub kills: i32,
+    pub deaths: i32,
+    pub assists: i32,
+    pub game_ended_in_early_surrender: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HistoryParticipantTimeline {
+    /// "TOP", "JUNGLE", "MIDDLE", "BOTTOM"
+    pub lane: String,
+    /// "SOLO", "DUO_CARRY", "DUO_SUPPORT", ...
+    pub role: String,
+}
+
+/// A page of the current summoner's match history
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MatchHistoryPage {
+    pub games: MatchHistoryGames,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MatchHistoryGames {
+    /// Games in the history
+    pub game_count: u32,
+    /// Newest first
+    pub games: Vec<MatchHistoryGame>,
+}
+
+/// Maps a timeline participant ID to a player
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ParticipantIdentity {
+    pub participant_id: i32,
+    pub player: ParticipantPlayer,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ParticipantPlayer {
+    pub puuid: String,
+    pub summoner_name: String,
+}
+
+impl MatchHistoryGame {
+    /// Timeline participant ID of the player with the given PUUID
+    pub fn participant_id(&self, puuid: &str) -> Option<i32> {
+        self.participant_identities
+            .iter()
+            .find(|p| p.player.puuid == puuid)
+            .map(|p| p.participant_id)
+    }
+}
+
+/// Event timeline of a finished game, one frame per minute
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GameTimeline {
+    pub frames: Vec<TimelineFrame>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TimelineFrame {
+    /// Milliseconds since game start
+    pub timestamp: i64,
+    pub events: Vec<TimelineEvent>,
+}
+
+/// Timeline event (only the fields used for ward tracking are parsed)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TimelineEvent {
+    /// e.g. "WARD_PLACED", "WARD_KILL", "CHAMPION_KILL"
+    #[serde(rename = "type")]
+    pub event_type: String,
+    /// Milliseconds since game start
+    pub timestamp: i64,
+    /// Participant that placed a ward
+    pub creator_id: i32,
+    /// Participant that killed a ward/champion
+    pub killer_id: i32,
+    /// e.g. "YELLOW_TRINKET", "CONTROL_WARD", "SIGHT_WARD"
+    pub ward_type: String,
+}
+
+/// Queue info within gameflow session
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameflowQueue {
+    #[serde(default)]
+    pub id: i32,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub game_mode: String,
+    #[serde(default, rename = "type")]
+    pub queue_type: String,
+    #[serde(default)]
+    pub is_ranked: bool,
+}
+
+impl GameflowSession {
+    /// Check if this is a TFT game
+    pub fn is_tft(&self) -> bool {
+        let mode = self.game_data.game_mode.to_uppercase();
+        let queue_mode = self.game_data.queue.game_mode.to_uppercase();
+        mode == "TFT" || queue_mode == "TFT"
+    }
+
+    /// Get the effective game mode (normalized)
+    pub fn game_mode(&self) -> &str {
+        if !self.game_data.game_mode.is_empty() {
+            &self.game_data.game_mode
+        } else if !self.game_data.queue.game_mode.is_empty() {
+            &self.game_data.queue.game_mode
+        } else {
+            "CLASSIC"
+        }
+    }
+
+    /// Check if this is a ranked game
+    pub fn is_ranked(&self) -> bool {
+        self.game_data.queue.is_ranked
+    }
+
+    /// Whether the client reports the game as ended by a remake vote
+    pub fn ended_in_remake(&self) -> bool {
+        let reason = self.termination_reason.to_ascii_lowercase();
+        reason.contains("earlysurrender") || reason.contains("remake")
+    }
+
+    /// All players in the game (both teams)
+    pub fn players(&self) -> impl Iterator<Item = &GameflowPlayer> {
+        self.game_data.team_one.iter().chain(self.game_data.team_two.iter())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RankedStats {
+    pub queues: Vec<RankedEntry>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -529,4 +1537,144 @@ pub struct RankedEntry {
     pub tier: String,
     pub division: String,
     pub league_points: i32,
+    /// Promotion series results (e.g. "WLN"), empty outside a series
+    #[serde(default)]
+    pub mini_series_progress: String,
+}
+
+/// Current ranked season and its splits
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SplitsConfig {
+    pub current_season_id: i32,
+    pub current_split_id: i32,
+    pub splits: Vec<SplitConfig>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SplitConfig {
+    pub split_id: i32,
+    /// Unix milliseconds, 0 if unknown
+    pub start_time_millis: i64,
+    pub end_time_millis: i64,
+}
+
+impl SplitsConfig {
+    /// Splits of the current season with their boundaries
+    pub fn ranked_splits(&self) -> Vec<RankedSplit> {
+        let time = |millis: i64| {
+            Some(millis)
+                .filter(|&m| m > 0)
+                .and_then(chrono::DateTime::from_timestamp_millis)
+        };
+        self.splits
+            .iter()
+            .map(|s| RankedSplit {
+                season_id: self.current_season_id,
+                split_id: s.split_id,
+                start: time(s.start_time_millis),
+                end: time(s.end_time_millis),
+            })
+            .collect()
+    }
+
+    /// The split currently in progress
+    pub fn current_split(&self) -> Option<RankedSplit> {
+        self.ranked_splits()
+            .into_iter()
+            .find(|s| s.split_id == self.current_split_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auth_token_is_redacted() {
+        let connection =
+            LcuConnection::parse_lockfile_content("LeagueClient:1234:56789:s3cr3t:https").unwrap();
+        assert_eq!(connection.port, 56789);
+        assert_eq!(connection.auth_token.expose(), "s3cr3t");
+
+        let debug = format!("{:?}", connection);
+        assert!(!debug.contains("s3cr3t"));
+
+        let json = serde_json::to_string(&connection.auth_token).unwrap();
+        assert!(!json.contains("s3cr3t"));
+
+        assert!(connection.auth_header().is_sensitive());
+    }
+
+    #[test]
+    fn test_connections_from_command_lines() {
+        let output = "CommandLine\n\
+            \"C:/LoL/LeagueClientUx.exe\" \"--remoting-auth-token=a-B_1\" \"--app-port=50123\"\n\
+            \"C:/LoL/LeagueClientUx.exe\" \"--app-port=50456\" \"--remoting-auth-token=xyz\"\n\
+            \"C:/LoL/LeagueClientUxRender.exe\" \"--type=renderer\"\n";
+        let connections: Vec<_> = LcuConnection::client_lines(output)
+            .iter()
+            .filter_map(|line| LcuConnection::from_command_line(line))
+            .collect();
+
+        assert_eq!(connections.len(), 2);
+        assert_eq!(connections[0].port, 50123);
+        assert_eq!(connections[0].auth_token.expose(), "a-B_1");
+        assert_eq!(connections[1].port, 50456);
+        assert_eq!(connections[1].protocol, "https");
+    }
+
+    #[test]
+    fn test_champ_select_local_state() {
+        let session: ChampSelectSession = serde_json::from_value(serde_json::json!({
+            "localPlayerCellId": 1,
+            "myTeam": [
+                {"cellId": 0, "championId": 86, "assignedPosition": "top"},
+                {"cellId": 1, "championId": 0, "championPickIntent": 64,
+                 "assignedPosition": "jungle", "spell1Id": 11, "spell2Id": 4}
+            ],
+            "actions": [[
+                {"actorCellId": 0, "championId": 86, "completed": true, "type": "pick"},
+                {"actorCellId": 1, "championId": 64, "completed": false, "type": "pick"}
+            ]]
+        }))
+        .unwrap();
+
+        let state = session.local_state().unwrap();
+        assert_eq!(state.champion_id, 64);
+        assert!(!state.locked);
+        assert_eq!(state.role, "jungle");
+        assert_eq!(state.spell1_id, 11);
+    }
+
+    #[test]
+    fn test_champion_mastery_label() {
+        let mastery: ChampionMastery = serde_json::from_value(serde_json::json!({
+            "championId": 64, "championLevel": 7, "championPoints": 350_412
+        }))
+        .unwrap();
+        assert_eq!(mastery.label(), "Mastery 7, 350k points");
+
+        let mastery = ChampionMastery { champion_level: 2, champion_points: 900, ..mastery };
+        assert_eq!(mastery.label(), "Mastery 2, 900 points");
+    }
+
+    #[test]
+    fn test_regalia_ranked_border() {
+        let regalia: Regalia = serde_json::from_value(serde_json::json!({
+            "crestType": "ranked",
+            "highestRankedEntry": {
+                "queueType": "RANKED_SOLO_5x5", "tier": "GOLD", "division": "II"
+            },
+            "lastSeasonHighestRank": "SILVER"
+        }))
+        .unwrap();
+        assert_eq!(regalia.ranked_border().as_deref(), Some("GOLD"));
+
+        let unranked: Regalia =
+            serde_json::from_value(serde_json::json!({ "highestRankedEntry": { "tier": "NONE" } }))
+                .unwrap();
+        assert_eq!(unranked.ranked_border(), None);
+    }
 }
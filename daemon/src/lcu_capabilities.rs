@@ -0,0 +1,142 @@
+//! LCU endpoint capabilities
+//!
+//! Riot renames or retires LCU endpoints between client versions. Once per
+//! connection, one endpoint of every optional feature is probed, and
+//! features whose endpoint the client doesn't serve are skipped instead of
+//! failing (and being retried) on every use. Until the probe has run, every
+//! feature counts as available.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use tracing::{debug, warn};
+
+use crate::LcuClient;
+
+/// Client features that depend on a specific LCU endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LcuFeature {
+    /// End-of-game stats (match finalization falls back to live data)
+    EndOfGame,
+    /// Ranked tier and LP
+    Ranked,
+    /// Ranked split boundaries
+    RankedSplits,
+    /// Party members
+    Lobby,
+    /// Draft capture
+    ChampSelect,
+    /// Rune page
+    Runes,
+    /// Post-game timelines
+    Timelines,
+    /// Champion mastery
+    Mastery,
+    /// Friends list
+    Friends,
+    /// TFT game state
+    Tft,
+    /// Ranked border
+    Regalia,
+    /// Honor level
+    Honor,
+}
+
+impl LcuFeature {
+    /// All probed features
+    pub const ALL: [LcuFeature; 12] = [
+        LcuFeature::EndOfGame,
+        LcuFeature::Ranked,
+        LcuFeature::RankedSplits,
+        LcuFeature::Lobby,
+        LcuFeature::ChampSelect,
+        LcuFeature::Runes,
+        LcuFeature::Timelines,
+        LcuFeature::Mastery,
+        LcuFeature::Friends,
+        LcuFeature::Tft,
+        LcuFeature::Regalia,
+        LcuFeature::Honor,
+    ];
+
+    /// Endpoint probed for the feature (path parameters are placeholders;
+    /// the route exists whatever the ID)
+    pub fn probe_path(&self) -> &'static str {
+        match self {
+            LcuFeature::EndOfGame => "/lol-end-of-game/v1/eog-stats-block",
+            LcuFeature::Ranked => "/lol-ranked/v1/ranked-stats/0",
+            LcuFeature::RankedSplits => "/lol-ranked/v1/splits-config",
+            LcuFeature::Lobby => "/lol-lobby/v2/lobby",
+            LcuFeature::ChampSelect => "/lol-champ-select/v1/session",
+            LcuFeature::Runes => "/lol-perks/v1/currentpage",
+            LcuFeature::Timelines => "/lol-match-history/v1/game-timelines/0",
+            LcuFeature::Mastery => "/lol-collections/v1/inventories/0/champion-mastery",
+            LcuFeature::Friends => "/lol-chat/v1/friends",
+            LcuFeature::Tft => "/lol-tft/v1/game-state",
+            LcuFeature::Regalia => "/lol-regalia/v2/current-summoner/regalia",
+            LcuFeature::Honor => "/lol-honor-v2/v1/profile",
+        }
+    }
+}
+
+/// Features the connected client lacks
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LcuCapabilities {
+    /// Whether the client was probed (unprobed clients support everything)
+    pub probed: bool,
+    pub missing: BTreeSet<LcuFeature>,
+}
+
+impl LcuCapabilities {
+    /// Probe every feature's endpoint. A failed request (client busy or
+    /// closing) doesn't count as missing.
+    pub async fn probe(client: &LcuClient) -> Self {
+        let mut missing = BTreeSet::new();
+        for feature in LcuFeature::ALL {
+            match client.has_endpoint(feature.probe_path()).await {
+                Ok(true) => {}
+                Ok(false) => {
+                    missing.insert(feature);
+                }
+                Err(e) => debug!("Probe of {:?} failed: {}", feature, e),
+            }
+        }
+        if !missing.is_empty() {
+            warn!(
+                "LCU endpoints missing on this client, skipping: {:?}",
+                missing
+            );
+        }
+        Self {
+            probed: true,
+            missing,
+        }
+    }
+
+    pub fn supports(&self, feature: LcuFeature) -> bool {
+        !self.missing.contains(&feature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unprobed_client_supports_everything() {
+        let capabilities = LcuCapabilities::default();
+        assert!(LcuFeature::ALL.iter().all(|f| capabilities.supports(*f)));
+
+        let capabilities = LcuCapabilities {
+            probed: true,
+            missing: BTreeSet::from([LcuFeature::Timelines]),
+        };
+        assert!(!capabilities.supports(LcuFeature::Timelines));
+        assert!(capabilities.supports(LcuFeature::EndOfGame));
+        assert_eq!(
+            serde_json::to_value(&capabilities).unwrap()["missing"],
+            serde_json::json!(["timelines"])
+        );
+    }
+}
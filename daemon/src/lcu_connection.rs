@@ -0,0 +1,258 @@
+//! Shared LCU connection
+//!
+//! Discovering the client runs WMIC/ps and reads the lockfile, which is far
+//! too slow to repeat for every request. The connection is discovered once,
+//! cached process-wide and shared by the integration, the finalizer and the
+//! gameflow monitor. A health-check task pings the cached connection and
+//! drops it when the client stops answering (a restarted client has a new
+//! port and password), after which the next caller re-discovers it.
+//!
+//! Players logged in on several accounts run one client per account. All of
+//! them are listed by `list_connections()`, and one can be selected to be
+//! followed instead of the client the lockfile points to.
+//!
+//! The first health check of a new connection also probes which optional
+//! endpoints the client serves (see `lcu_capabilities`).
+
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+use crate::protocol::ClientConnection;
+use crate::{LcuCapabilities, LcuClient, LcuConnection, LcuFeature, TaskSupervisor};
+
+/// Supervisor task checking the cached connection
+const HEALTH_CHECK_TASK: &str = "lcu_health_check";
+
+/// How often the cached connection is checked
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Minimum time between discovery attempts while the client isn't found
+const REDISCOVERY_INTERVAL: Duration = Duration::from_secs(2);
+
+static LCU_CONNECTION: OnceLock<LcuConnectionManager> = OnceLock::new();
+
+/// The shared LCU connection
+pub fn lcu_connection() -> &'static LcuConnectionManager {
+    LCU_CONNECTION.get_or_init(LcuConnectionManager::new)
+}
+
+#[derive(Default)]
+struct ConnectionState {
+    client: Option<LcuClient>,
+    /// Bumped on every discovery, so a stale health check can't drop a newer connection
+    generation: u64,
+    last_discovery: Option<Instant>,
+    health_check_started: bool,
+    /// Port of the client selected by the user (`None` follows the lockfile)
+    preferred_port: Option<u16>,
+    /// Endpoint probe of the connection, with the generation it belongs to
+    capabilities: Option<(u64, LcuCapabilities)>,
+}
+
+/// Cached LCU connection with liveness checks and re-discovery
+#[derive(Default)]
+pub struct LcuConnectionManager {
+    state: Mutex<ConnectionState>,
+}
+
+impl LcuConnectionManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Client for the cached connection, discovering it if needed.
+    ///
+    /// `None` while the client isn't running; discovery is retried at most
+    /// every `REDISCOVERY_INTERVAL`.
+    pub fn client(&self) -> Option<LcuClient> {
+        let mut state = self.state.lock().expect("Mutex poisoned");
+        if state.client.is_none() && should_discover(state.last_discovery, Instant::now()) {
+            Self::discover(&mut state);
+        }
+        state.client.clone()
+    }
+
+    /// What the current client was probed to support (everything until probed)
+    pub fn capabilities(&self) -> LcuCapabilities {
+        let state = self.state.lock().expect("Mutex poisoned");
+        match state.capabilities {
+            Some((generation, ref capabilities)) if generation == state.generation => {
+                capabilities.clone()
+            }
+            _ => LcuCapabilities::default(),
+        }
+    }
+
+    /// Whether the current client serves the endpoint `feature` needs
+    pub fn supports(&self, feature: LcuFeature) -> bool {
+        self.capabilities().supports(feature)
+    }
+
+    /// Drop the cached connection (e.g. after a failed request)
+    pub fn invalidate(&self) {
+        let mut state = self.state.lock().expect("Mutex poisoned");
+        if state.client.take().is_some() {
+            debug!("LCU connection invalidated");
+        }
+    }
+
+    /// Drop the cached connection and discover the client again right away,
+    /// without waiting for the discovery throttle; the new client, if found
+    pub fn reconnect(&self) -> Option<LcuClient> {
+        let mut state = self.state.lock().expect("Mutex poisoned");
+        state.client = None;
+        Self::discover(&mut state);
+        state.client.clone()
+    }
+
+    /// Every running client and the account logged in to it
+    pub async fn list_connections(&self) -> Vec<ClientConnection> {
+        let connections = match LcuConnection::discover_all() {
+            Ok(connections) => connections,
+            Err(e) => {
+                debug!("Client enumeration failed: {}", e);
+                return Vec::new();
+            }
+        };
+        let active_port = self.client().map(|c| c.port());
+
+        let mut listed = Vec::with_capacity(connections.len());
+        for connection in connections {
+            let port = connection.port;
+            let Ok(client) = LcuClient::from_connection(connection) else {
+                continue;
+            };
+            // A client still on the login screen has no summoner yet
+            let summoner = client.get_current_summoner().await.ok();
+            listed.push(ClientConnection {
+                port,
+                puuid: summoner.as_ref().map(|s| s.puuid.clone()).unwrap_or_default(),
+                riot_id: summoner.as_ref().map(|s| s.riot_id()).unwrap_or_default(),
+                active: active_port == Some(port),
+            });
+        }
+        listed
+    }
+
+    /// Follow the client on `port` (`None` goes back to the lockfile's client)
+    pub fn select(&self, port: Option<u16>) {
+        let mut state = self.state.lock().expect("Mutex poisoned");
+        if state.preferred_port == port {
+            return;
+        }
+        info!("LCU client selected: {:?}", port);
+        state.preferred_port = port;
+        state.client = None;
+        state.last_discovery = None;
+    }
+
+    /// Start the periodic health check (only once per process)
+    pub fn start_health_check(&'static self, supervisor: &TaskSupervisor) {
+        {
+            let mut state = self.state.lock().expect("Mutex poisoned");
+            if state.health_check_started {
+                return;
+            }
+            state.health_check_started = true;
+        }
+        supervisor.spawn(HEALTH_CHECK_TASK, move || self.run_health_check());
+    }
+
+    async fn run_health_check(&'static self) {
+        let mut interval = tokio::time::interval(HEALTH_CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            self.check_health().await;
+        }
+    }
+
+    /// Ping the cached connection and drop it if the client stopped answering
+    async fn check_health(&self) {
+        let (client, generation) = {
+            let state = self.state.lock().expect("Mutex poisoned");
+            match state.client {
+                Some(ref client) => (client.clone(), state.generation),
+                None => return,
+            }
+        };
+
+        if client.is_connected().await {
+            self.probe_if_new(&client, generation).await;
+            return;
+        }
+
+        let mut state = self.state.lock().expect("Mutex poisoned");
+        if state.generation == generation && state.client.take().is_some() {
+            info!("LCU connection lost, will re-discover");
+        }
+    }
+
+    /// Probe a connection's endpoints once
+    async fn probe_if_new(&self, client: &LcuClient, generation: u64) {
+        let probed = {
+            let state = self.state.lock().expect("Mutex poisoned");
+            state.capabilities.as_ref().map(|(g, _)| *g) == Some(generation)
+        };
+        if probed {
+            return;
+        }
+
+        let capabilities = LcuCapabilities::probe(client).await;
+        let mut state = self.state.lock().expect("Mutex poisoned");
+        if state.generation == generation {
+            state.capabilities = Some((generation, capabilities));
+        }
+    }
+
+    fn discover(state: &mut ConnectionState) {
+        state.last_discovery = Some(Instant::now());
+        let client = match state.preferred_port {
+            Some(port) => Self::discover_port(port),
+            None => LcuClient::new(),
+        };
+        match client {
+            Ok(client) => {
+                info!("LCU connection discovered");
+                state.client = Some(client);
+                state.generation += 1;
+            }
+            Err(e) => debug!("LCU discovery failed: {}", e),
+        }
+    }
+
+    /// Client on the selected port, or the lockfile's client once it's gone
+    fn discover_port(port: u16) -> crate::Result<LcuClient> {
+        let selected = LcuConnection::discover_all()
+            .ok()
+            .and_then(|all| all.into_iter().find(|c| c.port == port));
+        match selected {
+            Some(connection) => LcuClient::from_connection(connection),
+            None => {
+                warn!("Selected LCU client on port {} not found, using lockfile", port);
+                LcuClient::new()
+            }
+        }
+    }
+}
+
+/// Whether enough time has passed since the last discovery attempt
+fn should_discover(last_discovery: Option<Instant>, now: Instant) -> bool {
+    match last_discovery {
+        Some(last) => now.duration_since(last) >= REDISCOVERY_INTERVAL,
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discovery_is_throttled() {
+        let now = Instant::now();
+        assert!(should_discover(None, now));
+        assert!(!should_discover(Some(now), now + Duration::from_millis(500)));
+        assert!(should_discover(Some(now), now + REDISCOVERY_INTERVAL));
+    }
+}
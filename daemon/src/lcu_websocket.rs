@@ -5,19 +5,37 @@
 //! - Queue pop notifications
 //! - Match found events
 //! - End of game stats
+//!
+//! Each socket also keeps its recent events in a replay buffer, including ones
+//! dropped because its consumer fell behind, so the consumer can catch up.
+//! Backlog and drop counters are per socket as well; `SocketMetrics` reads
+//! them for whoever aggregates buffer metrics.
+//!
+//! A dropped connection is re-established in the background with exponential
+//! backoff, re-sending the active subscriptions. Consumers keep calling
+//! `recv` and can follow the `ConnectionState` to refresh anything they may
+//! have missed; `recv` only returns `None` once the socket gives up.
+//!
+//! The reader runs as a supervised task under the name the caller passes
+//! (one per socket), so a panic while reading reconnects instead of silently
+//! ending the event stream.
 
 use crate::{AppError, Result};
-use crate::LcuConnection;
-use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use crate::{BufferMetrics, BufferSettings, LcuConnection, RingBuffer, TaskSupervisor};
+use futures_util::stream::{SplitSink, SplitStream};
 use futures_util::{SinkExt, StreamExt};
+use serde::de::IgnoredAny;
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
-use tokio::sync::mpsc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+use tokio::sync::{mpsc, watch};
 use tokio_tungstenite::{
-    connect_async_tls_with_config,
+    connect_async_tls_with_config, MaybeTlsStream, WebSocketStream,
     tungstenite::{
         client::IntoClientRequest,
-        http::header::{AUTHORIZATION, HeaderValue},
+        http::header::AUTHORIZATION,
         Message,
     },
     Connector,
@@ -36,8 +54,160 @@ pub struct LcuEvent {
     pub data: serde_json::Value,
 }
 
+/// Backlog of sockets opened from now on; 0 until buffer settings are applied
+static WS_BACKLOG: AtomicUsize = AtomicUsize::new(0);
+
+/// Queue at most `backlog` unread events on sockets opened from now on
+/// (`BufferSettings::max_ws_backlog`)
+pub fn set_ws_backlog(backlog: usize) {
+    WS_BACKLOG.store(backlog, Ordering::Relaxed);
+}
+
+/// Backlog limit for new sockets
+fn ws_backlog() -> usize {
+    match WS_BACKLOG.load(Ordering::Relaxed) {
+        0 => BufferSettings::default().max_ws_backlog,
+        backlog => backlog,
+    }
+}
+
+/// Backlog of one socket, readable by whoever aggregates buffer metrics
+/// while the socket itself is owned by its consumer
+#[derive(Debug, Clone)]
+pub struct SocketMetrics {
+    event_tx: mpsc::WeakSender<LcuEvent>,
+    capacity: usize,
+    dropped: Arc<AtomicU64>,
+}
+
+impl SocketMetrics {
+    /// Events queued (none once the socket is closed), the backlog limit and
+    /// the events this socket dropped
+    pub fn metrics(&self) -> BufferMetrics {
+        let len = self
+            .event_tx
+            .upgrade()
+            .map_or(0, |tx| tx.max_capacity() - tx.capacity());
+        BufferMetrics {
+            len,
+            capacity: self.capacity,
+            dropped: self.dropped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Combined backlog of several sockets: queued events, limits and drops add up
+pub fn combine_backlog_metrics(sockets: impl IntoIterator<Item = BufferMetrics>) -> BufferMetrics {
+    sockets.into_iter().fold(BufferMetrics::default(), |total, socket| BufferMetrics {
+        len: total.len + socket.len,
+        capacity: total.capacity + socket.capacity,
+        dropped: total.dropped + socket.dropped,
+    })
+}
+
+/// Maximum events kept for replay
+const REPLAY_CAPACITY: usize = 512;
+
+/// Events older than this are never replayed
+const REPLAY_RETENTION: Duration = Duration::from_secs(120);
+
+/// Recent events with the time they were received
+#[derive(Debug)]
+struct ReplayBuffer {
+    events: RingBuffer<(Instant, LcuEvent)>,
+}
+
+impl ReplayBuffer {
+    fn new() -> Self {
+        Self {
+            events: RingBuffer::new(REPLAY_CAPACITY),
+        }
+    }
+
+    fn push_at(&mut self, event: LcuEvent, now: Instant) {
+        self.events.push((now, event));
+    }
+
+    /// Events received within `window` before `now`, oldest first
+    fn since(&self, window: Duration, now: Instant) -> Vec<LcuEvent> {
+        let window = window.min(REPLAY_RETENTION);
+        self.events
+            .iter()
+            .filter(|(at, _)| now.saturating_duration_since(*at) <= window)
+            .map(|(_, event)| event.clone())
+            .collect()
+    }
+}
+
+/// WAMP opcodes for (un)subscribing to LCU events
+const OPCODE_SUBSCRIBE: u8 = 5;
+const OPCODE_UNSUBSCRIBE: u8 = 6;
+
+type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Write half of the socket, kept for runtime (un)subscribe
+type WsWriter = SplitSink<WsStream, Message>;
+
+type WsReader = SplitStream<WsStream>;
+
+/// First delay before reconnecting a dropped socket
+const RECONNECT_INITIAL_DELAY: Duration = Duration::from_secs(1);
+
+/// Longest delay between reconnect attempts
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// Failed reconnect attempts before the socket is closed (client gone)
+const MAX_RECONNECT_ATTEMPTS: u32 = 8;
+
+/// Delay before reconnect attempt `attempt` (counting from 0)
+fn reconnect_delay(attempt: u32) -> Duration {
+    RECONNECT_INITIAL_DELAY
+        .saturating_mul(1 << attempt.min(16))
+        .min(RECONNECT_MAX_DELAY)
+}
+
+/// Connection state of an `LcuWebSocket`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    Connected,
+    /// Connection dropped, retrying with backoff
+    Reconnecting,
+    /// Reconnecting gave up or the socket was dropped; no more events
+    Closed,
+}
+
+/// Where a socket gets its credentials on every (re)connect
+#[derive(Debug, Clone)]
+enum Endpoint {
+    Fixed(LcuConnection),
+    /// The selected client, else the lockfile (the port changes when the
+    /// client restarts)
+    Discover,
+}
+
+impl Endpoint {
+    fn connection(&self) -> Result<LcuConnection> {
+        match self {
+            Endpoint::Fixed(connection) => Ok(connection.clone()),
+            Endpoint::Discover => match crate::lcu_connection().client() {
+                Some(client) => Ok(client.connection().clone()),
+                None => LcuConnection::from_lockfile(),
+            },
+        }
+    }
+}
+
+/// Events the integration consumes; `connect` subscribes to just these
+pub const DEFAULT_SUBSCRIPTIONS: &[LcuSubscription] = &[
+    LcuSubscription::JsonApiEventPrefix(uris::GAMEFLOW_PHASE),
+    LcuSubscription::JsonApiEventPrefix(uris::GAMEFLOW_SESSION),
+    LcuSubscription::JsonApiEventPrefix(uris::MATCHMAKING_SEARCH),
+    LcuSubscription::JsonApiEventPrefix(uris::CHAMP_SELECT_SESSION),
+    LcuSubscription::JsonApiEventPrefix(uris::EOG_STATS),
+];
+
 /// Subscriptions for LCU WebSocket events
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LcuSubscription {
     /// All JSON API events
     JsonApiEvent,
@@ -50,199 +220,201 @@ impl LcuSubscription {
         match self {
             LcuSubscription::JsonApiEvent => "OnJsonApiEvent".to_string(),
             LcuSubscription::JsonApiEventPrefix(prefix) => {
-                format!("OnJsonApiEvent_{}", prefix.replace('/', "_"))
+                // "/lol-gameflow/v1/session" -> "OnJsonApiEvent_lol-gameflow_v1_session"
+                format!("OnJsonApiEvent_{}", prefix.trim_start_matches('/').replace('/', "_"))
             }
         }
     }
+
+    fn message(&self, opcode: u8) -> Message {
+        Message::Text(format!(r#"[{}, "{}"]"#, opcode, self.as_subscription_string()).into())
+    }
 }
 
 /// LCU WebSocket client for receiving real-time events
 pub struct LcuWebSocket {
     /// Channel to receive events
     event_rx: mpsc::Receiver<LcuEvent>,
-    /// Handle to the WebSocket task
-    _task_handle: tokio::task::JoinHandle<()>,
+    /// Sender side of the backlog, for `metrics_handle`
+    event_tx: mpsc::WeakSender<LcuEvent>,
+    /// Events dropped because the backlog was full
+    dropped: Arc<AtomicU64>,
+    /// Recent events received by this socket
+    replay: Arc<Mutex<ReplayBuffer>>,
+    /// Write half of the current connection; `None` while reconnecting
+    writer: Arc<tokio::sync::Mutex<Option<WsWriter>>>,
+    /// Active subscriptions, re-sent after a reconnect
+    subscriptions: Arc<Mutex<Vec<LcuSubscription>>>,
+    state_rx: watch::Receiver<ConnectionState>,
 }
 
 impl LcuWebSocket {
-    /// Connect to the LCU WebSocket and start receiving events.
+    /// Connect to the LCU WebSocket and start receiving the events in
+    /// `DEFAULT_SUBSCRIPTIONS`.
     /// Automatically discovers the LCU connection from the lockfile.
-    pub async fn connect() -> Result<Self> {
-        let connection = LcuConnection::from_lockfile()?;
-        Self::connect_with(connection).await
+    ///
+    /// The reader runs on `supervisor` as `task`.
+    pub async fn connect(supervisor: &TaskSupervisor, task: &'static str) -> Result<Self> {
+        Self::connect_with_subscriptions(supervisor, task, DEFAULT_SUBSCRIPTIONS).await
     }
 
-    /// Connect to the LCU WebSocket with provided credentials.
-    pub async fn connect_with(connection: LcuConnection) -> Result<Self> {
-        let url = format!("wss://127.0.0.1:{}", connection.port);
-        info!("Connecting to LCU WebSocket at {}", url);
-
-        // Build the request with auth header
-        let mut request = url.into_client_request()
-            .map_err(|e| AppError::Other(format!("Failed to create WebSocket request: {}", e)))?;
-
-        let auth_value = {
-            let credentials = format!("riot:{}", connection.auth_token);
-            format!("Basic {}", BASE64.encode(credentials.as_bytes()))
-        };
-        request.headers_mut().insert(
-            AUTHORIZATION,
-            HeaderValue::from_str(&auth_value)
-                .map_err(|e| AppError::Other(format!("Invalid auth header: {}", e)))?,
-        );
-
-        // Configure TLS to accept the LCU's self-signed certificate
-        let tls_config = Self::create_tls_config()?;
-        let connector = Connector::Rustls(Arc::new(tls_config));
+    /// Connect to the LCU WebSocket and receive only the listed events.
+    ///
+    /// Subscribing to everything (`LcuSubscription::JsonApiEvent`) floods the
+    /// socket during champ select, and every message has to be parsed.
+    pub async fn connect_with_subscriptions(
+        supervisor: &TaskSupervisor,
+        task: &'static str,
+        subscriptions: &[LcuSubscription],
+    ) -> Result<Self> {
+        let socket = SocketSpawn { supervisor, task };
+        Self::connect_filtered(socket, Endpoint::Discover, ws_backlog(), subscriptions).await
+    }
 
-        // Connect to the WebSocket
-        let (ws_stream, _response) = connect_async_tls_with_config(request, None, false, Some(connector))
-            .await
-            .map_err(|e| AppError::Other(format!("WebSocket connection failed: {}", e)))?;
+    /// Connect to the LCU WebSocket with provided credentials.
+    pub async fn connect_with(
+        supervisor: &TaskSupervisor,
+        task: &'static str,
+        connection: LcuConnection,
+    ) -> Result<Self> {
+        Self::connect_with_backlog(supervisor, task, connection, ws_backlog()).await
+    }
 
-        info!("LCU WebSocket connected");
+    /// Connect to the LCU WebSocket, queueing at most `backlog` unread events.
+    ///
+    /// When the consumer falls behind, new events are dropped (and counted)
+    /// instead of stalling the socket reader.
+    pub async fn connect_with_backlog(
+        supervisor: &TaskSupervisor,
+        task: &'static str,
+        connection: LcuConnection,
+        backlog: usize,
+    ) -> Result<Self> {
+        let socket = SocketSpawn { supervisor, task };
+        let endpoint = Endpoint::Fixed(connection);
+        Self::connect_filtered(socket, endpoint, backlog, DEFAULT_SUBSCRIPTIONS).await
+    }
 
-        let (mut write, mut read) = ws_stream.split();
+    async fn connect_filtered(
+        socket: SocketSpawn<'_>,
+        endpoint: Endpoint,
+        backlog: usize,
+        subscriptions: &[LcuSubscription],
+    ) -> Result<Self> {
+        let (writer, reader) = open_socket(&endpoint.connection()?, subscriptions).await?;
 
         // Create event channel
-        let (event_tx, event_rx) = mpsc::channel::<LcuEvent>(100);
-
-        // Subscribe to all JSON API events
-        let sub_msg = format!(r#"[5, "OnJsonApiEvent"]"#);
-        write.send(Message::Text(sub_msg.into()))
-            .await
-            .map_err(|e| AppError::Other(format!("Failed to subscribe: {}", e)))?;
-
-        info!("Subscribed to LCU events");
+        let (event_tx, event_rx) = mpsc::channel::<LcuEvent>(backlog.max(1));
+        let weak_tx = event_tx.downgrade();
+        let (state_tx, state_rx) = watch::channel(ConnectionState::Connected);
+        let dropped = Arc::new(AtomicU64::new(0));
+        let replay = Arc::new(Mutex::new(ReplayBuffer::new()));
+        let writer = Arc::new(tokio::sync::Mutex::new(Some(writer)));
+        let subscriptions = Arc::new(Mutex::new(subscriptions.to_vec()));
+
+        let task = SocketTask {
+            endpoint,
+            event_tx,
+            state_tx: Arc::new(state_tx),
+            dropped: Arc::clone(&dropped),
+            replay: Arc::clone(&replay),
+            writer: Arc::clone(&writer),
+            subscriptions: Arc::clone(&subscriptions),
+        };
 
-        // Spawn task to handle incoming messages
-        let task_handle = tokio::spawn(async move {
-            while let Some(msg_result) = read.next().await {
-                match msg_result {
-                    Ok(Message::Text(text)) => {
-                        if let Some(event) = Self::parse_event(&text) {
-                            if event_tx.send(event).await.is_err() {
-                                debug!("Event receiver dropped, stopping WebSocket");
-                                break;
-                            }
-                        }
-                    }
-                    Ok(Message::Close(_)) => {
-                        info!("LCU WebSocket closed by server");
-                        break;
-                    }
-                    Ok(_) => {} // Ignore ping/pong/binary
-                    Err(e) => {
-                        warn!("WebSocket error: {}", e);
-                        break;
-                    }
-                }
-            }
-            info!("LCU WebSocket task ended");
+        // A restart after a panic has no reader left and reconnects first
+        let reader = Arc::new(Mutex::new(Some(reader)));
+        socket.supervisor.spawn(socket.task, move || {
+            let reader = reader.lock().expect("Mutex poisoned").take();
+            task.clone().run(reader)
         });
 
         Ok(Self {
             event_rx,
-            _task_handle: task_handle,
+            event_tx: weak_tx,
+            dropped,
+            replay,
+            writer,
+            subscriptions,
+            state_rx,
         })
     }
 
-    /// Create TLS config that accepts the LCU's self-signed certificate
-    fn create_tls_config() -> Result<rustls::ClientConfig> {
-        use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
-        use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
-        use rustls::DigitallySignedStruct;
-
-        /// Custom certificate verifier that accepts any certificate.
-        /// This is necessary because the LCU uses a self-signed certificate.
-        #[derive(Debug)]
-        struct AcceptAnyCert;
-
-        impl ServerCertVerifier for AcceptAnyCert {
-            fn verify_server_cert(
-                &self,
-                _end_entity: &CertificateDer<'_>,
-                _intermediates: &[CertificateDer<'_>],
-                _server_name: &ServerName<'_>,
-                _ocsp_response: &[u8],
-                _now: UnixTime,
-            ) -> std::result::Result<ServerCertVerified, rustls::Error> {
-                Ok(ServerCertVerified::assertion())
-            }
-
-            fn verify_tls12_signature(
-                &self,
-                _message: &[u8],
-                _cert: &CertificateDer<'_>,
-                _dss: &DigitallySignedStruct,
-            ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
-                Ok(HandshakeSignatureValid::assertion())
-            }
-
-            fn verify_tls13_signature(
-                &self,
-                _message: &[u8],
-                _cert: &CertificateDer<'_>,
-                _dss: &DigitallySignedStruct,
-            ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
-                Ok(HandshakeSignatureValid::assertion())
-            }
-
-            fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
-                vec![
-                    rustls::SignatureScheme::RSA_PKCS1_SHA256,
-                    rustls::SignatureScheme::RSA_PKCS1_SHA384,
-                    rustls::SignatureScheme::RSA_PKCS1_SHA512,
-                    rustls::SignatureScheme::ECDSA_NISTP256_SHA256,
-                    rustls::SignatureScheme::ECDSA_NISTP384_SHA384,
-                    rustls::SignatureScheme::ECDSA_NISTP521_SHA512,
-                    rustls::SignatureScheme::RSA_PSS_SHA256,
-                    rustls::SignatureScheme::RSA_PSS_SHA384,
-                    rustls::SignatureScheme::RSA_PSS_SHA512,
-                    rustls::SignatureScheme::ED25519,
-                ]
-            }
-        }
-
-        let config = rustls::ClientConfig::builder()
-            .dangerous()
-            .with_custom_certificate_verifier(Arc::new(AcceptAnyCert))
-            .with_no_client_auth();
-
-        Ok(config)
-    }
-
     /// Parse a WebSocket message into an LcuEvent.
     /// LCU sends messages in the format: [opcode, event_name, data]
     /// Opcode 8 = event message
+    ///
+    /// Deserializes straight into `LcuEvent` instead of going through an
+    /// intermediate `Value`, so the payload is only allocated once.
     fn parse_event(text: &str) -> Option<LcuEvent> {
-        let parsed: serde_json::Value = serde_json::from_str(text).ok()?;
-        let arr = parsed.as_array()?;
+        let (opcode, _event_name, event): (u64, IgnoredAny, LcuEvent) =
+            serde_json::from_str(text).ok()?;
 
         // Event messages have opcode 8
-        let opcode = arr.first()?.as_u64()?;
         if opcode != 8 {
             return None;
         }
 
-        // Get the event name and data
-        let event_name = arr.get(1)?.as_str()?;
-        let data = arr.get(2)?;
+        Some(event)
+    }
+
+    /// Start receiving another event type. While reconnecting, it is sent
+    /// once the connection is back.
+    pub async fn subscribe(&mut self, subscription: LcuSubscription) -> Result<()> {
+        let mut writer = self.writer.lock().await;
+        if self.subscriptions().contains(&subscription) {
+            return Ok(());
+        }
+
+        if let Some(writer) = writer.as_mut() {
+            writer
+                .send(subscription.message(OPCODE_SUBSCRIBE))
+                .await
+                .map_err(|e| AppError::WebSocketError(format!("Failed to subscribe: {}", e)))?;
+        }
+        debug!("Subscribed to {}", subscription.as_subscription_string());
+        self.subscriptions.lock().expect("Mutex poisoned").push(subscription);
+        Ok(())
+    }
 
-        // Extract the event details
-        let event_data = data.get("data")?;
-        let uri = data.get("uri")?.as_str()?;
-        let event_type = data.get("eventType")?.as_str()?;
+    /// Stop receiving an event type
+    pub async fn unsubscribe(&mut self, subscription: LcuSubscription) -> Result<()> {
+        let mut writer = self.writer.lock().await;
+        if !self.subscriptions().contains(&subscription) {
+            return Ok(());
+        }
 
-        Some(LcuEvent {
-            uri: uri.to_string(),
-            event_type: event_type.to_string(),
-            data: event_data.clone(),
-        })
+        if let Some(writer) = writer.as_mut() {
+            writer
+                .send(subscription.message(OPCODE_UNSUBSCRIBE))
+                .await
+                .map_err(|e| AppError::WebSocketError(format!("Failed to unsubscribe: {}", e)))?;
+        }
+        debug!("Unsubscribed from {}", subscription.as_subscription_string());
+        self.subscriptions
+            .lock()
+            .expect("Mutex poisoned")
+            .retain(|s| *s != subscription);
+        Ok(())
+    }
+
+    /// Active subscriptions
+    pub fn subscriptions(&self) -> Vec<LcuSubscription> {
+        self.subscriptions.lock().expect("Mutex poisoned").clone()
+    }
+
+    /// Current connection state
+    pub fn state(&self) -> ConnectionState {
+        *self.state_rx.borrow()
+    }
+
+    /// Receiver notified on every connection state change
+    pub fn state_changes(&self) -> watch::Receiver<ConnectionState> {
+        self.state_rx.clone()
     }
 
     /// Receive the next event from the WebSocket.
-    /// Returns None if the connection is closed.
+    /// Returns None once the socket is closed for good (reconnecting gave up).
     pub async fn recv(&mut self) -> Option<LcuEvent> {
         self.event_rx.recv().await
     }
@@ -251,6 +423,196 @@ impl LcuWebSocket {
     pub fn try_recv(&mut self) -> Option<LcuEvent> {
         self.event_rx.try_recv().ok()
     }
+
+    /// Events this socket received within the last `window`, oldest first,
+    /// including ones dropped because the backlog was full. At most two
+    /// minutes are kept.
+    pub fn replay(&self, window: Duration) -> Vec<LcuEvent> {
+        self.replay
+            .lock()
+            .expect("Mutex poisoned")
+            .since(window, Instant::now())
+    }
+
+    /// Backlog fill level and number of events dropped because it was full
+    pub fn backlog_metrics(&self) -> BufferMetrics {
+        BufferMetrics {
+            len: self.event_rx.len(),
+            capacity: self.event_rx.max_capacity(),
+            dropped: self.dropped.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Handle reading this socket's backlog metrics after the socket has
+    /// moved into its consumer's task
+    pub fn metrics_handle(&self) -> SocketMetrics {
+        SocketMetrics {
+            event_tx: self.event_tx.clone(),
+            capacity: self.event_rx.max_capacity(),
+            dropped: Arc::clone(&self.dropped),
+        }
+    }
+}
+
+/// Connect, authenticate and subscribe
+async fn open_socket(
+    connection: &LcuConnection,
+    subscriptions: &[LcuSubscription],
+) -> Result<(WsWriter, WsReader)> {
+    let url = format!("wss://{}:{}", connection.host, connection.port);
+    info!("Connecting to LCU WebSocket at {}", url);
+
+    // Build the request with auth header
+    let mut request = url.into_client_request()
+        .map_err(|e| AppError::WebSocketError(format!("Failed to create request: {}", e)))?;
+
+    request
+        .headers_mut()
+        .insert(AUTHORIZATION, connection.auth_header());
+
+    // Verify the LCU certificate against Riot's root
+    let tls_config = crate::tls::lcu_tls_config()?;
+    let connector = Connector::Rustls(Arc::new(tls_config));
+
+    // Connect to the WebSocket
+    let (ws_stream, _response) = connect_async_tls_with_config(request, None, false, Some(connector))
+        .await
+        .map_err(|e| AppError::WebSocketError(format!("Connection failed: {}", e)))?;
+
+    info!("LCU WebSocket connected");
+
+    let (mut write, read) = ws_stream.split();
+    for subscription in subscriptions {
+        write
+            .send(subscription.message(OPCODE_SUBSCRIBE))
+            .await
+            .map_err(|e| AppError::WebSocketError(format!("Failed to subscribe: {}", e)))?;
+    }
+
+    info!("Subscribed to {} LCU event types", subscriptions.len());
+    Ok((write, read))
+}
+
+/// Supervisor and task name the socket reader is spawned under
+struct SocketSpawn<'a> {
+    supervisor: &'a TaskSupervisor,
+    task: &'static str,
+}
+
+/// Background task reading the socket and reconnecting it when it drops
+#[derive(Clone)]
+struct SocketTask {
+    endpoint: Endpoint,
+    event_tx: mpsc::Sender<LcuEvent>,
+    state_tx: Arc<watch::Sender<ConnectionState>>,
+    dropped: Arc<AtomicU64>,
+    replay: Arc<Mutex<ReplayBuffer>>,
+    writer: Arc<tokio::sync::Mutex<Option<WsWriter>>>,
+    subscriptions: Arc<Mutex<Vec<LcuSubscription>>>,
+}
+
+impl SocketTask {
+    async fn run(self, reader: Option<WsReader>) {
+        let mut reader = match reader {
+            Some(reader) => reader,
+            None => match self.reconnect().await {
+                Some(reader) => reader,
+                None => {
+                    let _ = self.state_tx.send(ConnectionState::Closed);
+                    return;
+                }
+            },
+        };
+
+        loop {
+            let receiver_gone = self.forward_events(&mut reader).await;
+            *self.writer.lock().await = None;
+            if receiver_gone {
+                break;
+            }
+
+            match self.reconnect().await {
+                Some(next) => reader = next,
+                None => break,
+            }
+        }
+
+        let _ = self.state_tx.send(ConnectionState::Closed);
+        info!("LCU WebSocket task ended");
+    }
+
+    /// Forward events until the connection drops; true if the receiver is gone
+    async fn forward_events(&self, reader: &mut WsReader) -> bool {
+        while let Some(msg_result) = reader.next().await {
+            match msg_result {
+                Ok(Message::Text(text)) => {
+                    if let Some(event) = LcuWebSocket::parse_event(&text) {
+                        self.replay
+                            .lock()
+                            .expect("Mutex poisoned")
+                            .push_at(event.clone(), Instant::now());
+                        match self.event_tx.try_send(event) {
+                            Ok(()) => {}
+                            Err(mpsc::error::TrySendError::Full(event)) => {
+                                let total = self.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+                                if total == 1 || total % 100 == 0 {
+                                    warn!("LCU event backlog full, dropped {} events (latest: {})", total, event.uri);
+                                }
+                            }
+                            Err(mpsc::error::TrySendError::Closed(_)) => {
+                                debug!("Event receiver dropped, stopping WebSocket");
+                                return true;
+                            }
+                        }
+                    }
+                }
+                Ok(Message::Close(_)) => {
+                    info!("LCU WebSocket closed by server");
+                    return false;
+                }
+                Ok(_) => {} // Ignore ping/pong/binary
+                Err(e) => {
+                    warn!("WebSocket error: {}", e);
+                    return false;
+                }
+            }
+        }
+        false
+    }
+
+    /// Reconnect with exponential backoff; `None` when giving up
+    async fn reconnect(&self) -> Option<WsReader> {
+        let _ = self.state_tx.send(ConnectionState::Reconnecting);
+
+        for attempt in 0..MAX_RECONNECT_ATTEMPTS {
+            tokio::select! {
+                _ = tokio::time::sleep(reconnect_delay(attempt)) => {}
+                _ = self.event_tx.closed() => return None,
+            }
+
+            // Held while connecting so a concurrent (un)subscribe isn't lost
+            let mut writer = self.writer.lock().await;
+            let subscriptions = self.subscriptions.lock().expect("Mutex poisoned").clone();
+            let opened = match self.endpoint.connection() {
+                Ok(connection) => open_socket(&connection, &subscriptions).await,
+                Err(e) => Err(e),
+            };
+            match opened {
+                Ok((write, read)) => {
+                    *writer = Some(write);
+                    let _ = self.state_tx.send(ConnectionState::Connected);
+                    info!("LCU WebSocket reconnected after {} attempt(s)", attempt + 1);
+                    return Some(read);
+                }
+                Err(e) => {
+                    debug!("LCU WebSocket reconnect attempt {} failed: {}", attempt + 1, e)
+                }
+            }
+        }
+
+        warn!("LCU WebSocket could not reconnect after {} attempts", MAX_RECONNECT_ATTEMPTS);
+        None
+    }
 }
 
 /// Common LCU event URIs
@@ -279,4 +641,79 @@ mod tests {
         assert_eq!(event.event_type, "Update");
         assert_eq!(event.data, serde_json::json!("InProgress"));
     }
+
+    #[test]
+    fn test_parse_event_ignores_other_opcodes() {
+        let msg = r#"[0,"session-id",1]"#;
+        assert!(LcuWebSocket::parse_event(msg).is_none());
+
+        let msg = r#"[5,"OnJsonApiEvent",{"data":null,"eventType":"Delete","uri":"/lol-gameflow/v1/session"}]"#;
+        assert!(LcuWebSocket::parse_event(msg).is_none());
+    }
+
+    #[test]
+    fn test_subscription_names() {
+        assert_eq!(LcuSubscription::JsonApiEvent.as_subscription_string(), "OnJsonApiEvent");
+        assert_eq!(
+            LcuSubscription::JsonApiEventPrefix(uris::GAMEFLOW_PHASE).as_subscription_string(),
+            "OnJsonApiEvent_lol-gameflow_v1_gameflow-phase"
+        );
+    }
+
+    #[test]
+    fn test_replay_returns_events_within_window() {
+        let event = |uri: &str| LcuEvent {
+            uri: uri.to_string(),
+            event_type: "Update".to_string(),
+            data: serde_json::Value::Null,
+        };
+        let start = Instant::now();
+        let mut buffer = ReplayBuffer::new();
+        buffer.push_at(event(uris::GAMEFLOW_PHASE), start);
+        buffer.push_at(event(uris::GAMEFLOW_SESSION), start + Duration::from_secs(20));
+        buffer.push_at(event(uris::CHAMP_SELECT_SESSION), start + Duration::from_secs(28));
+
+        let now = start + Duration::from_secs(30);
+        let replayed = buffer.since(Duration::from_secs(10), now);
+        assert_eq!(replayed.len(), 2);
+        assert_eq!(replayed[0].uri, uris::GAMEFLOW_SESSION);
+        assert_eq!(buffer.since(Duration::from_secs(60), now).len(), 3);
+    }
+
+    #[test]
+    fn test_socket_metrics_combine() {
+        let socket = |len, capacity, dropped| BufferMetrics { len, capacity, dropped };
+        let combined = combine_backlog_metrics([socket(3, 256, 0), socket(0, 64, 7)]);
+        assert_eq!(combined, socket(3, 320, 7));
+        assert_eq!(combine_backlog_metrics(Vec::new()), BufferMetrics::default());
+    }
+
+    #[test]
+    fn test_closed_socket_metrics_keep_drops() {
+        let (event_tx, event_rx) = mpsc::channel::<LcuEvent>(4);
+        let handle = SocketMetrics {
+            event_tx: event_tx.downgrade(),
+            capacity: event_rx.max_capacity(),
+            dropped: Arc::new(AtomicU64::new(2)),
+        };
+        event_tx
+            .try_send(LcuEvent {
+                uri: uris::GAMEFLOW_PHASE.to_string(),
+                event_type: "Update".to_string(),
+                data: serde_json::Value::Null,
+            })
+            .unwrap();
+        assert_eq!(handle.metrics().len, 1);
+
+        drop(event_tx);
+        let metrics = handle.metrics();
+        assert_eq!((metrics.len, metrics.capacity, metrics.dropped), (0, 4, 2));
+    }
+
+    #[test]
+    fn test_reconnect_delay_backs_off() {
+        let delays: Vec<u64> = (0..7).map(|attempt| reconnect_delay(attempt).as_secs()).collect();
+        assert_eq!(delays, vec![1, 2, 4, 8, 16, 30, 30]);
+        assert_eq!(reconnect_delay(u32::MAX), RECONNECT_MAX_DELAY);
+    }
 }
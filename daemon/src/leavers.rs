@@ -0,0 +1,167 @@
+//! AFK and leaver detection
+//!
+//! A game with a leaver says little about how the player did. Participants
+//! are flagged when the end-of-game stats show no kill participation and very
+//! little gold, or when their live stats stopped changing for good (they
+//! never reconnected). Flagged matches list the leavers, so those games can
+//! be left out of the stats.
+
+use std::collections::HashMap;
+
+use crate::{riot_ids_match, CreateMatch, LiveMatch, LivePlayer, PlayerStats};
+
+/// Shorter games are remakes or too short to judge
+const MIN_GAME_SECS: i32 = 600;
+
+/// Gold earned below this share of the teammates' average counts as very low
+const LOW_GOLD_SHARE: f64 = 0.4;
+
+/// Live stats unchanged for this long means the player is gone (longer than
+/// any death timer)
+const IDLE_SECS: f64 = 300.0;
+
+/// Whether end-of-game stats look like an AFK or a leaver
+pub fn is_eog_leaver(stats: &PlayerStats, teammates_avg_gold: f64, game_length: i32) -> bool {
+    game_length >= MIN_GAME_SECS
+        && stats.champions_killed + stats.assists == 0
+        && (stats.gold_earned as f64) < teammates_avg_gold * LOW_GOLD_SHARE
+}
+
+/// What changes while a participant is playing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct StatLine {
+    kills: i32,
+    deaths: i32,
+    assists: i32,
+    cs: i32,
+    level: i32,
+    item_gold: i32,
+}
+
+impl From<&LivePlayer> for StatLine {
+    fn from(p: &LivePlayer) -> Self {
+        Self {
+            kills: p.kills,
+            deaths: p.deaths,
+            assists: p.assists,
+            cs: p.cs,
+            level: p.level,
+            item_gold: p.item_gold,
+        }
+    }
+}
+
+/// Participants whose live stats stopped changing
+#[derive(Debug, Default)]
+pub struct LeaverTracker {
+    /// Last stat line of each participant and the game time it changed
+    players: HashMap<String, (StatLine, f64)>,
+    game_time: f64,
+}
+
+impl LeaverTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn observe(&mut self, live: &LiveMatch) {
+        let now = live.game_time_secs;
+        self.game_time = now;
+        for player in &live.participants {
+            let line = StatLine::from(player);
+            let entry = self
+                .players
+                .entry(player.summoner_name.clone())
+                .or_insert((line, now));
+            if entry.0 != line {
+                *entry = (line, now);
+            }
+        }
+    }
+
+    /// Participants idle up to the last observation
+    pub fn idle_players(&self) -> Vec<String> {
+        let mut idle: Vec<String> = self
+            .players
+            .iter()
+            .filter(|(_, (_, changed_at))| self.game_time - changed_at >= IDLE_SECS)
+            .map(|(name, _)| name.clone())
+            .collect();
+        idle.sort();
+        idle
+    }
+}
+
+/// Flag `names` (live data) as leavers in a finished match, on top of those
+/// found in the end-of-game stats
+pub fn mark_leavers(game: &mut CreateMatch, names: &[String]) {
+    for participant in &mut game.participants {
+        if names
+            .iter()
+            .any(|n| riot_ids_match(n, &participant.summoner_name))
+        {
+            participant.left_game = true;
+        }
+    }
+    game.leavers = game
+        .participants
+        .iter()
+        .filter(|p| p.left_game)
+        .map(|p| p.summoner_name.clone())
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(name: &str, cs: i32) -> LivePlayer {
+        serde_json::from_value(serde_json::json!({
+            "summonerName": name, "champion": "Ahri", "team": "blue", "kills": 0,
+            "deaths": 0, "assists": 0, "cs": cs, "level": 1, "isDead": false
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_idle_players_are_leavers() {
+        let mut live: LiveMatch = serde_json::from_value(serde_json::json!({
+            "summonerName": "Me", "champion": "Ahri", "level": 1, "kills": 0, "deaths": 0,
+            "assists": 0, "cs": 0, "currentGold": 500.0, "gameTimeSecs": 60.0,
+            "gameMode": "CLASSIC", "team": "blue", "items": [], "trinket": null,
+            "spell1": null, "spell2": null, "runes": null, "participants": [], "isDead": false
+        }))
+        .unwrap();
+
+        let mut tracker = LeaverTracker::new();
+        for (time, cs) in [(60.0, 0), (200.0, 20), (400.0, 40)] {
+            live.game_time_secs = time;
+            live.participants = vec![player("Me", cs), player("Afk#EUW", 0)];
+            tracker.observe(&live);
+        }
+        assert_eq!(tracker.idle_players(), vec!["Afk#EUW".to_string()]);
+
+        let mut game: CreateMatch = serde_json::from_value(serde_json::json!({
+            "gameId": 1, "summonerName": "Me", "champion": "Ahri", "championLevel": 16,
+            "result": "win", "kills": 1, "deaths": 0, "assists": 4, "cs": 200,
+            "csPerMin": 7.0, "visionScore": 20, "killParticipation": 50, "damageDealt": 20000,
+            "gameMode": "CLASSIC", "playedAt": chrono::Utc::now(), "durationSecs": 1800,
+            "lpChange": null, "rank": null, "summonerSpell1": "Flash", "summonerSpell2": "Ignite",
+            "keystoneRune": "Electrocute", "secondaryTree": "Sorcery", "items": [],
+            "trinket": null, "badges": [],
+            "participants": [
+                { "summonerName": "Me", "champion": "Ahri", "team": "blue" },
+                { "summonerName": "Afk#EUW", "champion": "Zed", "team": "blue" },
+            ]
+        }))
+        .unwrap();
+        mark_leavers(&mut game, &tracker.idle_players());
+        assert_eq!(game.leavers, vec!["Afk#EUW".to_string()]);
+        assert!(game.participants[1].left_game);
+        assert!(!game.participants[0].left_game);
+    }
+}
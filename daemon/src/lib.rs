@@ -31,33 +31,114 @@
 pub use integration::LeagueIntegration;
 
 // Public modules (types that daemon actors may need)
+pub use assets::*;
+pub use buffers::*;
+pub use champ_select::*;
+pub use chunked::*;
+pub use clock::*;
+pub use connection_history::*;
+pub use ddragon::*;
+pub use deaths::*;
+pub use debug_socket::*;
 pub use error::*;
+pub use event_catalog::*;
+pub use event_discovery::*;
+pub use event_log::*;
 pub use events::*;
+pub use fps::*;
 pub use gameflow_monitor::*;
+pub use import::*;
+pub use latency::*;
+pub use leavers::*;
 pub use lcu::*;
+pub use lcu_capabilities::*;
+pub use lcu_connection::*;
 pub use lcu_websocket::*;
 pub use live_client::*;
 pub use live_match_service::*;
+pub use momentum::*;
+pub use notifications::*;
+pub use objectives::*;
 pub use poller::*;
+pub use polling::*;
+pub use rank::*;
+pub use rate_limit::set_lcu_rate_limit;
+pub use raw_matches::*;
+pub use remote::set_remote_host;
+pub use replay::*;
+pub use session_controller::*;
+pub use signature::*;
+pub use snapshots::*;
+pub use source_diff::*;
 pub use state::*;
+pub use stats_store::*;
+pub use storage::*;
+pub use supervisor::*;
+pub use team_comp::*;
+pub use tft::*;
+pub use tls::set_accept_any_certificate;
 pub use triggers::*;
 pub use types::*;
+pub use vision::*;
 
+mod asset_cache;
+mod assets;
+mod buffers;
+mod champ_select;
+mod chunked;
+mod clock;
+mod connection_history;
+mod ddragon;
+mod deaths;
+mod debug_socket;
 mod error;
+mod event_catalog;
+mod event_discovery;
+mod event_log;
 mod events;
+mod fps;
 mod game_finalizer;
 mod gameflow_monitor;
+mod import;
 mod integration;
+mod latency;
+mod leavers;
 mod lcu;
+mod lcu_capabilities;
+mod lcu_connection;
 mod lcu_websocket;
 mod live_client;
 mod live_match_service;
+mod momentum;
+mod notifications;
+mod objectives;
+pub mod panic_report;
 mod poller;
+mod polling;
+mod rank;
+mod rate_limit;
+mod raw_matches;
+mod remote;
+mod replay;
+mod session_controller;
+mod signature;
+mod snapshots;
+mod source_diff;
 pub mod protocol;
 pub mod sample_data;
 mod state;
+mod stats_store;
+mod storage;
+pub mod strings;
+mod supervisor;
+mod team_comp;
+#[cfg(test)]
+mod test_util;
+mod tft;
+mod tls;
 mod triggers;
 mod types;
+mod vision;
 
 /// League of Legends game ID (matches shared/games.json)
 pub const LEAGUE_GAME_ID: i32 = 1;
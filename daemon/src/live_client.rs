@@ -1,8 +1,18 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use tracing::debug;
 
-use crate::{LeagueError, Result};
+use crate::{
+    riot_ids_match, AbilityRanks, LeagueError, LiveItem, LiveMatch, LivePlayer, LiveRunes,
+    LiveSpell, Result, Team,
+};
 
-const LIVE_CLIENT_URL: &str = "https://127.0.0.1:2999";
+/// Live Client Data API port
+const LIVE_CLIENT_PORT: u16 = 2999;
+
+/// Live Client Data API base URL (on the remote gaming PC if configured)
+fn live_client_url() -> String {
+    format!("https://{}:{}", crate::remote::api_host(), LIVE_CLIENT_PORT)
+}
 
 pub struct LiveClientApi {
     client: reqwest::Client,
@@ -11,53 +21,119 @@ pub struct LiveClientApi {
 impl LiveClientApi {
     pub fn new() -> Result<Self> {
         let client = reqwest::Client::builder()
-            .danger_accept_invalid_certs(true)
+            .use_preconfigured_tls(crate::tls::lcu_tls_config()?)
             .timeout(std::time::Duration::from_secs(2))
             .build()?;
 
         Ok(Self { client })
     }
 
+    /// All game data, or as much of it as the game serves.
+    ///
+    /// While the game loads (or on a slow machine) `allgamedata` can answer
+    /// without some sections, or with an error body. Missing sections are
+    /// asked for at their own endpoints; only when none of them answer is
+    /// the poll an error.
     pub async fn get_all_game_data(&self) -> Result<GameData> {
-        let url = format!("{}/liveclientdata/allgamedata", LIVE_CLIENT_URL);
-        let response = self.client.get(&url).send().await?;
-        let data = response.json().await?;
+        let url = format!("{}/liveclientdata/allgamedata", live_client_url());
+        let response = self.client.get(&url).send().await.map_err(LeagueError::live_client)?;
+        let mut data: GameData = response.json().await.unwrap_or_default();
+        if data.missing_sections().is_empty() {
+            return Ok(data);
+        }
+
+        if data.active_player().is_none() {
+            data.active_player = self.get_active_player().await.ok();
+        }
+        if data.all_players.is_empty() {
+            data.all_players = self.get_player_list().await.unwrap_or_default();
+        }
+        if data.events.is_none() {
+            data.events = self.get_events().await.ok();
+        }
+        if data.game_data.is_none() {
+            data.game_data = self.get_game_stats().await.ok();
+        }
+
+        let missing = data.missing_sections();
+        if missing.len() == GameData::SECTIONS {
+            return Err(LeagueError::LiveClientUnavailable(
+                "no game data available yet".to_string(),
+            ));
+        }
+        if !missing.is_empty() {
+            debug!("Partial game data, missing: {}", missing.join(", "));
+        }
         Ok(data)
     }
 
     pub async fn get_active_player(&self) -> Result<ActivePlayer> {
-        let url = format!("{}/liveclientdata/activeplayer", LIVE_CLIENT_URL);
-        let response = self.client.get(&url).send().await?;
+        let url = format!("{}/liveclientdata/activeplayer", live_client_url());
+        let response = self.client.get(&url).send().await.map_err(LeagueError::live_client)?;
+        let data = response.json().await?;
+        Ok(data)
+    }
+
+    pub async fn get_active_player_name(&self) -> Result<String> {
+        let url = format!("{}/liveclientdata/activeplayername", live_client_url());
+        let response = self.client.get(&url).send().await.map_err(LeagueError::live_client)?;
+        let data = response.json().await?;
+        Ok(data)
+    }
+
+    pub async fn get_player_list(&self) -> Result<Vec<Player>> {
+        let url = format!("{}/liveclientdata/playerlist", live_client_url());
+        let response = self.client.get(&url).send().await.map_err(LeagueError::live_client)?;
+        let data = response.json().await?;
+        Ok(data)
+    }
+
+    pub async fn get_player_scores(&self, riot_id: &str) -> Result<PlayerScores> {
+        let url = format!("{}/liveclientdata/playerscores", live_client_url());
+        let response = self
+            .client
+            .get(&url)
+            .query(&[("riotId", riot_id)])
+            .send()
+            .await
+            .map_err(LeagueError::live_client)?;
+        let data = response.json().await?;
+        Ok(data)
+    }
+
+    pub async fn get_game_stats(&self) -> Result<GameInfo> {
+        let url = format!("{}/liveclientdata/gamestats", live_client_url());
+        let response = self.client.get(&url).send().await.map_err(LeagueError::live_client)?;
         let data = response.json().await?;
         Ok(data)
     }
 
     pub async fn get_events(&self) -> Result<GameEvents> {
-        let url = format!("{}/liveclientdata/eventdata", LIVE_CLIENT_URL);
-        let response = self.client.get(&url).send().await?;
+        let url = format!("{}/liveclientdata/eventdata", live_client_url());
+        let response = self.client.get(&url).send().await.map_err(LeagueError::live_client)?;
         let data = response.json().await?;
         Ok(data)
     }
 
     /// Get events with both parsed and raw JSON data (for runtime discovery)
     pub async fn get_events_raw(&self) -> Result<(Vec<GameEvent>, Vec<serde_json::Value>)> {
-        let url = format!("{}/liveclientdata/eventdata", LIVE_CLIENT_URL);
-        let response = self.client.get(&url).send().await?;
-        let json: serde_json::Value = response.json().await?;
+        let url = format!("{}/liveclientdata/eventdata", live_client_url());
+        let response = self.client.get(&url).send().await.map_err(LeagueError::live_client)?;
+        let mut json: serde_json::Value = response.json().await?;
 
-        let events_array = json
-            .get("Events")
-            .and_then(|v| v.as_array())
-            .ok_or_else(|| LeagueError::ParseError("Missing Events array".to_string()))?;
+        let events_array = match json.get_mut("Events").map(serde_json::Value::take) {
+            Some(serde_json::Value::Array(events)) => events,
+            _ => return Err(LeagueError::ParseError("Missing Events array".to_string())),
+        };
 
-        let mut events = Vec::new();
-        let mut raw_events = Vec::new();
+        let mut events = Vec::with_capacity(events_array.len());
+        let mut raw_events = Vec::with_capacity(events_array.len());
 
         for raw in events_array {
-            // Parse structured event
-            if let Ok(event) = serde_json::from_value::<GameEvent>(raw.clone()) {
+            // Parse structured event from a borrow, then keep the raw value by move
+            if let Ok(event) = GameEvent::deserialize(&raw) {
                 events.push(event);
-                raw_events.push(raw.clone());
+                raw_events.push(raw);
             }
         }
 
@@ -67,6 +143,52 @@ impl LiveClientApi {
     pub async fn is_game_active(&self) -> bool {
         self.get_active_player().await.is_ok()
     }
+
+    /// Whether the Live Client API is reachable (the game is loaded)
+    pub async fn is_reachable(&self) -> bool {
+        self.get_game_stats().await.is_ok()
+    }
+}
+
+/// Check for a running game process ("League of Legends.exe").
+///
+/// Catches games that are still loading, or whose Live Client API isn't up.
+pub fn is_game_process_running() -> bool {
+    // The game runs on another machine
+    if crate::remote::is_remote() {
+        return false;
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+
+        // CREATE_NO_WINDOW (0x08000000) prevents a console window from appearing
+        std::process::Command::new("tasklist")
+            .args(["/FI", "IMAGENAME eq League of Legends.exe", "/NH"])
+            .creation_flags(0x08000000)
+            .output()
+            .map(|output| String::from_utf8_lossy(&output.stdout).contains("League of Legends"))
+            .unwrap_or(false)
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        std::process::Command::new("ps")
+            .args(["x", "-o", "comm"])
+            .output()
+            .map(|output| {
+                String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .any(|line| line.ends_with("League of Legends"))
+            })
+            .unwrap_or(false)
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        false
+    }
 }
 
 impl Default for LiveClientApi {
@@ -75,13 +197,124 @@ impl Default for LiveClientApi {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+/// Live Client API game data. A section that is missing or doesn't parse
+/// (e.g. while the game is loading) is left empty rather than failing the
+/// rest.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct GameData {
-    pub active_player: ActivePlayer,
+    #[serde(deserialize_with = "lenient")]
+    pub active_player: Option<ActivePlayer>,
+    #[serde(deserialize_with = "lenient")]
     pub all_players: Vec<Player>,
-    pub events: GameEvents,
-    pub game_data: GameInfo,
+    #[serde(deserialize_with = "lenient")]
+    pub events: Option<GameEvents>,
+    #[serde(deserialize_with = "lenient")]
+    pub game_data: Option<GameInfo>,
+}
+
+/// Parse a value, falling back to the default if it doesn't fit
+fn lenient<'de, D, T>(deserializer: D) -> std::result::Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + Default,
+{
+    let value = serde_json::Value::deserialize(deserializer)?;
+    Ok(T::deserialize(value).unwrap_or_default())
+}
+
+impl GameData {
+    /// Number of sections in the game data
+    const SECTIONS: usize = 4;
+
+    /// The active player, if the game reported one (spectators have none)
+    pub fn active_player(&self) -> Option<&ActivePlayer> {
+        self.active_player
+            .as_ref()
+            .filter(|p| !p.summoner_name.is_empty())
+    }
+
+    /// Sections that are missing or didn't parse
+    pub fn missing_sections(&self) -> Vec<&'static str> {
+        let sections = [
+            ("activePlayer", self.active_player().is_some()),
+            ("allPlayers", !self.all_players.is_empty()),
+            ("events", self.events.is_some()),
+            ("gameData", self.game_data.is_some()),
+        ];
+        sections
+            .into_iter()
+            .filter(|(_, present)| !present)
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    /// Creates a LiveMatch from Live Client API GameData (needs the active
+    /// player, their scoreboard entry and the game clock)
+    pub fn to_live_match(&self) -> Option<LiveMatch> {
+        let active_player = self.active_player()?;
+        let game_info = self.game_data.as_ref()?;
+
+        // Find the active player in the all_players list to get their team and scores
+        let player = self
+            .all_players
+            .iter()
+            .find(|p| riot_ids_match(&p.summoner_name, &active_player.summoner_name))?;
+
+        let team = player.live_team();
+        let (items, trinket) = player.live_items();
+        let (spell1, spell2) = player.live_spells();
+        let runes = player.live_runes();
+
+        let participants: Vec<LivePlayer> = self
+            .all_players
+            .iter()
+            .map(|p| {
+                let (items, trinket) = p.live_items();
+                let (spell1, spell2) = p.live_spells();
+                LivePlayer {
+                    summoner_name: p.summoner_name.clone(),
+                    champion: p.champion_name.clone(),
+                    team: p.live_team(),
+                    kills: p.scores.kills,
+                    deaths: p.scores.deaths,
+                    assists: p.scores.assists,
+                    cs: p.scores.creep_score,
+                    level: p.level,
+                    is_dead: p.is_dead,
+                    item_gold: p.items.iter().map(|i| i.price * i.count.max(1)).sum(),
+                    position: p.position.clone(),
+                    items,
+                    trinket,
+                    spell1,
+                    spell2,
+                    runes: p.live_runes(),
+                }
+            })
+            .collect();
+
+        Some(LiveMatch {
+            summoner_name: active_player.summoner_name.clone(),
+            champion: player.champion_name.clone(),
+            level: active_player.level,
+            kills: player.scores.kills,
+            deaths: player.scores.deaths,
+            assists: player.scores.assists,
+            cs: player.scores.creep_score,
+            current_gold: active_player.current_gold,
+            game_time_secs: game_info.game_time,
+            game_mode: game_info.game_mode.clone(),
+            team,
+            items,
+            trinket,
+            spell1,
+            spell2,
+            runes,
+            ability_ranks: active_player.abilities.ranks(),
+            participants,
+            is_dead: player.is_dead,
+        })
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -97,6 +330,42 @@ pub struct ActivePlayer {
     pub champion_stats: ChampionStats,
     #[serde(default)]
     pub full_runes: Option<FullRunes>,
+    #[serde(default)]
+    pub abilities: Abilities,
+}
+
+/// Active player's abilities, keyed by slot
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct Abilities {
+    #[serde(rename = "Q")]
+    pub q: Ability,
+    #[serde(rename = "W")]
+    pub w: Ability,
+    #[serde(rename = "E")]
+    pub e: Ability,
+    #[serde(rename = "R")]
+    pub r: Ability,
+}
+
+impl Abilities {
+    pub fn ranks(&self) -> AbilityRanks {
+        AbilityRanks {
+            q: self.q.ability_level,
+            w: self.w.ability_level,
+            e: self.e.ability_level,
+            r: self.r.ability_level,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Ability {
+    #[serde(default)]
+    pub ability_level: i32,
+    #[serde(default)]
+    pub display_name: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -134,6 +403,8 @@ pub struct ChampionStats {
     pub health_regen_rate: f64,
     #[serde(default)]
     pub max_health: f64,
+    #[serde(default)]
+    pub current_health: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -157,6 +428,62 @@ pub struct Player {
     pub summoner_spells: Option<SummonerSpells>,
     #[serde(default)]
     pub runes: Option<PlayerRunes>,
+    #[serde(default)]
+    pub position: String,
+}
+
+impl Player {
+    fn live_team(&self) -> Team {
+        match self.team.to_lowercase().as_str() {
+            "chaos" | "red" => Team::Red,
+            _ => Team::Blue,
+        }
+    }
+
+    /// Items in slots 0-5 and the trinket in slot 6
+    fn live_items(&self) -> (Vec<LiveItem>, Option<LiveItem>) {
+        let mut items = Vec::new();
+        let mut trinket = None;
+        for item in &self.items {
+            let live_item = LiveItem {
+                item_id: item.item_id,
+                name: item.display_name.clone(),
+                slot: item.slot,
+                count: item.count.max(1),
+            };
+            if item.slot == 6 {
+                trinket = Some(live_item);
+            } else {
+                items.push(live_item);
+            }
+        }
+        (items, trinket)
+    }
+
+    fn live_spells(&self) -> (Option<LiveSpell>, Option<LiveSpell>) {
+        match self.summoner_spells {
+            Some(ref spells) => (
+                Some(LiveSpell {
+                    name: spells.summoner_spell_one.display_name.clone(),
+                }),
+                Some(LiveSpell {
+                    name: spells.summoner_spell_two.display_name.clone(),
+                }),
+            ),
+            None => (None, None),
+        }
+    }
+
+    fn live_runes(&self) -> Option<LiveRunes> {
+        self.runes.as_ref().map(|r| LiveRunes {
+            keystone_id: r.keystone.id,
+            keystone_name: r.keystone.display_name.clone(),
+            primary_tree_id: r.primary_rune_tree.id,
+            primary_tree_name: r.primary_rune_tree.display_name.clone(),
+            secondary_tree_id: r.secondary_rune_tree.id,
+            secondary_tree_name: r.secondary_rune_tree.display_name.clone(),
+        })
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -170,6 +497,9 @@ pub struct Item {
     pub slot: i32,
     #[serde(default)]
     pub count: i32,
+    /// Item cost in gold
+    #[serde(default)]
+    pub price: i32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -212,13 +542,25 @@ pub struct PlayerScores {
     pub creep_score: i32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GameEvents {
-    #[serde(rename = "Events")]
+    /// Events that parse (one malformed event doesn't drop the rest)
+    #[serde(rename = "Events", deserialize_with = "parseable_events")]
     pub events: Vec<GameEvent>,
 }
 
+fn parseable_events<'de, D>(deserializer: D) -> std::result::Result<Vec<GameEvent>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let events = Vec::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(events
+        .iter()
+        .filter_map(|raw| GameEvent::deserialize(raw).ok())
+        .collect())
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GameEvent {
@@ -234,6 +576,18 @@ pub struct GameEvent {
     pub victim_name: Option<String>,
     #[serde(rename = "Assisters", default)]
     pub assisters: Vec<String>,
+    /// Size of a multikill (2 = double kill)
+    #[serde(rename = "KillStreak", default)]
+    pub kill_streak: Option<u32>,
+    /// Element of a `DragonKill` ("Fire", ..., "Elder")
+    #[serde(rename = "DragonType", default)]
+    pub dragon_type: Option<String>,
+    /// "True" when an objective was stolen
+    #[serde(rename = "Stolen", default)]
+    pub stolen: Option<String>,
+    /// Turret name of a `TurretKilled` (e.g. "Turret_T1_L_03_A")
+    #[serde(rename = "TurretKilled", default)]
+    pub turret_killed: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -241,7 +595,82 @@ pub struct GameEvent {
 pub struct GameInfo {
     pub game_mode: String,
     pub game_time: f64,
+    #[serde(default)]
     pub map_name: String,
+    #[serde(default)]
     pub map_number: i32,
+    #[serde(default)]
     pub map_terrain: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_participants_carry_their_builds() {
+        let data: GameData = serde_json::from_value(json!({
+            "activePlayer": { "summonerName": "Me#EUW", "level": 9, "currentGold": 300.0 },
+            "allPlayers": [
+                {
+                    "summonerName": "Me#EUW", "championName": "Ahri", "team": "ORDER",
+                    "items": [{ "itemID": 3165, "displayName": "Morellonomicon", "slot": 0, "count": 1 }]
+                },
+                {
+                    "summonerName": "Them#EUW", "championName": "Zed", "team": "CHAOS",
+                    "items": [
+                        { "itemID": 3142, "displayName": "Youmuu's Ghostblade", "slot": 0, "count": 1 },
+                        { "itemID": 3364, "displayName": "Oracle Lens", "slot": 6, "count": 1 }
+                    ],
+                    "summonerSpells": {
+                        "summonerSpellOne": { "displayName": "Flash" },
+                        "summonerSpellTwo": { "displayName": "Ignite" }
+                    },
+                    "runes": {
+                        "keystone": { "id": 8112, "displayName": "Electrocute" },
+                        "primaryRuneTree": { "id": 8100, "displayName": "Domination" },
+                        "secondaryRuneTree": { "id": 8300, "displayName": "Inspiration" }
+                    }
+                }
+            ],
+            "events": { "Events": [] },
+            "gameData": {
+                "gameMode": "CLASSIC", "gameTime": 600.0, "mapName": "Map11",
+                "mapNumber": 11, "mapTerrain": "Default"
+            }
+        }))
+        .unwrap();
+
+        let live = data.to_live_match().unwrap();
+        assert_eq!(live.items[0].item_id, 3165);
+
+        let zed = &live.participants[1];
+        assert_eq!(zed.team, Team::Red);
+        assert_eq!(zed.items.len(), 1);
+        assert_eq!(zed.items[0].name, "Youmuu's Ghostblade");
+        assert_eq!(zed.trinket.as_ref().unwrap().item_id, 3364);
+        assert_eq!(zed.spell2.as_ref().unwrap().name, "Ignite");
+        assert_eq!(zed.runes.as_ref().unwrap().keystone_name, "Electrocute");
+    }
+
+    #[test]
+    fn test_partial_game_data_keeps_what_parsed() {
+        let data: GameData = serde_json::from_value(json!({
+            "activePlayer": { "error": "Spectator mode doesn't currently support this feature" },
+            "allPlayers": [{ "summonerName": "Them#EUW", "championName": "Zed", "team": "CHAOS" }],
+            "events": { "Events": [
+                { "EventID": 0, "EventName": "GameStart", "EventTime": 0.0 },
+                { "EventID": 1, "EventName": "ChampionKill" }
+            ] },
+            "gameData": null
+        }))
+        .unwrap();
+
+        assert!(data.active_player().is_none());
+        assert_eq!(data.all_players[0].champion_name, "Zed");
+        assert_eq!(data.events.as_ref().unwrap().events.len(), 1);
+        assert_eq!(data.missing_sections(), vec!["activePlayer", "gameData"]);
+        assert!(data.to_live_match().is_none());
+    }
+}
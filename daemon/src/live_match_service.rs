@@ -1,10 +1,8 @@
-use std::time::Duration;
-use tokio::sync::mpsc;
-use tokio::time::interval;
+use tokio::sync::{broadcast, mpsc};
 use tracing::{debug, error, info, warn};
 
 use crate::Result;
-use crate::LiveMatch;
+use crate::{polling_config, DdragonResolver, LiveMatch, TaskSupervisor};
 
 use super::LiveClientApi;
 
@@ -19,16 +17,31 @@ pub enum LiveMatchEvent {
 
 /// Service that streams live match data during active games
 pub struct LiveMatchService {
-    shutdown_tx: Option<mpsc::Sender<()>>,
+    shutdown_tx: Option<broadcast::Sender<()>>,
+    supervisor: TaskSupervisor,
+    resolver: DdragonResolver,
 }
 
+/// Supervisor task name for the polling loop
+const LIVE_MATCH_TASK: &str = "live_match_service";
+
 impl LiveMatchService {
-    pub fn new() -> Self {
+    /// Service whose polling loop runs on `supervisor`, so its restarts
+    /// show up in the integration's task health
+    pub fn new(supervisor: TaskSupervisor) -> Self {
         Self {
             shutdown_tx: None,
+            supervisor,
+            resolver: DdragonResolver::new(),
         }
     }
 
+    /// Resolve names the Live Client API leaves out through Data Dragon
+    pub fn with_resolver(mut self, resolver: DdragonResolver) -> Self {
+        self.resolver = resolver;
+        self
+    }
+
     /// Start streaming live match data
     pub async fn start(&mut self, event_tx: mpsc::Sender<LiveMatchEvent>) -> Result<()> {
         if self.shutdown_tx.is_some() {
@@ -38,56 +51,75 @@ impl LiveMatchService {
 
         info!("Starting LiveMatchService");
 
-        let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
-        self.shutdown_tx = Some(shutdown_tx);
+        let (shutdown_tx, _) = broadcast::channel::<()>(1);
+        self.shutdown_tx = Some(shutdown_tx.clone());
 
-        tokio::spawn(async move {
-            let api = match LiveClientApi::new() {
-                Ok(api) => api,
-                Err(e) => {
-                    error!("Failed to create LiveClientApi: {}", e);
-                    return;
-                }
-            };
+        let resolver = self.resolver.clone();
+        self.supervisor.spawn(LIVE_MATCH_TASK, move || {
+            Self::run(event_tx.clone(), shutdown_tx.subscribe(), resolver.clone())
+        });
 
-            let mut poll_interval = interval(Duration::from_secs(1));
+        Ok(())
+    }
 
-            loop {
-                tokio::select! {
-                    _ = shutdown_rx.recv() => {
-                        info!("LiveMatchService shutting down");
-                        break;
-                    }
-                    _ = poll_interval.tick() => {
-                        match Self::poll_and_emit(&api, &event_tx).await {
-                            Ok(()) => {}
-                            Err(e) => {
-                                debug!("Failed to poll live match data: {}", e);
-                                // Don't break on error - game might still be loading
-                            }
+    /// Polling loop, restarted by the supervisor if it panics
+    async fn run(
+        event_tx: mpsc::Sender<LiveMatchEvent>,
+        mut shutdown_rx: broadcast::Receiver<()>,
+        resolver: DdragonResolver,
+    ) {
+        let api = match LiveClientApi::new() {
+            Ok(api) => api,
+            Err(e) => {
+                error!("Failed to create LiveClientApi: {}", e);
+                return;
+            }
+        };
+
+        // First poll right away; slows to the idle interval while no game answers
+        let mut wait = std::time::Duration::ZERO;
+        let mut active = true;
+        loop {
+            tokio::select! {
+                _ = shutdown_rx.recv() => {
+                    info!("LiveMatchService shutting down");
+                    break;
+                }
+                _ = tokio::time::sleep(wait) => {
+                    match Self::poll_and_emit(&api, &event_tx, &resolver).await {
+                        Ok(()) => active = true,
+                        Err(e) => {
+                            debug!("Failed to poll live match data: {}", e);
+                            // Don't break on error - game might still be loading
+                            active = false;
                         }
                     }
                 }
             }
+            let config = polling_config();
+            wait = config.interval(config.live_data_ms, active);
+        }
 
-            // Send ended event to clear the live match
-            let _ = event_tx.send(LiveMatchEvent::Ended).await;
-        });
-
-        Ok(())
+        // Send ended event to clear the live match
+        let _ = event_tx.send(LiveMatchEvent::Ended).await;
     }
 
     /// Stop streaming live match data
     pub async fn stop(&mut self) -> Result<()> {
         if let Some(tx) = self.shutdown_tx.take() {
-            let _ = tx.send(()).await;
+            self.supervisor.stop(LIVE_MATCH_TASK);
+            let _ = tx.send(());
             info!("LiveMatchService stopped");
         }
         Ok(())
     }
 
     /// Poll the Live Client API and send an update event
-    async fn poll_and_emit(api: &LiveClientApi, event_tx: &mpsc::Sender<LiveMatchEvent>) -> Result<()> {
+    async fn poll_and_emit(
+        api: &LiveClientApi,
+        event_tx: &mpsc::Sender<LiveMatchEvent>,
+        resolver: &DdragonResolver,
+    ) -> Result<()> {
         let game_data = match api.get_all_game_data().await {
             Ok(data) => data,
             Err(e) => {
@@ -96,8 +128,9 @@ impl LiveMatchService {
             }
         };
 
-        match LiveMatch::from_game_data(&game_data) {
-            Some(live_match) => {
+        match game_data.to_live_match() {
+            Some(mut live_match) => {
+                resolver.fill_live_match(&mut live_match);
                 if let Err(e) = event_tx.send(LiveMatchEvent::Update(live_match.clone())).await {
                     warn!("Failed to send live-match-update event: {}", e);
                 }
@@ -126,9 +159,3 @@ impl LiveMatchService {
         self.shutdown_tx.is_some()
     }
 }
-
-impl Default for LiveMatchService {
-    fn default() -> Self {
-        Self::new()
-    }
-}
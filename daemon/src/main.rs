@@ -1,20 +1,28 @@
 //! League Pack Daemon Entry Point
 //!
 //! Standalone binary that communicates with the main daemon via NDJSON over stdin/stdout.
-//! Uses the gamepack-runtime crate for the protocol handling.
+//! Uses the gamepack-runtime crate for the protocol handling; pack-specific
+//! commands (`LeagueCommand`) arrive through the handler's `handle_command`.
 
 use std::io;
-use std::sync::RwLock;
+use std::sync::Arc;
 
 use gamepack_runtime::{
     run_gamepack, GameEvent, GameStatus, GamepackHandler, GamepackResult, InitResponse,
     IsMatchInProgressResponse, MatchData,
 };
+use serde_json::json;
 use tokio::runtime::Runtime;
-use tracing::info;
+use tokio::sync::{Mutex, RwLock};
+use tracing::{info, warn};
 use tracing_subscriber::EnvFilter;
 
-use league_integration::LeagueIntegration;
+use league_integration::protocol::{ImportProgress, LeagueCommand, StatusDetails};
+use league_integration::{
+    combine_backlog_metrics, import_match_history, notification_writer, spawn_background,
+    GameSessionController, LeagueError, LeagueIntegration, ReplyChannel, StatsSettings,
+    TaskSupervisor,
+};
 
 /// Game ID for League of Legends
 const GAME_ID: i32 = 1;
@@ -22,24 +30,115 @@ const GAME_ID: i32 = 1;
 /// Game slug
 const SLUG: &str = "league";
 
+/// Background task importing the match history
+const IMPORT_TASK: &str = "match_history_import";
+
 /// Wrapper that implements GamepackHandler for LeagueIntegration
 ///
 /// Uses RwLock for interior mutability so that `&self` trait methods
 /// can call `&mut self` methods on the integration.
+///
+/// Cheap to clone; clones (the debug socket's) share the integration.
+#[derive(Clone)]
 struct LeagueHandler {
-    runtime: Runtime,
-    integration: RwLock<LeagueIntegration>,
+    runtime: Arc<Runtime>,
+    integration: Arc<RwLock<LeagueIntegration>>,
+    /// Running while the daemon opted into gameflow-driven sessions
+    controller: Arc<Mutex<Option<GameSessionController>>>,
 }
 
 impl LeagueHandler {
     fn new() -> Self {
-        let runtime = Runtime::new().expect("Failed to create tokio runtime");
-        let integration = RwLock::new(LeagueIntegration::new());
+        let runtime = Arc::new(Runtime::new().expect("Failed to create tokio runtime"));
+        let integration =
+            Arc::new(RwLock::new(LeagueIntegration::new(StatsSettings::default())));
         Self {
             runtime,
             integration,
+            controller: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Run a pack command against the integration; shared by the protocol
+    /// channel and the debug socket, each passing itself as `channel`
+    fn dispatch(&self, command: LeagueCommand, channel: &dyn ReplyChannel) -> serde_json::Value {
+        // A backfill takes many requests, so it doesn't hold the integration
+        if let LeagueCommand::ImportMatchHistory { limit } = command {
+            return self.runtime.block_on(async {
+                match import_match_history(&self.integration, limit, channel).await {
+                    Ok(progress) => {
+                        serde_json::to_value(progress).unwrap_or(serde_json::Value::Null)
+                    }
+                    Err(e) => {
+                        warn!("Match history import failed: {}", e);
+                        e.response()
+                    }
+                }
+            });
+        }
+
+        self.runtime.block_on(async {
+            let mut integration = self.integration.write().await;
+            let reply = integration.handle_command(command, channel).await;
+            let gameflow_sessions = integration.gameflow_sessions();
+            let supervisor = integration.supervisor().clone();
+            drop(integration);
+
+            self.follow_gameflow_sessions(gameflow_sessions, supervisor).await;
+            reply
+        })
+    }
+
+    /// Start a match history import in the background; its progress, ending
+    /// with a `done` line, is written to the protocol channel
+    fn start_import(&self, limit: Option<u32>) -> serde_json::Value {
+        let integration = Arc::clone(&self.integration);
+        let _runtime = self.runtime.enter();
+        spawn_background(IMPORT_TASK, async move {
+            let writer = notification_writer();
+            if let Err(e) = import_match_history(&integration, limit, writer).await {
+                warn!("Match history import failed: {}", e);
+                writer.write_import_progress(&ImportProgress {
+                    done: true,
+                    error: Some(e.to_string()),
+                    ..Default::default()
+                });
+            }
+        });
+        serde_json::to_value(ImportProgress::default()).unwrap_or(serde_json::Value::Null)
+    }
+
+    /// Start or stop the session controller to match the configured mode
+    async fn follow_gameflow_sessions(&self, enabled: bool, supervisor: TaskSupervisor) {
+        let mut controller = self.controller.lock().await;
+        if controller.is_some() == enabled {
+            return;
+        }
+
+        if !enabled {
+            *controller = None;
+            return;
+        }
+        let mut started = GameSessionController::new(Arc::clone(&self.integration), supervisor);
+        match started.start_notifying().await {
+            Ok(()) => *controller = Some(started),
+            Err(e) => warn!("Failed to start gameflow-driven sessions: {}", e),
         }
     }
+
+    /// Whether the session controller, not the daemon, drives sessions
+    async fn sessions_follow_gameflow(&self) -> bool {
+        self.controller.lock().await.is_some()
+    }
+
+    /// Serve pack commands on the debug socket next to stdin/stdout
+    fn start_debug_socket(&self, path: std::path::PathBuf) {
+        let handler = self.clone();
+        league_integration::start_debug_socket(
+            path,
+            Arc::new(move |command, channel| handler.dispatch(command, channel)),
+        );
+    }
 }
 
 impl GamepackHandler for LeagueHandler {
@@ -53,14 +152,25 @@ impl GamepackHandler for LeagueHandler {
     }
 
     fn detect_running(&self) -> bool {
-        let integration = self.integration.read().expect("RwLock poisoned");
         self.runtime
-            .block_on(async { integration.detect_running().await })
+            .block_on(async { self.integration.read().await.detect_running().await })
+            .is_running()
     }
 
     fn get_status(&self) -> GameStatus {
-        let mut integration = self.integration.write().expect("RwLock poisoned");
-        let status = self.runtime.block_on(async { integration.get_status().await });
+        let status = self.runtime.block_on(async {
+            let mut status = self.integration.write().await.get_status().await;
+            // The session controller's socket isn't the integration's to count
+            let controller = self.controller.lock().await;
+            if let Some(backlog) = controller.as_ref().and_then(|c| c.backlog_metrics()) {
+                status.metrics.ws_backlog =
+                    combine_backlog_metrics([status.metrics.ws_backlog, backlog]);
+            }
+            status
+        });
+
+        // GameStatus has no room for the rest; the host reads it from here
+        notification_writer().write_status_details(&StatusDetails::from(&status));
 
         // Convert IntegrationStatus to GameStatus
         let mut game_status = if status.connected {
@@ -77,32 +187,60 @@ impl GamepackHandler for LeagueHandler {
     }
 
     fn poll_events(&mut self) -> Vec<GameEvent> {
-        let mut integration = self.integration.write().expect("RwLock poisoned");
         self.runtime
-            .block_on(async { integration.poll_events().await })
+            .block_on(async { self.integration.write().await.poll_events().await })
     }
 
     fn get_live_data(&self) -> Option<serde_json::Value> {
-        let mut integration = self.integration.write().expect("RwLock poisoned");
         self.runtime.block_on(async {
+            let mut integration = self.integration.write().await;
             integration.get_live_data().await.map(|data| data.data)
         })
     }
 
+    /// Ignored while the session controller drives sessions (they are
+    /// reported as `SessionNotification`s instead)
     fn on_session_start(&mut self) -> Option<serde_json::Value> {
-        let mut integration = self.integration.write().expect("RwLock poisoned");
-        self.runtime
-            .block_on(async { integration.session_start().await })
+        self.runtime.block_on(async {
+            if self.sessions_follow_gameflow().await {
+                info!("Sessions follow the gameflow, ignoring session start");
+                return None;
+            }
+            self.integration.write().await.session_start().await
+        })
     }
 
     fn on_session_end(&mut self, context: serde_json::Value) -> Option<MatchData> {
-        let mut integration = self.integration.write().expect("RwLock poisoned");
-        let result = self
-            .runtime
-            .block_on(async { integration.session_end(context).await });
+        let result = self.runtime.block_on(async {
+            if self.sessions_follow_gameflow().await {
+                info!("Sessions follow the gameflow, ignoring session end");
+                return None;
+            }
+            self.integration.write().await.session_end(context).await
+        });
 
-        // Convert from local MatchData to protocol MatchData
-        result.map(|m| MatchData::new(m.game_slug, m.game_id, m.result.to_string(), m.details))
+        // Convert from local MatchData to protocol MatchData. Besides "win" and
+        // "loss", the result can be "remake" or "aborted" (see `MatchResult`)
+        result.map(|m| {
+            // MatchData::new takes no times, so they travel in the details
+            let mut details = m.details;
+            if let serde_json::Value::Object(ref mut map) = details {
+                map.insert("played_at".to_string(), json!(m.played_at));
+                map.insert("started_at".to_string(), json!(m.started_at));
+                map.insert("ended_at".to_string(), json!(m.ended_at));
+            }
+            MatchData::new(m.game_slug, m.game_id, m.result.to_string(), details)
+        })
+    }
+
+    /// Pack-specific commands (`LeagueCommand`) from the parent daemon
+    fn handle_command(&mut self, command: serde_json::Value) -> Option<serde_json::Value> {
+        let reply = match serde_json::from_value::<LeagueCommand>(command) {
+            Ok(LeagueCommand::ImportMatchHistory { limit }) => self.start_import(limit),
+            Ok(command) => self.dispatch(command, notification_writer()),
+            Err(e) => LeagueError::InvalidRequest(e.to_string()).response(),
+        };
+        Some(reply)
     }
 
     fn shutdown(&mut self) {
@@ -114,13 +252,17 @@ impl GamepackHandler for LeagueHandler {
         subpack: u8,
         external_match_id: &str,
     ) -> IsMatchInProgressResponse {
-        // Check if the game is actually still running
-        let integration = self.integration.read().expect("RwLock poisoned");
-        let is_running = self.runtime.block_on(async {
-            integration.detect_running().await
+        // Check if the game is actually still running; the client alone
+        // doesn't keep a match going
+        let (game_running, playing_other_match) = self.runtime.block_on(async {
+            let integration = self.integration.read().await;
+            (
+                integration.detect_running().await.has_game(),
+                integration.is_playing_other_match(external_match_id),
+            )
         });
 
-        if !is_running {
+        if !game_running {
             info!(
                 "Match {} (subpack {}) not in progress - game not running",
                 external_match_id, subpack
@@ -129,9 +271,14 @@ impl GamepackHandler for LeagueHandler {
             // We could try to fetch final stats from Riot API here, but for now
             // just return that it ended
             IsMatchInProgressResponse::ended()
+        } else if playing_other_match {
+            info!(
+                "Match {} (subpack {}) ended - a different game is in progress",
+                external_match_id, subpack
+            );
+            IsMatchInProgressResponse::ended()
         } else {
             // Game is running - the match may still be in progress
-            // The integration's is_in_game would be more accurate but requires state
             info!(
                 "Match {} (subpack {}) may still be in progress - game running",
                 external_match_id, subpack
@@ -155,6 +302,9 @@ fn main() {
         .with_writer(io::stderr)
         .init();
 
+    // Report panics to the parent daemon before exiting
+    league_integration::panic_report::install_panic_hook();
+
     info!(
         "League pack daemon starting (protocol v{})",
         companion_pack_protocol::PROTOCOL_VERSION
@@ -162,6 +312,9 @@ fn main() {
 
     // Create handler and run the main loop
     let handler = LeagueHandler::new();
+    if let Some(path) = league_integration::debug_socket_path() {
+        handler.start_debug_socket(path);
+    }
     run_gamepack(handler);
 
     info!("League pack daemon shut down");
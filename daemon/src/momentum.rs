@@ -0,0 +1,517 @@
+//! Gold lead tracking and highlight windows
+//!
+//! Samples each team's gold once per game minute and detects momentum shifts:
+//! lead changes and large gold swings within a short window. Team gold is
+//! estimated from item value, since the Live Client API only reports current
+//! gold for the active player.
+//!
+//! Kills, objectives and gold swings also feed a decaying excitement score.
+//! When it runs hot the tracker opens a highlight window, which is reported
+//! as a teamfight once it cools down, or as a comeback when the player's team
+//! retakes the lead after a large deficit.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{LiveMatch, Team};
+
+/// Gold difference below which neither team is considered ahead
+const EVEN_THRESHOLD: i32 = 500;
+/// Gold difference change that counts as a swing
+const SWING_THRESHOLD: i32 = 3000;
+/// Window (in minutes) over which swings are measured
+const SWING_WINDOW_MINUTES: u32 = 2;
+/// Seconds for the excitement score to halve
+const EXCITEMENT_HALF_LIFE_SECS: f64 = 10.0;
+/// Score at which a highlight window opens
+const WINDOW_OPEN_SCORE: f64 = 5.0;
+/// Score below which an open window closes
+const WINDOW_CLOSE_SCORE: f64 = 1.0;
+/// Kills inside a window for it to count as a teamfight
+const TEAMFIGHT_MIN_KILLS: u32 = 3;
+/// Gold deficit the player's team must come back from
+const COMEBACK_DEFICIT: i32 = 3000;
+/// How far back a comeback window reaches when no fight is in progress
+const COMEBACK_LOOKBACK_SECS: f64 = 30.0;
+/// Excitement added by a gold swing
+const GOLD_SWING_WEIGHT: f64 = 2.0;
+
+/// Excitement added by a Live Client API event
+fn event_weight(event_name: &str, player_involved: bool) -> f64 {
+    let base = match event_name {
+        "ChampionKill" => 1.0,
+        "Multikill" => 1.0,
+        "Ace" => 2.0,
+        "DragonKill" | "HeraldKill" | "HordeKill" => 1.5,
+        "BaronKill" => 2.5,
+        "TurretKilled" | "InhibKilled" => 1.0,
+        _ => 0.0,
+    };
+    if player_involved {
+        base * 1.5
+    } else {
+        base
+    }
+}
+
+/// Team gold at a game minute
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoldSample {
+    pub minute: u32,
+    pub blue_gold: i32,
+    pub red_gold: i32,
+}
+
+impl GoldSample {
+    /// Blue gold minus red gold
+    pub fn diff(&self) -> i32 {
+        self.blue_gold - self.red_gold
+    }
+}
+
+/// Momentum shift detected on the gold timeline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum MomentumMarker {
+    /// A different team took the gold lead
+    #[serde(rename_all = "camelCase")]
+    LeadChange {
+        minute: u32,
+        leader: Team,
+        /// Blue gold minus red gold after the change
+        gold_diff: i32,
+    },
+    /// The gold difference moved sharply within the swing window
+    #[serde(rename_all = "camelCase")]
+    GoldSwing {
+        minute: u32,
+        /// Team the swing favoured
+        toward: Team,
+        /// Size of the swing in gold
+        swing: i32,
+        gold_diff: i32,
+    },
+}
+
+impl MomentumMarker {
+    /// Game event name used when emitting the marker
+    pub fn event_name(&self) -> &'static str {
+        match self {
+            MomentumMarker::LeadChange { .. } => "GoldLeadChange",
+            MomentumMarker::GoldSwing { .. } => "GoldSwing",
+        }
+    }
+
+    /// Game minute the marker occurred at
+    pub fn minute(&self) -> u32 {
+        match self {
+            MomentumMarker::LeadChange { minute, .. } | MomentumMarker::GoldSwing { minute, .. } => {
+                *minute
+            }
+        }
+    }
+}
+
+/// What made a highlight window worth clipping
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HighlightKind {
+    /// Several kills in quick succession
+    Teamfight,
+    /// The player's team retook the lead after a large gold deficit
+    Comeback,
+}
+
+/// Stretch of game time with sustained action
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HighlightWindow {
+    pub kind: HighlightKind,
+    pub start_secs: f64,
+    pub end_secs: f64,
+    /// Highest excitement score reached inside the window
+    pub peak_score: f64,
+    pub kills: u32,
+    pub player_involved: bool,
+}
+
+impl HighlightWindow {
+    /// Game event name used when emitting the window
+    pub fn event_name(&self) -> &'static str {
+        "HighlightWindow"
+    }
+}
+
+/// Highlight window while the excitement score is above the close threshold
+#[derive(Debug, Clone, Copy)]
+struct OpenWindow {
+    start_secs: f64,
+    last_event_secs: f64,
+    peak_score: f64,
+    kills: u32,
+    player_involved: bool,
+}
+
+/// Builds the per-minute gold timeline for a game and detects momentum markers
+#[derive(Debug, Clone, Default)]
+pub struct MomentumTracker {
+    samples: Vec<GoldSample>,
+    markers: Vec<MomentumMarker>,
+    /// Team that held the lead most recently (ignores even stretches)
+    leader: Option<Team>,
+    /// Minute of the last swing, so one swing isn't reported every minute
+    last_swing_minute: Option<u32>,
+    /// The active player's team, once a live snapshot was seen
+    player_team: Option<Team>,
+    /// Largest gold deficit of the player's team since it last led (negative)
+    worst_deficit: i32,
+    /// Excitement score as of `score_secs`
+    score: f64,
+    score_secs: f64,
+    /// Earliest event still contributing to the score
+    heat_start_secs: Option<f64>,
+    /// Kills and player involvement while the score heats up, before a window opens
+    recent_kills: u32,
+    recent_player_involved: bool,
+    window: Option<OpenWindow>,
+    /// Kills already counted from events or live scores
+    counted_kills: i32,
+    /// Windows not yet taken by `take_highlights`
+    pending: Vec<HighlightWindow>,
+    highlights: Vec<HighlightWindow>,
+}
+
+impl MomentumTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a live snapshot. Samples at most once per game minute and
+    /// returns any markers detected by the new sample.
+    ///
+    /// Kills in the snapshot's scores that no polled event accounted for
+    /// (e.g. events missed while the game was loading) feed the excitement
+    /// score here.
+    pub fn record(&mut self, live: &LiveMatch) -> Vec<MomentumMarker> {
+        self.player_team = Some(live.team);
+
+        let total_kills: i32 = live.participants.iter().map(|p| p.kills).sum();
+        let missed = total_kills - self.counted_kills;
+        if missed > 0 {
+            for _ in 0..missed {
+                self.add_heat(live.game_time_secs, event_weight("ChampionKill", false), true, false);
+            }
+        } else {
+            self.cool_to(live.game_time_secs);
+        }
+
+        let minute = (live.game_time_secs / 60.0).floor() as u32;
+        if self.samples.last().is_some_and(|s| s.minute >= minute) {
+            return Vec::new();
+        }
+
+        let team_gold = |team: Team| -> i32 {
+            live.participants
+                .iter()
+                .filter(|p| p.team == team)
+                .map(|p| p.item_gold)
+                .sum()
+        };
+
+        self.push_sample(GoldSample {
+            minute,
+            blue_gold: team_gold(Team::Blue),
+            red_gold: team_gold(Team::Red),
+        })
+    }
+
+    fn push_sample(&mut self, sample: GoldSample) -> Vec<MomentumMarker> {
+        let mut detected = Vec::new();
+        let diff = sample.diff();
+
+        let leader = if diff >= EVEN_THRESHOLD {
+            Some(Team::Blue)
+        } else if diff <= -EVEN_THRESHOLD {
+            Some(Team::Red)
+        } else {
+            None
+        };
+
+        if let Some(leader) = leader {
+            if self.leader.is_some_and(|prev| prev != leader) {
+                detected.push(MomentumMarker::LeadChange {
+                    minute: sample.minute,
+                    leader,
+                    gold_diff: diff,
+                });
+            }
+            self.leader = Some(leader);
+        }
+
+        let window_start = sample.minute.saturating_sub(SWING_WINDOW_MINUTES);
+        let swing_ready = self
+            .last_swing_minute
+            .is_none_or(|m| sample.minute >= m + SWING_WINDOW_MINUTES);
+
+        if swing_ready {
+            if let Some(base) = self.samples.iter().find(|s| s.minute >= window_start) {
+                let swing = diff - base.diff();
+                if swing.abs() >= SWING_THRESHOLD {
+                    detected.push(MomentumMarker::GoldSwing {
+                        minute: sample.minute,
+                        toward: if swing > 0 { Team::Blue } else { Team::Red },
+                        swing: swing.abs(),
+                        gold_diff: diff,
+                    });
+                    self.last_swing_minute = Some(sample.minute);
+                }
+            }
+        }
+
+        let time = sample.minute as f64 * 60.0;
+        for marker in &detected {
+            if let MomentumMarker::GoldSwing { .. } = marker {
+                self.add_heat(time, GOLD_SWING_WEIGHT, false, false);
+            }
+        }
+        self.check_comeback(&sample, &detected);
+
+        self.samples.push(sample);
+        self.markers.extend(detected.iter().cloned());
+        detected
+    }
+
+    /// Feed a polled game event (by Live Client API event name) into the
+    /// excitement score
+    pub fn observe_event(&mut self, event_name: &str, time: f64, player_involved: bool) {
+        let weight = event_weight(event_name, player_involved);
+        if weight > 0.0 {
+            self.add_heat(time, weight, event_name == "ChampionKill", player_involved);
+        } else {
+            self.cool_to(time);
+        }
+    }
+
+    /// Highlight windows completed since the last call
+    pub fn take_highlights(&mut self) -> Vec<HighlightWindow> {
+        std::mem::take(&mut self.pending)
+    }
+
+    /// All highlight windows completed so far
+    pub fn highlights(&self) -> &[HighlightWindow] {
+        &self.highlights
+    }
+
+    /// Current excitement score (decayed to the last observed game time)
+    pub fn excitement(&self) -> f64 {
+        self.score
+    }
+
+    fn add_heat(&mut self, time: f64, weight: f64, is_kill: bool, player_involved: bool) {
+        self.cool_to(time);
+        if is_kill {
+            self.counted_kills += 1;
+        }
+
+        self.score += weight;
+        let heat_start = *self.heat_start_secs.get_or_insert(time);
+
+        if let Some(ref mut window) = self.window {
+            window.last_event_secs = time;
+            window.peak_score = window.peak_score.max(self.score);
+            window.kills += is_kill as u32;
+            window.player_involved |= player_involved;
+        } else if self.score >= WINDOW_OPEN_SCORE {
+            // The kills that heated the score up belong to the window too
+            self.window = Some(OpenWindow {
+                start_secs: heat_start,
+                last_event_secs: time,
+                peak_score: self.score,
+                kills: self.recent_kills + is_kill as u32,
+                player_involved: self.recent_player_involved || player_involved,
+            });
+        }
+
+        if self.window.is_none() {
+            self.recent_kills += is_kill as u32;
+            self.recent_player_involved |= player_involved;
+        }
+    }
+
+    /// Decay the score to `time`, closing the window once it has cooled down
+    fn cool_to(&mut self, time: f64) {
+        if time > self.score_secs {
+            let elapsed = time - self.score_secs;
+            self.score *= 0.5_f64.powf(elapsed / EXCITEMENT_HALF_LIFE_SECS);
+            self.score_secs = time;
+        }
+        if self.score >= WINDOW_CLOSE_SCORE {
+            return;
+        }
+
+        self.score = 0.0;
+        self.heat_start_secs = None;
+        self.recent_kills = 0;
+        self.recent_player_involved = false;
+
+        if let Some(window) = self.window.take() {
+            if window.kills >= TEAMFIGHT_MIN_KILLS {
+                self.complete(HighlightWindow {
+                    kind: HighlightKind::Teamfight,
+                    start_secs: window.start_secs,
+                    end_secs: window.last_event_secs,
+                    peak_score: window.peak_score,
+                    kills: window.kills,
+                    player_involved: window.player_involved,
+                });
+            }
+        }
+    }
+
+    /// Report a comeback when the player's team takes the lead after trailing
+    /// by at least `COMEBACK_DEFICIT`
+    fn check_comeback(&mut self, sample: &GoldSample, detected: &[MomentumMarker]) {
+        let Some(team) = self.player_team else {
+            return;
+        };
+        let player_diff = if team == Team::Blue { sample.diff() } else { -sample.diff() };
+        self.worst_deficit = self.worst_deficit.min(player_diff);
+
+        let took_lead = detected
+            .iter()
+            .any(|m| matches!(m, MomentumMarker::LeadChange { leader, .. } if *leader == team));
+        if !took_lead {
+            return;
+        }
+
+        if self.worst_deficit <= -COMEBACK_DEFICIT {
+            let end_secs = sample.minute as f64 * 60.0;
+            let (start_secs, kills) = match self.window {
+                Some(ref window) => (window.start_secs, window.kills),
+                None => ((end_secs - COMEBACK_LOOKBACK_SECS).max(0.0), 0),
+            };
+            self.complete(HighlightWindow {
+                kind: HighlightKind::Comeback,
+                start_secs,
+                end_secs,
+                peak_score: self.score,
+                kills,
+                player_involved: true,
+            });
+        }
+        self.worst_deficit = 0;
+    }
+
+    fn complete(&mut self, window: HighlightWindow) {
+        self.highlights.push(window.clone());
+        self.pending.push(window);
+    }
+
+    /// Per-minute gold samples so far
+    pub fn samples(&self) -> &[GoldSample] {
+        &self.samples
+    }
+
+    /// All markers detected so far
+    pub fn markers(&self) -> &[MomentumMarker] {
+        &self.markers
+    }
+
+    /// Forget all samples and highlights (e.g. at session start)
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(minute: u32, blue_gold: i32, red_gold: i32) -> GoldSample {
+        GoldSample {
+            minute,
+            blue_gold,
+            red_gold,
+        }
+    }
+
+    #[test]
+    fn test_lead_change() {
+        let mut tracker = MomentumTracker::new();
+        assert!(tracker.push_sample(sample(1, 2000, 1000)).is_empty());
+        // Even stretch doesn't count as a change
+        assert!(tracker.push_sample(sample(2, 3000, 3000)).is_empty());
+
+        let markers = tracker.push_sample(sample(3, 4000, 5000));
+        assert_eq!(
+            markers,
+            vec![MomentumMarker::LeadChange {
+                minute: 3,
+                leader: Team::Red,
+                gold_diff: -1000,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_gold_swing_within_window() {
+        let mut tracker = MomentumTracker::new();
+        tracker.push_sample(sample(10, 20000, 20000));
+        tracker.push_sample(sample(11, 21000, 22000));
+
+        let markers = tracker.push_sample(sample(12, 21000, 24000));
+        assert!(markers.contains(&MomentumMarker::GoldSwing {
+            minute: 12,
+            toward: Team::Red,
+            swing: 3000,
+            gold_diff: -3000,
+        }));
+
+        // Same swing isn't reported again on the next minute
+        let markers = tracker.push_sample(sample(13, 21000, 24500));
+        assert!(!markers.iter().any(|m| m.event_name() == "GoldSwing"));
+        assert_eq!(tracker.samples().len(), 4);
+    }
+
+    #[test]
+    fn test_teamfight_window() {
+        let mut tracker = MomentumTracker::new();
+        // A lone kill cools down without a window
+        tracker.observe_event("ChampionKill", 100.0, false);
+        tracker.observe_event("TurretKilled", 160.0, false);
+        assert!(tracker.take_highlights().is_empty());
+
+        for (i, time) in [300.0, 302.0, 305.0, 306.0].into_iter().enumerate() {
+            tracker.observe_event("ChampionKill", time, i == 2);
+        }
+        tracker.observe_event("Ace", 306.0, false);
+        assert!(tracker.excitement() >= WINDOW_OPEN_SCORE);
+
+        // Closes once the score has decayed
+        tracker.observe_event("DragonKill", 400.0, false);
+        let highlights = tracker.take_highlights();
+        assert_eq!(highlights.len(), 1);
+        assert_eq!(highlights[0].kind, HighlightKind::Teamfight);
+        assert_eq!(highlights[0].start_secs, 300.0);
+        assert_eq!(highlights[0].end_secs, 306.0);
+        assert_eq!(highlights[0].kills, 4);
+        assert!(highlights[0].player_involved);
+        assert!(tracker.take_highlights().is_empty());
+    }
+
+    #[test]
+    fn test_comeback_window() {
+        let mut tracker = MomentumTracker::new();
+        tracker.player_team = Some(Team::Red);
+
+        tracker.push_sample(sample(10, 20000, 19000));
+        tracker.push_sample(sample(14, 26000, 22000));
+        assert!(tracker.take_highlights().is_empty());
+
+        tracker.push_sample(sample(18, 28000, 29000));
+        let highlights = tracker.take_highlights();
+        assert_eq!(highlights.len(), 1);
+        assert_eq!(highlights[0].kind, HighlightKind::Comeback);
+        assert_eq!(highlights[0].end_secs, 1080.0);
+    }
+}
@@ -0,0 +1,400 @@
+//! Push-based event streaming
+//!
+//! After a `Subscribe` command the pack pushes gameflow changes and game
+//! events as unsolicited `EventNotification` lines the moment they happen,
+//! so the daemon doesn't have to poll with `PollEvents`. Like panic reports,
+//! notifications are written straight to stdout (the protocol channel), one
+//! NDJSON line each.
+//!
+//! Gameflow changes come from the `GameflowMonitor` (LCU WebSocket with a
+//! polling fallback). Game events are the ones the integration's own poll
+//! produces, published here as they are emitted, so they carry the same
+//! trigger decisions, streaks and highlights as the `PollEvents` reply.
+//!
+//! With a batch window set, routine notifications are coalesced (events of
+//! consecutive notifications on the same topic are merged) and written in a
+//! single write once the window ends. Urgent ones (gameflow changes, events
+//! involving the player) flush the batch and go out immediately.
+
+use std::collections::BTreeSet;
+use std::io::Write;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use serde_json::json;
+use tokio::sync::{broadcast, mpsc, Mutex};
+use tracing::{debug, info, warn};
+
+use crate::protocol::{
+    EventNotification, EventTopic, GameEvent, ImportProgress, ResponseChunk, SessionNotification,
+    StatusDetails,
+};
+use crate::{BufferMetrics, GameflowEvent, GameflowMonitor, TaskSupervisor};
+
+/// Supervisor task forwarding gameflow changes
+const GAMEFLOW_PUSH_TASK: &str = "push_gameflow";
+
+/// Supervisor task forwarding game events
+const GAME_EVENT_PUSH_TASK: &str = "push_game_events";
+
+static NOTIFICATION_WRITER: OnceLock<NotificationWriter> = OnceLock::new();
+
+/// The shared notification writer
+pub fn notification_writer() -> &'static NotificationWriter {
+    NOTIFICATION_WRITER.get_or_init(NotificationWriter::default)
+}
+
+/// Write one notification to the protocol channel right away
+pub fn write_notification(notification: &EventNotification) {
+    notification_writer().write(notification);
+}
+
+#[derive(Default)]
+struct NotificationBatch {
+    window: Duration,
+    pending: Vec<EventNotification>,
+    flush_scheduled: bool,
+}
+
+/// Writes notifications to stdout, optionally batching routine ones
+#[derive(Default)]
+pub struct NotificationWriter {
+    batch: std::sync::Mutex<NotificationBatch>,
+}
+
+impl NotificationWriter {
+    /// Coalesce routine notifications within `window` (zero disables batching)
+    pub fn set_batch_window(&self, window: Duration) {
+        self.batch.lock().expect("Mutex poisoned").window = window;
+        if window.is_zero() {
+            self.flush();
+        }
+    }
+
+    /// Current batch window (zero when batching is off)
+    pub fn batch_window(&self) -> Duration {
+        self.batch.lock().expect("Mutex poisoned").window
+    }
+
+    /// Write `notification` now, after anything batched before it
+    pub fn write(&self, notification: &EventNotification) {
+        let mut batch = self.batch.lock().expect("Mutex poisoned");
+        let mut notifications = std::mem::take(&mut batch.pending);
+        notifications.push(notification.clone());
+        write_lines(&render_lines(&notifications));
+    }
+
+    /// Write the chunks of a streamed response now, after anything batched
+    pub fn write_chunks(&self, chunks: &[ResponseChunk]) {
+        let mut batch = self.batch.lock().expect("Mutex poisoned");
+        let mut lines = render_lines(&std::mem::take(&mut batch.pending));
+        lines.push_str(&render_lines(chunks));
+        write_lines(&lines);
+    }
+
+    /// Write a match history import update now, after anything batched
+    pub fn write_import_progress(&self, progress: &ImportProgress) {
+        let mut batch = self.batch.lock().expect("Mutex poisoned");
+        let mut lines = render_lines(&std::mem::take(&mut batch.pending));
+        lines.push_str(&render_lines(std::slice::from_ref(progress)));
+        write_lines(&lines);
+    }
+
+    /// Write a gameflow-driven session start or end now, after anything
+    /// batched
+    pub fn write_session(&self, notification: &SessionNotification) {
+        let mut batch = self.batch.lock().expect("Mutex poisoned");
+        let mut lines = render_lines(&std::mem::take(&mut batch.pending));
+        lines.push_str(&render_lines(std::slice::from_ref(notification)));
+        write_lines(&lines);
+    }
+
+    /// Write the status extras now, after anything batched
+    pub fn write_status_details(&self, details: &StatusDetails) {
+        let mut batch = self.batch.lock().expect("Mutex poisoned");
+        let mut lines = render_lines(&std::mem::take(&mut batch.pending));
+        lines.push_str(&render_lines(std::slice::from_ref(details)));
+        write_lines(&lines);
+    }
+
+    /// Batch a routine notification (written now if batching is off)
+    pub fn queue(&'static self, notification: EventNotification) {
+        let mut batch = self.batch.lock().expect("Mutex poisoned");
+        let runtime = match tokio::runtime::Handle::try_current() {
+            Ok(runtime) if !batch.window.is_zero() => runtime,
+            _ => {
+                drop(batch);
+                self.write(&notification);
+                return;
+            }
+        };
+
+        coalesce(&mut batch.pending, notification);
+        if !batch.flush_scheduled {
+            batch.flush_scheduled = true;
+            let window = batch.window;
+            runtime.spawn(async move {
+                tokio::time::sleep(window).await;
+                self.flush();
+            });
+        }
+    }
+
+    /// Write everything batched so far
+    pub fn flush(&self) {
+        let mut batch = self.batch.lock().expect("Mutex poisoned");
+        batch.flush_scheduled = false;
+        let notifications = std::mem::take(&mut batch.pending);
+        write_lines(&render_lines(&notifications));
+    }
+}
+
+/// Add a notification to a batch, merging it into the last one when the
+/// topic matches (order across topics is kept)
+fn coalesce(pending: &mut Vec<EventNotification>, notification: EventNotification) {
+    match pending.last_mut() {
+        Some(last) if last.topic == notification.topic => last.events.extend(notification.events),
+        _ => pending.push(notification),
+    }
+}
+
+/// NDJSON lines for a batch of notifications
+fn render_lines<T: serde::Serialize>(messages: &[T]) -> String {
+    let mut lines = String::new();
+    for message in messages {
+        match serde_json::to_string(message) {
+            Ok(line) => {
+                lines.push_str(&line);
+                lines.push('\n');
+            }
+            Err(e) => warn!("Failed to serialize notification: {}", e),
+        }
+    }
+    lines
+}
+
+/// Write a batch with a single write and flush
+fn write_lines(lines: &str) {
+    if lines.is_empty() {
+        return;
+    }
+
+    let stdout = std::io::stdout();
+    let mut out = stdout.lock();
+    let _ = out.write_all(lines.as_bytes());
+    let _ = out.flush();
+}
+
+/// Game events published but not yet forwarded
+const GAME_EVENT_BACKLOG: usize = 64;
+
+/// Topics the daemon subscribed to and the tasks pushing them
+pub struct EventPush {
+    topics: BTreeSet<EventTopic>,
+    /// Kept alive while gameflow changes are pushed (stops on drop)
+    monitor: Option<GameflowMonitor>,
+    /// Game events from the integration's poll, for the push task
+    game_events: broadcast::Sender<GameEvent>,
+}
+
+impl EventPush {
+    pub fn new() -> Self {
+        Self {
+            topics: BTreeSet::new(),
+            monitor: None,
+            game_events: broadcast::channel(GAME_EVENT_BACKLOG).0,
+        }
+    }
+
+    /// Whether `topic` is currently pushed
+    pub fn is_subscribed(&self, topic: EventTopic) -> bool {
+        self.topics.contains(&topic)
+    }
+
+    /// Subscribed topics
+    pub fn topics(&self) -> Vec<EventTopic> {
+        self.topics.iter().copied().collect()
+    }
+
+    /// Backlog of the gameflow push's WebSocket, while gameflow is pushed
+    pub fn backlog_metrics(&self) -> Option<BufferMetrics> {
+        self.monitor.as_ref().and_then(GameflowMonitor::backlog_metrics)
+    }
+
+    /// Start pushing `topics` (already subscribed topics are left running)
+    pub async fn subscribe(&mut self, topics: &[EventTopic], supervisor: &TaskSupervisor) {
+        for &topic in topics {
+            if !self.topics.insert(topic) {
+                continue;
+            }
+            info!("Pushing {:?} notifications", topic);
+
+            match topic {
+                EventTopic::Gameflow => self.start_gameflow(supervisor).await,
+                EventTopic::GameEvents => self.start_game_events(supervisor),
+            }
+        }
+    }
+
+    /// Stop pushing `topics`
+    pub fn unsubscribe(&mut self, topics: &[EventTopic], supervisor: &TaskSupervisor) {
+        for &topic in topics {
+            if !self.topics.remove(&topic) {
+                continue;
+            }
+            info!("No longer pushing {:?} notifications", topic);
+
+            match topic {
+                EventTopic::Gameflow => {
+                    self.monitor = None;
+                    supervisor.abort(GAMEFLOW_PUSH_TASK);
+                }
+                EventTopic::GameEvents => supervisor.abort(GAME_EVENT_PUSH_TASK),
+            }
+        }
+    }
+
+    /// Restart the gameflow push on a new WebSocket
+    pub async fn reconnect(&mut self, supervisor: &TaskSupervisor) {
+        if self.is_subscribed(EventTopic::Gameflow) {
+            self.monitor = None;
+            self.start_gameflow(supervisor).await;
+        }
+    }
+
+    /// Push game events the integration just emitted (if subscribed)
+    pub fn publish_game_events(&self, events: &[GameEvent]) {
+        if !self.is_subscribed(EventTopic::GameEvents) {
+            return;
+        }
+        for event in events {
+            let _ = self.game_events.send(event.clone());
+        }
+    }
+
+    fn start_game_events(&self, supervisor: &TaskSupervisor) {
+        let game_events = self.game_events.clone();
+        supervisor.spawn(GAME_EVENT_PUSH_TASK, move || {
+            forward_game_events(game_events.subscribe())
+        });
+    }
+
+    async fn start_gameflow(&mut self, supervisor: &TaskSupervisor) {
+        let (event_tx, event_rx) = mpsc::channel(32);
+        let mut monitor = GameflowMonitor::new(supervisor.clone());
+        if let Err(e) = monitor.start(event_tx).await {
+            warn!("Failed to start gameflow monitor: {}", e);
+            return;
+        }
+        self.monitor = Some(monitor);
+
+        let event_rx = Arc::new(Mutex::new(event_rx));
+        supervisor.spawn(GAMEFLOW_PUSH_TASK, move || {
+            forward_gameflow(Arc::clone(&event_rx))
+        });
+    }
+}
+
+impl Default for EventPush {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+async fn forward_gameflow(event_rx: Arc<Mutex<mpsc::Receiver<GameflowEvent>>>) {
+    let mut event_rx = event_rx.lock().await;
+    while let Some(event) = event_rx.recv().await {
+        if let GameflowEvent::PhaseChanged(change) = event {
+            write_notification(&EventNotification::new(
+                EventTopic::Gameflow,
+                vec![GameEvent::new(
+                    "PhaseChanged".to_string(),
+                    0.0,
+                    json!({
+                        "phase": change.phase,
+                        "phase_key": change.display_key,
+                        "phase_label": change.display_name,
+                        "is_in_game": change.is_in_game,
+                    }),
+                )],
+            ));
+        }
+    }
+}
+
+async fn forward_game_events(mut event_rx: broadcast::Receiver<GameEvent>) {
+    loop {
+        match event_rx.recv().await {
+            Ok(event) => {
+                // Events involving the player may start a clip, so they don't wait
+                let urgent = event.data["is_player_involved"] == true;
+                let notification = EventNotification::new(EventTopic::GameEvents, vec![event]);
+                if urgent {
+                    write_notification(&notification);
+                } else {
+                    notification_writer().queue(notification);
+                }
+            }
+            Err(broadcast::error::RecvError::Lagged(missed)) => {
+                debug!("Game event push lagged, {} events dropped", missed);
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notification_is_one_tagged_line() {
+        let event = GameEvent::new(
+            "ChampionKill".to_string(),
+            312.5,
+            json!({ "killer_name": "Me", "victim_name": "Them", "is_player_involved": true }),
+        );
+        let notification = EventNotification::new(EventTopic::GameEvents, vec![event]);
+
+        let line = serde_json::to_string(&notification).unwrap();
+        assert!(!line.contains('\n'));
+
+        let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(value["type"], "EventNotification");
+        assert_eq!(value["topic"], "game_events");
+        assert_eq!(value["events"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_game_events_are_published_only_while_subscribed() {
+        let kill = GameEvent::new("ChampionKill".to_string(), 312.5, json!({}));
+        let mut push = EventPush::new();
+        let mut published = push.game_events.subscribe();
+
+        push.publish_game_events(std::slice::from_ref(&kill));
+        assert!(published.try_recv().is_err());
+
+        push.topics.insert(EventTopic::GameEvents);
+        push.publish_game_events(&[kill]);
+        assert!(published.try_recv().is_ok());
+    }
+
+    #[test]
+    fn test_batch_coalesces_consecutive_topics() {
+        let notification = |topic, name: &str| {
+            EventNotification::new(topic, vec![GameEvent::new(name.to_string(), 0.0, json!({}))])
+        };
+
+        let mut pending = Vec::new();
+        coalesce(&mut pending, notification(EventTopic::GameEvents, "ChampionKill"));
+        coalesce(&mut pending, notification(EventTopic::GameEvents, "TurretKilled"));
+        coalesce(&mut pending, notification(EventTopic::Gameflow, "PhaseChanged"));
+        coalesce(&mut pending, notification(EventTopic::GameEvents, "DragonKill"));
+
+        let topics: Vec<_> = pending.iter().map(|n| (n.topic, n.events.len())).collect();
+        assert_eq!(
+            topics,
+            vec![(EventTopic::GameEvents, 2), (EventTopic::Gameflow, 1), (EventTopic::GameEvents, 1)]
+        );
+        assert_eq!(render_lines(&pending).lines().count(), 3);
+    }
+}
@@ -0,0 +1,567 @@
+//! Objective timers from live data
+//!
+//! The Live Client API reports objective kills but no spawn timers, so they
+//! are derived from the kill events and the game clock: dragon soul
+//! progress, the next dragon (or Elder once a soul is claimed), the next
+//! Baron and which outer turrets still carry plates. An objective taken with
+//! champion kills right before it (or stolen) is reported as contested.
+//!
+//! A dragon or Baron counts as stolen when the Live Client flags it, or when
+//! the killer took it alone while their team was losing the fight around it
+//! (the other team had the pit and was finishing it).
+//!
+//! Every objective the player's team takes is also kept with the player's
+//! credit for it (killer, assister or none), for objective participation.
+
+use serde::Serialize;
+use std::collections::{BTreeSet, VecDeque};
+
+use crate::live_client::GameEvent as LiveEvent;
+use crate::{
+    riot_ids_match, LeagueEventType, LiveMatch, ObjectiveCredit, ObjectiveParticipation,
+    ObjectiveRole, ParsedGameEvent, Team,
+};
+
+/// First dragon spawn
+const DRAGON_FIRST_SPAWN_SECS: f64 = 300.0;
+/// Dragon respawn after a kill
+const DRAGON_RESPAWN_SECS: f64 = 300.0;
+/// Elder spawn after the soul is claimed, and Elder respawn
+const ELDER_RESPAWN_SECS: f64 = 360.0;
+/// First Baron spawn
+const BARON_FIRST_SPAWN_SECS: f64 = 1200.0;
+/// Baron respawn after a kill
+const BARON_RESPAWN_SECS: f64 = 360.0;
+/// Turret plates fall off at 14:00
+const PLATES_FALL_SECS: f64 = 840.0;
+/// Dragons one team needs for the soul
+const DRAGONS_FOR_SOUL: u32 = 4;
+/// Dragon whose type decides the soul (the map changes with the third)
+const SOUL_DRAGON: usize = 3;
+/// How far before an objective kill champion kills count as contesting it
+const CONTEST_WINDOW_SECS: f64 = 20.0;
+/// Champion kills in the window for an objective to count as contested
+const MIN_CONTEST_KILLS: u32 = 2;
+/// Lead-in of a contested objective clip before the first kill
+const CONTEST_LEAD_SECS: f64 = 5.0;
+
+/// Dragon type reported for the Elder Dragon
+const ELDER_DRAGON_TYPE: &str = "Elder";
+
+/// Lane of a turret
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Lane {
+    Top,
+    Mid,
+    Bot,
+}
+
+/// Spawn timer of an epic monster
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectiveTimer {
+    /// "dragon", "elder" or "baron"
+    pub objective: &'static str,
+    pub spawns_at_secs: f64,
+    /// 0 while the objective is up
+    pub spawns_in_secs: f64,
+    pub alive: bool,
+}
+
+/// Dragon soul race
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DragonSoul {
+    pub blue_dragons: u32,
+    pub red_dragons: u32,
+    /// Soul element, known once the third dragon is taken
+    pub soul_type: Option<String>,
+    pub claimed_by: Option<Team>,
+}
+
+/// Outer turret that still carries plates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct PlatedTurret {
+    /// Team owning the turret
+    pub team: Team,
+    pub lane: Lane,
+}
+
+/// Objective state for overlays (`objectives` in the live data)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectiveTimers {
+    pub dragon: ObjectiveTimer,
+    pub baron: ObjectiveTimer,
+    pub soul: DragonSoul,
+    /// Seconds until plates fall, `None` once they have
+    pub plates_fall_in_secs: Option<f64>,
+    /// Empty once plates have fallen
+    pub plated_turrets: Vec<PlatedTurret>,
+}
+
+/// An objective taken in a fight
+#[derive(Debug, Clone)]
+pub struct ContestedObjective {
+    /// "dragon", "elder", "baron" or "herald"
+    pub objective: &'static str,
+    pub stolen: bool,
+    /// Event for the trigger evaluator (`count` = champion kills in the fight)
+    pub event: ParsedGameEvent,
+}
+
+impl ContestedObjective {
+    /// `ObjectiveSteal` event for a stolen dragon or Baron
+    pub fn steal_event(&self) -> Option<ParsedGameEvent> {
+        if !self.stolen || self.objective == "herald" {
+            return None;
+        }
+        Some(ParsedGameEvent {
+            event_type: LeagueEventType::ObjectiveSteal,
+            ..self.event.clone()
+        })
+    }
+}
+
+/// A champion kill shortly before an objective
+#[derive(Debug, Clone)]
+struct RecentKill {
+    time: f64,
+    victim: Option<String>,
+    /// Killer, victim and assisters
+    involved: Vec<String>,
+}
+
+/// Tracks objective kills of the current game
+#[derive(Debug, Clone, Default)]
+pub struct ObjectiveTracker {
+    soul: DragonSoul,
+    dragons_taken: usize,
+    next_dragon_at: Option<f64>,
+    next_baron_at: Option<f64>,
+    /// Outer turrets destroyed
+    fallen_outer: BTreeSet<PlatedTurret>,
+    /// Champion kills within the contest window
+    recent_kills: VecDeque<RecentKill>,
+    /// Objectives the player's team took
+    participation: ObjectiveParticipation,
+}
+
+impl ObjectiveTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Objectives the player's team took so far, with the player's credit
+    pub fn participation(&self) -> &ObjectiveParticipation {
+        &self.participation
+    }
+
+    /// Feed a Live Client API event; returns the objective if it was contested.
+    ///
+    /// `snapshot` resolves the killer's team for the soul count and for
+    /// objective participation.
+    pub fn observe(
+        &mut self,
+        event: &LiveEvent,
+        snapshot: Option<&LiveMatch>,
+        player_name: &str,
+    ) -> Option<ContestedObjective> {
+        let time = event.event_time;
+        self.recent_kills
+            .retain(|kill| time - kill.time <= CONTEST_WINDOW_SECS);
+
+        let objective = match LeagueEventType::from(event.event_name.as_str()) {
+            LeagueEventType::ChampionKill => {
+                let involved = event
+                    .killer_name
+                    .iter()
+                    .chain(event.victim_name.iter())
+                    .chain(event.assisters.iter())
+                    .cloned()
+                    .collect();
+                self.recent_kills.push_back(RecentKill {
+                    time,
+                    victim: event.victim_name.clone(),
+                    involved,
+                });
+                return None;
+            }
+            LeagueEventType::TurretKilled => {
+                if let Some(turret) = event.turret_killed.as_deref().and_then(outer_turret) {
+                    self.fallen_outer.insert(turret);
+                }
+                return None;
+            }
+            LeagueEventType::DragonKill => {
+                let team = killer_team(event, snapshot);
+                self.dragon_killed(event.dragon_type.as_deref().unwrap_or_default(), team, time)
+            }
+            LeagueEventType::BaronKill => {
+                self.next_baron_at = Some(time + BARON_RESPAWN_SECS);
+                "baron"
+            }
+            LeagueEventType::HeraldKill => "herald",
+            _ => return None,
+        };
+        self.credit_objective(objective, event, snapshot, player_name);
+
+        let flagged = event
+            .stolen
+            .as_deref()
+            .is_some_and(|s| s.eq_ignore_ascii_case("true"));
+        let stolen = flagged || (objective != "herald" && self.looks_stolen(event, snapshot));
+        let kills = self.recent_kills.len() as u32;
+        if !stolen && kills < MIN_CONTEST_KILLS {
+            return None;
+        }
+
+        let mut assisters = event.assisters.clone();
+        for kill in &self.recent_kills {
+            for name in &kill.involved {
+                let is_name = |other: &String| riot_ids_match(other, name);
+                if !assisters.iter().any(is_name)
+                    && !event.killer_name.as_ref().is_some_and(is_name)
+                {
+                    assisters.push(name.clone());
+                }
+            }
+        }
+        let is_player = |name: &String| !player_name.is_empty() && riot_ids_match(name, player_name);
+        let is_player_involved =
+            event.killer_name.as_ref().is_some_and(is_player) || assisters.iter().any(is_player);
+        let fight_start = self.recent_kills.front().map_or(time, |kill| kill.time);
+
+        Some(ContestedObjective {
+            objective,
+            stolen,
+            event: ParsedGameEvent {
+                event_type: LeagueEventType::ObjectiveContested,
+                event_time: time,
+                killer_name: event.killer_name.clone(),
+                victim_name: None,
+                assisters,
+                is_player_involved,
+                count: Some(kills),
+                clip_start_secs: Some((fight_start - CONTEST_LEAD_SECS).max(0.0)),
+            },
+        })
+    }
+
+    /// Record an objective taken by the player's team with the player's credit
+    fn credit_objective(
+        &mut self,
+        objective: &'static str,
+        event: &LiveEvent,
+        snapshot: Option<&LiveMatch>,
+        player_name: &str,
+    ) {
+        let Some(snapshot) = snapshot else {
+            return;
+        };
+        if player_name.is_empty() || killer_team(event, Some(snapshot)) != Some(snapshot.team) {
+            return;
+        }
+        let is_player = |name: &String| riot_ids_match(name, player_name);
+        let credit = if event.killer_name.as_ref().is_some_and(is_player) {
+            Some(ObjectiveRole::Killer)
+        } else if event.assisters.iter().any(is_player) {
+            Some(ObjectiveRole::Assister)
+        } else {
+            None
+        };
+        self.participation.objectives.push(ObjectiveCredit {
+            objective: objective.to_string(),
+            game_time_secs: event.event_time,
+            credit,
+        });
+    }
+
+    /// Whether an objective was taken without help by a team that lost more
+    /// champions than the other in the fight around it
+    fn looks_stolen(&self, event: &LiveEvent, snapshot: Option<&LiveMatch>) -> bool {
+        if !event.assisters.is_empty() {
+            return false;
+        }
+        let (Some(team), Some(snapshot)) = (killer_team(event, snapshot), snapshot) else {
+            return false;
+        };
+
+        let team_of = |name: &str| {
+            snapshot
+                .participants
+                .iter()
+                .find(|p| riot_ids_match(&p.summoner_name, name))
+                .map(|p| p.team)
+        };
+        let (mut lost, mut killed) = (0, 0);
+        for victim in self.recent_kills.iter().filter_map(|k| k.victim.as_deref()) {
+            match team_of(victim) {
+                Some(victim_team) if victim_team == team => lost += 1,
+                Some(_) => killed += 1,
+                None => {}
+            }
+        }
+        lost > killed
+    }
+
+    fn dragon_killed(&mut self, dragon_type: &str, team: Option<Team>, time: f64) -> &'static str {
+        if dragon_type.eq_ignore_ascii_case(ELDER_DRAGON_TYPE) {
+            self.next_dragon_at = Some(time + ELDER_RESPAWN_SECS);
+            return "elder";
+        }
+
+        self.dragons_taken += 1;
+        if self.dragons_taken == SOUL_DRAGON && !dragon_type.is_empty() {
+            self.soul.soul_type = Some(dragon_type.to_string());
+        }
+        match team {
+            Some(Team::Blue) => self.soul.blue_dragons += 1,
+            Some(Team::Red) => self.soul.red_dragons += 1,
+            None => {}
+        }
+        if self.soul.claimed_by.is_none() {
+            self.soul.claimed_by = [
+                (Team::Blue, self.soul.blue_dragons),
+                (Team::Red, self.soul.red_dragons),
+            ]
+            .into_iter()
+            .find(|(_, dragons)| *dragons >= DRAGONS_FOR_SOUL)
+            .map(|(team, _)| team);
+        }
+
+        let respawn = if self.soul.claimed_by.is_some() {
+            ELDER_RESPAWN_SECS
+        } else {
+            DRAGON_RESPAWN_SECS
+        };
+        self.next_dragon_at = Some(time + respawn);
+        "dragon"
+    }
+
+    /// Timers at `game_time`
+    pub fn timers(&self, game_time: f64) -> ObjectiveTimers {
+        let dragon = if self.soul.claimed_by.is_some() {
+            "elder"
+        } else {
+            "dragon"
+        };
+        let plates_up = game_time < PLATES_FALL_SECS;
+
+        let plated_turrets = if plates_up {
+            [Team::Blue, Team::Red]
+                .into_iter()
+                .flat_map(|team| {
+                    [Lane::Top, Lane::Mid, Lane::Bot].map(|lane| PlatedTurret { team, lane })
+                })
+                .filter(|turret| !self.fallen_outer.contains(turret))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        ObjectiveTimers {
+            dragon: timer(
+                dragon,
+                self.next_dragon_at.unwrap_or(DRAGON_FIRST_SPAWN_SECS),
+                game_time,
+            ),
+            baron: timer(
+                "baron",
+                self.next_baron_at.unwrap_or(BARON_FIRST_SPAWN_SECS),
+                game_time,
+            ),
+            soul: self.soul.clone(),
+            plates_fall_in_secs: plates_up.then(|| PLATES_FALL_SECS - game_time),
+            plated_turrets,
+        }
+    }
+}
+
+fn timer(objective: &'static str, spawns_at_secs: f64, game_time: f64) -> ObjectiveTimer {
+    ObjectiveTimer {
+        objective,
+        spawns_at_secs,
+        spawns_in_secs: (spawns_at_secs - game_time).max(0.0),
+        alive: game_time >= spawns_at_secs,
+    }
+}
+
+/// Team of the player who took an objective
+fn killer_team(event: &LiveEvent, snapshot: Option<&LiveMatch>) -> Option<Team> {
+    let killer = event.killer_name.as_ref()?;
+    snapshot?
+        .participants
+        .iter()
+        .find(|p| riot_ids_match(&p.summoner_name, killer))
+        .map(|p| p.team)
+}
+
+/// Outer turret from a Live Client turret name (`Turret_T1_L_03_A`: T1 is
+/// blue's, L/C/R is top/mid/bot, 03 is the outer top/bot and 05 the outer
+/// mid turret)
+fn outer_turret(name: &str) -> Option<PlatedTurret> {
+    let mut parts = name.split('_').skip(1);
+    let team = match parts.next()? {
+        "T1" => Team::Blue,
+        "T2" => Team::Red,
+        _ => return None,
+    };
+    let lane = match (parts.next()?, parts.next()?) {
+        ("L", "03") => Lane::Top,
+        ("C", "05") => Lane::Mid,
+        ("R", "03") => Lane::Bot,
+        _ => return None,
+    };
+    Some(PlatedTurret { team, lane })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(name: &str, time: f64, killer: &str) -> LiveEvent {
+        LiveEvent {
+            event_id: 0,
+            event_name: name.to_string(),
+            event_time: time,
+            killer_name: Some(killer.to_string()),
+            victim_name: None,
+            assisters: Vec::new(),
+            kill_streak: None,
+            dragon_type: None,
+            stolen: None,
+            turret_killed: None,
+        }
+    }
+
+    fn dragon(time: f64, dragon_type: &str) -> LiveEvent {
+        LiveEvent {
+            dragon_type: Some(dragon_type.to_string()),
+            ..event("DragonKill", time, "Blue1")
+        }
+    }
+
+    #[test]
+    fn test_soul_and_elder_timers() {
+        let mut tracker = ObjectiveTracker::new();
+        let timers = tracker.timers(60.0);
+        assert_eq!(timers.dragon.spawns_in_secs, 240.0);
+        assert_eq!(timers.plated_turrets.len(), 6);
+
+        let kills = [
+            ("Fire", Team::Red),
+            ("Water", Team::Blue),
+            ("Chemtech", Team::Blue),
+            ("Chemtech", Team::Blue),
+        ];
+        for (i, (dragon_type, team)) in kills.into_iter().enumerate() {
+            tracker.dragon_killed(dragon_type, Some(team), 300.0 * (i + 1) as f64);
+        }
+        let timers = tracker.timers(1250.0);
+        assert_eq!(timers.soul.soul_type.as_deref(), Some("Chemtech"));
+        assert_eq!((timers.soul.blue_dragons, timers.soul.red_dragons), (3, 1));
+        assert_eq!(timers.soul.claimed_by, None);
+        assert!(timers.baron.alive);
+        assert!(timers.plated_turrets.is_empty());
+
+        tracker.dragon_killed("Chemtech", Some(Team::Blue), 1500.0);
+        let timers = tracker.timers(1500.0);
+        assert_eq!(timers.soul.claimed_by, Some(Team::Blue));
+        assert_eq!(timers.dragon.objective, "elder");
+        assert_eq!(timers.dragon.spawns_at_secs, 1860.0);
+
+        tracker.observe(&dragon(1900.0, "Elder"), None, "");
+        assert_eq!(tracker.timers(1900.0).dragon.spawns_at_secs, 2260.0);
+    }
+
+    #[test]
+    fn test_fight_at_objective_is_contested() {
+        let mut tracker = ObjectiveTracker::new();
+        let mut turret = event("TurretKilled", 500.0, "Red1");
+        turret.turret_killed = Some("Turret_T1_C_05_A".to_string());
+        tracker.observe(&turret, None, "Me");
+        assert_eq!(tracker.timers(600.0).plated_turrets.len(), 5);
+
+        let mut kill = event("ChampionKill", 1290.0, "Me");
+        kill.victim_name = Some("Red1".to_string());
+        tracker.observe(&kill, None, "Me");
+        tracker.observe(&event("ChampionKill", 1295.0, "Red2"), None, "Me");
+
+        let contest = tracker
+            .observe(&event("BaronKill", 1300.0, "Blue2"), None, "Me")
+            .unwrap();
+        assert_eq!(contest.objective, "baron");
+        assert_eq!(contest.event.count, Some(2));
+        assert!(contest.event.is_player_involved);
+        assert_eq!(contest.event.clip_start_secs, Some(1285.0));
+        assert_eq!(tracker.timers(1300.0).baron.spawns_at_secs, 1660.0);
+
+        // A quiet dragon isn't contested, unless stolen
+        assert!(tracker
+            .observe(&dragon(1400.0, "Fire"), None, "Me")
+            .is_none());
+        let mut stolen = dragon(1700.0, "Fire");
+        stolen.stolen = Some("True".to_string());
+        assert!(tracker.observe(&stolen, None, "Me").unwrap().stolen);
+    }
+
+    #[test]
+    fn test_solo_take_by_losing_team_is_a_steal() {
+        let mut snapshot: LiveMatch = serde_json::from_value(serde_json::json!({
+            "summonerName": "Me", "champion": "Lee Sin", "level": 14,
+            "kills": 3, "deaths": 2, "assists": 5, "cs": 180, "currentGold": 400.0,
+            "gameTimeSecs": 1500.0, "gameMode": "CLASSIC", "team": "blue",
+            "items": [], "trinket": null, "spell1": null, "spell2": null, "runes": null,
+            "participants": [], "isDead": false
+        }))
+        .unwrap();
+        let teams = [("Me", "blue"), ("Blue2", "blue"), ("Red1", "red"), ("Red2", "red")];
+        snapshot.participants = teams
+            .into_iter()
+            .map(|(name, team)| {
+                serde_json::from_value(serde_json::json!({
+                    "summonerName": name, "champion": "Ahri", "team": team, "kills": 0,
+                    "deaths": 0, "assists": 0, "cs": 0, "level": 14, "isDead": false
+                }))
+                .unwrap()
+            })
+            .collect();
+
+        let mut tracker = ObjectiveTracker::new();
+        let mut kill = event("ChampionKill", 1490.0, "Red1");
+        kill.victim_name = Some("Blue2".to_string());
+        tracker.observe(&kill, Some(&snapshot), "Me");
+        let mut kill = event("ChampionKill", 1495.0, "Red2");
+        kill.victim_name = Some("Blue3".to_string());
+        tracker.observe(&kill, Some(&snapshot), "Me");
+
+        let contest = tracker
+            .observe(&event("BaronKill", 1500.0, "Me"), Some(&snapshot), "Me")
+            .unwrap();
+        assert!(contest.stolen);
+        let steal = contest.steal_event().unwrap();
+        assert_eq!(steal.event_type, LeagueEventType::ObjectiveSteal);
+        assert!(steal.is_player_involved);
+
+        // Taken with help, the same fight is only contested
+        let mut baron = event("BaronKill", 1505.0, "Me");
+        baron.assisters = vec!["Blue4".to_string()];
+        assert!(tracker.observe(&baron, Some(&snapshot), "Me").unwrap().steal_event().is_none());
+
+        // Objectives the player's team took count toward participation,
+        // the enemy's don't
+        tracker.observe(&event("HeraldKill", 1600.0, "Blue2"), Some(&snapshot), "Me");
+        tracker.observe(&event("HeraldKill", 1900.0, "Red1"), Some(&snapshot), "Me");
+        let participation = tracker.participation();
+        assert_eq!(participation.taken(), 3);
+        assert_eq!(participation.participated(), 2);
+        assert_eq!(participation.objectives[0].credit, Some(ObjectiveRole::Killer));
+        assert_eq!(participation.objectives[2].credit, None);
+    }
+}
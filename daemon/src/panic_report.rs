@@ -0,0 +1,120 @@
+//! Panic reporting over the IPC protocol
+//!
+//! Without a hook, a panic only prints to stderr and the parent daemon sees the
+//! pipe close with no explanation. The hook installed here writes an `error`
+//! message (code `PANIC`) to stdout, flushes it and aborts the pack.
+//!
+//! A panic inside a supervised task or a one-off background task isn't an
+//! error of the pack: it unwinds as usual, ending only that task (the
+//! supervisor restarts its own), so the hook reports it as a `TaskPanicked`
+//! notification instead.
+
+use std::backtrace::Backtrace;
+use std::io::Write;
+use std::panic::PanicHookInfo;
+
+use serde_json::{json, Value};
+
+use crate::protocol::TaskPanicked;
+use crate::supervisor::{panic_scope, PanicScope};
+
+/// Error code used for pack crashes
+pub const ERROR_CODE_PANIC: &str = "PANIC";
+
+/// Install the panic hook.
+///
+/// The previous hook still runs first so the panic is also logged to stderr.
+pub fn install_panic_hook() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        previous(info);
+
+        let location = info
+            .location()
+            .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()));
+        let scope = panic_scope();
+        let report = match scope {
+            Some(PanicScope::Supervised(task) | PanicScope::Background(task)) => {
+                serde_json::to_value(TaskPanicked {
+                    task: task.to_string(),
+                    message: panic_message(info),
+                    location,
+                    restarted: matches!(scope, Some(PanicScope::Supervised(_))),
+                })
+                .unwrap_or(Value::Null)
+            }
+            None => {
+                let backtrace = Backtrace::force_capture().to_string();
+                build_panic_report(&panic_message(info), location.as_deref(), &backtrace)
+            }
+        };
+
+        // stdout is the protocol channel - write one NDJSON line and flush
+        let stdout = std::io::stdout();
+        let mut out = stdout.lock();
+        let _ = writeln!(out, "{}", report);
+        let _ = out.flush();
+        drop(out);
+
+        if scope.is_none() {
+            std::process::abort();
+        }
+    }));
+}
+
+/// Extract the panic payload as a string
+fn panic_message(info: &PanicHookInfo<'_>) -> String {
+    if let Some(s) = info.payload().downcast_ref::<&str>() {
+        (*s).to_string()
+    } else if let Some(s) = info.payload().downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+/// Build the protocol error message for a panic
+pub fn build_panic_report(message: &str, location: Option<&str>, backtrace: &str) -> Value {
+    json!({
+        "type": "error",
+        "code": ERROR_CODE_PANIC,
+        "message": message,
+        "details": {
+            "location": location,
+            "backtrace": backtrace,
+            "thread": std::thread::current().name(),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_panic_report() {
+        let report = build_panic_report("boom", Some("src/lib.rs:1:1"), "0: main");
+        assert_eq!(report["type"], "error");
+        assert_eq!(report["code"], ERROR_CODE_PANIC);
+        assert_eq!(report["message"], "boom");
+        assert_eq!(report["details"]["location"], "src/lib.rs:1:1");
+        assert_eq!(report["details"]["backtrace"], "0: main");
+
+        // Must serialize to a single NDJSON line
+        assert!(!report.to_string().contains('\n'));
+    }
+
+    #[test]
+    fn test_task_restart_is_not_an_error() {
+        let report = serde_json::to_value(TaskPanicked {
+            task: "poll_events".to_string(),
+            message: "boom".to_string(),
+            location: None,
+            restarted: true,
+        })
+        .unwrap();
+        assert_eq!(report["type"], "TaskPanicked");
+        assert_eq!(report["task"], "poll_events");
+        assert!(report.get("code").is_none());
+    }
+}
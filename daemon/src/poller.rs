@@ -1,23 +1,23 @@
 use std::sync::Arc;
-use std::time::Duration;
 use tokio::sync::{broadcast, RwLock};
 use tracing::{debug, info};
 
-use super::{LiveClientApi, ParsedGameEvent};
-use crate::Result;
+use super::{LiveClientApi, ParsedGameEvent, StreakTracker};
+use crate::{polling_config, riot_ids_match, Result};
 
 pub struct GamePoller {
     api: LiveClientApi,
-    poll_interval: Duration,
     last_event_id: Arc<RwLock<i32>>,
+    streaks: Arc<RwLock<StreakTracker>>,
 }
 
 impl GamePoller {
-    pub fn new(poll_interval_ms: u64) -> Result<Self> {
+    /// Poller at the configured event interval (see `polling_config`)
+    pub fn new() -> Result<Self> {
         Ok(Self {
             api: LiveClientApi::new()?,
-            poll_interval: Duration::from_millis(poll_interval_ms),
             last_event_id: Arc::new(RwLock::new(-1)),
+            streaks: Arc::new(RwLock::new(StreakTracker::new())),
         })
     }
 
@@ -28,11 +28,21 @@ impl GamePoller {
     ) {
         info!("Starting game event polling");
 
+        // Slows to the idle interval while no game answers
+        let mut active = true;
         loop {
+            let config = polling_config();
+            let wait = config.interval(config.event_poll_ms, active);
             tokio::select! {
-                _ = tokio::time::sleep(self.poll_interval) => {
-                    if let Err(e) = self.poll_events(&event_tx).await {
-                        debug!("Polling error (game may not be active): {}", e);
+                _ = tokio::time::sleep(wait) => {
+                    match self.poll_events(&event_tx).await {
+                        Ok(()) => active = true,
+                        Err(e) => {
+                            if active {
+                                debug!("Polling error (game may not be active): {}", e);
+                            }
+                            active = false;
+                        }
                     }
                 }
                 _ = shutdown_rx.recv() => {
@@ -49,6 +59,7 @@ impl GamePoller {
         let player_name = &active_player.summoner_name;
 
         let mut last_id = self.last_event_id.write().await;
+        let mut streaks = self.streaks.write().await;
 
         for event in events.events {
             if event.event_id <= *last_id {
@@ -57,9 +68,10 @@ impl GamePoller {
 
             *last_id = event.event_id;
 
-            let is_player_involved = event.killer_name.as_ref() == Some(player_name)
-                || event.victim_name.as_ref() == Some(player_name)
-                || event.assisters.contains(player_name);
+            let is_player = |name: &String| riot_ids_match(name, player_name);
+            let is_player_involved = event.killer_name.as_ref().is_some_and(is_player)
+                || event.victim_name.as_ref().is_some_and(is_player)
+                || event.assisters.iter().any(is_player);
 
             let parsed = ParsedGameEvent {
                 event_type: super::LeagueEventType::from(event.event_name.as_str()),
@@ -68,9 +80,15 @@ impl GamePoller {
                 victim_name: event.victim_name,
                 assisters: event.assisters,
                 is_player_involved,
+                count: event.kill_streak,
+                clip_start_secs: None,
             };
 
+            let streak_events = streaks.observe(&parsed, player_name);
             let _ = event_tx.send(parsed);
+            for streak in streak_events {
+                let _ = event_tx.send(streak);
+            }
         }
 
         Ok(())
@@ -79,5 +97,6 @@ impl GamePoller {
     pub async fn reset(&self) {
         let mut last_id = self.last_event_id.write().await;
         *last_id = -1;
+        self.streaks.write().await.reset();
     }
 }
@@ -0,0 +1,53 @@
+//! Poll intervals
+//!
+//! The game event poller, the live match service and the gameflow monitor's
+//! polling fallback each run in their own task, and the intervals can change
+//! while they run (`Configure` command). Like the remote host, the config is
+//! process-wide and read again before every poll.
+
+use std::sync::RwLock;
+use tracing::info;
+
+use crate::PollingConfig;
+
+static POLLING_CONFIG: RwLock<Option<PollingConfig>> = RwLock::new(None);
+
+/// Current poll intervals
+pub fn polling_config() -> PollingConfig {
+    POLLING_CONFIG
+        .read()
+        .expect("RwLock poisoned")
+        .unwrap_or_default()
+}
+
+/// Replace the poll intervals (running polls pick them up on their next wait)
+pub fn set_polling_config(config: PollingConfig) {
+    info!(
+        "Polling every {}ms (events), {}ms (live data), {}ms (gameflow fallback), adaptive: {}",
+        config.event_poll_ms, config.live_data_ms, config.gameflow_fallback_ms, config.adaptive
+    );
+    *POLLING_CONFIG.write().expect("RwLock poisoned") = Some(config);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_idle_polls_slow_down() {
+        let config = PollingConfig {
+            idle_ms: 2000,
+            ..PollingConfig::default()
+        };
+        assert_eq!(config.interval(250, true), Duration::from_millis(250));
+        assert_eq!(config.interval(250, false), Duration::from_secs(10));
+
+        let fixed = PollingConfig {
+            adaptive: false,
+            ..config
+        };
+        assert_eq!(fixed.interval(250, false), Duration::from_millis(250));
+        assert_eq!(fixed.interval(0, true), Duration::from_millis(50));
+    }
+}
@@ -1,132 +1,136 @@
 //! League-specific protocol types
 //!
-//! Re-exports shared types from gamepack-runtime and defines
-//! League-specific data structures.
+//! Re-exports shared types from gamepack-runtime and the League data types
+//! from `pack-league-types`.
 
-use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::QueueEstimate;
+
 // Re-export shared types from gamepack runtime
 pub use gamepack_runtime::{
     GameEvent, GameStatus, GamepackCommand, GamepackResponse, InitResponse,
     MatchData as ProtocolMatchData, PROTOCOL_VERSION,
 };
 
-// ============================================================================
-// League-Specific Data Types
-// ============================================================================
+// League data types (shared with other components)
+pub use pack_league_types::protocol::*;
 
-/// Live match data for League UI display
+/// Unsolicited message pushing events for a subscribed topic
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct LiveMatchData {
-    /// Game ID
-    pub game_id: i32,
-    /// Current game time in seconds
-    pub game_time_secs: f64,
-    /// Game-specific live data as JSON
-    pub data: Value,
-}
-
-/// Match result for League
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
-pub enum MatchResult {
-    Win,
-    Loss,
-    Remake,
+#[serde(tag = "type")]
+pub struct EventNotification {
+    pub topic: EventTopic,
+    pub events: Vec<GameEvent>,
 }
 
-impl std::fmt::Display for MatchResult {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            MatchResult::Win => write!(f, "win"),
-            MatchResult::Loss => write!(f, "loss"),
-            MatchResult::Remake => write!(f, "remake"),
-        }
+impl EventNotification {
+    pub fn new(topic: EventTopic, events: Vec<GameEvent>) -> Self {
+        Self { topic, events }
     }
 }
 
-/// League-specific match data returned when a match ends
+/// Unsolicited message carrying part of a large command response
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct MatchData {
-    /// Game slug (e.g., "league")
-    pub game_slug: String,
-    /// Game ID
-    pub game_id: i32,
-    /// When the match was played
-    pub played_at: DateTime<Utc>,
-    /// Match duration in seconds
-    pub duration_secs: i32,
-    /// Match result
-    pub result: MatchResult,
-    /// Game-specific details as JSON
-    pub details: Value,
+#[serde(tag = "type")]
+pub struct ResponseChunk {
+    pub stream_id: String,
+    /// Position in the stream, starting at 0
+    pub sequence: u32,
+    /// Set on the last chunk of the stream
+    pub is_final: bool,
+    pub items: Vec<Value>,
 }
 
-/// Session context for tracking game session state
+/// Response to a command whose result was streamed as `ResponseChunk`s
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SessionContext {
-    /// Session start time
-    pub started_at: DateTime<Utc>,
-    /// Game-specific session data
-    pub data: Value,
+pub struct ChunkedResponse {
+    pub stream_id: String,
+    pub chunks: u32,
+    pub total_items: usize,
 }
 
-impl SessionContext {
-    /// Create a new session context
-    pub fn new(data: Value) -> Self {
-        Self {
-            started_at: Utc::now(),
-            data,
-        }
-    }
+/// Unsolicited progress of an `ImportMatchHistory` command, written after
+/// every page of match history
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub struct ImportProgress {
+    /// Games read from the match history so far
+    pub scanned: u32,
+    /// Games not stored before
+    pub imported: u32,
+    /// Games already stored, remakes and unsupported modes
+    pub skipped: u32,
+    /// Games in the match history, as reported by the client
+    pub total: u32,
+    pub done: bool,
+    /// Why a background import stopped, on its `done` line
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
-/// Connection status
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
-#[serde(rename_all = "snake_case")]
-pub enum ConnectionStatus {
-    #[default]
-    Disconnected,
-    Connected,
-    InGame,
+/// Unsolicited message reporting a session the pack started or ended on its
+/// own (`Configure { gameflow_sessions: true }`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub struct SessionNotification {
+    pub state: SessionState,
+    /// Context of a started session
+    pub context: Option<Value>,
+    /// Match data of an ended session, when the game produced any
+    pub match_data: Option<MatchData>,
+}
+
+/// Unsolicited message carrying what the runtime's `GameStatus` leaves out,
+/// written after every status call
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub struct StatusDetails {
+    pub metrics: IntegrationMetrics,
+    pub running: RunningStatus,
+    pub profile: Option<ProfileMetadata>,
+    pub queue_estimate: Option<QueueEstimate>,
 }
 
-impl std::fmt::Display for ConnectionStatus {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ConnectionStatus::Disconnected => write!(f, "disconnected"),
-            ConnectionStatus::Connected => write!(f, "connected"),
-            ConnectionStatus::InGame => write!(f, "in_game"),
+impl From<&IntegrationStatus> for StatusDetails {
+    fn from(status: &IntegrationStatus) -> Self {
+        Self {
+            metrics: status.metrics.clone(),
+            running: status.running,
+            profile: status.profile.clone(),
+            queue_estimate: status.queue_estimate.clone(),
         }
     }
 }
 
-/// Integration status
+/// Unsolicited message reporting a panic in a background task; the pack
+/// keeps running
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct IntegrationStatus {
-    /// Game slug
-    pub game_slug: String,
-    /// Whether connected to the game client
-    pub connected: bool,
-    /// Detailed connection status
-    pub connection_status: ConnectionStatus,
-    /// Current game phase
-    pub game_phase: Option<String>,
-    /// Whether actively in a match
-    pub is_in_game: bool,
+#[serde(tag = "type")]
+pub struct TaskPanicked {
+    pub task: String,
+    pub message: String,
+    pub location: Option<String>,
+    /// Whether the supervisor restarts the task (one-off tasks are just lost)
+    pub restarted: bool,
 }
 
-impl IntegrationStatus {
-    /// Create a disconnected status
-    pub fn disconnected(slug: &str) -> Self {
-        Self {
-            game_slug: slug.to_string(),
-            connected: false,
-            connection_status: ConnectionStatus::Disconnected,
-            game_phase: None,
-            is_in_game: false,
-        }
-    }
+/// Whether a `SessionNotification` reports a start or an end
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionState {
+    Started,
+    Ended,
+}
+
+/// Result of a `SimulateEvent` command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulatedEventResult {
+    /// The event as it will be emitted
+    pub event: GameEvent,
+    /// Whether the current trigger settings would record a clip
+    pub triggered: bool,
+    /// Trigger name (e.g. "kill", "baron") if triggered
+    pub trigger: Option<String>,
 }
@@ -0,0 +1,207 @@
+//! Ranked LP tracking
+//!
+//! League points reset on every division change, so subtracting LP before
+//! and after a game is wrong across promotions (Gold IV 70LP -> Gold III
+//! 10LP is +40, not -60). Ranks are mapped to a single ordinal value where
+//! each division spans 100 LP; Master and above share one open-ended ladder.
+
+use tracing::info;
+
+use crate::{RankChange, RankedEntry};
+
+/// Tiers from lowest to highest
+const TIERS: [&str; 10] = [
+    "IRON",
+    "BRONZE",
+    "SILVER",
+    "GOLD",
+    "PLATINUM",
+    "EMERALD",
+    "DIAMOND",
+    "MASTER",
+    "GRANDMASTER",
+    "CHALLENGER",
+];
+
+/// Divisions from lowest to highest
+const DIVISIONS: [&str; 4] = ["IV", "III", "II", "I"];
+
+/// First tier without divisions
+const APEX_TIER: usize = 7;
+
+/// LP span of one division
+const LP_PER_DIVISION: i32 = 100;
+
+/// Position of a rank on the ladder
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RankPosition {
+    tier: usize,
+    /// Always 0 for apex tiers
+    division: usize,
+    league_points: i32,
+}
+
+impl RankPosition {
+    /// `None` for unranked or unknown tiers
+    fn of(entry: &RankedEntry) -> Option<Self> {
+        let tier = TIERS.iter().position(|t| t.eq_ignore_ascii_case(&entry.tier))?;
+        let division = if tier >= APEX_TIER {
+            0
+        } else {
+            DIVISIONS.iter().position(|d| d.eq_ignore_ascii_case(&entry.division))?
+        };
+        Some(Self {
+            tier,
+            division,
+            league_points: entry.league_points,
+        })
+    }
+
+    /// Bracket index (tier and division); higher is better
+    fn bracket(&self) -> usize {
+        self.tier * DIVISIONS.len() + self.division
+    }
+
+    /// Single LP value across the whole ladder
+    fn ordinal(&self) -> i32 {
+        let tier = self.tier.min(APEX_TIER);
+        (tier * DIVISIONS.len() + self.division) as i32 * LP_PER_DIVISION + self.league_points
+    }
+}
+
+/// Rank change between two snapshots of the same queue
+pub fn rank_change(pre: &RankedEntry, post: &RankedEntry) -> Option<RankChange> {
+    let before = RankPosition::of(pre)?;
+    let after = RankPosition::of(post)?;
+
+    let series_progress = Some(post.mini_series_progress.clone()).filter(|s| !s.is_empty());
+    Some(RankChange {
+        lp_change: after.ordinal() - before.ordinal(),
+        promoted: after.bracket() > before.bracket(),
+        demoted: after.bracket() < before.bracket(),
+        series_progress,
+    })
+}
+
+/// Holds the pre-game rank until the post-game rank is known
+#[derive(Debug, Clone, Default)]
+pub struct RankTracker {
+    pre_game: Option<RankedEntry>,
+}
+
+impl RankTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store the rank at the start of the game
+    pub fn capture(&mut self, rank: Option<RankedEntry>) {
+        if let Some(ref rank) = rank {
+            info!(
+                "Captured pre-game rank: {} {} ({}LP)",
+                rank.tier, rank.division, rank.league_points
+            );
+        }
+        self.pre_game = rank;
+    }
+
+    /// Whether a pre-game rank is held
+    pub fn is_captured(&self) -> bool {
+        self.pre_game.is_some()
+    }
+
+    /// Compare against the rank after the game, keeping the pre-game rank
+    pub fn change(&self, post_game: Option<&RankedEntry>) -> Option<RankChange> {
+        rank_change(self.pre_game.as_ref()?, post_game?)
+    }
+
+    /// Compare against the rank after the game; forgets the pre-game rank
+    pub fn finish(&mut self, post_game: Option<&RankedEntry>) -> Option<RankChange> {
+        let change = self.change(post_game);
+        let pre_game = self.pre_game.take()?;
+        let change = change?;
+
+        if change.promoted {
+            info!("Promoted: {} {} -> {:?}", pre_game.tier, pre_game.division, post_game);
+        } else if change.demoted {
+            info!("Demoted: {} {} -> {:?}", pre_game.tier, pre_game.division, post_game);
+        }
+        Some(change)
+    }
+
+    /// Forget the pre-game rank
+    pub fn clear(&mut self) {
+        self.pre_game = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rank(tier: &str, division: &str, league_points: i32) -> RankedEntry {
+        RankedEntry {
+            queue_type: "RANKED_SOLO_5x5".to_string(),
+            tier: tier.to_string(),
+            division: division.to_string(),
+            league_points,
+            mini_series_progress: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_promotion_and_demotion() {
+        let change = rank_change(&rank("GOLD", "IV", 70), &rank("GOLD", "III", 10)).unwrap();
+        assert_eq!(change.lp_change, 40);
+        assert!(change.promoted);
+        assert!(!change.demoted);
+
+        let change = rank_change(&rank("PLATINUM", "IV", 0), &rank("GOLD", "I", 75)).unwrap();
+        assert_eq!(change.lp_change, -25);
+        assert!(change.demoted);
+
+        let change = rank_change(&rank("SILVER", "II", 40), &rank("SILVER", "II", 59)).unwrap();
+        assert_eq!(change.lp_change, 19);
+        assert!(!change.promoted && !change.demoted);
+    }
+
+    #[test]
+    fn test_apex_tiers_share_one_ladder() {
+        let change = rank_change(&rank("DIAMOND", "I", 90), &rank("MASTER", "I", 15)).unwrap();
+        assert_eq!(change.lp_change, 25);
+        assert!(change.promoted);
+
+        let change =
+            rank_change(&rank("MASTER", "I", 480), &rank("GRANDMASTER", "I", 502)).unwrap();
+        assert_eq!(change.lp_change, 22);
+        assert!(change.promoted);
+
+        assert!(rank_change(&rank("", "", 0), &rank("GOLD", "IV", 0)).is_none());
+    }
+
+    #[test]
+    fn test_tracker_reports_series_progress_once() {
+        let mut tracker = RankTracker::new();
+        tracker.capture(Some(rank("GOLD", "I", 100)));
+
+        let mut post = rank("GOLD", "I", 100);
+        post.mini_series_progress = "WLN".to_string();
+        let change = tracker.finish(Some(&post)).unwrap();
+        assert_eq!(change.series_progress.as_deref(), Some("WLN"));
+        assert_eq!(change.lp_change, 0);
+
+        assert!(tracker.finish(Some(&post)).is_none());
+    }
+
+    #[test]
+    fn test_change_keeps_pre_game_rank() {
+        let mut tracker = RankTracker::new();
+        tracker.capture(Some(rank("SILVER", "II", 40)));
+
+        let post = rank("SILVER", "II", 61);
+        assert_eq!(tracker.change(Some(&post)).unwrap().lp_change, 21);
+        assert!(tracker.is_captured());
+        assert_eq!(tracker.finish(Some(&post)).unwrap().lp_change, 21);
+        assert!(!tracker.is_captured());
+    }
+}
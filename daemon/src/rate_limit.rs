@@ -0,0 +1,75 @@
+//! Shared rate limiting for LCU requests
+//!
+//! `LcuClient` instances are created on demand all over the integration
+//! (status polls, finalization, backfill), so the limiter lives in a single
+//! process-wide token bucket that every client waits on before sending.
+
+use governor::{DefaultDirectRateLimiter, Quota, RateLimiter};
+use std::num::NonZeroU32;
+use std::sync::{Arc, OnceLock, RwLock};
+use tracing::info;
+
+use crate::LcuRateLimitSettings;
+
+/// Limits in effect and the limiter built from them
+type Limiter = (LcuRateLimitSettings, Arc<DefaultDirectRateLimiter>);
+
+static LCU_LIMITER: OnceLock<RwLock<Limiter>> = OnceLock::new();
+
+fn build_limiter(settings: LcuRateLimitSettings) -> Arc<DefaultDirectRateLimiter> {
+    let per_second = NonZeroU32::new(settings.requests_per_second).unwrap_or(NonZeroU32::MIN);
+    let burst = NonZeroU32::new(settings.burst).unwrap_or(per_second);
+    Arc::new(RateLimiter::direct(Quota::per_second(per_second).allow_burst(burst)))
+}
+
+fn slot() -> &'static RwLock<Limiter> {
+    LCU_LIMITER.get_or_init(|| {
+        let settings = LcuRateLimitSettings::default();
+        RwLock::new((settings, build_limiter(settings)))
+    })
+}
+
+/// Replace the shared LCU limiter with one using the given limits
+pub fn set_lcu_rate_limit(settings: LcuRateLimitSettings) {
+    info!(
+        "LCU rate limit set to {}/s (burst {})",
+        settings.requests_per_second, settings.burst
+    );
+    *slot().write().expect("RwLock poisoned") = (settings, build_limiter(settings));
+}
+
+/// Limits of the shared LCU limiter
+pub(crate) fn lcu_rate_limit() -> LcuRateLimitSettings {
+    slot().read().expect("RwLock poisoned").0
+}
+
+/// The shared LCU limiter
+pub(crate) fn lcu_limiter() -> Arc<DefaultDirectRateLimiter> {
+    Arc::clone(&slot().read().expect("RwLock poisoned").1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_burst_is_enforced() {
+        let limiter = build_limiter(LcuRateLimitSettings {
+            requests_per_second: 1,
+            burst: 3,
+        });
+        assert!(limiter.check().is_ok());
+        assert!(limiter.check().is_ok());
+        assert!(limiter.check().is_ok());
+        assert!(limiter.check().is_err());
+    }
+
+    #[test]
+    fn test_zero_limits_are_clamped() {
+        let limiter = build_limiter(LcuRateLimitSettings {
+            requests_per_second: 0,
+            burst: 0,
+        });
+        assert!(limiter.check().is_ok());
+    }
+}
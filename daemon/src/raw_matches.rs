@@ -0,0 +1,169 @@
+//! Persisted raw end-of-game payloads
+//!
+//! The full `/lol-end-of-game/v1/eog-stats-block` response of every finished
+//! game is kept in the `league_match_raw` directory (one JSON file per match,
+//! next to the stats file), together with what parsing needs besides the
+//! payload. When parsing learns new fields, stored matches can be parsed
+//! again instead of staying incomplete.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+use crate::{
+    ChampSelectDraft, CreateMatch, GameModeContext, LeagueError, MatchRunes,
+    RankChange, Result, SourceDiff, StatsSettings,
+};
+
+/// Directory of raw payloads inside the data directory
+pub(crate) const RAW_MATCHES_DIR: &str = "league_match_raw";
+
+/// End-of-game payload as received, with the context it was parsed in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RawMatch {
+    pub game_id: i64,
+    pub played_at: DateTime<Utc>,
+    /// Unparsed end-of-game stats block
+    pub payload: Value,
+    #[serde(default)]
+    pub game_mode: Option<GameModeContext>,
+    #[serde(default)]
+    pub rank: Option<String>,
+    #[serde(default)]
+    pub rank_change: Option<RankChange>,
+    #[serde(default)]
+    pub runes: Option<MatchRunes>,
+    #[serde(default)]
+    pub draft: Option<ChampSelectDraft>,
+    #[serde(default)]
+    pub puuid: Option<String>,
+    /// Where the last live snapshot disagreed with the payload
+    #[serde(default)]
+    pub source_diff: Option<SourceDiff>,
+}
+
+impl RawMatch {
+    /// Raw record for a match parsed from `payload`
+    pub fn new(parsed: &CreateMatch, payload: Value, game_mode: Option<&GameModeContext>) -> Self {
+        Self {
+            game_id: parsed.game_id,
+            played_at: parsed.played_at,
+            payload,
+            game_mode: game_mode.cloned(),
+            rank: parsed.rank.clone(),
+            rank_change: parsed.rank_change.clone(),
+            runes: parsed.runes.clone(),
+            draft: parsed.draft.clone(),
+            puuid: parsed.puuid.clone(),
+            source_diff: None,
+        }
+    }
+}
+
+/// Store of raw payloads, keyed by match
+#[derive(Debug, Default)]
+pub struct RawMatchStore {
+    /// `None` keeps nothing (no data directory)
+    dir: Option<PathBuf>,
+}
+
+impl RawMatchStore {
+    /// Open the store in the data directory from the given settings
+    pub fn open(settings: &StatsSettings) -> Self {
+        match settings.data_dir() {
+            Some(dir) => Self::open_at(dir.join(RAW_MATCHES_DIR)),
+            None => {
+                warn!("No data directory available, raw match data will not be persisted");
+                Self::default()
+            }
+        }
+    }
+
+    /// Open the store at an explicit directory
+    pub fn open_at(dir: PathBuf) -> Self {
+        debug!("Raw match store at {:?}", dir);
+        Self { dir: Some(dir) }
+    }
+
+    /// Store a match's payload, replacing an earlier one
+    pub fn save(&self, match_id: &str, raw: &RawMatch) {
+        let Some(path) = self.path_for(match_id) else {
+            return;
+        };
+        if let Err(e) = write_raw(&path, raw) {
+            warn!("Failed to save raw match data to {:?}: {}", path, e);
+        }
+    }
+
+    /// Stored payload of a match
+    pub fn load(&self, match_id: &str) -> Result<RawMatch> {
+        let path = self
+            .path_for(match_id)
+            .ok_or_else(|| LeagueError::NotFound(format!("No raw data for match {}", match_id)))?;
+        let content = std::fs::read_to_string(&path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => {
+                LeagueError::NotFound(format!("No raw data for match {}", match_id))
+            }
+            _ => e.into(),
+        })?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// File of a match; `None` without a data directory or for IDs that
+    /// aren't safe as a file name
+    fn path_for(&self, match_id: &str) -> Option<PathBuf> {
+        let safe = !match_id.is_empty()
+            && match_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !safe {
+            return None;
+        }
+        Some(self.dir.as_ref()?.join(format!("{}.json", match_id)))
+    }
+}
+
+/// Write through a temp file so a crash can't leave a truncated payload
+fn write_raw(path: &Path, raw: &RawMatch) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_vec(raw)?)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_raw_match_round_trip() {
+        let dir = TempDir::new("raw");
+        let store = RawMatchStore::open_at(dir.path().to_path_buf());
+
+        let raw = RawMatch {
+            game_id: 42,
+            played_at: Utc::now(),
+            payload: serde_json::json!({ "gameId": 42, "gameLength": 1800 }),
+            game_mode: None,
+            rank: Some("GOLD II".to_string()),
+            rank_change: None,
+            runes: None,
+            draft: None,
+            puuid: None,
+            source_diff: None,
+        };
+        store.save("42", &raw);
+
+        let loaded = store.load("42").unwrap();
+        assert_eq!(loaded.payload, raw.payload);
+        assert_eq!(loaded.rank.as_deref(), Some("GOLD II"));
+        assert!(store.load("43").is_err());
+        assert!(store.load("../stats").is_err());
+    }
+}
@@ -0,0 +1,111 @@
+//! Remote gaming PC support
+//!
+//! In dual-PC streaming setups the companion runs on the streaming machine
+//! while League runs on the gaming PC. The LCU and Live Client API are then
+//! reached at the gaming PC's address, with the lockfile credentials copied
+//! over by the user since the lockfile can't be discovered remotely. Both
+//! APIs only listen on loopback, so the ports have to be forwarded on the
+//! gaming PC.
+//!
+//! Like the rate limiter, this is process-wide: clients are created on demand
+//! all over the integration.
+
+use std::sync::RwLock;
+use tracing::info;
+use zeroize::Zeroizing;
+
+use crate::RemoteHostSettings;
+
+/// Host used when no remote PC is configured
+const LOCAL_HOST: &str = "127.0.0.1";
+
+#[derive(Clone)]
+struct RemoteHost {
+    host: String,
+    lockfile: Option<Zeroizing<String>>,
+}
+
+static REMOTE_HOST: RwLock<Option<RemoteHost>> = RwLock::new(None);
+
+/// Point LCU and Live Client connections at a remote host (or back at the
+/// local machine when `settings.host` is `None`)
+pub fn set_remote_host(settings: &RemoteHostSettings) {
+    let remote = settings
+        .host
+        .as_ref()
+        .filter(|host| !host.trim().is_empty())
+        .map(|host| RemoteHost {
+            host: host.trim().to_string(),
+            lockfile: settings.lockfile.clone().map(Zeroizing::new),
+        });
+
+    match remote {
+        Some(ref remote) => info!("Reading League from remote host {}", remote.host),
+        None => info!("Reading League from the local machine"),
+    }
+    *REMOTE_HOST.write().expect("RwLock poisoned") = remote;
+}
+
+/// Whether a remote host is configured
+pub(crate) fn is_remote() -> bool {
+    REMOTE_HOST.read().expect("RwLock poisoned").is_some()
+}
+
+/// Host the League APIs are reached at
+pub(crate) fn api_host() -> String {
+    REMOTE_HOST
+        .read()
+        .expect("RwLock poisoned")
+        .as_ref()
+        .map_or_else(|| LOCAL_HOST.to_string(), |r| r.host.clone())
+}
+
+/// Configured remote host, `None` when reading the local machine
+pub(crate) fn remote_host() -> Option<String> {
+    REMOTE_HOST
+        .read()
+        .expect("RwLock poisoned")
+        .as_ref()
+        .map(|r| r.host.clone())
+}
+
+/// Lockfile contents supplied for the remote host
+pub(crate) fn remote_lockfile() -> Option<Zeroizing<String>> {
+    REMOTE_HOST
+        .read()
+        .expect("RwLock poisoned")
+        .as_ref()
+        .and_then(|r| r.lockfile.clone())
+}
+
+/// Serializes the tests that configure a remote host
+#[cfg(test)]
+static TEST_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Lets a test configure a remote host: tests holding a guard run one at a
+/// time, and the previous configuration is restored on drop
+#[cfg(test)]
+pub(crate) struct RemoteHostGuard {
+    previous: Option<RemoteHost>,
+    _lock: std::sync::MutexGuard<'static, ()>,
+}
+
+#[cfg(test)]
+impl RemoteHostGuard {
+    pub(crate) fn acquire() -> Self {
+        // A failed test poisons the lock but still restored the host
+        let lock = TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let previous = REMOTE_HOST.read().expect("RwLock poisoned").clone();
+        Self {
+            previous,
+            _lock: lock,
+        }
+    }
+}
+
+#[cfg(test)]
+impl Drop for RemoteHostGuard {
+    fn drop(&mut self) {
+        *REMOTE_HOST.write().expect("RwLock poisoned") = self.previous.take();
+    }
+}
@@ -0,0 +1,140 @@
+//! Replay API client
+//!
+//! While a replay is open in the game client (with `EnableReplayApi=1` in
+//! `game.cfg`), the game serves `/replay/*` next to the Live Client API.
+//! It exposes the playback position, the camera and render settings, and
+//! can render a range of the replay to a file itself, which gives
+//! re-recorded highlights the game's full quality instead of a capture.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::{
+    LeagueError, ReplayPlayback, ReplayPlaybackUpdate, ReplayRecording, ReplayRender, Result,
+};
+
+/// Replay API port (shared with the Live Client API)
+const REPLAY_PORT: u16 = 2999;
+
+/// Replay API base URL (on the remote gaming PC if configured)
+fn replay_url() -> String {
+    format!(
+        "https://{}:{}/replay",
+        crate::remote::api_host(),
+        REPLAY_PORT
+    )
+}
+
+/// Nothing answers on the port while no game or replay is running
+fn api_unreachable(e: reqwest::Error) -> LeagueError {
+    LeagueError::ReplayUnavailable(e.to_string())
+}
+
+pub struct ReplayApi {
+    client: reqwest::Client,
+}
+
+impl ReplayApi {
+    pub fn new() -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .use_preconfigured_tls(crate::tls::lcu_tls_config()?)
+            .timeout(std::time::Duration::from_secs(2))
+            .build()?;
+
+        Ok(Self { client })
+    }
+
+    /// Whether a replay is loaded and the Replay API is enabled
+    pub async fn is_available(&self) -> bool {
+        self.get_playback().await.is_ok()
+    }
+
+    pub async fn get_playback(&self) -> Result<ReplayPlayback> {
+        self.get("playback").await
+    }
+
+    /// Seek, pause/resume or change speed; returns the new state
+    pub async fn set_playback(&self, update: &ReplayPlaybackUpdate) -> Result<ReplayPlayback> {
+        self.post("playback", update).await
+    }
+
+    pub async fn get_render(&self) -> Result<ReplayRender> {
+        self.get("render").await
+    }
+
+    /// Move the camera or change render settings; returns the new settings
+    pub async fn set_render(&self, render: &ReplayRender) -> Result<ReplayRender> {
+        self.post("render", render).await
+    }
+
+    pub async fn get_recording(&self) -> Result<ReplayRecording> {
+        self.get("recording").await
+    }
+
+    /// Render `start_time..end_time` to a file with the game's recorder.
+    ///
+    /// Seeks to the start and resumes playback, since the game only records
+    /// while the replay plays.
+    pub async fn record_range(&self, recording: &ReplayRecording) -> Result<ReplayRecording> {
+        if recording.end_time <= recording.start_time {
+            return Err(LeagueError::InvalidRequest(format!(
+                "Empty replay range {:.1}s-{:.1}s",
+                recording.start_time, recording.end_time
+            )));
+        }
+
+        self.set_playback(&ReplayPlaybackUpdate {
+            time: Some(recording.start_time),
+            paused: Some(true),
+            speed: None,
+        })
+        .await?;
+
+        let started = self
+            .post(
+                "recording",
+                &ReplayRecording {
+                    recording: true,
+                    ..recording.clone()
+                },
+            )
+            .await?;
+
+        self.set_playback(&ReplayPlaybackUpdate {
+            paused: Some(false),
+            ..Default::default()
+        })
+        .await?;
+        Ok(started)
+    }
+
+    async fn get<T: DeserializeOwned>(&self, endpoint: &str) -> Result<T> {
+        let url = format!("{}/{}", replay_url(), endpoint);
+        let response = self.client.get(&url).send().await.map_err(api_unreachable)?;
+        Self::parse(endpoint, response).await
+    }
+
+    async fn post<B: Serialize, T: DeserializeOwned>(&self, endpoint: &str, body: &B) -> Result<T> {
+        let url = format!("{}/{}", replay_url(), endpoint);
+        let response = self
+            .client
+            .post(&url)
+            .json(body)
+            .send()
+            .await
+            .map_err(api_unreachable)?;
+        Self::parse(endpoint, response).await
+    }
+
+    /// The game answers 404 while no replay is loaded or the API is disabled
+    async fn parse<T: DeserializeOwned>(endpoint: &str, response: reqwest::Response) -> Result<T> {
+        if !response.status().is_success() {
+            return Err(LeagueError::ReplayUnavailable(format!(
+                "/replay/{} returned {}",
+                endpoint,
+                response.status()
+            )));
+        }
+        Ok(response.json().await?)
+    }
+}
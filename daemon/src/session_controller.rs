@@ -0,0 +1,296 @@
+//! Gameflow-driven session management
+//!
+//! Sessions are normally started and ended by the daemon (`on_session_start`
+//! / `on_session_end`). A daemon can instead opt into a
+//! `GameSessionController`, which follows the gameflow monitor and drives the
+//! integration itself: the pre-game rank is captured in champ select, the
+//! session starts when the game does, and the finalizer runs once the
+//! end-of-game screen is up. Spectated games are left to the daemon.
+//!
+//! The daemon opts in with `Configure { gameflow_sessions: true }`; sessions
+//! are then reported as `SessionNotification`s on the protocol channel, and
+//! the daemon's own session start/end calls are ignored.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use serde_json::Value;
+use tokio::sync::{mpsc, Mutex, RwLock};
+use tracing::{info, warn};
+
+use crate::protocol::{MatchData, SessionNotification, SessionState};
+use crate::{
+    notification_writer, BufferMetrics, GameflowEvent, GameflowMonitor, GameflowPhase,
+    LeagueIntegration, TaskSupervisor,
+};
+
+/// Supervisor task name for the controller loop
+const CONTROLLER_TASK: &str = "session_controller";
+
+/// Supervisor task writing session updates to the protocol channel
+const NOTIFY_TASK: &str = "session_notifications";
+
+/// Gameflow events buffered between the monitor and the controller
+const EVENT_BUFFER: usize = 32;
+
+/// What the controller does in response to a phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionAction {
+    /// Remember the rank before the game, for the LP change
+    CapturePreGameRank,
+    StartSession,
+    /// End the session; `Some(reason)` when no end-of-game stats will follow
+    EndSession(Option<&'static str>),
+}
+
+/// Decides session actions from the sequence of gameflow phases
+#[derive(Debug, Default)]
+pub struct SessionDriver {
+    rank_captured: bool,
+    in_session: bool,
+}
+
+impl SessionDriver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn in_session(&self) -> bool {
+        self.in_session
+    }
+
+    pub fn on_phase(&mut self, phase: GameflowPhase) -> Option<SessionAction> {
+        if self.in_session {
+            let end = match phase {
+                GameflowPhase::EndOfGame => Some(None),
+                _ if phase.abort_reason().is_some() => Some(phase.abort_reason()),
+                // Back in the client without an end-of-game screen
+                GameflowPhase::Lobby | GameflowPhase::Matchmaking | GameflowPhase::ChampSelect => {
+                    Some(None)
+                }
+                _ => None,
+            };
+            if let Some(reason) = end {
+                self.in_session = false;
+                self.rank_captured = false;
+                return Some(SessionAction::EndSession(reason));
+            }
+            return None;
+        }
+
+        match phase {
+            GameflowPhase::ChampSelect if !self.rank_captured => {
+                self.rank_captured = true;
+                Some(SessionAction::CapturePreGameRank)
+            }
+            _ if phase.is_in_game() => {
+                self.in_session = true;
+                Some(SessionAction::StartSession)
+            }
+            // Dodged: the next champ select captures the rank again
+            GameflowPhase::Lobby | GameflowPhase::Matchmaking | GameflowPhase::None => {
+                self.rank_captured = false;
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Session lifecycle reported to the daemon
+#[derive(Debug, Clone)]
+pub enum SessionUpdate {
+    /// Session started, with its context
+    Started(Option<Value>),
+    /// Session ended, with the match data if the game produced any
+    Ended(Option<MatchData>),
+}
+
+impl From<SessionUpdate> for SessionNotification {
+    fn from(update: SessionUpdate) -> Self {
+        match update {
+            SessionUpdate::Started(context) => SessionNotification {
+                state: SessionState::Started,
+                context,
+                match_data: None,
+            },
+            SessionUpdate::Ended(match_data) => SessionNotification {
+                state: SessionState::Ended,
+                context: None,
+                match_data,
+            },
+        }
+    }
+}
+
+/// Drives the integration's sessions from gameflow phase changes
+pub struct GameSessionController {
+    integration: Arc<RwLock<LeagueIntegration>>,
+    monitor: GameflowMonitor,
+    supervisor: TaskSupervisor,
+}
+
+impl GameSessionController {
+    /// Controller whose monitor and loop run on `supervisor`
+    pub fn new(integration: Arc<RwLock<LeagueIntegration>>, supervisor: TaskSupervisor) -> Self {
+        Self {
+            integration,
+            monitor: GameflowMonitor::new(supervisor.clone()),
+            supervisor,
+        }
+    }
+
+    /// Start following the gameflow, reporting session starts and ends to
+    /// the daemon as `SessionNotification`s
+    pub async fn start_notifying(&mut self) -> Result<()> {
+        let (updates_tx, updates_rx) = mpsc::channel(EVENT_BUFFER);
+        self.start(updates_tx).await?;
+
+        let updates = Arc::new(Mutex::new(updates_rx));
+        self.supervisor.spawn(NOTIFY_TASK, move || write_session_updates(Arc::clone(&updates)));
+        Ok(())
+    }
+
+    /// Start following the gameflow; session starts and ends go to `updates`
+    pub async fn start(&mut self, updates: mpsc::Sender<SessionUpdate>) -> Result<()> {
+        let (event_tx, event_rx) = mpsc::channel(EVENT_BUFFER);
+        self.monitor.start(event_tx).await?;
+
+        // Shared so a restarted loop picks up where the crashed one stopped
+        let events = Arc::new(Mutex::new(event_rx));
+        let driver = Arc::new(Mutex::new(SessionDriver::new()));
+        let integration = Arc::clone(&self.integration);
+        self.supervisor.spawn(CONTROLLER_TASK, move || {
+            run_controller(
+                Arc::clone(&events),
+                Arc::clone(&driver),
+                Arc::clone(&integration),
+                updates.clone(),
+            )
+        });
+
+        info!("Gameflow-driven session management started");
+        Ok(())
+    }
+
+    /// Backlog of the monitor's WebSocket; `None` before it first connected
+    pub fn backlog_metrics(&self) -> Option<BufferMetrics> {
+        self.monitor.backlog_metrics()
+    }
+
+    pub fn stop(&mut self) {
+        self.monitor.stop();
+        self.supervisor.stop(CONTROLLER_TASK);
+        self.supervisor.stop(NOTIFY_TASK);
+        info!("Gameflow-driven session management stopped");
+    }
+}
+
+impl Drop for GameSessionController {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+async fn write_session_updates(updates: Arc<Mutex<mpsc::Receiver<SessionUpdate>>>) {
+    let mut updates = updates.lock().await;
+    while let Some(update) = updates.recv().await {
+        notification_writer().write_session(&SessionNotification::from(update));
+    }
+}
+
+async fn run_controller(
+    events: Arc<Mutex<mpsc::Receiver<GameflowEvent>>>,
+    driver: Arc<Mutex<SessionDriver>>,
+    integration: Arc<RwLock<LeagueIntegration>>,
+    updates: mpsc::Sender<SessionUpdate>,
+) {
+    let mut events = events.lock().await;
+    while let Some(event) = events.recv().await {
+        let GameflowEvent::PhaseChanged(change) = event else {
+            continue;
+        };
+        let phase = GameflowPhase::from(change.phase.as_str());
+        let Some(action) = driver.lock().await.on_phase(phase) else {
+            continue;
+        };
+
+        info!("Gameflow {:?}: {:?}", phase, action);
+        let mut integration = integration.write().await;
+        let update = match action {
+            SessionAction::CapturePreGameRank => {
+                integration.capture_pre_game_rank().await;
+                None
+            }
+            SessionAction::StartSession => {
+                Some(SessionUpdate::Started(integration.session_start().await))
+            }
+            SessionAction::EndSession(reason) => {
+                if let Some(reason) = reason {
+                    integration.mark_aborted(reason);
+                }
+                Some(SessionUpdate::Ended(
+                    integration.session_end(Value::Null).await,
+                ))
+            }
+        };
+
+        if let Some(update) = update {
+            if updates.send(update).await.is_err() {
+                warn!("Session update receiver dropped, stopping session controller");
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_becomes_a_tagged_notification() {
+        let started = SessionNotification::from(SessionUpdate::Started(None));
+        let value = serde_json::to_value(&started).unwrap();
+        assert_eq!(value["type"], "SessionNotification");
+        assert_eq!(value["state"], "started");
+
+        let ended = SessionNotification::from(SessionUpdate::Ended(None));
+        assert_eq!(ended.state, SessionState::Ended);
+    }
+
+    #[test]
+    fn test_driver_follows_a_game() {
+        let mut driver = SessionDriver::new();
+        assert_eq!(driver.on_phase(GameflowPhase::Lobby), None);
+        assert_eq!(
+            driver.on_phase(GameflowPhase::ChampSelect),
+            Some(SessionAction::CapturePreGameRank)
+        );
+
+        // A dodge forgets the captured rank
+        assert_eq!(driver.on_phase(GameflowPhase::Lobby), None);
+        assert_eq!(
+            driver.on_phase(GameflowPhase::ChampSelect),
+            Some(SessionAction::CapturePreGameRank)
+        );
+
+        assert_eq!(
+            driver.on_phase(GameflowPhase::GameStart),
+            Some(SessionAction::StartSession)
+        );
+        assert_eq!(driver.on_phase(GameflowPhase::InProgress), None);
+        assert_eq!(driver.on_phase(GameflowPhase::WaitingForStats), None);
+        assert_eq!(
+            driver.on_phase(GameflowPhase::EndOfGame),
+            Some(SessionAction::EndSession(None))
+        );
+        assert!(!driver.in_session());
+
+        driver.on_phase(GameflowPhase::InProgress);
+        assert_eq!(
+            driver.on_phase(GameflowPhase::TerminatedInError),
+            Some(SessionAction::EndSession(Some("terminated_in_error")))
+        );
+    }
+}
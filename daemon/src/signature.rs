@@ -0,0 +1,124 @@
+//! Champion signature moments
+//!
+//! Some champions are known for one play: Lee Sin's kick, Thresh's hook,
+//! Blitzcrank's grab. When the player is on such a champion, their kills are
+//! likelier to be the clip worth keeping, so they're tagged with the moment
+//! and a raised importance. The Live Client API doesn't report ability casts,
+//! only ability ranks, so the detectable part of the pattern is that the
+//! signature ability has been learned.
+
+use serde::Serialize;
+
+use crate::{AbilityRanks, LiveMatch};
+
+/// Importance of a kill without a signature moment
+pub const BASE_IMPORTANCE: f32 = 1.0;
+
+/// Importance of a kill on a signature champion with the ability learned
+pub const SIGNATURE_IMPORTANCE: f32 = 1.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum AbilitySlot {
+    Q,
+    W,
+    E,
+    R,
+}
+
+/// A champion's signature play
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignatureMoment {
+    /// Champion name as the Live Client API reports it
+    pub champion: &'static str,
+    pub ability: AbilitySlot,
+    pub name: &'static str,
+}
+
+const fn moment(
+    champion: &'static str,
+    ability: AbilitySlot,
+    name: &'static str,
+) -> SignatureMoment {
+    SignatureMoment {
+        champion,
+        ability,
+        name,
+    }
+}
+
+/// Champions with a signature play
+const SIGNATURE_MOMENTS: &[SignatureMoment] = &[
+    moment("Lee Sin", AbilitySlot::R, "Dragon's Rage kick"),
+    moment("Thresh", AbilitySlot::Q, "Death Sentence hook"),
+    moment("Blitzcrank", AbilitySlot::Q, "Rocket Grab"),
+    moment("Pyke", AbilitySlot::Q, "Bone Skewer hook"),
+    moment("Nautilus", AbilitySlot::Q, "Dredge Line hook"),
+    moment("Ashe", AbilitySlot::R, "Enchanted Crystal Arrow"),
+    moment("Malphite", AbilitySlot::R, "Unstoppable Force"),
+    moment("Amumu", AbilitySlot::R, "Curse of the Sad Mummy"),
+    moment("Orianna", AbilitySlot::R, "Command: Shockwave"),
+    moment("Katarina", AbilitySlot::R, "Death Lotus"),
+    moment("Jhin", AbilitySlot::R, "Curtain Call"),
+    moment("Yasuo", AbilitySlot::R, "Last Breath"),
+    moment("Sett", AbilitySlot::R, "The Show Stopper"),
+    moment("Zed", AbilitySlot::R, "Death Mark"),
+];
+
+impl SignatureMoment {
+    /// Whether the signature ability has at least one rank
+    pub fn is_learned(&self, ranks: &AbilityRanks) -> bool {
+        let rank = match self.ability {
+            AbilitySlot::Q => ranks.q,
+            AbilitySlot::W => ranks.w,
+            AbilitySlot::E => ranks.e,
+            AbilitySlot::R => ranks.r,
+        };
+        rank > 0
+    }
+}
+
+/// Signature moment of a champion, if it has one
+pub fn signature_moment(champion: &str) -> Option<&'static SignatureMoment> {
+    SIGNATURE_MOMENTS
+        .iter()
+        .find(|m| m.champion.eq_ignore_ascii_case(champion))
+}
+
+/// Signature moment a kill by the player may be, from the latest snapshot
+pub fn signature_for_kill(live: &LiveMatch) -> Option<&'static SignatureMoment> {
+    signature_moment(&live.champion).filter(|m| m.is_learned(&live.ability_ranks))
+}
+
+/// Importance of a player kill given its signature moment
+pub fn kill_importance(signature: Option<&SignatureMoment>) -> f32 {
+    match signature {
+        Some(_) => SIGNATURE_IMPORTANCE,
+        None => BASE_IMPORTANCE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_needs_ability_learned() {
+        let lee_sin = signature_moment("lee sin").unwrap();
+        assert_eq!(lee_sin.ability, AbilitySlot::R);
+
+        let mut ranks = AbilityRanks {
+            q: 3,
+            w: 1,
+            e: 1,
+            r: 0,
+        };
+        assert!(!lee_sin.is_learned(&ranks));
+        ranks.r = 1;
+        assert!(lee_sin.is_learned(&ranks));
+
+        assert!(signature_moment("Ahri").is_none());
+        assert_eq!(kill_importance(Some(lee_sin)), SIGNATURE_IMPORTANCE);
+        assert_eq!(kill_importance(None), BASE_IMPORTANCE);
+    }
+}
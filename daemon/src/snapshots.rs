@@ -0,0 +1,276 @@
+//! Scoreboard snapshot history
+//!
+//! During a game the recorder samples the Live Client API's full game data
+//! at a fixed interval and keeps every player's gold, CS and KDA. When the
+//! game ends the timelines are saved in the `league_match_snapshots`
+//! directory (one JSON file per match, next to the stats file), for
+//! gold-difference graphs and power spike detection in the UI.
+//!
+//! The Live Client API only reports the active player's current gold, so a
+//! player's gold is the value of their items, like in the live scoreboard.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{debug, warn};
+
+use crate::{
+    GameData, LeagueError, LiveClientApi, Result, StatsSettings, TaskSupervisor,
+    Team,
+};
+
+/// Directory of snapshot timelines inside the data directory
+pub(crate) const SNAPSHOTS_DIR: &str = "league_match_snapshots";
+
+/// Supervisor task name for the sampling loop
+const SNAPSHOT_TASK: &str = "snapshot_recorder";
+
+/// Seconds between snapshots unless configured
+pub const DEFAULT_SNAPSHOT_INTERVAL_SECS: u64 = 30;
+
+/// One player's line on the scoreboard
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerSnapshot {
+    pub summoner_name: String,
+    pub champion: String,
+    pub team: Team,
+    pub level: i32,
+    /// Value of held items
+    pub gold: i32,
+    pub cs: i32,
+    pub kills: i32,
+    pub deaths: i32,
+    pub assists: i32,
+    /// Held items, trinket included, for how builds came together
+    #[serde(default)]
+    pub item_ids: Vec<i32>,
+}
+
+/// The scoreboard at one point in the game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoreboardSnapshot {
+    pub game_time_secs: f64,
+    pub players: Vec<PlayerSnapshot>,
+}
+
+impl ScoreboardSnapshot {
+    /// Scoreboard from game data; `None` without the game clock
+    pub fn from_game_data(data: &GameData) -> Option<Self> {
+        let players = data
+            .all_players
+            .iter()
+            .map(|p| PlayerSnapshot {
+                summoner_name: p.summoner_name.clone(),
+                champion: p.champion_name.clone(),
+                team: match p.team.to_lowercase().as_str() {
+                    "chaos" | "red" => Team::Red,
+                    _ => Team::Blue,
+                },
+                level: p.level,
+                gold: p.items.iter().map(|i| i.price * i.count.max(1)).sum(),
+                cs: p.scores.creep_score,
+                kills: p.scores.kills,
+                deaths: p.scores.deaths,
+                assists: p.scores.assists,
+                item_ids: p.items.iter().map(|i| i.item_id).collect(),
+            })
+            .collect();
+        Some(Self {
+            game_time_secs: data.game_data.as_ref()?.game_time,
+            players,
+        })
+    }
+}
+
+/// Samples the scoreboard while a game is running
+#[derive(Debug, Clone)]
+pub struct SnapshotRecorder {
+    /// `None` disables sampling
+    interval: Option<Duration>,
+    snapshots: Arc<Mutex<Vec<ScoreboardSnapshot>>>,
+}
+
+impl Default for SnapshotRecorder {
+    fn default() -> Self {
+        Self::new(&StatsSettings::default())
+    }
+}
+
+impl SnapshotRecorder {
+    pub fn new(settings: &StatsSettings) -> Self {
+        let secs = settings
+            .snapshot_interval_secs
+            .unwrap_or(DEFAULT_SNAPSHOT_INTERVAL_SECS);
+        Self {
+            interval: (secs > 0).then(|| Duration::from_secs(secs)),
+            snapshots: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Start sampling a new game, dropping anything left from the last one
+    pub fn start(&self, supervisor: &TaskSupervisor) {
+        self.snapshots.lock().expect("Mutex poisoned").clear();
+        let Some(interval) = self.interval else {
+            return;
+        };
+
+        let recorder = self.clone();
+        supervisor.spawn(SNAPSHOT_TASK, move || recorder.clone().run(interval));
+    }
+
+    /// Stop sampling and hand over the snapshots taken
+    pub fn finish(&self, supervisor: &TaskSupervisor) -> Vec<ScoreboardSnapshot> {
+        supervisor.abort(SNAPSHOT_TASK);
+        std::mem::take(&mut *self.snapshots.lock().expect("Mutex poisoned"))
+    }
+
+    fn record(&self, snapshot: ScoreboardSnapshot) {
+        let mut snapshots = self.snapshots.lock().expect("Mutex poisoned");
+        // Game time stands still while paused or in the loading screen
+        let advanced = snapshots
+            .last()
+            .is_none_or(|last| snapshot.game_time_secs > last.game_time_secs);
+        if advanced && !snapshot.players.is_empty() {
+            snapshots.push(snapshot);
+        }
+    }
+
+    async fn run(self, interval: Duration) {
+        let api = match LiveClientApi::new() {
+            Ok(api) => api,
+            Err(e) => {
+                warn!("Failed to create Live Client API for snapshots: {}", e);
+                return;
+            }
+        };
+
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            match api.get_all_game_data().await {
+                Ok(data) => match ScoreboardSnapshot::from_game_data(&data) {
+                    Some(snapshot) => self.record(snapshot),
+                    None => debug!("Scoreboard snapshot skipped: no game clock yet"),
+                },
+                Err(e) => debug!("Scoreboard snapshot skipped: {}", e),
+            }
+        }
+    }
+}
+
+/// Store of snapshot timelines, keyed by match
+#[derive(Debug, Default)]
+pub struct SnapshotStore {
+    /// `None` keeps nothing (no data directory)
+    dir: Option<PathBuf>,
+}
+
+impl SnapshotStore {
+    /// Open the store in the data directory from the given settings
+    pub fn open(settings: &StatsSettings) -> Self {
+        match settings.data_dir() {
+            Some(dir) => Self::open_at(dir.join(SNAPSHOTS_DIR)),
+            None => {
+                warn!("No data directory available, snapshots will not be persisted");
+                Self::default()
+            }
+        }
+    }
+
+    /// Open the store at an explicit directory
+    pub fn open_at(dir: PathBuf) -> Self {
+        debug!("Snapshot store at {:?}", dir);
+        Self { dir: Some(dir) }
+    }
+
+    /// Store a match's snapshots, replacing earlier ones
+    pub fn save(&self, match_id: &str, snapshots: &[ScoreboardSnapshot]) {
+        let Some(path) = self.path_for(match_id) else {
+            return;
+        };
+        if let Err(e) = write_snapshots(&path, snapshots) {
+            warn!("Failed to save snapshots to {:?}: {}", path, e);
+        }
+    }
+
+    /// Stored snapshots of a match, oldest first
+    pub fn load(&self, match_id: &str) -> Result<Vec<ScoreboardSnapshot>> {
+        let path = self
+            .path_for(match_id)
+            .ok_or_else(|| LeagueError::NotFound(format!("No snapshots for match {}", match_id)))?;
+        let content = std::fs::read_to_string(&path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => {
+                LeagueError::NotFound(format!("No snapshots for match {}", match_id))
+            }
+            _ => e.into(),
+        })?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// File of a match; `None` without a data directory or for IDs that
+    /// aren't safe as a file name
+    fn path_for(&self, match_id: &str) -> Option<PathBuf> {
+        let safe = !match_id.is_empty()
+            && match_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !safe {
+            return None;
+        }
+        Some(self.dir.as_ref()?.join(format!("{}.json", match_id)))
+    }
+}
+
+/// Write through a temp file so a crash can't leave a truncated timeline
+fn write_snapshots(path: &Path, snapshots: &[ScoreboardSnapshot]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_vec(snapshots)?)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn snapshot(game_time_secs: f64, gold: i32) -> ScoreboardSnapshot {
+        ScoreboardSnapshot {
+            game_time_secs,
+            players: vec![PlayerSnapshot {
+                summoner_name: "Me".to_string(),
+                champion: "Ahri".to_string(),
+                team: Team::Blue,
+                level: 6,
+                gold,
+                cs: 40,
+                kills: 1,
+                deaths: 0,
+                assists: 2,
+                item_ids: vec![1056, 3340],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_snapshots_skip_paused_time_and_round_trip() {
+        let recorder = SnapshotRecorder::default();
+        recorder.record(snapshot(30.0, 500));
+        recorder.record(snapshot(30.0, 500));
+        recorder.record(snapshot(60.0, 1300));
+        let snapshots = std::mem::take(&mut *recorder.snapshots.lock().unwrap());
+        assert_eq!(snapshots.len(), 2);
+
+        let dir = TempDir::new("snapshots");
+        let store = SnapshotStore::open_at(dir.path().to_path_buf());
+        store.save("42", &snapshots);
+        assert_eq!(store.load("42").unwrap(), snapshots);
+        assert!(store.load("../stats").is_err());
+    }
+}
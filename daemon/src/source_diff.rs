@@ -0,0 +1,173 @@
+//! Live data vs end-of-game stats
+//!
+//! A game that ends with both a live snapshot and end-of-game stats is
+//! described twice. The sources should agree on the player's champion, KDA
+//! and CS, and on everyone's KDA; where they don't, one of them is parsed
+//! wrong (or stale). The differences are logged and kept with the raw match
+//! so data-quality issues can be counted across users.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tracing::{debug, warn};
+
+use crate::{riot_ids_match, CreateMatch, LiveMatch};
+
+/// CS the last snapshot may trail the final count by (minions killed
+/// between the last poll and the nexus falling)
+const CS_TOLERANCE: i32 = 5;
+
+/// A value the two sources disagree on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldDiscrepancy {
+    /// e.g. "kills" or "participants.Faker#KR1.deaths"
+    pub field: String,
+    pub live: Value,
+    pub end_of_game: Value,
+}
+
+/// Differences between the last live snapshot and the end-of-game stats
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceDiff {
+    pub game_id: i64,
+    /// Game seconds between the last snapshot and the end of the game
+    pub snapshot_lag_secs: f64,
+    pub discrepancies: Vec<FieldDiscrepancy>,
+}
+
+impl SourceDiff {
+    /// Compare the last snapshot with the match parsed from end-of-game stats
+    pub fn compare(live: &LiveMatch, parsed: &CreateMatch) -> Self {
+        let mut discrepancies = Vec::new();
+        let mut check = |field: String, live: Value, end_of_game: Value, differs: bool| {
+            if differs {
+                discrepancies.push(FieldDiscrepancy {
+                    field,
+                    live,
+                    end_of_game,
+                });
+            }
+        };
+
+        check(
+            "champion".to_string(),
+            json!(live.champion),
+            json!(parsed.champion),
+            !live.champion.eq_ignore_ascii_case(&parsed.champion),
+        );
+        for (field, live_value, eog_value) in [
+            ("kills", live.kills, parsed.kills),
+            ("deaths", live.deaths, parsed.deaths),
+            ("assists", live.assists, parsed.assists),
+        ] {
+            check(
+                field.to_string(),
+                json!(live_value),
+                json!(eog_value),
+                live_value != eog_value,
+            );
+        }
+        // The snapshot can only trail, never lead
+        let cs_behind = parsed.cs - live.cs;
+        check(
+            "cs".to_string(),
+            json!(live.cs),
+            json!(parsed.cs),
+            !(0..=CS_TOLERANCE).contains(&cs_behind),
+        );
+
+        for player in &live.participants {
+            let Some(participant) = parsed
+                .participants
+                .iter()
+                .find(|p| riot_ids_match(&p.summoner_name, &player.summoner_name))
+            else {
+                check(
+                    format!("participants.{}", player.summoner_name),
+                    json!(player.champion),
+                    Value::Null,
+                    true,
+                );
+                continue;
+            };
+            let kda = |k: i32, d: i32, a: i32| json!([k, d, a]);
+            let live_kda = kda(player.kills, player.deaths, player.assists);
+            let eog_kda = kda(participant.kills, participant.deaths, participant.assists);
+            let differs = live_kda != eog_kda;
+            check(
+                format!("participants.{}.kda", player.summoner_name),
+                live_kda,
+                eog_kda,
+                differs,
+            );
+        }
+
+        Self {
+            game_id: parsed.game_id,
+            snapshot_lag_secs: (parsed.duration_secs as f64 - live.game_time_secs).max(0.0),
+            discrepancies,
+        }
+    }
+
+    /// Log the differences, if any
+    pub fn log(&self) {
+        if self.discrepancies.is_empty() {
+            debug!(
+                "Live data matches end-of-game stats for game {}",
+                self.game_id
+            );
+            return;
+        }
+        let fields: Vec<&str> = self
+            .discrepancies
+            .iter()
+            .map(|d| d.field.as_str())
+            .collect();
+        warn!(
+            "Live data differs from end-of-game stats for game {} ({:.0}s before the end): {:?}",
+            self.game_id, self.snapshot_lag_secs, fields
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kda_mismatch_and_cs_tolerance() {
+        let live: LiveMatch = serde_json::from_value(json!({
+            "summonerName": "Me", "champion": "Ahri", "level": 16,
+            "kills": 7, "deaths": 3, "assists": 9, "cs": 212, "currentGold": 900.0,
+            "gameTimeSecs": 1795.0, "gameMode": "CLASSIC", "team": "blue",
+            "items": [], "trinket": null, "spell1": null, "spell2": null, "runes": null,
+            "participants": [{
+                "summonerName": "Them", "champion": "Zed", "team": "red", "kills": 4,
+                "deaths": 6, "assists": 2, "cs": 190, "level": 15, "isDead": false
+            }],
+            "isDead": false
+        }))
+        .unwrap();
+        let parsed: CreateMatch = serde_json::from_value(json!({
+            "gameId": 1, "summonerName": "Me", "champion": "Ahri", "championLevel": 16,
+            "result": "win", "kills": 8, "deaths": 3, "assists": 9, "cs": 215,
+            "csPerMin": 7.2, "visionScore": 20, "killParticipation": 50, "damageDealt": 20000,
+            "gameMode": "CLASSIC", "playedAt": "2025-03-01T20:00:00Z", "durationSecs": 1800,
+            "lpChange": null, "rank": null, "summonerSpell1": "Flash", "summonerSpell2": "Ignite",
+            "keystoneRune": "Electrocute", "secondaryTree": "Sorcery", "items": [], "trinket": null,
+            "participants": [], "badges": []
+        }))
+        .unwrap();
+
+        let diff = SourceDiff::compare(&live, &parsed);
+        let fields: Vec<&str> = diff
+            .discrepancies
+            .iter()
+            .map(|d| d.field.as_str())
+            .collect();
+        assert_eq!(fields, vec!["kills", "participants.Them"]);
+        assert_eq!(diff.discrepancies[0].end_of_game, json!(8));
+        assert_eq!(diff.snapshot_lag_secs, 5.0);
+    }
+}
@@ -5,6 +5,11 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::protocol::{RecordingAction, RecordingHint, RecordingQuality};
+
+/// Typical champ select length
+const CHAMP_SELECT_SECS: u32 = 90;
+
 /// The current phase of the League of Legends client/game.
 ///
 /// These phases are returned by the LCU API at `/lol-gameflow/v1/gameflow-phase`.
@@ -41,9 +46,30 @@ pub enum GameflowPhase {
     TerminatedInError,
     /// Checked into a tournament
     CheckedIntoTournament,
+    /// Spectating someone else's game
+    WatchInProgress,
 }
 
 impl GameflowPhase {
+    /// All phases
+    pub const ALL: [GameflowPhase; 15] = [
+        GameflowPhase::None,
+        GameflowPhase::Lobby,
+        GameflowPhase::Matchmaking,
+        GameflowPhase::ReadyCheck,
+        GameflowPhase::ChampSelect,
+        GameflowPhase::GameStart,
+        GameflowPhase::FailedToLaunch,
+        GameflowPhase::InProgress,
+        GameflowPhase::Reconnect,
+        GameflowPhase::WaitingForStats,
+        GameflowPhase::PreEndOfGame,
+        GameflowPhase::EndOfGame,
+        GameflowPhase::TerminatedInError,
+        GameflowPhase::CheckedIntoTournament,
+        GameflowPhase::WatchInProgress,
+    ];
+
     /// Returns true if the user is actively in a game
     pub fn is_in_game(&self) -> bool {
         matches!(
@@ -52,6 +78,29 @@ impl GameflowPhase {
         )
     }
 
+    /// Why a session ended without a game, for phases where no end-of-game
+    /// stats will ever arrive
+    pub fn abort_reason(&self) -> Option<&'static str> {
+        match self {
+            GameflowPhase::FailedToLaunch => Some("failed_to_launch"),
+            GameflowPhase::TerminatedInError => Some("terminated_in_error"),
+            _ => None,
+        }
+    }
+
+    /// Returns true once the game is over (stats pending or shown)
+    pub fn is_post_game(&self) -> bool {
+        matches!(
+            self,
+            GameflowPhase::WaitingForStats | GameflowPhase::PreEndOfGame | GameflowPhase::EndOfGame
+        )
+    }
+
+    /// Returns true if the user is spectating a game
+    pub fn is_spectating(&self) -> bool {
+        matches!(self, GameflowPhase::WatchInProgress)
+    }
+
     /// Returns true if the user is in the client (not in-game)
     pub fn is_in_client(&self) -> bool {
         matches!(
@@ -78,6 +127,28 @@ impl GameflowPhase {
         )
     }
 
+    /// Locale-independent string key for this phase (e.g. "phase.in_game").
+    /// Frontends should translate this instead of matching `display_name()`.
+    pub fn string_key(&self) -> &'static str {
+        match self {
+            GameflowPhase::None => "phase.not_running",
+            GameflowPhase::Lobby => "phase.lobby",
+            GameflowPhase::Matchmaking => "phase.matchmaking",
+            GameflowPhase::ReadyCheck => "phase.ready_check",
+            GameflowPhase::ChampSelect => "phase.champ_select",
+            GameflowPhase::GameStart => "phase.game_start",
+            GameflowPhase::FailedToLaunch => "phase.failed_to_launch",
+            GameflowPhase::InProgress => "phase.in_game",
+            GameflowPhase::Reconnect => "phase.reconnect",
+            GameflowPhase::WaitingForStats => "phase.waiting_for_stats",
+            GameflowPhase::PreEndOfGame => "phase.pre_end_of_game",
+            GameflowPhase::EndOfGame => "phase.end_of_game",
+            GameflowPhase::TerminatedInError => "phase.terminated_in_error",
+            GameflowPhase::CheckedIntoTournament => "phase.tournament",
+            GameflowPhase::WatchInProgress => "phase.spectating",
+        }
+    }
+
     /// Returns the display name for this phase
     pub fn display_name(&self) -> &'static str {
         match self {
@@ -95,6 +166,7 @@ impl GameflowPhase {
             GameflowPhase::EndOfGame => "Post Game",
             GameflowPhase::TerminatedInError => "Error",
             GameflowPhase::CheckedIntoTournament => "Tournament",
+            GameflowPhase::WatchInProgress => "Spectating",
         }
     }
 }
@@ -116,6 +188,7 @@ impl From<&str> for GameflowPhase {
             "EndOfGame" => GameflowPhase::EndOfGame,
             "TerminatedInError" => GameflowPhase::TerminatedInError,
             "CheckedIntoTournament" => GameflowPhase::CheckedIntoTournament,
+            "WatchInProgress" => GameflowPhase::WatchInProgress,
             _ => GameflowPhase::None,
         }
     }
@@ -142,7 +215,9 @@ impl CaptureMode {
     /// Determine capture mode from gameflow phase
     pub fn from_phase(phase: GameflowPhase) -> Self {
         match phase {
-            GameflowPhase::InProgress | GameflowPhase::Reconnect => CaptureMode::FullscreenGame,
+            GameflowPhase::InProgress | GameflowPhase::Reconnect | GameflowPhase::WatchInProgress => {
+                CaptureMode::FullscreenGame
+            }
             GameflowPhase::ChampSelect => CaptureMode::ClientWindow,
             _ => CaptureMode::Idle,
         }
@@ -160,6 +235,61 @@ impl CaptureMode {
     }
 }
 
+/// Typical game length by mode, falling back to the map
+fn expected_game_secs(game_mode: &str, map_id: i32) -> u32 {
+    let minutes = match game_mode {
+        "TFT" => 35,
+        "ARAM" | "CHERRY" | "URF" => 20,
+        "NEXUSBLITZ" => 15,
+        "ONEFORALL" | "CLASSIC" => 30,
+        _ => match map_id {
+            12 => 20,
+            21 => 15,
+            30 => 20,
+            _ => 30,
+        },
+    };
+    minutes * 60
+}
+
+/// Recording advice for a gameflow phase, so the capture side doesn't need
+/// League-specific heuristics. `game_mode` is the API key ("" if unknown).
+pub fn recording_hint(phase: GameflowPhase, game_mode: &str, map_id: i32) -> RecordingHint {
+    let game_secs = expected_game_secs(game_mode, map_id);
+    let game_quality = if game_mode == "TFT" {
+        RecordingQuality::Standard
+    } else {
+        RecordingQuality::High
+    };
+
+    let (action, expected_duration_secs, suggested_quality) = match phase {
+        GameflowPhase::ChampSelect => (
+            RecordingAction::Start,
+            Some(CHAMP_SELECT_SECS + game_secs),
+            Some(RecordingQuality::Low),
+        ),
+        GameflowPhase::GameStart
+        | GameflowPhase::InProgress
+        | GameflowPhase::Reconnect
+        | GameflowPhase::WatchInProgress => {
+            (RecordingAction::Start, Some(game_secs), Some(game_quality))
+        }
+        GameflowPhase::WaitingForStats | GameflowPhase::PreEndOfGame => {
+            (RecordingAction::Pause, None, None)
+        }
+        _ => (RecordingAction::Stop, None, None),
+    };
+
+    RecordingHint {
+        action,
+        expected_duration_secs,
+        suggested_quality,
+        phase: phase.display_name().to_string(),
+        game_mode: (!game_mode.is_empty()).then(|| game_mode.to_string()),
+        map_id: (map_id != 0).then_some(map_id),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -173,6 +303,15 @@ mod tests {
         assert!(!GameflowPhase::Lobby.is_in_game());
     }
 
+    #[test]
+    fn test_gameflow_phase_is_post_game() {
+        assert!(GameflowPhase::WaitingForStats.is_post_game());
+        assert!(GameflowPhase::PreEndOfGame.is_post_game());
+        assert!(GameflowPhase::EndOfGame.is_post_game());
+        assert!(!GameflowPhase::InProgress.is_post_game());
+        assert!(!GameflowPhase::Lobby.is_post_game());
+    }
+
     #[test]
     fn test_gameflow_phase_is_in_client() {
         assert!(GameflowPhase::Lobby.is_in_client());
@@ -197,6 +336,15 @@ mod tests {
         assert_eq!(GameflowPhase::from("Unknown"), GameflowPhase::None);
     }
 
+    #[test]
+    fn test_gameflow_phase_spectating() {
+        let phase = GameflowPhase::from("WatchInProgress");
+        assert!(phase.is_spectating());
+        // Spectated games are never the user's own game
+        assert!(!phase.is_in_game());
+        assert!(!GameflowPhase::InProgress.is_spectating());
+    }
+
     #[test]
     fn test_capture_mode_from_phase() {
         assert_eq!(
@@ -220,6 +368,26 @@ mod tests {
         assert!(!CaptureMode::Idle.needs_staging());
     }
 
+    #[test]
+    fn test_recording_hint() {
+        let hint = recording_hint(GameflowPhase::InProgress, "ARAM", 12);
+        assert_eq!(hint.action, RecordingAction::Start);
+        assert_eq!(hint.expected_duration_secs, Some(20 * 60));
+        assert_eq!(hint.suggested_quality, Some(RecordingQuality::High));
+
+        let hint = recording_hint(GameflowPhase::ChampSelect, "TFT", 22);
+        assert_eq!(hint.expected_duration_secs, Some(90 + 35 * 60));
+        assert_eq!(hint.suggested_quality, Some(RecordingQuality::Low));
+
+        // Unknown mode falls back to the map
+        assert_eq!(
+            recording_hint(GameflowPhase::GameStart, "", 12).expected_duration_secs,
+            Some(20 * 60)
+        );
+        assert_eq!(recording_hint(GameflowPhase::PreEndOfGame, "", 0).action, RecordingAction::Pause);
+        assert_eq!(recording_hint(GameflowPhase::Lobby, "", 0).action, RecordingAction::Stop);
+    }
+
     #[test]
     fn test_capture_mode_scale_to_fill() {
         assert!(CaptureMode::FullscreenGame.scale_to_fill());
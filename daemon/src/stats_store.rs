@@ -0,0 +1,558 @@
+//! Persisted statistics
+//!
+//! Small JSON file in the pack's data directory holding stats that outlive a
+//! session (queue times, dodges, remakes, Arena results, champions played,
+//! personal bests, repeat opponents, duo partners, game results, ranked splits, hover bans,
+//! daily playtime). Writes go to a temp file and are renamed into
+//! place so a crash can't leave a truncated file behind.
+
+use chrono::{DateTime, Local, Timelike, Utc};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tracing::{debug, info, warn};
+
+use crate::{
+    ArenaMatchDetails, CreateMatch, DuoGameRecord, GameResultIndex, GameResultRecord, GameflowPhase, NonGameKind, NonGameRecord, PackStats,
+    PersonalRecord, PlaytimeSummary, QueueEstimate, QueueTimeRecord, RankedSplit, Result, RivalRecord, StatsSettings,
+};
+
+/// File name of the stats file inside the data directory
+pub(crate) const STATS_FILE: &str = "stats.json";
+
+/// Maximum records kept per table (oldest are dropped first)
+const MAX_RECORDS: usize = 500;
+
+/// Move an unreadable stats file out of the way so the next save doesn't
+/// overwrite it; it's kept as `stats.json.corrupt-<timestamp>`
+fn set_aside(path: &Path) {
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(format!(".corrupt-{}", Utc::now().format("%Y%m%d%H%M%S")));
+    match std::fs::rename(path, &backup) {
+        Ok(()) => warn!("Kept unreadable stats file as {:?}", backup),
+        Err(e) => warn!("Failed to set aside unreadable stats file {:?}: {}", path, e),
+    }
+}
+
+/// Statistics backed by a JSON file
+#[derive(Debug, Default)]
+pub struct StatsStore {
+    /// `None` keeps stats in memory only
+    path: Option<PathBuf>,
+    stats: PackStats,
+    /// Finished games by champion and mode, rebuilt on load
+    game_index: GameResultIndex,
+    /// Soft daily playtime limit
+    daily_limit_secs: Option<u64>,
+}
+
+impl StatsStore {
+    /// Open the store for the given settings, loading existing stats
+    pub fn open(settings: &StatsSettings) -> Self {
+        let mut store = match settings.data_dir() {
+            Some(dir) => Self::open_at(dir.join(STATS_FILE)),
+            None => {
+                warn!("No data directory available, stats will not be persisted");
+                Self::default()
+            }
+        };
+        store.set_daily_limit(settings.daily_playtime_limit_mins);
+        store
+    }
+
+    /// Soft daily playtime limit in minutes (`None` or 0 turns it off)
+    pub fn set_daily_limit(&mut self, mins: Option<u32>) {
+        self.daily_limit_secs = mins.filter(|&mins| mins > 0).map(|mins| mins as u64 * 60);
+    }
+
+    /// Open the store at an explicit file path
+    pub fn open_at(path: PathBuf) -> Self {
+        let mut stats = match std::fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                warn!("Ignoring unreadable stats file {:?}: {}", path, e);
+                set_aside(&path);
+                PackStats::default()
+            }),
+            Err(_) => PackStats::default(),
+        };
+        // Older files keyed opponents by raw Riot ID
+        stats.rivalries.rekey();
+
+        let game_index = GameResultIndex::build(&stats.game_results);
+
+        debug!("Stats store at {:?}", path);
+        Self {
+            path: Some(path),
+            stats,
+            game_index,
+            daily_limit_secs: None,
+        }
+    }
+
+    /// Current statistics
+    pub fn stats(&self) -> &PackStats {
+        &self.stats
+    }
+
+    /// Record a completed queue and persist
+    pub fn record_queue_time(&mut self, record: QueueTimeRecord) {
+        info!(
+            "Queue {} ({}) took {:.0}s",
+            record.queue_name, record.queue_id, record.wait_secs
+        );
+
+        push_capped(&mut self.stats.queue_times, record);
+        self.persist();
+    }
+
+    /// Estimated wait in the queue `timer` is timing, from the waits
+    /// recorded around the hour matchmaking started
+    pub fn queue_estimate(&self, timer: &QueueTimer) -> Option<QueueEstimate> {
+        let (queue_id, started_at, elapsed_secs) = timer.current()?;
+        let local_hour = started_at.with_timezone(&Local).hour();
+        self.stats.queue_estimate(queue_id, local_hour, elapsed_secs)
+    }
+
+    /// Record a dodged lobby or remade game and persist
+    pub fn record_non_game(&mut self, record: NonGameRecord) {
+        info!(
+            "Recorded {:?} in queue {} ({:.0}s lost)",
+            record.kind, record.queue_id, record.lost_secs
+        );
+
+        push_capped(&mut self.stats.non_games, record);
+        self.persist();
+    }
+
+    /// Record a finished Arena game and persist
+    pub fn record_arena_match(&mut self, details: ArenaMatchDetails) {
+        info!(
+            "Arena game {} finished in place {} on {}",
+            details.game_id, details.placement, details.champion
+        );
+
+        push_capped(&mut self.stats.arena_match_details, details);
+        self.persist();
+    }
+
+    /// Count a finished game on `champion` and persist. Returns true if it
+    /// was the first one; never for the very first recorded game, since an
+    /// empty history says nothing about what was played before.
+    pub fn record_champion_play(&mut self, champion: &str) -> bool {
+        let history_known = !self.stats.champion_games.is_empty();
+        let games = self.stats.champion_games.entry(champion.to_string()).or_insert(0);
+        *games += 1;
+        let first_time = history_known && *games == 1;
+        self.persist();
+
+        if first_time {
+            info!("First recorded game on {}", champion);
+        }
+        first_time
+    }
+
+    /// Count an enemy ban of the champion the player was hovering and persist
+    pub fn record_hover_ban(&mut self, champion: &str) {
+        *self.stats.hover_bans.entry(champion.to_string()).or_insert(0) += 1;
+        info!("Hovered champion {} was banned", champion);
+        self.persist();
+    }
+
+    /// Compare a finished game against the personal bests, persist the new
+    /// bests and return the records it broke
+    pub fn record_personal_bests(&mut self, game: &CreateMatch) -> Vec<PersonalRecord> {
+        let broken = self.stats.personal_bests.update(game);
+        self.persist();
+
+        if !broken.is_empty() {
+            info!("Personal records broken: {:?}", broken);
+        }
+        broken
+    }
+
+    /// Count a finished game against its opponents, persist and return the
+    /// head-to-head records of opponents met before
+    pub fn record_opponents(&mut self, game: &CreateMatch) -> Vec<RivalRecord> {
+        let repeat = self.stats.rivalries.update(game);
+        self.persist();
+
+        for rival in &repeat {
+            info!(
+                "Met {} again ({} games, {}-{})",
+                rival.riot_id, rival.games, rival.wins, rival.losses
+            );
+        }
+        repeat
+    }
+
+    /// Record the game for each party member on the player's team and persist
+    pub fn record_duo_games(&mut self, game: &CreateMatch, premades: &[String]) {
+        let records = DuoGameRecord::from_match(game, premades);
+        if records.is_empty() {
+            return;
+        }
+
+        for record in records {
+            info!("Played with {} ({})", record.partner, if record.won { "win" } else { "loss" });
+            push_capped(&mut self.stats.duo_games, record);
+        }
+        self.persist();
+    }
+
+    /// Record a finished game's result (remakes are skipped) and persist
+    pub fn record_game_result(&mut self, game: &CreateMatch) {
+        let local_hour = game.played_at.with_timezone(&Local).hour();
+        if let Some(record) = GameResultRecord::from_match(game, local_hour) {
+            self.game_index.push(&record);
+            let dropped = push_capped(&mut self.stats.game_results, record);
+            self.game_index.drop_oldest(dropped);
+            self.persist();
+        }
+    }
+
+    /// Add a finished session's playtime to the days it spanned and persist.
+    /// Returns true if it took today's playtime over the daily limit.
+    pub fn record_playtime(&mut self, started_at: DateTime<Utc>, ended_at: DateTime<Utc>) -> bool {
+        let today = ended_at.with_timezone(&Local).date_naive();
+        let before = self.stats.playtime_on(today);
+
+        self.stats.add_playtime(
+            started_at.with_timezone(&Local).naive_local(),
+            ended_at.with_timezone(&Local).naive_local(),
+        );
+        let excess = self.stats.daily_playtime.len().saturating_sub(MAX_RECORDS);
+        self.stats.daily_playtime.drain(..excess);
+        self.persist();
+
+        let after = self.stats.playtime_on(today);
+        debug!("Played {}s today", after);
+        self.daily_limit_secs.is_some_and(|limit| before < limit && after >= limit)
+    }
+
+    /// Playtime today and on the `days - 1` days before
+    pub fn playtime_summary(&self, days: u32) -> PlaytimeSummary {
+        let today = Local::now().date_naive();
+        self.stats.playtime_summary(today, days, self.daily_limit_secs)
+    }
+
+    /// Merge game results imported from the match history (games already
+    /// stored are skipped), keeping the newest ones; how many were new
+    pub fn import_game_results(&mut self, records: Vec<GameResultRecord>) -> usize {
+        let mut known: HashSet<i64> = self.stats.game_results.iter().map(|r| r.game_id).collect();
+        let before = self.stats.game_results.len();
+        self.stats
+            .game_results
+            .extend(records.into_iter().filter(|r| known.insert(r.game_id)));
+        let added = self.stats.game_results.len() - before;
+        if added == 0 {
+            return 0;
+        }
+
+        self.stats.game_results.sort_by_key(|r| r.played_at);
+        let excess = self.stats.game_results.len().saturating_sub(MAX_RECORDS);
+        self.stats.game_results.drain(..excess);
+        self.game_index = GameResultIndex::build(&self.stats.game_results);
+        info!("Imported {} games from match history", added);
+        self.persist();
+        added
+    }
+
+    /// Game IDs of the stored game results
+    pub fn game_result_ids(&self) -> HashSet<i64> {
+        self.stats.game_results.iter().map(|r| r.game_id).collect()
+    }
+
+    /// Finished games on a champion (in a game mode, if given) that ended
+    /// in `since..until`, oldest first
+    pub fn champion_games(
+        &self,
+        champion: &str,
+        game_mode: Option<&str>,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+    ) -> Vec<&GameResultRecord> {
+        self.game_index
+            .query(&self.stats.game_results, champion, game_mode, since, until)
+    }
+
+    /// Merge the splits reported by the client and persist if any changed
+    pub fn record_ranked_splits(&mut self, splits: Vec<RankedSplit>) {
+        if self.stats.update_ranked_splits(splits) {
+            info!("Ranked splits updated: {} known", self.stats.ranked_splits.len());
+            self.persist();
+        }
+    }
+
+    fn persist(&self) {
+        if let Some(ref path) = self.path {
+            if let Err(e) = write_stats(path, &self.stats) {
+                warn!("Failed to save stats to {:?}: {}", path, e);
+            }
+        }
+    }
+}
+
+/// Append a record, dropping the oldest beyond `MAX_RECORDS`; returns how many were dropped
+fn push_capped<T>(records: &mut Vec<T>, record: T) -> usize {
+    records.push(record);
+    if records.len() <= MAX_RECORDS {
+        return 0;
+    }
+    let excess = records.len() - MAX_RECORDS;
+    records.drain(..excess);
+    excess
+}
+
+fn write_stats(path: &Path, stats: &PackStats) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_vec_pretty(stats)?)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Queue the player is searching in
+#[derive(Debug, Clone, Default)]
+pub struct QueueInfo {
+    pub queue_id: i32,
+    pub queue_name: String,
+}
+
+/// Measures Matchmaking -> ReadyCheck durations from gameflow phases
+#[derive(Debug, Default)]
+pub struct QueueTimer {
+    started: Option<(Instant, DateTime<Utc>, QueueInfo)>,
+}
+
+impl QueueTimer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether a queue is being timed
+    pub fn is_timing(&self) -> bool {
+        self.started.is_some()
+    }
+
+    /// Queue being timed, when matchmaking started and seconds waited so far
+    pub fn current(&self) -> Option<(i32, DateTime<Utc>, f64)> {
+        let (started, started_at, queue) = self.started.as_ref()?;
+        Some((queue.queue_id, *started_at, started.elapsed().as_secs_f64()))
+    }
+
+    /// Feed the current phase. Returns a record when a match was found.
+    ///
+    /// `queue` is only used when matchmaking starts.
+    pub fn on_phase(
+        &mut self,
+        phase: GameflowPhase,
+        queue: QueueInfo,
+    ) -> Option<QueueTimeRecord> {
+        self.on_phase_at(phase, Instant::now(), queue)
+    }
+
+    fn on_phase_at(
+        &mut self,
+        phase: GameflowPhase,
+        now: Instant,
+        queue: QueueInfo,
+    ) -> Option<QueueTimeRecord> {
+        match phase {
+            GameflowPhase::Matchmaking => {
+                if self.started.is_none() {
+                    self.started = Some((now, Utc::now(), queue));
+                }
+                None
+            }
+            GameflowPhase::ReadyCheck => {
+                let (started, started_at, info) = self.started.take()?;
+                Some(QueueTimeRecord {
+                    queue_id: info.queue_id,
+                    queue_name: info.queue_name,
+                    started_at,
+                    wait_secs: now.saturating_duration_since(started).as_secs_f64(),
+                    local_hour: Some(started_at.with_timezone(&Local).hour()),
+                })
+            }
+            // Queue cancelled or client left matchmaking
+            _ => {
+                self.started = None;
+                None
+            }
+        }
+    }
+}
+
+/// Detects champ selects that end without a game
+///
+/// Who dodged is inferred from the phase the client moves to: another
+/// player's dodge puts everyone back in queue, while dodging yourself drops
+/// you back to the lobby.
+#[derive(Debug, Default)]
+pub struct DodgeTracker {
+    champ_select: Option<(Instant, i32)>,
+}
+
+impl DodgeTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the player is in champ select
+    pub fn in_champ_select(&self) -> bool {
+        self.champ_select.is_some()
+    }
+
+    /// Feed the current phase. Returns a record when champ select was dodged.
+    ///
+    /// `queue_id` is only used when champ select starts.
+    pub fn on_phase(&mut self, phase: GameflowPhase, queue_id: i32) -> Option<NonGameRecord> {
+        self.on_phase_at(phase, Instant::now(), queue_id)
+    }
+
+    fn on_phase_at(
+        &mut self,
+        phase: GameflowPhase,
+        now: Instant,
+        queue_id: i32,
+    ) -> Option<NonGameRecord> {
+        if phase == GameflowPhase::ChampSelect {
+            if self.champ_select.is_none() {
+                self.champ_select = Some((now, queue_id));
+            }
+            return None;
+        }
+
+        let (started, queue_id) = self.champ_select.take()?;
+        let kind = match phase {
+            GameflowPhase::Matchmaking | GameflowPhase::ReadyCheck => NonGameKind::OtherDodge,
+            GameflowPhase::Lobby => NonGameKind::SelfDodge,
+            GameflowPhase::None | GameflowPhase::TerminatedInError => NonGameKind::Dodge,
+            // Game is loading
+            _ => return None,
+        };
+
+        Some(NonGameRecord {
+            kind,
+            queue_id,
+            occurred_at: Utc::now(),
+            lost_secs: now.saturating_duration_since(started).as_secs_f64(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+    use std::time::Duration;
+
+    fn ranked() -> QueueInfo {
+        QueueInfo {
+            queue_id: 420,
+            queue_name: "Ranked Solo/Duo".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_queue_timer_records_match_found() {
+        let mut timer = QueueTimer::new();
+        let start = Instant::now();
+
+        assert!(timer.on_phase_at(GameflowPhase::Matchmaking, start, ranked()).is_none());
+        // Repeated polls during matchmaking don't restart the timer
+        assert!(timer
+            .on_phase_at(GameflowPhase::Matchmaking, start + Duration::from_secs(30), ranked())
+            .is_none());
+
+        let record = timer
+            .on_phase_at(GameflowPhase::ReadyCheck, start + Duration::from_secs(95), ranked())
+            .unwrap();
+        assert_eq!(record.queue_id, 420);
+        assert_eq!(record.wait_secs, 95.0);
+        assert!(!timer.is_timing());
+    }
+
+    #[test]
+    fn test_queue_timer_cancelled() {
+        let mut timer = QueueTimer::new();
+        let start = Instant::now();
+
+        timer.on_phase_at(GameflowPhase::Matchmaking, start, ranked());
+        timer.on_phase_at(GameflowPhase::Lobby, start, ranked());
+        assert!(timer.on_phase_at(GameflowPhase::ReadyCheck, start, ranked()).is_none());
+    }
+
+    #[test]
+    fn test_dodge_tracker_attribution() {
+        let mut tracker = DodgeTracker::new();
+        let start = Instant::now();
+
+        tracker.on_phase_at(GameflowPhase::ChampSelect, start, 420);
+        let record = tracker
+            .on_phase_at(GameflowPhase::Matchmaking, start + Duration::from_secs(40), 0)
+            .unwrap();
+        assert_eq!(record.kind, NonGameKind::OtherDodge);
+        assert_eq!(record.queue_id, 420);
+        assert_eq!(record.lost_secs, 40.0);
+
+        tracker.on_phase_at(GameflowPhase::ChampSelect, start, 420);
+        let record = tracker.on_phase_at(GameflowPhase::Lobby, start, 0).unwrap();
+        assert_eq!(record.kind, NonGameKind::SelfDodge);
+
+        // Champ select that leads into a game isn't a dodge
+        tracker.on_phase_at(GameflowPhase::ChampSelect, start, 420);
+        assert!(tracker.on_phase_at(GameflowPhase::GameStart, start, 0).is_none());
+        assert!(!tracker.in_champ_select());
+    }
+
+    #[test]
+    fn test_store_persists_and_reloads() {
+        let dir = TempDir::new("stats");
+        let path = dir.join(STATS_FILE);
+
+        let mut store = StatsStore::open_at(path.clone());
+        store.record_queue_time(QueueTimeRecord {
+            queue_id: 420,
+            queue_name: "Ranked Solo/Duo".to_string(),
+            started_at: Utc::now(),
+            wait_secs: 42.0,
+            local_hour: Some(21),
+        });
+
+        let reloaded = StatsStore::open_at(path.clone());
+        assert_eq!(reloaded.stats().queue_times.len(), 1);
+    }
+
+    #[test]
+    fn test_unreadable_file_is_set_aside() {
+        let dir = TempDir::new("stats");
+        let path = dir.join(STATS_FILE);
+        std::fs::write(&path, "{ not json").unwrap();
+
+        let store = StatsStore::open_at(path.clone());
+        assert!(store.stats().queue_times.is_empty());
+        assert!(!path.exists());
+
+        let backups: Vec<String> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(backups.len(), 1);
+        assert!(backups[0].starts_with("stats.json.corrupt-"));
+        assert_eq!(std::fs::read_to_string(dir.join(&backups[0])).unwrap(), "{ not json");
+    }
+
+    #[test]
+    fn test_first_time_champion() {
+        let mut store = StatsStore::default();
+
+        // No history yet: can't tell whether it's a first game
+        assert!(!store.record_champion_play("Ahri"));
+        assert!(store.record_champion_play("Zed"));
+        assert!(!store.record_champion_play("Zed"));
+        assert_eq!(store.stats().champion_games["Zed"], 2);
+    }
+}
@@ -0,0 +1,511 @@
+//! Data directory maintenance
+//!
+//! Match history isn't kept in a database but in files next to the stats
+//! file: a JSON-lines log, a directory of per-match JSON-lines logs and two
+//! directories of per-match JSON. Logs
+//! collect lines torn by crashes and directories collect temp files left by
+//! interrupted writes, and every history read pays for both. This reports
+//! what each store holds, checks that every record still parses, and
+//! compacts the stores, the file counterpart of a VACUUM. The one index,
+//! game results by champion and mode (`GameResultIndex`), lives in memory
+//! and is rebuilt whenever the stats file loads, so there is no on-disk
+//! index for an ANALYZE to refresh.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, warn};
+
+use crate::{Result, StatsSettings};
+
+/// Problems listed per store by an integrity check (the rest are counted)
+const MAX_PROBLEMS: usize = 20;
+
+/// How a store keeps its records
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StoreLayout {
+    /// One JSON document
+    Document,
+    /// One JSON record per line
+    Lines,
+    /// One JSON file per match
+    Directory,
+    /// One JSON-lines log per match
+    LogDirectory,
+}
+
+/// Every store in the data directory: name, file or directory name, layout
+const STORES: &[(&str, &str, StoreLayout)] = &[
+    (
+        "stats",
+        crate::stats_store::STATS_FILE,
+        StoreLayout::Document,
+    ),
+    (
+        "league_game_events",
+        crate::event_log::EVENTS_DIR,
+        StoreLayout::LogDirectory,
+    ),
+    (
+        "league_event_discovery",
+        crate::event_discovery::DISCOVERY_FILE,
+        StoreLayout::Lines,
+    ),
+    (
+        "league_match_raw",
+        crate::raw_matches::RAW_MATCHES_DIR,
+        StoreLayout::Directory,
+    ),
+    (
+        "league_match_snapshots",
+        crate::snapshots::SNAPSHOTS_DIR,
+        StoreLayout::Directory,
+    ),
+];
+
+/// Size and record count of one store
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreReport {
+    pub name: &'static str,
+    pub layout: StoreLayout,
+    pub path: PathBuf,
+    /// Whether anything has been stored yet
+    pub exists: bool,
+    pub bytes: u64,
+    /// Lines of a log, files of a directory, or entries across the stats
+    /// file's tables
+    pub rows: u64,
+    /// Entries per table (stats file only)
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub tables: BTreeMap<String, u64>,
+    /// Temp files left by interrupted writes
+    pub stray_files: u64,
+}
+
+/// What the data directory holds
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageReport {
+    /// `None` when nothing is persisted
+    pub data_dir: Option<PathBuf>,
+    pub total_bytes: u64,
+    pub stores: Vec<StoreReport>,
+}
+
+/// Records of one store that no longer parse
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreIntegrity {
+    pub name: &'static str,
+    pub checked_rows: u64,
+    pub unreadable_rows: u64,
+    /// The first unreadable records, with what's wrong with them
+    pub problems: Vec<String>,
+}
+
+/// Outcome of an integrity check
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityReport {
+    /// Whether every record of every store parsed
+    pub ok: bool,
+    pub stores: Vec<StoreIntegrity>,
+}
+
+/// What compacting one store changed
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreCompaction {
+    pub name: &'static str,
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+    /// Unreadable log lines dropped
+    pub rows_dropped: u64,
+    pub stray_files_removed: u64,
+}
+
+/// Outcome of compacting the data directory
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompactionReport {
+    pub reclaimed_bytes: u64,
+    pub stores: Vec<StoreCompaction>,
+}
+
+/// Maintenance over the stores in the data directory
+#[derive(Debug, Default)]
+pub struct DataStorage {
+    /// `None` without a data directory
+    dir: Option<PathBuf>,
+}
+
+impl DataStorage {
+    /// The data directory from the given settings
+    pub fn open(settings: &StatsSettings) -> Self {
+        match settings.data_dir() {
+            Some(dir) => Self::open_at(dir),
+            None => Self::default(),
+        }
+    }
+
+    /// An explicit data directory
+    pub fn open_at(dir: PathBuf) -> Self {
+        debug!("Data storage at {:?}", dir);
+        Self { dir: Some(dir) }
+    }
+
+    /// Stores with their paths (none without a data directory)
+    fn stores(&self) -> impl Iterator<Item = (&'static str, PathBuf, StoreLayout)> + '_ {
+        self.dir.iter().flat_map(|dir| {
+            STORES
+                .iter()
+                .map(move |&(name, file, layout)| (name, dir.join(file), layout))
+        })
+    }
+
+    /// Size and record count of every store
+    pub fn report(&self) -> Result<StorageReport> {
+        let mut stores = Vec::new();
+        for (name, path, layout) in self.stores() {
+            stores.push(report_store(name, path, layout)?);
+        }
+        Ok(StorageReport {
+            data_dir: self.dir.clone(),
+            total_bytes: stores.iter().map(|s| s.bytes).sum(),
+            stores,
+        })
+    }
+
+    /// Parse every record of every store
+    pub fn check_integrity(&self) -> Result<IntegrityReport> {
+        let mut stores = Vec::new();
+        for (name, path, layout) in self.stores() {
+            stores.push(check_store(name, &path, layout)?);
+        }
+        Ok(IntegrityReport {
+            ok: stores.iter().all(|s| s.unreadable_rows == 0),
+            stores,
+        })
+    }
+
+    /// Drop unreadable log lines and temp files left by interrupted writes.
+    /// Unreadable match files are kept, since they may still be recoverable.
+    pub fn compact(&self) -> Result<CompactionReport> {
+        let mut stores = Vec::new();
+        for (name, path, layout) in self.stores() {
+            let compaction = compact_store(name, &path, layout)?;
+            if compaction.rows_dropped > 0 || compaction.stray_files_removed > 0 {
+                info!(
+                    "Compacted {}: dropped {} unreadable rows, removed {} temp files",
+                    name, compaction.rows_dropped, compaction.stray_files_removed
+                );
+            }
+            stores.push(compaction);
+        }
+        Ok(CompactionReport {
+            reclaimed_bytes: stores
+                .iter()
+                .map(|s| s.bytes_before.saturating_sub(s.bytes_after))
+                .sum(),
+            stores,
+        })
+    }
+}
+
+/// Temp file a document is written through
+fn document_temp(path: &Path) -> PathBuf {
+    path.with_extension("json.tmp")
+}
+
+fn is_temp_file(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "tmp")
+}
+
+/// Files of a directory store; empty if it doesn't exist yet
+fn dir_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut files = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        if entry.file_type()?.is_file() {
+            files.push(entry.path());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn file_size(path: &Path) -> u64 {
+    std::fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+}
+
+/// Bytes a store takes, temp files included
+fn store_bytes(path: &Path, layout: StoreLayout) -> Result<u64> {
+    Ok(match layout {
+        StoreLayout::Document => file_size(path) + file_size(&document_temp(path)),
+        StoreLayout::Lines => file_size(path),
+        StoreLayout::Directory | StoreLayout::LogDirectory => {
+            dir_files(path)?.iter().map(|f| file_size(f)).sum()
+        }
+    })
+}
+
+fn report_store(name: &'static str, path: PathBuf, layout: StoreLayout) -> Result<StoreReport> {
+    let mut report = StoreReport {
+        name,
+        layout,
+        exists: path.exists(),
+        bytes: store_bytes(&path, layout)?,
+        rows: 0,
+        tables: BTreeMap::new(),
+        stray_files: 0,
+        path,
+    };
+    if !report.exists {
+        return Ok(report);
+    }
+
+    match layout {
+        StoreLayout::Document => {
+            report.stray_files = document_temp(&report.path).exists() as u64;
+            // Only entry counts are reported, an unreadable file shows up
+            // in the integrity check
+            let content = std::fs::read_to_string(&report.path)?;
+            if let Ok(serde_json::Value::Object(tables)) = serde_json::from_str(&content) {
+                for (table, value) in tables {
+                    let entries = match value {
+                        serde_json::Value::Array(a) => a.len(),
+                        serde_json::Value::Object(o) => o.len(),
+                        _ => continue,
+                    };
+                    report.tables.insert(table, entries as u64);
+                }
+            }
+            report.rows = report.tables.values().sum();
+        }
+        StoreLayout::Lines => report.rows = count_lines(&report.path)?,
+        StoreLayout::Directory => {
+            for file in dir_files(&report.path)? {
+                if is_temp_file(&file) {
+                    report.stray_files += 1;
+                } else {
+                    report.rows += 1;
+                }
+            }
+        }
+        StoreLayout::LogDirectory => {
+            for file in dir_files(&report.path)? {
+                if is_temp_file(&file) {
+                    report.stray_files += 1;
+                } else {
+                    report.rows += count_lines(&file)?;
+                }
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Non-blank lines of a log
+fn count_lines(path: &Path) -> Result<u64> {
+    let mut rows = 0;
+    for line in BufReader::new(std::fs::File::open(path)?).lines() {
+        if !line?.trim().is_empty() {
+            rows += 1;
+        }
+    }
+    Ok(rows)
+}
+
+fn check_store(name: &'static str, path: &Path, layout: StoreLayout) -> Result<StoreIntegrity> {
+    let mut integrity = StoreIntegrity {
+        name,
+        checked_rows: 0,
+        unreadable_rows: 0,
+        problems: Vec::new(),
+    };
+    let mut record =
+        |what: String, parsed: std::result::Result<serde_json::Value, serde_json::Error>| {
+            integrity.checked_rows += 1;
+            if let Err(e) = parsed {
+                integrity.unreadable_rows += 1;
+                if integrity.problems.len() < MAX_PROBLEMS {
+                    integrity.problems.push(format!("{}: {}", what, e));
+                }
+            }
+        };
+
+    match layout {
+        StoreLayout::Document => match std::fs::read_to_string(path) {
+            Ok(content) => record(name.to_string(), serde_json::from_str(&content)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        },
+        StoreLayout::Lines => {
+            let file = match std::fs::File::open(path) {
+                Ok(file) => file,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(integrity),
+                Err(e) => return Err(e.into()),
+            };
+            for (number, line) in BufReader::new(file).lines().enumerate() {
+                let line = line?;
+                if !line.trim().is_empty() {
+                    record(format!("line {}", number + 1), serde_json::from_str(&line));
+                }
+            }
+        }
+        StoreLayout::LogDirectory => {
+            for file in dir_files(path)? {
+                if is_temp_file(&file) {
+                    continue;
+                }
+                let file_name = file.file_name().unwrap_or_default().to_string_lossy();
+                let lines = BufReader::new(std::fs::File::open(&file)?).lines();
+                for (number, line) in lines.enumerate() {
+                    let line = line?;
+                    if !line.trim().is_empty() {
+                        let what = format!("{} line {}", file_name, number + 1);
+                        record(what, serde_json::from_str(&line));
+                    }
+                }
+            }
+        }
+        StoreLayout::Directory => {
+            for file in dir_files(path)? {
+                if is_temp_file(&file) {
+                    continue;
+                }
+                let content = std::fs::read_to_string(&file)?;
+                let file_name = file.file_name().unwrap_or_default().to_string_lossy();
+                record(file_name.into_owned(), serde_json::from_str(&content));
+            }
+        }
+    }
+    Ok(integrity)
+}
+
+fn compact_store(name: &'static str, path: &Path, layout: StoreLayout) -> Result<StoreCompaction> {
+    let mut compaction = StoreCompaction {
+        name,
+        bytes_before: store_bytes(path, layout)?,
+        bytes_after: 0,
+        rows_dropped: 0,
+        stray_files_removed: 0,
+    };
+
+    match layout {
+        StoreLayout::Document => {
+            let tmp = document_temp(path);
+            if tmp.exists() {
+                std::fs::remove_file(&tmp)?;
+                compaction.stray_files_removed += 1;
+            }
+        }
+        StoreLayout::Lines => {
+            compaction.rows_dropped = compact_lines(path)?;
+        }
+        StoreLayout::Directory | StoreLayout::LogDirectory => {
+            for file in dir_files(path)? {
+                if is_temp_file(&file) {
+                    match std::fs::remove_file(&file) {
+                        Ok(()) => compaction.stray_files_removed += 1,
+                        Err(e) => warn!("Failed to remove {:?}: {}", file, e),
+                    }
+                } else if layout == StoreLayout::LogDirectory {
+                    compaction.rows_dropped += compact_lines(&file)?;
+                }
+            }
+        }
+    }
+
+    compaction.bytes_after = store_bytes(path, layout)?;
+    Ok(compaction)
+}
+
+/// Rewrite a log without its unreadable and blank lines; unreadable lines
+/// dropped
+fn compact_lines(path: &Path) -> Result<u64> {
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut kept = Vec::new();
+    let mut dropped = 0;
+    let mut blank = false;
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            blank = true;
+        } else if serde_json::from_str::<serde_json::Value>(&line).is_ok() {
+            kept.extend_from_slice(line.as_bytes());
+            kept.push(b'\n');
+        } else {
+            dropped += 1;
+        }
+    }
+    if dropped == 0 && !blank {
+        return Ok(0);
+    }
+
+    // Through a temp file so a crash can't lose the readable lines
+    let tmp = path.with_extension("jsonl.tmp");
+    let mut out = std::fs::File::create(&tmp)?;
+    out.write_all(&kept)?;
+    out.sync_all()?;
+    std::fs::rename(&tmp, path)?;
+    Ok(dropped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_report_check_and_compact() {
+        let dir = TempDir::new("storage");
+        std::fs::create_dir_all(dir.join("league_match_raw")).unwrap();
+        std::fs::create_dir_all(dir.join("league_game_events")).unwrap();
+        std::fs::write(
+            dir.join("stats.json"),
+            r#"{"matches": [1, 2], "plays": {"Ahri": 3}}"#,
+        )
+        .unwrap();
+        let events_dir = dir.join("league_game_events");
+        std::fs::write(events_dir.join("1.jsonl"), "{\"matchId\":\"1\"}\n").unwrap();
+        std::fs::write(events_dir.join("2.jsonl"), "{\"matchId\":\"2\"}\n{\"matchI").unwrap();
+        std::fs::write(dir.join("league_match_raw").join("1.json"), "{}").unwrap();
+        std::fs::write(dir.join("league_match_raw").join("2.json.tmp"), "{").unwrap();
+        let storage = DataStorage::open_at(dir.path().to_path_buf());
+
+        let report = storage.report().unwrap();
+        let stats = &report.stores[0];
+        assert_eq!(stats.rows, 3);
+        assert_eq!(stats.tables["plays"], 1);
+        let events = &report.stores[1];
+        assert_eq!(events.rows, 3);
+        assert!(!report.stores[2].exists);
+        let raw = &report.stores[3];
+        assert_eq!((raw.rows, raw.stray_files), (1, 1));
+
+        let integrity = storage.check_integrity().unwrap();
+        assert!(!integrity.ok);
+        assert_eq!(integrity.stores[1].checked_rows, 3);
+        assert_eq!(integrity.stores[1].unreadable_rows, 1);
+
+        let compaction = storage.compact().unwrap();
+        assert_eq!(compaction.stores[1].rows_dropped, 1);
+        assert_eq!(compaction.stores[3].stray_files_removed, 1);
+        assert!(compaction.reclaimed_bytes > 0);
+        assert!(storage.check_integrity().unwrap().ok);
+    }
+}
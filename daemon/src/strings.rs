@@ -0,0 +1,119 @@
+//! Localizable display strings
+//!
+//! Phases and badges carry a stable string key (`phase.in_game`,
+//! `badge.perfect`). Frontends translate the key; the built-in English table
+//! is derived from the same enums so keys and labels can't drift apart.
+//! A [`StringTable`] can layer a locale's translations over the English text.
+
+use serde_json::Value;
+use std::collections::HashMap;
+
+use crate::{Badge, GameflowPhase, LeagueError, LocaleStrings, Result};
+
+/// Built-in English text for a string key
+pub fn english(key: &str) -> Option<&'static str> {
+    GameflowPhase::ALL
+        .iter()
+        .find(|p| p.string_key() == key)
+        .map(|p| p.display_name())
+        .or_else(|| {
+            Badge::ALL
+                .iter()
+                .find(|b| b.string_key() == key)
+                .map(|b| b.label())
+        })
+}
+
+/// All known string keys with their English text
+pub fn english_table() -> Vec<(&'static str, &'static str)> {
+    GameflowPhase::ALL
+        .iter()
+        .map(|p| (p.string_key(), p.display_name()))
+        .chain(Badge::ALL.iter().map(|b| (b.string_key(), b.label())))
+        .collect()
+}
+
+/// Locale table of translated strings, falling back to English
+#[derive(Debug, Clone, Default)]
+pub struct StringTable {
+    locale: String,
+    strings: HashMap<String, String>,
+}
+
+impl StringTable {
+    /// English-only table
+    pub fn english() -> Self {
+        Self {
+            locale: "en_US".to_string(),
+            strings: HashMap::new(),
+        }
+    }
+
+    /// Build a table from a flat JSON object (`{"phase.in_game": "En jeu"}`)
+    pub fn from_json(locale: &str, value: &Value) -> Result<Self> {
+        let obj = value
+            .as_object()
+            .ok_or_else(|| LeagueError::ParseError("Locale table must be a JSON object".into()))?;
+
+        let strings = obj
+            .iter()
+            .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
+            .collect();
+
+        Ok(Self {
+            locale: locale.to_string(),
+            strings,
+        })
+    }
+
+    /// Build a table from translations sent with `Configure`
+    pub fn from_locale(locale: LocaleStrings) -> Self {
+        Self {
+            locale: locale.locale,
+            strings: locale.strings.into_iter().collect(),
+        }
+    }
+
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// Translated text for a key, falling back to English, then to the key itself
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        self.strings
+            .get(key)
+            .map(String::as_str)
+            .or_else(|| english(key))
+            .unwrap_or(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_english_lookup() {
+        assert_eq!(english("phase.in_game"), Some("In Game"));
+        assert_eq!(english("badge.farm_master"), Some("Farm Master"));
+        assert_eq!(english("nope"), None);
+    }
+
+    #[test]
+    fn test_keys_are_unique() {
+        let table = english_table();
+        let mut keys: Vec<_> = table.iter().map(|(k, _)| *k).collect();
+        keys.sort();
+        keys.dedup();
+        assert_eq!(keys.len(), table.len());
+    }
+
+    #[test]
+    fn test_locale_fallback() {
+        let table = StringTable::from_json("fr_FR", &json!({ "phase.in_game": "En jeu" })).unwrap();
+        assert_eq!(table.get("phase.in_game"), "En jeu");
+        assert_eq!(table.get("badge.mvp"), "MVP");
+        assert_eq!(table.get("unknown.key"), "unknown.key");
+    }
+}
@@ -0,0 +1,354 @@
+//! Supervision for long-running internal tasks
+//!
+//! Background loops (gameflow monitor, live match service, ...) used to be
+//! fire-and-forget `tokio::spawn`s, so a panic silently killed them. The
+//! supervisor owns their handles, restarts crashed tasks with exponential
+//! backoff, and exposes per-task health for status reporting.
+
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+pub use pack_league_types::protocol::{TaskHealth, TaskState};
+
+/// Initial delay before restarting a crashed task
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+/// Maximum delay between restarts
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+tokio::task_local! {
+    /// Name of the supervised task being polled
+    static SUPERVISED_TASK: &'static str;
+    /// Name of the one-off background task being polled
+    static BACKGROUND_TASK: &'static str;
+}
+
+thread_local! {
+    /// Name of the background thread, once marked
+    static BACKGROUND_THREAD: Cell<Option<&'static str>> = const { Cell::new(None) };
+}
+
+/// What a panic on the current thread takes down, short of the whole pack
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PanicScope {
+    /// A supervised task, which is restarted
+    Supervised(&'static str),
+    /// A one-off background task or thread, which is only lost
+    Background(&'static str),
+}
+
+/// Where a panic on this thread would be contained, if anywhere
+pub(crate) fn panic_scope() -> Option<PanicScope> {
+    SUPERVISED_TASK
+        .try_with(|name| PanicScope::Supervised(*name))
+        .or_else(|_| BACKGROUND_TASK.try_with(|name| PanicScope::Background(*name)))
+        .ok()
+        .or_else(|| BACKGROUND_THREAD.with(Cell::get).map(PanicScope::Background))
+}
+
+/// Spawn a one-off task that isn't worth supervising (a download, a debug
+/// client). A panic in it ends the task, not the pack.
+pub fn spawn_background<F>(name: &'static str, future: F) -> JoinHandle<()>
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    tokio::spawn(BACKGROUND_TASK.scope(name, future))
+}
+
+/// Mark the current thread as a background thread: a panic on it ends the
+/// thread, not the pack
+pub(crate) fn mark_background_thread(name: &'static str) {
+    BACKGROUND_THREAD.with(|thread| thread.set(Some(name)));
+}
+
+struct TaskEntry {
+    health: TaskHealth,
+    /// Set by `stop()` so a crashed task is not restarted
+    stopping: bool,
+    handle: JoinHandle<()>,
+}
+
+#[derive(Default)]
+struct Inner {
+    tasks: Mutex<HashMap<&'static str, TaskEntry>>,
+}
+
+impl Inner {
+    fn update(&self, name: &str, f: impl FnOnce(&mut TaskEntry)) {
+        if let Some(entry) = self.tasks.lock().expect("Mutex poisoned").get_mut(name) {
+            f(entry);
+        }
+    }
+
+    fn is_stopping(&self, name: &str) -> bool {
+        self.tasks
+            .lock()
+            .expect("Mutex poisoned")
+            .get(name)
+            .map(|e| e.stopping)
+            .unwrap_or(true)
+    }
+}
+
+/// Aborts the wrapped task when dropped, so aborting the supervising loop
+/// also aborts the task it is watching.
+struct AbortOnDrop(JoinHandle<()>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Tracks and restarts internal tasks. Cheap to clone; clones share state.
+#[derive(Clone)]
+pub struct TaskSupervisor {
+    inner: Arc<Inner>,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl TaskSupervisor {
+    /// Create a supervisor with the default 1s-30s restart backoff
+    pub fn new() -> Self {
+        Self::with_backoff(INITIAL_BACKOFF, MAX_BACKOFF)
+    }
+
+    /// Create a supervisor with a custom restart backoff
+    pub fn with_backoff(initial_backoff: Duration, max_backoff: Duration) -> Self {
+        Self {
+            inner: Arc::new(Inner::default()),
+            initial_backoff,
+            max_backoff,
+        }
+    }
+
+    /// Spawn a supervised task.
+    ///
+    /// `factory` is called to create the task future, and called again to
+    /// restart it whenever it panics. A task that returns normally is not
+    /// restarted. Spawning a task under an existing name replaces it.
+    pub fn spawn<F, Fut>(&self, name: &'static str, factory: F)
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let inner = Arc::clone(&self.inner);
+        let initial_backoff = self.initial_backoff;
+        let max_backoff = self.max_backoff;
+
+        // Hold the lock across the spawn so the loop can't observe a missing entry
+        let mut tasks = self.inner.tasks.lock().expect("Mutex poisoned");
+
+        let handle = tokio::spawn(async move {
+            let mut backoff = initial_backoff;
+
+            loop {
+                inner.update(name, |e| e.health.state = TaskState::Running);
+                let started = Instant::now();
+                let mut task = AbortOnDrop(tokio::spawn(SUPERVISED_TASK.scope(name, factory())));
+
+                match (&mut task.0).await {
+                    Ok(()) => {
+                        info!("Task '{}' finished", name);
+                        inner.update(name, |e| e.health.state = TaskState::Finished);
+                        return;
+                    }
+                    Err(e) if e.is_cancelled() => {
+                        inner.update(name, |e| e.health.state = TaskState::Stopped);
+                        return;
+                    }
+                    Err(e) => {
+                        let message = panic_message(e.into_panic());
+                        error!("Task '{}' crashed: {}", name, message);
+
+                        if inner.is_stopping(name) {
+                            inner.update(name, |e| {
+                                e.health.state = TaskState::Stopped;
+                                e.health.last_error = Some(message);
+                            });
+                            return;
+                        }
+
+                        // A task that ran for a while before crashing starts over at the initial delay
+                        if started.elapsed() >= max_backoff {
+                            backoff = initial_backoff;
+                        }
+
+                        inner.update(name, |e| {
+                            e.health.state = TaskState::Restarting;
+                            e.health.restarts += 1;
+                            e.health.last_error = Some(message);
+                        });
+                        warn!("Restarting task '{}' in {:?}", name, backoff);
+                    }
+                }
+
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(max_backoff);
+
+                if inner.is_stopping(name) {
+                    inner.update(name, |e| e.health.state = TaskState::Stopped);
+                    return;
+                }
+            }
+        });
+
+        let entry = TaskEntry {
+            health: TaskHealth {
+                name: name.to_string(),
+                state: TaskState::Running,
+                restarts: 0,
+                last_error: None,
+            },
+            stopping: false,
+            handle,
+        };
+
+        if let Some(previous) = tasks.insert(name, entry) {
+            previous.handle.abort();
+        }
+
+        info!("Supervising task '{}'", name);
+    }
+
+    /// Prevent a task from being restarted again.
+    ///
+    /// The task itself is left to exit via its own shutdown signal, so it can
+    /// still run any cleanup it needs.
+    pub fn stop(&self, name: &str) {
+        self.inner.update(name, |e| e.stopping = true);
+    }
+
+    /// Abort a task immediately
+    pub fn abort(&self, name: &str) {
+        self.inner.update(name, |e| {
+            e.stopping = true;
+            e.handle.abort();
+            e.health.state = TaskState::Stopped;
+        });
+    }
+
+    /// Abort all supervised tasks
+    pub fn shutdown(&self) {
+        for entry in self.inner.tasks.lock().expect("Mutex poisoned").values_mut() {
+            entry.stopping = true;
+            entry.handle.abort();
+            entry.health.state = TaskState::Stopped;
+        }
+    }
+
+    /// Health of all supervised tasks, sorted by name
+    pub fn health(&self) -> Vec<TaskHealth> {
+        let mut health: Vec<TaskHealth> = self
+            .inner
+            .tasks
+            .lock()
+            .expect("Mutex poisoned")
+            .values()
+            .map(|e| e.health.clone())
+            .collect();
+        health.sort_by(|a, b| a.name.cmp(&b.name));
+        health
+    }
+
+    /// Health of a single task
+    pub fn task_health(&self, name: &str) -> Option<TaskHealth> {
+        self.inner
+            .tasks
+            .lock()
+            .expect("Mutex poisoned")
+            .get(name)
+            .map(|e| e.health.clone())
+    }
+}
+
+impl Default for TaskSupervisor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Extract a readable message from a panic payload
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        (*s).to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[tokio::test]
+    async fn test_restarts_crashed_task() {
+        let supervisor =
+            TaskSupervisor::with_backoff(Duration::from_millis(1), Duration::from_millis(5));
+        let runs = Arc::new(AtomicU32::new(0));
+
+        let counter = Arc::clone(&runs);
+        supervisor.spawn("flaky", move || {
+            let counter = Arc::clone(&counter);
+            async move {
+                if counter.fetch_add(1, Ordering::SeqCst) < 2 {
+                    panic!("flaky failure");
+                }
+            }
+        });
+
+        for _ in 0..100 {
+            if supervisor.task_health("flaky").map(|h| h.state) == Some(TaskState::Finished) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+
+        let health = supervisor.task_health("flaky").unwrap();
+        assert_eq!(health.state, TaskState::Finished);
+        assert_eq!(health.restarts, 2);
+        assert_eq!(health.last_error.as_deref(), Some("flaky failure"));
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_supervised_task_is_known_while_running() {
+        let supervisor = TaskSupervisor::new();
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let tx = Arc::new(Mutex::new(Some(tx)));
+        supervisor.spawn("named", move || {
+            let tx = Arc::clone(&tx);
+            async move {
+                if let Some(tx) = tx.lock().unwrap().take() {
+                    let _ = tx.send(panic_scope());
+                }
+            }
+        });
+
+        assert_eq!(rx.await.unwrap(), Some(PanicScope::Supervised("named")));
+        assert_eq!(panic_scope(), None);
+    }
+
+    #[tokio::test]
+    async fn test_background_work_has_a_panic_scope() {
+        let scope = spawn_background("fetch", async {
+            assert_eq!(panic_scope(), Some(PanicScope::Background("fetch")));
+        });
+        scope.await.unwrap();
+
+        let thread = std::thread::spawn(|| {
+            mark_background_thread("client");
+            panic_scope()
+        });
+        assert_eq!(thread.join().unwrap(), Some(PanicScope::Background("client")));
+        assert_eq!(panic_scope(), None);
+    }
+}
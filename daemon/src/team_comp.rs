@@ -0,0 +1,161 @@
+//! Team composition analysis
+//!
+//! Once champ select is over, each team's picks are described with a few
+//! coarse descriptors for overlays and post-game notes. The inputs are Data
+//! Dragon's class tags and attack/magic ratings, so the results are
+//! heuristics rather than matchup knowledge:
+//! - damage: physical share of the summed attack and magic ratings
+//! - style: tanks count as engage, mages and marksmen as poke
+//! - scaling: marksmen and mages scale late, assassins and fighters peak early
+
+use crate::{
+    ChampSelectDraft, CompScaling, DamageProfile, DdragonResolver, DraftComposition, FightStyle,
+    TeamComposition,
+};
+
+/// Physical share above which a team counts as physical-heavy (and below
+/// `1 - PHYSICAL_HEAVY_SHARE` as magic-heavy)
+const PHYSICAL_HEAVY_SHARE: f64 = 0.65;
+
+/// Champion traits used by the analysis, from Data Dragon `champion.json`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChampionProfile {
+    /// Class tags, primary first ("Fighter", "Mage", ...)
+    pub tags: Vec<String>,
+    /// Attack and magic ratings (0-10)
+    pub attack: u8,
+    pub magic: u8,
+}
+
+impl ChampionProfile {
+    fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    fn primary_tag(&self) -> &str {
+        self.tags.first().map(String::as_str).unwrap_or_default()
+    }
+
+    /// Positive for late-game champions, negative for early ones
+    fn scaling_score(&self) -> i32 {
+        match self.primary_tag() {
+            "Marksman" | "Mage" => 2,
+            "Fighter" => -1,
+            "Assassin" => -2,
+            _ => 0,
+        }
+    }
+}
+
+/// Describe a team from its champions' profiles; `None` without any
+pub fn analyze_team(profiles: &[ChampionProfile]) -> Option<TeamComposition> {
+    if profiles.is_empty() {
+        return None;
+    }
+
+    let attack: u32 = profiles.iter().map(|p| p.attack as u32).sum();
+    let magic: u32 = profiles.iter().map(|p| p.magic as u32).sum();
+    let physical_share = if attack + magic == 0 {
+        0.5
+    } else {
+        attack as f64 / (attack + magic) as f64
+    };
+    let damage = if physical_share >= PHYSICAL_HEAVY_SHARE {
+        DamageProfile::Physical
+    } else if physical_share <= 1.0 - PHYSICAL_HEAVY_SHARE {
+        DamageProfile::Magic
+    } else {
+        DamageProfile::Mixed
+    };
+
+    let engage = profiles.iter().filter(|p| p.has_tag("Tank")).count();
+    let poke = profiles
+        .iter()
+        .filter(|p| matches!(p.primary_tag(), "Mage" | "Marksman") && !p.has_tag("Tank"))
+        .count();
+    let style = if engage >= 2 && engage >= poke {
+        FightStyle::Engage
+    } else if poke >= 3 && engage <= 1 {
+        FightStyle::Poke
+    } else {
+        FightStyle::Balanced
+    };
+
+    let scaling_total: i32 = profiles.iter().map(ChampionProfile::scaling_score).sum();
+    let scaling = match scaling_total as f64 / profiles.len() as f64 {
+        avg if avg >= 1.0 => CompScaling::Late,
+        avg if avg <= -0.5 => CompScaling::Early,
+        _ => CompScaling::Mid,
+    };
+
+    Some(TeamComposition {
+        physical_share,
+        damage,
+        style,
+        scaling,
+        analyzed: profiles.len(),
+    })
+}
+
+/// Compositions of a finished draft; `None` until Data Dragon knows the
+/// player's team
+pub fn analyze_draft(
+    draft: &ChampSelectDraft,
+    ddragon: &DdragonResolver,
+) -> Option<DraftComposition> {
+    let team = |is_ally: bool| -> Vec<ChampionProfile> {
+        draft
+            .picks
+            .iter()
+            .filter(|p| p.is_ally == is_ally)
+            .filter_map(|p| ddragon.champion_profile(p.champion_id))
+            .collect()
+    };
+
+    Some(DraftComposition {
+        ally: analyze_team(&team(true))?,
+        enemy: analyze_team(&team(false)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(tags: &[&str], attack: u8, magic: u8) -> ChampionProfile {
+        ChampionProfile {
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            attack,
+            magic,
+        }
+    }
+
+    #[test]
+    fn test_analyze_team() {
+        let dive = [
+            profile(&["Tank", "Fighter"], 5, 3),
+            profile(&["Fighter", "Tank"], 8, 2),
+            profile(&["Assassin"], 10, 1),
+            profile(&["Marksman"], 9, 1),
+            profile(&["Tank", "Support"], 4, 5),
+        ];
+        let comp = analyze_team(&dive).unwrap();
+        assert_eq!(comp.damage, DamageProfile::Physical);
+        assert_eq!(comp.style, FightStyle::Engage);
+        assert_eq!(comp.scaling, CompScaling::Mid);
+        assert_eq!(comp.analyzed, 5);
+
+        let poke = [
+            profile(&["Mage"], 2, 9),
+            profile(&["Mage", "Support"], 1, 8),
+            profile(&["Marksman"], 8, 2),
+            profile(&["Mage"], 2, 10),
+        ];
+        let comp = analyze_team(&poke).unwrap();
+        assert_eq!(comp.damage, DamageProfile::Magic);
+        assert_eq!(comp.style, FightStyle::Poke);
+        assert_eq!(comp.scaling, CompScaling::Late);
+
+        assert!(analyze_team(&[]).is_none());
+    }
+}
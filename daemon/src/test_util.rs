@@ -0,0 +1,31 @@
+//! Helpers shared by unit tests
+
+use std::path::{Path, PathBuf};
+
+/// Fresh directory under the system temp dir, removed on drop
+pub(crate) struct TempDir(PathBuf);
+
+impl TempDir {
+    /// Create `pack-league-<label>-<uuid>` under the system temp dir
+    pub(crate) fn new(label: &str) -> Self {
+        let path = std::env::temp_dir()
+            .join(format!("pack-league-{}-{}", label, uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&path).expect("failed to create temp dir");
+        Self(path)
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.0
+    }
+
+    /// `path` inside the directory
+    pub(crate) fn join(&self, path: impl AsRef<Path>) -> PathBuf {
+        self.0.join(path)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
@@ -0,0 +1,270 @@
+//! TFT stage and round progression
+//!
+//! The Live Client API reports almost nothing during TFT, so the LCU TFT
+//! game state is polled instead and turned into game events: round start and
+//! end, fights won and lost, carousels, eliminations and the player reaching
+//! the top four. These give TFT players clip triggers comparable to kills in
+//! League. The same state, with the gold and level the Live Client does
+//! report, makes up the live board.
+
+use serde::Serialize;
+use serde_json::json;
+use std::collections::HashSet;
+
+use crate::protocol::GameEvent;
+use crate::{ActivePlayer, TftGameState, TftPlayerState, TftUnit};
+
+/// Round type of shared draft (carousel) rounds
+const CAROUSEL: &str = "CAROUSEL";
+
+/// Round type of rounds fought against another player
+const PVP: &str = "PVP";
+
+/// Turns successive TFT game states into progression events
+#[derive(Debug, Default)]
+pub struct TftTracker {
+    /// Current (stage, round)
+    round: Option<(u32, u32)>,
+    round_type: String,
+    /// The player's health when the current round started
+    round_start_health: Option<i32>,
+    eliminated: HashSet<String>,
+    reached_top_four: bool,
+}
+
+impl TftTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply a state update, returning the events it caused
+    pub fn update(&mut self, state: &TftGameState, game_time_secs: f64) -> Vec<GameEvent> {
+        let mut events = Vec::new();
+
+        let round = (state.stage, state.round);
+        let local_health = state.players.iter().find(|p| p.is_local_player).map(|p| p.health);
+        if state.stage > 0 && self.round != Some(round) {
+            if let Some((stage, round)) = self.round {
+                events.push(GameEvent::new(
+                    "TftRoundEnd".to_string(),
+                    game_time_secs,
+                    json!({ "stage": stage, "round": round, "label": round_label(stage, round) }),
+                ));
+
+                // Losing a player fight costs health by the time the round ends
+                if self.round_type == PVP {
+                    if let (Some(before), Some(after)) = (self.round_start_health, local_health) {
+                        let damage = (before - after).max(0);
+                        let event_type = if damage == 0 { "TftFightWon" } else { "TftFightLost" };
+                        events.push(GameEvent::new(
+                            event_type.to_string(),
+                            game_time_secs,
+                            json!({
+                                "stage": stage,
+                                "round": round,
+                                "label": round_label(stage, round),
+                                "damage": damage,
+                                "is_player_involved": true,
+                            }),
+                        ));
+                    }
+                }
+            }
+
+            events.push(GameEvent::new(
+                "TftRoundStart".to_string(),
+                game_time_secs,
+                json!({
+                    "stage": state.stage,
+                    "round": state.round,
+                    "label": round_label(state.stage, state.round),
+                    "round_type": state.round_type,
+                }),
+            ));
+            if state.round_type == CAROUSEL {
+                events.push(GameEvent::new(
+                    "TftCarousel".to_string(),
+                    game_time_secs,
+                    json!({ "stage": state.stage, "round": state.round }),
+                ));
+            }
+            self.round = Some(round);
+            self.round_type = state.round_type.clone();
+            self.round_start_health = local_health;
+        }
+
+        // Everyone knocked out in the same update shares the placement
+        let alive = state.players.iter().filter(|p| p.health > 0).count();
+        for player in state.players.iter().filter(|p| p.health <= 0) {
+            if !self.eliminated.insert(player.summoner_name.clone()) {
+                continue;
+            }
+            events.push(GameEvent::new(
+                "TftPlayerEliminated".to_string(),
+                game_time_secs,
+                json!({
+                    "summoner_name": player.summoner_name,
+                    "placement": alive + 1,
+                    "is_player_involved": player.is_local_player,
+                }),
+            ));
+        }
+
+        let local_alive = state.players.iter().any(|p| p.is_local_player && p.health > 0);
+        if !self.reached_top_four && local_alive && alive <= 4 && state.players.len() > 4 {
+            self.reached_top_four = true;
+            events.push(GameEvent::new(
+                "TftTopFour".to_string(),
+                game_time_secs,
+                json!({ "players_remaining": alive, "is_player_involved": true }),
+            ));
+        }
+
+        events
+    }
+
+    /// Forget the current game
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Round label as shown in game (e.g. "3-2")
+fn round_label(stage: u32, round: u32) -> String {
+    format!("{}-{}", stage, round)
+}
+
+/// The player's TFT board, served as live data during TFT games
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TftLiveBoard {
+    pub game_time_secs: f64,
+    pub stage: u32,
+    pub round: u32,
+    /// e.g. "4-2"
+    pub round_label: String,
+    pub round_type: String,
+    pub gold: i32,
+    /// Tactician level
+    pub level: i32,
+    pub health: i32,
+    pub units: Vec<TftUnit>,
+    /// Health of everyone in the lobby
+    pub players: Vec<TftPlayerState>,
+}
+
+impl TftLiveBoard {
+    /// Board of the local player; `None` before the lobby is known.
+    /// Gold and level come from the Live Client when it answers.
+    pub fn new(
+        state: &TftGameState,
+        active: Option<&ActivePlayer>,
+        game_time_secs: f64,
+    ) -> Option<Self> {
+        let local = state.players.iter().find(|p| p.is_local_player)?;
+        Some(Self {
+            game_time_secs,
+            stage: state.stage,
+            round: state.round,
+            round_label: round_label(state.stage, state.round),
+            round_type: state.round_type.clone(),
+            gold: active.map_or(0, |a| a.current_gold as i32),
+            level: active.map_or(0, |a| a.level),
+            health: local.health,
+            units: local.board.clone(),
+            players: state
+                .players
+                .iter()
+                .map(|p| TftPlayerState {
+                    board: Vec::new(),
+                    ..p.clone()
+                })
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TftPlayerState;
+
+    fn state(stage: u32, round: u32, round_type: &str, healths: &[i32]) -> TftGameState {
+        TftGameState {
+            stage,
+            round,
+            round_type: round_type.to_string(),
+            players: healths
+                .iter()
+                .enumerate()
+                .map(|(i, &health)| TftPlayerState {
+                    summoner_name: format!("Player{}", i),
+                    health,
+                    is_local_player: i == 0,
+                    board: Vec::new(),
+                })
+                .collect(),
+        }
+    }
+
+    fn names(events: &[GameEvent]) -> Vec<&str> {
+        events.iter().map(|e| e.event_type.as_str()).collect()
+    }
+
+    #[test]
+    fn test_tracker_emits_rounds_eliminations_and_top_four() {
+        let mut tracker = TftTracker::new();
+
+        let events = tracker.update(&state(2, 4, "CAROUSEL", &[80, 70, 60, 50, 40, 30]), 600.0);
+        assert_eq!(names(&events), vec!["TftRoundStart", "TftCarousel"]);
+        assert_eq!(events[0].data["label"], "2-4");
+
+        // Same round again: nothing new
+        assert!(tracker.update(&state(2, 4, "CAROUSEL", &[80, 70, 60, 50, 40, 30]), 610.0).is_empty());
+
+        let events = tracker.update(&state(2, 5, "PVP", &[60, 50, 40, 30, 0, 0]), 700.0);
+        assert_eq!(
+            names(&events),
+            vec!["TftRoundEnd", "TftRoundStart", "TftPlayerEliminated", "TftPlayerEliminated", "TftTopFour"]
+        );
+        assert_eq!(events[2].data["placement"], 5);
+        assert_eq!(events[2].data["is_player_involved"], false);
+
+        let events = tracker.update(&state(2, 5, "PVP", &[0, 50, 40, 30, 0, 0]), 720.0);
+        assert_eq!(names(&events), vec!["TftPlayerEliminated"]);
+        assert_eq!(events[0].data["placement"], 4);
+        assert_eq!(events[0].data["is_player_involved"], true);
+    }
+
+    #[test]
+    fn test_pvp_round_reports_fight_result_and_board() {
+        let mut tracker = TftTracker::new();
+        tracker.update(&state(4, 1, "PVP", &[60, 50, 40]), 1200.0);
+
+        let events = tracker.update(&state(4, 2, "PVP", &[60, 42, 40]), 1240.0);
+        assert_eq!(names(&events), vec!["TftRoundEnd", "TftFightWon", "TftRoundStart"]);
+        assert_eq!(events[1].data["label"], "4-1");
+
+        let events = tracker.update(&state(4, 3, "PVE", &[51, 42, 40]), 1280.0);
+        assert_eq!(names(&events)[1], "TftFightLost");
+        assert_eq!(events[1].data["label"], "4-2");
+        assert_eq!(events[1].data["damage"], 9);
+
+        let mut game = state(4, 3, "PVE", &[51, 42, 40]);
+        game.players[0].board.push(TftUnit {
+            character_id: "TFT13_Jinx".to_string(),
+            star_level: 2,
+            item_ids: vec![44],
+        });
+        let active = ActivePlayer {
+            level: 7,
+            current_gold: 38.0,
+            ..ActivePlayer::default()
+        };
+        let board = TftLiveBoard::new(&game, Some(&active), 1280.0).unwrap();
+        assert_eq!((board.gold, board.level, board.health), (38, 7, 51));
+        assert_eq!(board.round_label, "4-3");
+        assert_eq!(board.units.len(), 1);
+        assert!(board.players.iter().all(|p| p.board.is_empty()));
+    }
+}
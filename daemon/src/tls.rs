@@ -0,0 +1,295 @@
+//! TLS configuration for the League client APIs
+//!
+//! The LCU and Live Client Data API serve certificates signed by Riot's own
+//! root (`riotgames.pem`). We verify the chain against that root instead of
+//! accepting any certificate, so a local process can't impersonate the client
+//! and capture the auth token. The certificates are issued for `127.0.0.1`
+//! without a subject alt name, so the hostname check is skipped - the chain
+//! check is what pins the connection to Riot.
+//!
+//! The pack doesn't bundle the certificate: it is looked up at
+//! `LEAGUE_RIOT_CERT`, next to the pack binary, then in the League install
+//! directory (the client ships it). When it can't be found, connections fail
+//! with an error naming every place that was searched; an explicit
+//! `LEAGUE_RIOT_CERT` that doesn't hold a usable certificate is an error even
+//! if a copy exists elsewhere.
+//!
+//! Accepting any certificate is still available as an explicit opt-out via
+//! [`set_accept_any_certificate`] or `LEAGUE_ACCEPT_ANY_CERT=1`.
+
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::WebPkiServerVerifier;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{CertificateError, DigitallySignedStruct, RootCertStore};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::{debug, info, warn};
+
+use crate::{AppError, Result};
+
+/// File name of Riot's root certificate
+const RIOT_CERT_FILE: &str = "riotgames.pem";
+/// Environment variable pointing at an explicit certificate path
+const RIOT_CERT_ENV: &str = "LEAGUE_RIOT_CERT";
+/// Environment variable that disables certificate verification
+const ACCEPT_ANY_ENV: &str = "LEAGUE_ACCEPT_ANY_CERT";
+
+static ACCEPT_ANY_CERT: AtomicBool = AtomicBool::new(false);
+/// Whether a missing certificate was reported (connections retry often)
+static MISSING_CERT_REPORTED: AtomicBool = AtomicBool::new(false);
+static RIOT_ROOTS: Mutex<Option<Arc<RootCertStore>>> = Mutex::new(None);
+
+/// Opt out of certificate verification for LCU / Live Client connections
+pub fn set_accept_any_certificate(accept: bool) {
+    if accept {
+        warn!("LCU certificate verification disabled");
+    }
+    ACCEPT_ANY_CERT.store(accept, Ordering::Relaxed);
+}
+
+fn accept_any_certificate() -> bool {
+    ACCEPT_ANY_CERT.load(Ordering::Relaxed)
+        || std::env::var(ACCEPT_ANY_ENV).map(|v| v == "1" || v == "true").unwrap_or(false)
+}
+
+/// Build the rustls config used for LCU and Live Client connections
+pub(crate) fn lcu_tls_config() -> Result<rustls::ClientConfig> {
+    let builder = rustls::ClientConfig::builder().dangerous();
+
+    let verifier: Arc<dyn ServerCertVerifier> = if accept_any_certificate() {
+        Arc::new(AcceptAnyCert)
+    } else {
+        let roots = riot_roots().map_err(|e| {
+            if !MISSING_CERT_REPORTED.swap(true, Ordering::Relaxed) {
+                warn!("{}", e);
+            }
+            AppError::Other(e)
+        })?;
+        Arc::new(RiotCertVerifier::new(roots)?)
+    };
+
+    Ok(builder
+        .with_custom_certificate_verifier(verifier)
+        .with_no_client_auth())
+}
+
+/// Riot's root certificate store, or why it couldn't be loaded.
+///
+/// Cached once found; a miss is retried next time since the install
+/// directory can only be discovered while the client is running.
+fn riot_roots() -> std::result::Result<Arc<RootCertStore>, String> {
+    let mut cached = RIOT_ROOTS.lock().expect("Mutex poisoned");
+    if let Some(roots) = cached.as_ref() {
+        return Ok(Arc::clone(roots));
+    }
+
+    let roots = match std::env::var(RIOT_CERT_ENV) {
+        Ok(path) => load_explicit_roots(&PathBuf::from(path))?,
+        Err(_) => search_riot_roots(&cert_search_paths())?,
+    };
+    MISSING_CERT_REPORTED.store(false, Ordering::Relaxed);
+    *cached = Some(Arc::clone(&roots));
+    Ok(roots)
+}
+
+/// Candidate locations for `riotgames.pem` without `LEAGUE_RIOT_CERT`, in
+/// priority order: next to the pack binary, then the League install directory.
+fn cert_search_paths() -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+
+    if let Ok(exe) = std::env::current_exe() {
+        if let Some(dir) = exe.parent() {
+            paths.push(dir.join(RIOT_CERT_FILE));
+            if let Some(pack_root) = dir.parent() {
+                paths.push(pack_root.join(RIOT_CERT_FILE));
+            }
+        }
+    }
+
+    if let Ok(install_dir) = crate::LcuConnection::install_directory() {
+        paths.push(install_dir.join(RIOT_CERT_FILE));
+    }
+
+    paths
+}
+
+/// The certificate `LEAGUE_RIOT_CERT` points at; no fallback, since the
+/// variable is an explicit choice
+fn load_explicit_roots(path: &Path) -> std::result::Result<Arc<RootCertStore>, String> {
+    if !path.exists() {
+        return Err(format!("{} points at {:?}, which does not exist", RIOT_CERT_ENV, path));
+    }
+    load_roots(path).ok_or_else(|| {
+        format!("{} points at {:?}, which holds no usable certificate", RIOT_CERT_ENV, path)
+    })
+}
+
+/// The first usable certificate among `paths`
+fn search_riot_roots(paths: &[PathBuf]) -> std::result::Result<Arc<RootCertStore>, String> {
+    if let Some(roots) = paths.iter().filter(|p| p.exists()).find_map(|p| load_roots(p)) {
+        return Ok(roots);
+    }
+
+    let searched: Vec<String> = paths.iter().map(|p| format!("{:?}", p)).collect();
+    Err(format!(
+        "{} not found (searched {}); it ships with the League client. Point {} at it, \
+         or set {}=1 to skip certificate verification",
+        RIOT_CERT_FILE,
+        if searched.is_empty() { "nowhere".to_string() } else { searched.join(", ") },
+        RIOT_CERT_ENV,
+        ACCEPT_ANY_ENV
+    ))
+}
+
+fn load_roots(path: &Path) -> Option<Arc<RootCertStore>> {
+    let certs = match CertificateDer::pem_file_iter(path) {
+        Ok(iter) => iter.filter_map(|c| c.ok()).collect::<Vec<_>>(),
+        Err(e) => {
+            warn!("Failed to read {:?}: {}", path, e);
+            return None;
+        }
+    };
+
+    let mut store = RootCertStore::empty();
+    let (added, _ignored) = store.add_parsable_certificates(certs);
+    if added == 0 {
+        debug!("No usable certificates in {:?}", path);
+        return None;
+    }
+    info!("Loaded Riot root certificate from {:?}", path);
+    Some(Arc::new(store))
+}
+
+/// Verifies the certificate chain against Riot's root, ignoring the hostname.
+#[derive(Debug)]
+struct RiotCertVerifier {
+    inner: Arc<WebPkiServerVerifier>,
+}
+
+impl RiotCertVerifier {
+    fn new(roots: Arc<RootCertStore>) -> Result<Self> {
+        let inner = WebPkiServerVerifier::builder(roots)
+            .build()
+            .map_err(|e| AppError::Other(format!("Failed to build certificate verifier: {}", e)))?;
+        Ok(Self { inner })
+    }
+}
+
+impl ServerCertVerifier for RiotCertVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> std::result::Result<ServerCertVerified, rustls::Error> {
+        match self
+            .inner
+            .verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now)
+        {
+            // Chain is valid; the LCU cert just isn't issued for an IP SAN
+            Err(rustls::Error::InvalidCertificate(CertificateError::NotValidForName))
+            | Err(rustls::Error::InvalidCertificate(
+                CertificateError::NotValidForNameContext { .. },
+            )) => Ok(ServerCertVerified::assertion()),
+            other => other,
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+/// Certificate verifier that accepts any certificate (explicit opt-out only).
+#[derive(Debug)]
+struct AcceptAnyCert;
+
+impl ServerCertVerifier for AcceptAnyCert {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> std::result::Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        _message: &[u8],
+        _cert: &CertificateDer<'_>,
+        _dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        Ok(HandshakeSignatureValid::assertion())
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        _message: &[u8],
+        _cert: &CertificateDer<'_>,
+        _dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        Ok(HandshakeSignatureValid::assertion())
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
+        vec![
+            rustls::SignatureScheme::RSA_PKCS1_SHA256,
+            rustls::SignatureScheme::RSA_PKCS1_SHA384,
+            rustls::SignatureScheme::RSA_PKCS1_SHA512,
+            rustls::SignatureScheme::ECDSA_NISTP256_SHA256,
+            rustls::SignatureScheme::ECDSA_NISTP384_SHA384,
+            rustls::SignatureScheme::ECDSA_NISTP521_SHA512,
+            rustls::SignatureScheme::RSA_PSS_SHA256,
+            rustls::SignatureScheme::RSA_PSS_SHA384,
+            rustls::SignatureScheme::RSA_PSS_SHA512,
+            rustls::SignatureScheme::ED25519,
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_missing_certificate_names_the_searched_paths() {
+        let dir = TempDir::new("tls");
+        let empty = dir.join(RIOT_CERT_FILE);
+        std::fs::write(&empty, "not a certificate").unwrap();
+        let missing = dir.join("elsewhere").join(RIOT_CERT_FILE);
+
+        let error = search_riot_roots(&[empty.clone(), missing.clone()]).unwrap_err();
+        assert!(error.contains(&format!("{:?}", missing)));
+        assert!(error.contains(RIOT_CERT_ENV));
+
+        let error = load_explicit_roots(&missing).unwrap_err();
+        assert!(error.contains("does not exist"));
+        assert!(load_explicit_roots(&empty).unwrap_err().contains("no usable certificate"));
+    }
+}
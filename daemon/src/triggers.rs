@@ -1,45 +1,155 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
 use super::{LeagueEventType, ParsedGameEvent};
-use crate::TriggerSettings;
+use crate::{game_name_key, TriggerConfig, TriggerKind, TriggerRule, TriggerScope};
+
+/// Why an event did or didn't trigger a clip, stored with the event so the
+/// decision can be reviewed (and rules tuned) later
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TriggerDecision {
+    pub triggered: bool,
+    /// Trigger name ("kill", "baron", ...) when triggered
+    pub trigger: Option<String>,
+    /// Rule that decided the outcome (e.g. "kill"), if one applies
+    pub rule: Option<TriggerKind>,
+    /// Why no clip was triggered
+    pub reason: Option<String>,
+    /// Settings of the deciding rule at the time of the decision
+    pub rule_config: Option<TriggerRule>,
+}
 
 #[derive(Clone)]
 pub struct TriggerEvaluator {
-    pub(crate) settings: TriggerSettings,
+    pub(crate) config: TriggerConfig,
+    /// Game time each rule last triggered, for cooldowns
+    last_triggered: HashMap<TriggerKind, f64>,
+    /// Game name keys of the player's teammates, for team-scoped rules
+    teammates: HashSet<String>,
 }
 
 impl TriggerEvaluator {
-    pub fn new(settings: TriggerSettings) -> Self {
-        Self { settings }
+    pub fn new(config: TriggerConfig) -> Self {
+        Self {
+            config,
+            last_triggered: HashMap::new(),
+            teammates: HashSet::new(),
+        }
+    }
+
+    pub fn update_config(&mut self, config: TriggerConfig) {
+        self.config = config;
     }
 
-    pub fn update_settings(&mut self, settings: TriggerSettings) {
-        self.settings = settings;
+    /// Set the names of the player's teammates (for team-scoped rules)
+    pub fn set_teammates(&mut self, teammates: impl IntoIterator<Item = String>) {
+        self.teammates = teammates.into_iter().map(|name| game_name_key(&name)).collect();
+    }
+
+    /// Forget cooldowns and teammates (new game)
+    pub fn reset(&mut self) {
+        self.last_triggered.clear();
+        self.teammates.clear();
     }
 
     pub fn should_trigger(&self, event: &ParsedGameEvent) -> bool {
-        if !event.is_player_involved {
+        self.check(event).is_ok()
+    }
+
+    /// Evaluate an event and record the reasoning. A triggered event starts
+    /// its rule's cooldown.
+    pub fn evaluate(&mut self, event: &ParsedGameEvent) -> TriggerDecision {
+        let rule = self.rule_for(event);
+        let result = self.check(event);
+        let triggered = result.is_ok();
+
+        if let (true, Some((kind, _))) = (triggered, rule.as_ref()) {
+            self.last_triggered.insert(*kind, event.event_time);
+        }
+
+        TriggerDecision {
+            triggered,
+            trigger: triggered.then(|| self.get_trigger_name(event)),
+            rule: rule.as_ref().map(|(kind, _)| *kind),
+            reason: result.err().map(String::from),
+            rule_config: rule.map(|(_, config)| config),
+        }
+    }
+
+    /// `Ok` if the event should be clipped, otherwise why not
+    fn check(&self, event: &ParsedGameEvent) -> Result<(), &'static str> {
+        let Some((kind, rule)) = self.rule_for(event) else {
+            return Err(if self.is_in_scope(event, TriggerScope::Player) {
+                "no_rule"
+            } else {
+                "player_not_involved"
+            });
+        };
+
+        if !self.is_in_scope(event, rule.scope) {
+            return Err(match rule.scope {
+                TriggerScope::Player => "player_not_involved",
+                TriggerScope::Team => "team_not_involved",
+            });
+        }
+        if !rule.enabled {
+            return Err("rule_disabled");
+        }
+        if event.event_type == LeagueEventType::Multikill
+            && event.count.is_some_and(|count| count < self.config.min_multikill)
+        {
+            return Err("below_min_multikill");
+        }
+        if let Some(&last) = self.last_triggered.get(&kind) {
+            if rule.cooldown_secs > 0.0 && event.event_time - last < rule.cooldown_secs {
+                return Err("cooldown");
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether the player (or, for team scope, a teammate) takes part in the event
+    fn is_in_scope(&self, event: &ParsedGameEvent, scope: TriggerScope) -> bool {
+        if event.is_player_involved {
+            return true;
+        }
+        if scope == TriggerScope::Player {
             return false;
         }
 
-        match event.event_type {
+        let is_teammate = |name: &String| self.teammates.contains(&game_name_key(name));
+        event.killer_name.as_ref().is_some_and(is_teammate)
+            || event.victim_name.as_ref().is_some_and(is_teammate)
+            || event.assisters.iter().any(is_teammate)
+    }
+
+    /// The rule that applies to an event.
+    ///
+    /// A kill can match several rules (kill, death, assist); the first
+    /// enabled one wins, otherwise the first that applies is reported.
+    fn rule_for(&self, event: &ParsedGameEvent) -> Option<(TriggerKind, TriggerRule)> {
+        let kind = match event.event_type {
             LeagueEventType::ChampionKill => {
-                if event.killer_name.is_some() && self.settings.on_kill {
-                    return true;
-                }
-                if event.victim_name.is_some() && self.settings.on_death {
-                    return true;
-                }
-                if !event.assisters.is_empty() && self.settings.on_assist {
-                    return true;
+                let applicable = [
+                    (event.killer_name.is_some(), TriggerKind::Kill),
+                    (event.victim_name.is_some(), TriggerKind::Death),
+                    (!event.assisters.is_empty(), TriggerKind::Assist),
+                ];
+                let mut applicable = applicable
+                    .into_iter()
+                    .filter(|(applies, _)| *applies)
+                    .map(|(_, kind)| (kind, self.config.rule(kind)));
+
+                let first = applicable.next()?;
+                if first.1.enabled {
+                    return Some(first);
                 }
-                false
+                return applicable.find(|(_, rule)| rule.enabled).or(Some(first));
             }
-            LeagueEventType::Multikill => self.settings.on_multikill,
-            LeagueEventType::Ace => self.settings.on_ace,
-            LeagueEventType::TurretKilled => self.settings.on_tower_kill,
-            LeagueEventType::DragonKill => self.settings.on_dragon,
-            LeagueEventType::BaronKill => self.settings.on_baron,
-            _ => false,
-        }
+            ref event_type => trigger_kind(event_type)?,
+        };
+        Some((kind, self.config.rule(kind)))
     }
 
     pub fn get_trigger_name(&self, event: &ParsedGameEvent) -> String {
@@ -54,11 +164,110 @@ impl TriggerEvaluator {
                 }
             }
             LeagueEventType::Multikill => "multikill".to_string(),
+            LeagueEventType::KillingSpree => "killing_spree".to_string(),
+            LeagueEventType::Shutdown => "shutdown".to_string(),
+            LeagueEventType::FirstBloodAssist => "first_blood".to_string(),
+            LeagueEventType::MultikillChain => "multikill_chain".to_string(),
             LeagueEventType::Ace => "ace".to_string(),
             LeagueEventType::TurretKilled => "tower".to_string(),
             LeagueEventType::DragonKill => "dragon".to_string(),
             LeagueEventType::BaronKill => "baron".to_string(),
+            LeagueEventType::ObjectiveContested => "objective_contested".to_string(),
+            LeagueEventType::ObjectiveSteal => "objective_steal".to_string(),
+            LeagueEventType::WardPlaced => "ward_placed".to_string(),
+            LeagueEventType::ControlWardBought => "control_ward_bought".to_string(),
             _ => "event".to_string(),
         }
     }
 }
+
+/// Trigger rule deciding whether an event type is clipped. A kill is a
+/// kill here; for the player's death or assist `TriggerEvaluator` picks the
+/// matching rule.
+pub fn trigger_kind(event_type: &LeagueEventType) -> Option<TriggerKind> {
+    let kind = match event_type {
+        LeagueEventType::ChampionKill | LeagueEventType::Shutdown => TriggerKind::Kill,
+        LeagueEventType::Multikill
+        | LeagueEventType::KillingSpree
+        | LeagueEventType::MultikillChain => TriggerKind::Multikill,
+        LeagueEventType::FirstBloodAssist => TriggerKind::Assist,
+        LeagueEventType::Ace => TriggerKind::Ace,
+        LeagueEventType::TurretKilled => TriggerKind::TowerKill,
+        LeagueEventType::DragonKill => TriggerKind::Dragon,
+        LeagueEventType::BaronKill => TriggerKind::Baron,
+        LeagueEventType::ObjectiveContested => TriggerKind::ObjectiveContested,
+        LeagueEventType::ObjectiveSteal => TriggerKind::ObjectiveSteal,
+        LeagueEventType::WardPlaced | LeagueEventType::ControlWardBought => TriggerKind::Ward,
+        _ => return None,
+    };
+    Some(kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TriggerSettings;
+
+    fn event(
+        event_type: LeagueEventType,
+        time: f64,
+        killer: &str,
+        count: Option<u32>,
+    ) -> ParsedGameEvent {
+        ParsedGameEvent {
+            event_type,
+            event_time: time,
+            killer_name: Some(killer.to_string()),
+            victim_name: Some("Enemy".to_string()),
+            assisters: Vec::new(),
+            is_player_involved: killer == "Me",
+            count,
+            clip_start_secs: None,
+        }
+    }
+
+    #[test]
+    fn test_legacy_settings_migrate() {
+        let legacy = serde_json::to_value(TriggerSettings::default()).unwrap();
+        let config: TriggerConfig = serde_json::from_value(legacy).unwrap();
+        assert_eq!(config, TriggerConfig::default());
+        assert!(config.rule(TriggerKind::Kill).enabled);
+        assert!(!config.rule(TriggerKind::Assist).enabled);
+
+        let current: TriggerConfig = serde_json::from_value(serde_json::json!({
+            "rules": {"death": {"enabled": true, "cooldownSecs": 60.0}},
+            "minMultikill": 3
+        }))
+        .unwrap();
+        assert_eq!(current.rule(TriggerKind::Death).cooldown_secs, 60.0);
+        assert_eq!(current.rule(TriggerKind::Death).pre_secs, crate::DEFAULT_PRE_SECS);
+        assert!(!current.rule(TriggerKind::Kill).enabled);
+    }
+
+    #[test]
+    fn test_cooldown_threshold_and_scope() {
+        let mut config = TriggerConfig::default();
+        config.min_multikill = 3;
+        config.rules.insert(
+            TriggerKind::Kill,
+            TriggerRule {
+                cooldown_secs: 60.0,
+                scope: TriggerScope::Team,
+                ..TriggerRule::enabled(true)
+            },
+        );
+        let mut triggers = TriggerEvaluator::new(config);
+        triggers.set_teammates(["Ally".to_string()]);
+
+        assert!(triggers.evaluate(&event(LeagueEventType::ChampionKill, 100.0, "Me", None)).triggered);
+        let decision = triggers.evaluate(&event(LeagueEventType::ChampionKill, 130.0, "Ally", None));
+        assert_eq!(decision.reason.as_deref(), Some("cooldown"));
+        assert!(triggers.evaluate(&event(LeagueEventType::ChampionKill, 161.0, "Ally", None)).triggered);
+        let decision = triggers.evaluate(&event(LeagueEventType::ChampionKill, 300.0, "Stranger", None));
+        assert_eq!(decision.reason.as_deref(), Some("team_not_involved"));
+
+        let double = triggers.evaluate(&event(LeagueEventType::Multikill, 200.0, "Me", Some(2)));
+        assert_eq!(double.reason.as_deref(), Some("below_min_multikill"));
+        assert!(triggers.evaluate(&event(LeagueEventType::Multikill, 201.0, "Me", Some(3))).triggered);
+    }
+}
@@ -1,13 +1,7 @@
 //! League-specific Types
 //!
 //! These types are used by the League integration and daemon actors.
+//! They live in the `pack-league-types` crate so other components can share
+//! them without depending on the daemon.
 
-mod game_mode;
-mod live_match;
-mod match_data;
-mod settings;
-
-pub use game_mode::*;
-pub use live_match::*;
-pub use match_data::*;
-pub use settings::*;
+pub use pack_league_types::*;
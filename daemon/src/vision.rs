@@ -0,0 +1,270 @@
+//! Vision recap from ward interactions
+//!
+//! The Live Client API doesn't report wards, so ward placements and clears
+//! are read from the finished game's timeline (Match-V5 style frames served
+//! by the LCU match history). Support players get a `VisionRecap` event and
+//! per-ward events instead of only kill highlights.
+//!
+//! During the game, `VisionTracker` guesses control ward activity from the
+//! player's inventory: the stack growing is a purchase, shrinking is a
+//! placement. The Live Client API doesn't report trinket charges, so other
+//! wards only show up in the post-game recap.
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::protocol::GameEvent;
+use crate::{GameTimeline, LeagueEventType, LiveItem, ParsedGameEvent};
+
+/// Ward type of control (pink) wards in the timeline
+const CONTROL_WARD: &str = "CONTROL_WARD";
+
+/// Item ID of the control ward
+pub const CONTROL_WARD_ITEM: i32 = 2055;
+
+/// A stack shrinking this soon after a purchase is taken as an undo or sell
+/// in the shop rather than a placement
+const UNDO_WINDOW_SECS: f64 = 10.0;
+
+/// What the player did to a ward
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WardAction {
+    Placed,
+    Cleared,
+}
+
+/// A ward the player placed or cleared
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WardEvent {
+    pub action: WardAction,
+    pub game_time_secs: f64,
+    /// Timeline ward type (e.g. "YELLOW_TRINKET", "CONTROL_WARD")
+    pub ward_type: String,
+}
+
+impl WardEvent {
+    /// Game event name used when emitting the ward event
+    pub fn event_name(&self) -> &'static str {
+        match self.action {
+            WardAction::Placed => "WardPlaced",
+            WardAction::Cleared => "WardCleared",
+        }
+    }
+}
+
+/// The player's ward activity over a whole game
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VisionRecap {
+    pub wards_placed: u32,
+    pub wards_cleared: u32,
+    pub control_wards_placed: u32,
+    /// Individual ward events, in game order
+    pub events: Vec<WardEvent>,
+}
+
+impl VisionRecap {
+    /// Collect the ward events of one participant from a timeline
+    pub fn from_timeline(timeline: &GameTimeline, participant_id: i32) -> Self {
+        let mut recap = Self::default();
+
+        for event in timeline.frames.iter().flat_map(|f| &f.events) {
+            let action = match event.event_type.as_str() {
+                // "UNDEFINED" placements are champion-created wards, not items
+                "WARD_PLACED"
+                    if event.creator_id == participant_id && event.ward_type != "UNDEFINED" =>
+                {
+                    WardAction::Placed
+                }
+                "WARD_KILL" if event.killer_id == participant_id => WardAction::Cleared,
+                _ => continue,
+            };
+
+            match action {
+                WardAction::Placed => {
+                    recap.wards_placed += 1;
+                    if event.ward_type == CONTROL_WARD {
+                        recap.control_wards_placed += 1;
+                    }
+                }
+                WardAction::Cleared => recap.wards_cleared += 1,
+            }
+
+            recap.events.push(WardEvent {
+                action,
+                game_time_secs: event.timestamp as f64 / 1000.0,
+                ward_type: event.ward_type.clone(),
+            });
+        }
+
+        recap
+    }
+
+    /// Ward events followed by the aggregate `VisionRecap` event at `game_end_secs`
+    pub fn to_game_events(&self, game_end_secs: f64) -> Vec<GameEvent> {
+        let mut events: Vec<GameEvent> = self
+            .events
+            .iter()
+            .map(|ward| {
+                GameEvent::new(
+                    ward.event_name().to_string(),
+                    ward.game_time_secs,
+                    json!({
+                        "ward_type": ward.ward_type,
+                        "is_player_involved": true,
+                    }),
+                )
+            })
+            .collect();
+
+        events.push(GameEvent::new(
+            "VisionRecap".to_string(),
+            game_end_secs,
+            json!({
+                "wards_placed": self.wards_placed,
+                "wards_cleared": self.wards_cleared,
+                "control_wards_placed": self.control_wards_placed,
+            }),
+        ));
+        events
+    }
+}
+
+/// Control ward purchases and placements from inventory changes
+#[derive(Debug, Default)]
+pub struct VisionTracker {
+    /// Control wards held at the last observation; `None` before the first
+    control_wards: Option<i32>,
+    last_bought_at: Option<f64>,
+}
+
+impl VisionTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Compare the player's items at `game_time` with the last observation.
+    /// The first observation only sets the baseline (e.g. joining mid-game).
+    pub fn observe(&mut self, items: &[LiveItem], game_time: f64) -> Vec<ParsedGameEvent> {
+        let held: i32 = items
+            .iter()
+            .filter(|item| item.item_id == CONTROL_WARD_ITEM)
+            .map(|item| item.count.max(1))
+            .sum();
+        let Some(previous) = self.control_wards.replace(held) else {
+            return Vec::new();
+        };
+
+        let (event_type, count) = match held - previous {
+            0 => return Vec::new(),
+            bought if bought > 0 => {
+                self.last_bought_at = Some(game_time);
+                (LeagueEventType::ControlWardBought, bought)
+            }
+            placed => {
+                let undone = self
+                    .last_bought_at
+                    .is_some_and(|at| game_time - at <= UNDO_WINDOW_SECS);
+                if undone {
+                    return Vec::new();
+                }
+                (LeagueEventType::WardPlaced, -placed)
+            }
+        };
+
+        vec![ParsedGameEvent {
+            event_type,
+            event_time: game_time,
+            killer_name: None,
+            victim_name: None,
+            assisters: Vec::new(),
+            is_player_involved: true,
+            count: Some(count as u32),
+            clip_start_secs: None,
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TimelineEvent, TimelineFrame};
+
+    fn event(
+        event_type: &str,
+        timestamp: i64,
+        creator_id: i32,
+        killer_id: i32,
+        ward_type: &str,
+    ) -> TimelineEvent {
+        TimelineEvent {
+            event_type: event_type.to_string(),
+            timestamp,
+            creator_id,
+            killer_id,
+            ward_type: ward_type.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_recap_counts_only_player_wards() {
+        let timeline = GameTimeline {
+            frames: vec![TimelineFrame {
+                timestamp: 60_000,
+                events: vec![
+                    event("WARD_PLACED", 65_000, 5, 0, "YELLOW_TRINKET"),
+                    event("WARD_PLACED", 70_000, 3, 0, "YELLOW_TRINKET"),
+                    event("WARD_PLACED", 90_000, 5, 0, "CONTROL_WARD"),
+                    event("WARD_PLACED", 95_000, 5, 0, "UNDEFINED"),
+                    event("WARD_KILL", 110_000, 0, 5, "SIGHT_WARD"),
+                    event("CHAMPION_KILL", 120_000, 0, 5, ""),
+                ],
+            }],
+        };
+
+        let recap = VisionRecap::from_timeline(&timeline, 5);
+        assert_eq!(recap.wards_placed, 2);
+        assert_eq!(recap.control_wards_placed, 1);
+        assert_eq!(recap.wards_cleared, 1);
+        assert_eq!(recap.events[0].game_time_secs, 65.0);
+
+        let events = recap.to_game_events(1500.0);
+        assert_eq!(events.len(), 4);
+        assert_eq!(events[2].event_type, "WardCleared");
+        assert_eq!(events[3].event_type, "VisionRecap");
+    }
+
+    #[test]
+    fn test_tracker_detects_control_wards() {
+        let wards = |count: i32| {
+            vec![LiveItem {
+                item_id: CONTROL_WARD_ITEM,
+                name: "Control Ward".to_string(),
+                slot: 3,
+                count,
+            }]
+        };
+        let kinds = |events: Vec<ParsedGameEvent>| -> Vec<_> {
+            events.into_iter().map(|e| (e.event_type, e.count)).collect()
+        };
+
+        let mut tracker = VisionTracker::new();
+        assert!(tracker.observe(&wards(1), 100.0).is_empty());
+        assert_eq!(
+            kinds(tracker.observe(&wards(2), 200.0)),
+            vec![(LeagueEventType::ControlWardBought, Some(1))]
+        );
+        // Sold back right away
+        assert!(tracker.observe(&wards(1), 205.0).is_empty());
+        assert_eq!(
+            kinds(tracker.observe(&[], 400.0)),
+            vec![(LeagueEventType::WardPlaced, Some(1))]
+        );
+    }
+}
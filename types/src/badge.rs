@@ -0,0 +1,158 @@
+//! Achievement badges awarded at end of game
+
+use serde::{Deserialize, Serialize};
+
+use super::{GameMode, ARAM, ARENA, NEXUS_BLITZ, ONE_FOR_ALL, SWARM, URF};
+
+/// Achievement badge computed from end-of-game stats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Badge {
+    /// No deaths with at least one takedown
+    Perfect,
+    /// 5+ KDA (lower in modes with frequent deaths)
+    Legendary,
+    /// Most kills on the winning team
+    Mvp,
+    /// 8+ CS per minute (lower in ARAM/URF, not awarded in Arena)
+    FarmMaster,
+    /// 30%+ of the team's champion damage
+    Carry,
+    /// First recorded game on this champion
+    FirstTime,
+    /// Most kills in a recorded game
+    PersonalBestKills,
+    /// Highest KDA in a recorded game
+    PersonalBestKda,
+    /// Highest CS per minute in a recorded game
+    PersonalBestCsPerMin,
+    /// Shortest recorded win
+    PersonalBestShortestWin,
+}
+
+impl Badge {
+    /// All badges
+    pub const ALL: [Badge; 10] = [
+        Badge::Perfect,
+        Badge::Legendary,
+        Badge::Mvp,
+        Badge::FarmMaster,
+        Badge::Carry,
+        Badge::FirstTime,
+        Badge::PersonalBestKills,
+        Badge::PersonalBestKda,
+        Badge::PersonalBestCsPerMin,
+        Badge::PersonalBestShortestWin,
+    ];
+
+    /// English label (this is what gets stored in `badges_json`)
+    pub fn label(&self) -> &'static str {
+        match self {
+            Badge::Perfect => "Perfect",
+            Badge::Legendary => "Legendary",
+            Badge::Mvp => "MVP",
+            Badge::FarmMaster => "Farm Master",
+            Badge::Carry => "Carry",
+            Badge::FirstTime => "First Time",
+            Badge::PersonalBestKills => "Personal Best: Kills",
+            Badge::PersonalBestKda => "Personal Best: KDA",
+            Badge::PersonalBestCsPerMin => "Personal Best: CS/min",
+            Badge::PersonalBestShortestWin => "Personal Best: Shortest Win",
+        }
+    }
+
+    /// Locale-independent string key (e.g. "badge.perfect")
+    pub fn string_key(&self) -> &'static str {
+        match self {
+            Badge::Perfect => "badge.perfect",
+            Badge::Legendary => "badge.legendary",
+            Badge::Mvp => "badge.mvp",
+            Badge::FarmMaster => "badge.farm_master",
+            Badge::Carry => "badge.carry",
+            Badge::FirstTime => "badge.first_time",
+            Badge::PersonalBestKills => "badge.personal_best_kills",
+            Badge::PersonalBestKda => "badge.personal_best_kda",
+            Badge::PersonalBestCsPerMin => "badge.personal_best_cs_per_min",
+            Badge::PersonalBestShortestWin => "badge.personal_best_shortest_win",
+        }
+    }
+
+    /// Look up a badge from its stored English label
+    pub fn from_label(label: &str) -> Option<Badge> {
+        Badge::ALL.into_iter().find(|b| b.label() == label)
+    }
+}
+
+/// Stat thresholds for badges in a game mode
+///
+/// Summoner's Rift numbers are out of reach or trivial elsewhere: ARAM and
+/// URF games are short and full of deaths, and Arena has no minions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BadgeThresholds {
+    /// Minimum KDA for `Legendary`
+    pub legendary_kda: f64,
+    /// Minimum CS per minute for `FarmMaster` (`None` if the mode has no farm)
+    pub farm_master_cs_per_min: Option<f64>,
+    /// Minimum share of team champion damage for `Carry`
+    pub carry_damage_share: f64,
+}
+
+impl BadgeThresholds {
+    /// Summoner's Rift thresholds
+    pub const CLASSIC: BadgeThresholds = BadgeThresholds {
+        legendary_kda: 5.0,
+        farm_master_cs_per_min: Some(8.0),
+        carry_damage_share: 0.30,
+    };
+
+    /// Thresholds for a game mode
+    pub fn for_mode(mode: &GameMode) -> Self {
+        if *mode == ARAM || *mode == ONE_FOR_ALL {
+            BadgeThresholds {
+                legendary_kda: 3.0,
+                farm_master_cs_per_min: Some(6.0),
+                ..Self::CLASSIC
+            }
+        } else if *mode == URF || *mode == NEXUS_BLITZ {
+            BadgeThresholds {
+                legendary_kda: 4.0,
+                farm_master_cs_per_min: Some(6.0),
+                ..Self::CLASSIC
+            }
+        } else if *mode == SWARM {
+            BadgeThresholds {
+                // Monster waves, no lanes to farm
+                farm_master_cs_per_min: None,
+                ..Self::CLASSIC
+            }
+        } else if *mode == ARENA {
+            BadgeThresholds {
+                legendary_kda: 3.0,
+                farm_master_cs_per_min: None,
+                // Teams of two: half the damage is an even split
+                carry_damage_share: 0.65,
+            }
+        } else {
+            Self::CLASSIC
+        }
+    }
+}
+
+impl Default for BadgeThresholds {
+    fn default() -> Self {
+        Self::CLASSIC
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{from_api_key, CLASSIC};
+
+    #[test]
+    fn test_thresholds_per_mode() {
+        assert_eq!(BadgeThresholds::for_mode(&CLASSIC), BadgeThresholds::CLASSIC);
+        assert!(BadgeThresholds::for_mode(&ARAM).legendary_kda < 5.0);
+        assert_eq!(BadgeThresholds::for_mode(from_api_key("CHERRY")).farm_master_cs_per_min, None);
+    }
+}
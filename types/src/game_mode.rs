@@ -20,6 +20,9 @@ pub struct GameMode {
     pub has_kda: bool,
     /// Whether this mode is a team game (vs free-for-all like TFT)
     pub is_team_based: bool,
+    /// Whether the game is played against the computer: no LP, no map side
+    /// and no enemy team of players
+    pub is_pve: bool,
 }
 
 // ============================================================================
@@ -34,6 +37,7 @@ pub const CLASSIC: GameMode = GameMode {
     is_placement_based: false,
     has_kda: true,
     is_team_based: true,
+    is_pve: false,
 };
 
 /// Teamfight Tactics
@@ -44,6 +48,7 @@ pub const TFT: GameMode = GameMode {
     is_placement_based: true,
     has_kda: false,
     is_team_based: false,
+    is_pve: false,
 };
 
 /// ARAM (All Random All Mid)
@@ -54,6 +59,7 @@ pub const ARAM: GameMode = GameMode {
     is_placement_based: false,
     has_kda: true,
     is_team_based: true,
+    is_pve: false,
 };
 
 /// Arena (2v2v2v2)
@@ -64,6 +70,7 @@ pub const ARENA: GameMode = GameMode {
     is_placement_based: true,  // Placement 1st-4th
     has_kda: true,
     is_team_based: true,  // 2-player teams
+    is_pve: false,
 };
 
 /// URF (Ultra Rapid Fire)
@@ -74,6 +81,7 @@ pub const URF: GameMode = GameMode {
     is_placement_based: false,
     has_kda: true,
     is_team_based: true,
+    is_pve: false,
 };
 
 /// One for All
@@ -84,6 +92,7 @@ pub const ONE_FOR_ALL: GameMode = GameMode {
     is_placement_based: false,
     has_kda: true,
     is_team_based: true,
+    is_pve: false,
 };
 
 /// Nexus Blitz
@@ -94,6 +103,40 @@ pub const NEXUS_BLITZ: GameMode = GameMode {
     is_placement_based: false,
     has_kda: true,
     is_team_based: true,
+    is_pve: false,
+};
+
+/// Swiftplay (shorter Summoner's Rift games)
+pub const SWIFTPLAY: GameMode = GameMode {
+    guid: "gm-550e8400-0001-4a00-a716-446655440008",
+    api_key: "SWIFTPLAY",
+    display_name: "Swiftplay",
+    is_placement_based: false,
+    has_kda: true,
+    is_team_based: true,
+    is_pve: false,
+};
+
+/// Swarm (up to four players against waves of monsters)
+pub const SWARM: GameMode = GameMode {
+    guid: "gm-550e8400-0001-4a00-a716-446655440009",
+    api_key: "STRAWBERRY",
+    display_name: "Swarm",
+    is_placement_based: false,
+    has_kda: false, // Kills are monsters, not champions
+    is_team_based: true,
+    is_pve: true,
+};
+
+/// Ultimate Spellbook
+pub const ULTIMATE_SPELLBOOK: GameMode = GameMode {
+    guid: "gm-550e8400-0001-4a00-a716-446655440010",
+    api_key: "ULTBOOK",
+    display_name: "Ultimate Spellbook",
+    is_placement_based: false,
+    has_kda: true,
+    is_team_based: true,
+    is_pve: false,
 };
 
 /// Unknown/Other game mode (fallback)
@@ -104,6 +147,7 @@ pub const UNKNOWN: GameMode = GameMode {
     is_placement_based: false,
     has_kda: true,
     is_team_based: true,
+    is_pve: false,
 };
 
 /// All known game modes
@@ -115,6 +159,9 @@ pub const ALL_MODES: &[&GameMode] = &[
     &URF,
     &ONE_FOR_ALL,
     &NEXUS_BLITZ,
+    &SWIFTPLAY,
+    &SWARM,
+    &ULTIMATE_SPELLBOOK,
 ];
 
 /// Look up a game mode by its API key (case-insensitive)
@@ -130,6 +177,8 @@ pub fn from_api_key(key: &str) -> &'static GameMode {
         "PRACTICETOOL" => &CLASSIC,
         "TUTORIAL" => &CLASSIC,
         "CHERRY" => &ARENA,
+        "QUICKPLAY" => &SWIFTPLAY,
+        "SWARM" => &SWARM,
         _ => &UNKNOWN,
     }
 }
@@ -155,11 +204,20 @@ pub struct GameModeContext {
     pub queue_name: String,
     /// Whether this is a ranked game
     pub is_ranked: bool,
+    /// Map ID from the game client (11 = Summoner's Rift, 12 = Howling Abyss, ...)
+    #[serde(default)]
+    pub map_id: i32,
 }
 
 impl GameModeContext {
     /// Create context from LCU session data
-    pub fn from_session(game_mode: &str, queue_id: i32, queue_name: &str, is_ranked: bool) -> Self {
+    pub fn from_session(
+        game_mode: &str,
+        queue_id: i32,
+        queue_name: &str,
+        is_ranked: bool,
+        map_id: i32,
+    ) -> Self {
         let mode = from_api_key(game_mode);
         Self {
             mode_guid: mode.guid.to_string(),
@@ -168,6 +226,19 @@ impl GameModeContext {
             queue_id,
             queue_name: queue_name.to_string(),
             is_ranked,
+            map_id,
+        }
+    }
+
+    /// English name of the map, if known
+    pub fn map_name(&self) -> Option<&'static str> {
+        match self.map_id {
+            11 => Some("Summoner's Rift"),
+            12 => Some("Howling Abyss"),
+            21 => Some("Nexus Blitz"),
+            22 => Some("Convergence"),
+            30 => Some("Rings of Wrath"),
+            _ => None,
         }
     }
 
@@ -176,8 +247,45 @@ impl GameModeContext {
         self.mode_guid == TFT.guid
     }
 
+    /// Check if this is an Arena game
+    pub fn is_arena(&self) -> bool {
+        self.mode_guid == ARENA.guid
+    }
+
     /// Get the game mode definition
     pub fn game_mode(&self) -> &'static GameMode {
         from_guid(&self.mode_guid).unwrap_or(&UNKNOWN)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_context_keeps_queue_and_map() {
+        let context = GameModeContext::from_session("CLASSIC", 420, "Ranked Solo/Duo", true, 11);
+        assert_eq!(context.game_mode().api_key, "CLASSIC");
+        assert_eq!(context.queue_id, 420);
+        assert_eq!(context.map_name(), Some("Summoner's Rift"));
+
+        // Contexts stored before the map was recorded
+        let stored: GameModeContext = serde_json::from_value(serde_json::json!({
+            "modeGuid": CLASSIC.guid, "modeKey": "CLASSIC", "displayName": "Classic",
+            "queueId": 440, "queueName": "Ranked Flex", "isRanked": true
+        }))
+        .unwrap();
+        assert_eq!(stored.map_name(), None);
+    }
+
+    #[test]
+    fn test_newer_modes_are_known() {
+        assert_eq!(from_api_key("swiftplay"), &SWIFTPLAY);
+        assert_eq!(from_api_key("ULTBOOK"), &ULTIMATE_SPELLBOOK);
+
+        let swarm = GameModeContext::from_session("STRAWBERRY", 1810, "Swarm", false, 0);
+        assert!(swarm.game_mode().is_pve);
+        assert!(!swarm.game_mode().has_kda);
+        assert!(!from_api_key("CLASSIC").is_pve);
+    }
+}
@@ -0,0 +1,25 @@
+//! Shared League of Legends pack types
+//!
+//! Match details, live match data, settings and the pack's protocol data
+//! types. The pack daemon and other components (e.g. the main daemon) depend
+//! on this crate for the same serde definitions without pulling in the
+//! network stack.
+
+mod badge;
+mod game_mode;
+mod live_match;
+mod match_data;
+pub mod protocol;
+mod replay;
+mod riot_id;
+mod settings;
+mod stats;
+
+pub use badge::*;
+pub use game_mode::*;
+pub use live_match::*;
+pub use match_data::*;
+pub use replay::*;
+pub use riot_id::*;
+pub use settings::*;
+pub use stats::*;
@@ -0,0 +1,247 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::Team;
+
+/// Item in a slot (0-5 are regular items, 6 is trinket)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveItem {
+    pub item_id: i32,
+    pub name: String,
+    pub slot: i32,
+    /// Stack size (e.g. control wards)
+    #[serde(default)]
+    pub count: i32,
+}
+
+/// Summoner spell info
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveSpell {
+    pub name: String,
+}
+
+/// Rune info
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveRunes {
+    pub keystone_id: i32,
+    pub keystone_name: String,
+    pub primary_tree_id: i32,
+    pub primary_tree_name: String,
+    pub secondary_tree_id: i32,
+    pub secondary_tree_name: String,
+}
+
+/// Represents a player in an active game with real-time stats
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LivePlayer {
+    pub summoner_name: String,
+    pub champion: String,
+    pub team: Team,
+    pub kills: i32,
+    pub deaths: i32,
+    pub assists: i32,
+    pub cs: i32,
+    pub level: i32,
+    pub is_dead: bool,
+    /// Total cost of held items (the Live Client API doesn't expose gold for other players)
+    #[serde(default)]
+    pub item_gold: i32,
+    /// Assigned position ("TOP", "JUNGLE", ...), empty outside role queues
+    #[serde(default)]
+    pub position: String,
+    /// Items in slots 0-5
+    #[serde(default)]
+    pub items: Vec<LiveItem>,
+    /// Trinket in slot 6
+    #[serde(default)]
+    pub trinket: Option<LiveItem>,
+    #[serde(default)]
+    pub spell1: Option<LiveSpell>,
+    #[serde(default)]
+    pub spell2: Option<LiveSpell>,
+    /// Keystone and trees (the rest of the page is only visible for the
+    /// active player)
+    #[serde(default)]
+    pub runes: Option<LiveRunes>,
+}
+
+/// Rank of the active player's abilities (0 until learned)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AbilityRanks {
+    pub q: i32,
+    pub w: i32,
+    pub e: i32,
+    pub r: i32,
+}
+
+/// Represents the current game state with real-time data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveMatch {
+    pub summoner_name: String,
+    pub champion: String,
+    pub level: i32,
+    pub kills: i32,
+    pub deaths: i32,
+    pub assists: i32,
+    pub cs: i32,
+    pub current_gold: f64,
+    pub game_time_secs: f64,
+    pub game_mode: String,
+    pub team: Team,
+    /// Items in slots 0-5 (regular items)
+    pub items: Vec<LiveItem>,
+    /// Trinket in slot 6
+    pub trinket: Option<LiveItem>,
+    /// Summoner spells
+    pub spell1: Option<LiveSpell>,
+    pub spell2: Option<LiveSpell>,
+    /// Rune info
+    pub runes: Option<LiveRunes>,
+    #[serde(default)]
+    pub ability_ranks: AbilityRanks,
+    pub participants: Vec<LivePlayer>,
+    pub is_dead: bool,
+}
+
+/// The local player's pick while in champ select (pre-game live data)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChampSelectState {
+    /// Hovered or locked champion (0 if none yet)
+    pub champion_id: i32,
+    /// Whether the pick is locked in
+    pub locked: bool,
+    /// Assigned position ("top", "jungle", ...), empty in blind pick
+    pub role: String,
+    pub spell1_id: u64,
+    pub spell2_id: u64,
+    /// Current rune page
+    pub keystone_id: Option<i32>,
+    pub primary_tree_id: Option<i32>,
+    pub secondary_tree_id: Option<i32>,
+}
+
+/// Field groups a live data consumer can request.
+///
+/// A lightweight overlay can ask for just `GameTime` + `Scores` instead of the
+/// full snapshot, which also lets the integration skip `allgamedata`. An
+/// empty mask is the same as no mask: every field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LiveField {
+    /// `gameTimeSecs`
+    GameTime,
+    /// `kills`, `deaths`, `assists`, `cs`
+    Scores,
+    /// `champion`, `level`, `currentGold`, `team`, `gameMode`, `isDead`
+    Player,
+    /// `items`, `trinket`
+    Items,
+    /// `spell1`, `spell2`, `runes`, `abilityRanks`
+    Loadout,
+    /// `participants`
+    Participants,
+    /// `assets` (CDN icon URLs)
+    Assets,
+    /// `objectives` (dragon soul, spawn timers, turret plates)
+    Objectives,
+}
+
+impl LiveField {
+    /// The groups a request is restricted to; `None` (every field) when it
+    /// sent no mask or an empty one
+    pub fn mask(fields: Option<&[LiveField]>) -> Option<&[LiveField]> {
+        fields.filter(|f| !f.is_empty())
+    }
+
+    /// JSON keys of the live data object covered by this group
+    pub fn keys(&self) -> &'static [&'static str] {
+        match self {
+            LiveField::GameTime => &["gameTimeSecs"],
+            LiveField::Scores => &["kills", "deaths", "assists", "cs"],
+            LiveField::Player => &["champion", "level", "currentGold", "team", "gameMode", "isDead"],
+            LiveField::Items => &["items", "trinket"],
+            LiveField::Loadout => &["spell1", "spell2", "runes", "abilityRanks"],
+            LiveField::Participants => &["participants"],
+            LiveField::Assets => &["assets"],
+            LiveField::Objectives => &["objectives"],
+        }
+    }
+
+    /// Whether these fields can be served without fetching `allgamedata`
+    pub fn is_lightweight(fields: &[LiveField]) -> bool {
+        fields
+            .iter()
+            .all(|f| matches!(f, LiveField::GameTime | LiveField::Scores | LiveField::Objectives))
+    }
+
+    /// Keep only the requested groups (plus `summonerName`) in a live data object
+    pub fn select(fields: &[LiveField], data: Value) -> Value {
+        let Value::Object(mut map) = data else {
+            return data;
+        };
+
+        let mut selected = Map::new();
+        if let Some(name) = map.remove("summonerName") {
+            selected.insert("summonerName".to_string(), name);
+        }
+        for key in fields.iter().flat_map(|f| f.keys()) {
+            if let Some(value) = map.remove(*key) {
+                selected.insert(key.to_string(), value);
+            }
+        }
+
+        Value::Object(selected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_select_live_fields() {
+        let data = json!({
+            "summonerName": "Me",
+            "kills": 3,
+            "deaths": 1,
+            "assists": 7,
+            "cs": 120,
+            "gameTimeSecs": 600.0,
+            "items": [],
+            "participants": [],
+        });
+
+        let selected = LiveField::select(&[LiveField::Scores, LiveField::GameTime], data);
+        assert_eq!(
+            selected,
+            json!({
+                "summonerName": "Me",
+                "kills": 3,
+                "deaths": 1,
+                "assists": 7,
+                "cs": 120,
+                "gameTimeSecs": 600.0,
+            })
+        );
+        assert!(LiveField::is_lightweight(&[LiveField::Scores, LiveField::GameTime]));
+        assert!(!LiveField::is_lightweight(&[LiveField::Items]));
+    }
+
+    #[test]
+    fn test_empty_mask_selects_every_field() {
+        assert_eq!(LiveField::mask(Some(&[])), None);
+        assert_eq!(LiveField::mask(None), None);
+        assert_eq!(
+            LiveField::mask(Some(&[LiveField::Scores])),
+            Some(&[LiveField::Scores][..])
+        );
+    }
+}
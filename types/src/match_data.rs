@@ -0,0 +1,552 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{riot_ids_match, GameModeContext};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum MatchResult {
+    Win,
+    Loss,
+    Remake,
+}
+
+impl ToString for MatchResult {
+    fn to_string(&self) -> String {
+        match self {
+            MatchResult::Win => "win".to_string(),
+            MatchResult::Loss => "loss".to_string(),
+            MatchResult::Remake => "remake".to_string(),
+        }
+    }
+}
+
+impl From<&str> for MatchResult {
+    fn from(s: &str) -> Self {
+        match s {
+            "win" => MatchResult::Win,
+            "loss" => MatchResult::Loss,
+            "remake" => MatchResult::Remake,
+            _ => MatchResult::Loss,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Team {
+    Blue,
+    Red,
+}
+
+impl Team {
+    /// Side for an LCU team ID (100 blue, 200 red); `None` for modes without sides
+    pub fn from_team_id(team_id: i32) -> Option<Team> {
+        match team_id {
+            100 => Some(Team::Blue),
+            200 => Some(Team::Red),
+            _ => None,
+        }
+    }
+}
+
+impl ToString for Team {
+    fn to_string(&self) -> String {
+        match self {
+            Team::Blue => "blue".to_string(),
+            Team::Red => "red".to_string(),
+        }
+    }
+}
+
+impl From<&str> for Team {
+    fn from(s: &str) -> Self {
+        match s {
+            "blue" => Team::Blue,
+            "red" => Team::Red,
+            _ => Team::Blue,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Participant {
+    /// Riot ID ("name#tag") when the end-of-game stats have it
+    pub summoner_name: String,
+    pub champion: String,
+    pub team: Team,
+    /// Share of the team's champion damage (0.0-1.0), from end-of-game stats
+    #[serde(default)]
+    pub damage_share: Option<f64>,
+    #[serde(default)]
+    pub kills: i32,
+    #[serde(default)]
+    pub deaths: i32,
+    #[serde(default)]
+    pub assists: i32,
+    /// Flagged as AFK or leaver
+    #[serde(default)]
+    pub left_game: bool,
+    /// Items in slots 0-5 at the last live snapshot (empty for games parsed
+    /// from end-of-game stats)
+    #[serde(default)]
+    pub item_ids: Vec<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Match {
+    pub id: String,
+    pub game_id: i64,
+    pub summoner_name: String,
+    pub champion: String,
+    pub champion_level: i32,
+    pub result: MatchResult,
+    pub kills: i32,
+    pub deaths: i32,
+    pub assists: i32,
+    pub cs: i32,
+    pub cs_per_min: f64,
+    pub vision_score: i32,
+    pub kill_participation: i32,
+    pub damage_dealt: i64,
+    pub game_mode: String,
+    pub played_at: DateTime<Utc>,
+    pub duration_secs: i32,
+    pub created_at: DateTime<Utc>,
+    pub lp_change: Option<i32>,
+    pub rank: Option<String>,
+    // Summoner spells
+    pub summoner_spell1: String,
+    pub summoner_spell2: String,
+    // Runes
+    pub keystone_rune: String,
+    pub secondary_tree: String,
+    #[serde(default)]
+    pub runes: Option<MatchRunes>,
+    // Items (JSON array stored as string in DB)
+    pub items: Vec<String>,
+    pub trinket: Option<String>,
+    // Team compositions (JSON array stored as string in DB)
+    pub participants: Vec<Participant>,
+    // Achievement badges (JSON array)
+    pub badges: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchWithClips {
+    #[serde(flatten)]
+    pub match_data: Match,
+    pub clips: Vec<Clip>,
+    pub events: Vec<StoredGameEvent>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Clip {
+    pub id: String,
+    pub match_id: String,
+    pub file_path: String,
+    pub thumbnail_path: Option<String>,
+    pub start_time_secs: f64,
+    pub end_time_secs: f64,
+    pub trigger_event: String,
+    pub trigger_data: Option<String>,
+    pub file_size_bytes: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A game event stored in the database
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredGameEvent {
+    pub id: String,
+    pub match_id: String,
+    pub event_type: String,
+    pub event_time_secs: f64,
+    pub data: serde_json::Value,
+    pub has_clip: bool,
+}
+
+/// Full rune page the player locked in with
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchRunes {
+    pub page_name: String,
+    pub primary_tree_id: i32,
+    pub secondary_tree_id: i32,
+    /// Selected perks in page order: keystone, primary, secondary, stat shards
+    pub perk_ids: Vec<i32>,
+}
+
+impl MatchRunes {
+    /// The keystone perk, if the page has any perks
+    pub fn keystone_id(&self) -> Option<i32> {
+        self.perk_ids.first().copied()
+    }
+}
+
+/// Canonical position name for the role spellings used by champ select,
+/// the end-of-game stats and the Live Client API (e.g. "middle", "MID" ->
+/// "MIDDLE"); `None` for unknown or empty roles
+pub fn normalize_role(role: &str) -> Option<String> {
+    let role = match role.trim().to_ascii_uppercase().as_str() {
+        "TOP" => "TOP",
+        "JUNGLE" => "JUNGLE",
+        "MIDDLE" | "MID" => "MIDDLE",
+        "BOTTOM" | "BOT" | "ADC" => "BOTTOM",
+        "UTILITY" | "SUPPORT" => "UTILITY",
+        _ => return None,
+    };
+    Some(role.to_string())
+}
+
+/// A ban in champ select
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DraftBan {
+    pub champion_id: i32,
+    /// Champion name, filled in when the game is finalized
+    #[serde(default)]
+    pub champion: Option<String>,
+    /// Banned by the player's team
+    pub is_ally: bool,
+    /// Enemy ban of the champion the player was hovering
+    #[serde(default)]
+    pub hover_banned: bool,
+}
+
+/// A locked-in pick in champ select
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DraftPick {
+    pub cell_id: i64,
+    pub champion_id: i32,
+    /// Champion name, filled in when the game is finalized
+    #[serde(default)]
+    pub champion: Option<String>,
+    /// Picked by the player's team
+    pub is_ally: bool,
+    /// Assigned position, if known (enemy positions usually aren't)
+    pub role: String,
+    /// Seconds after champ select started
+    pub locked_at_secs: f64,
+}
+
+/// Draft context captured during champ select
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChampSelectDraft {
+    /// The player's assigned position, empty in blind pick
+    pub assigned_role: String,
+    pub champion_id: i32,
+    /// When the player locked in, in seconds after champ select started
+    pub lock_in_secs: Option<f64>,
+    pub bans: Vec<DraftBan>,
+    /// Picks in lock-in order
+    pub picks: Vec<DraftPick>,
+    /// Total time spent in champ select
+    pub duration_secs: f64,
+    /// Team compositions of the locked-in picks
+    #[serde(default)]
+    pub composition: Option<DraftComposition>,
+}
+
+impl ChampSelectDraft {
+    /// Bans by the player's team (`true`) or the enemy team, in ban order
+    pub fn team_bans(&self, is_ally: bool) -> impl Iterator<Item = &DraftBan> {
+        self.bans.iter().filter(move |b| b.is_ally == is_ally)
+    }
+
+    /// Picks by the player's team (`true`) or the enemy team, in lock-in order
+    pub fn team_picks(&self, is_ally: bool) -> impl Iterator<Item = &DraftPick> {
+        self.picks.iter().filter(move |p| p.is_ally == is_ally)
+    }
+
+    /// Fill in champion names of bans and picks that don't have one yet
+    pub fn name_champions(&mut self, name: impl Fn(i32) -> Option<String>) {
+        for ban in self.bans.iter_mut().filter(|b| b.champion.is_none()) {
+            ban.champion = name(ban.champion_id);
+        }
+        for pick in self.picks.iter_mut().filter(|p| p.champion.is_none()) {
+            pick.champion = name(pick.champion_id);
+        }
+    }
+}
+
+/// Damage type a team leans on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DamageProfile {
+    Physical,
+    Magic,
+    Mixed,
+}
+
+/// How a team wants to start fights
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FightStyle {
+    /// Tanks and divers that force fights
+    Engage,
+    /// Ranged damage that whittles down before fighting
+    Poke,
+    Balanced,
+}
+
+/// Stage of the game a team is strongest in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompScaling {
+    Early,
+    Mid,
+    Late,
+}
+
+/// Descriptors of one team's drafted champions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamComposition {
+    /// Physical share of the team's damage ratings (0-1)
+    pub physical_share: f64,
+    pub damage: DamageProfile,
+    pub style: FightStyle,
+    pub scaling: CompScaling,
+    /// Champions with known static data (the others are left out)
+    pub analyzed: usize,
+}
+
+/// Compositions of both teams in a draft
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DraftComposition {
+    pub ally: TeamComposition,
+    /// Missing when no enemy pick was visible
+    pub enemy: Option<TeamComposition>,
+}
+
+/// Arena duo partner
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArenaPartner {
+    pub summoner_name: String,
+    pub champion: String,
+}
+
+/// Arena (2v2v2v2) result, stored in the `arena_match_details` table
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArenaMatchDetails {
+    pub game_id: i64,
+    /// Final placement of the player's duo (1-4)
+    pub placement: u8,
+    pub champion: String,
+    pub partner: Option<ArenaPartner>,
+    /// Augments picked during the game, in pick order
+    pub augment_ids: Vec<i32>,
+    pub played_at: DateTime<Utc>,
+}
+
+impl ArenaMatchDetails {
+    /// Top two duos count as a win
+    pub fn is_win(&self) -> bool {
+        self.placement <= 2
+    }
+}
+
+/// Game server latency over one game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencySummary {
+    pub average_ms: u32,
+    pub max_ms: u32,
+    pub samples: usize,
+}
+
+/// How the player was credited for an objective their team took
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ObjectiveRole {
+    Killer,
+    Assister,
+}
+
+/// A dragon, Baron or Herald taken by the player's team
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectiveCredit {
+    /// "dragon", "elder", "baron" or "herald"
+    pub objective: String,
+    pub game_time_secs: f64,
+    /// `None` when the player had no part in it
+    pub credit: Option<ObjectiveRole>,
+}
+
+/// The player's share of their team's epic objectives over one game
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectiveParticipation {
+    pub objectives: Vec<ObjectiveCredit>,
+}
+
+impl ObjectiveParticipation {
+    /// Objectives the team took
+    pub fn taken(&self) -> u32 {
+        self.objectives.len() as u32
+    }
+
+    /// Objectives the player was credited for as killer or assister
+    pub fn participated(&self) -> u32 {
+        self.objectives.iter().filter(|o| o.credit.is_some()).count() as u32
+    }
+
+    /// Objective participation in percent, `None` if the team took none
+    pub fn percent(&self) -> Option<f64> {
+        let taken = self.taken();
+        (taken > 0).then(|| self.participated() as f64 / taken as f64 * 100.0)
+    }
+}
+
+/// Ranked standing change over one game
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RankChange {
+    /// LP gained or lost, counted across division and tier boundaries
+    pub lp_change: i32,
+    pub promoted: bool,
+    pub demoted: bool,
+    /// Promotion series results (e.g. "WLN"), if in a series
+    pub series_progress: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateMatch {
+    pub game_id: i64,
+    /// Account the game was played on, so players with several accounts
+    /// can keep their matches apart
+    #[serde(default)]
+    pub puuid: Option<String>,
+    pub summoner_name: String,
+    pub champion: String,
+    pub champion_level: i32,
+    pub result: MatchResult,
+    pub kills: i32,
+    pub deaths: i32,
+    pub assists: i32,
+    pub cs: i32,
+    pub cs_per_min: f64,
+    pub vision_score: i32,
+    pub kill_participation: i32,
+    pub damage_dealt: i64,
+    /// Share of the team's champion damage (0.0-1.0)
+    #[serde(default)]
+    pub damage_share: Option<f64>,
+    pub game_mode: String,
+    /// Queue, map and ranked flag from the client's gameflow session (sets
+    /// apart the queues that share a game mode, e.g. every "CLASSIC" queue)
+    #[serde(default)]
+    pub queue: Option<GameModeContext>,
+    /// When the game ended (same as `ended_at` once that is known)
+    pub played_at: DateTime<Utc>,
+    /// When the game clock started
+    #[serde(default)]
+    pub started_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub ended_at: Option<DateTime<Utc>>,
+    pub duration_secs: i32,
+    pub lp_change: Option<i32>,
+    pub rank: Option<String>,
+    /// Promotion, demotion and series state for ranked games
+    #[serde(default)]
+    pub rank_change: Option<RankChange>,
+    /// Map side the player was on (`None` in Arena and other sideless modes)
+    #[serde(default)]
+    pub side: Option<Team>,
+    /// Ping to the game server, sampled during the game
+    #[serde(default)]
+    pub latency: Option<LatencySummary>,
+    /// Dragons, Barons and Heralds the team took, and the player's part in them
+    #[serde(default)]
+    pub objective_participation: Option<ObjectiveParticipation>,
+    /// Ranked season and split the game was played in
+    #[serde(default)]
+    pub season_id: Option<i32>,
+    #[serde(default)]
+    pub split_id: Option<i32>,
+    pub summoner_spell1: String,
+    pub summoner_spell2: String,
+    pub keystone_rune: String,
+    pub secondary_tree: String,
+    /// Rune page captured at champ select lock-in
+    #[serde(default)]
+    pub runes: Option<MatchRunes>,
+    /// Position played ("TOP", "JUNGLE", "MIDDLE", "BOTTOM" or "UTILITY"),
+    /// from champ select or the end-of-game stats
+    #[serde(default)]
+    pub role: Option<String>,
+    /// Bans, picks and lock-in timing from champ select
+    #[serde(default)]
+    pub draft: Option<ChampSelectDraft>,
+    /// Placement, duo partner and augments for Arena games
+    #[serde(default)]
+    pub arena: Option<ArenaMatchDetails>,
+    /// Item names (the ID as a string when the name isn't known)
+    pub items: Vec<String>,
+    pub trinket: Option<String>,
+    /// Item IDs in the same order as `items`
+    #[serde(default)]
+    pub item_ids: Vec<i32>,
+    #[serde(default)]
+    pub trinket_id: Option<i32>,
+    pub participants: Vec<Participant>,
+    /// Participants flagged as AFK or leaver (see `Participant::left_game`)
+    #[serde(default)]
+    pub leavers: Vec<String>,
+    pub badges: Vec<String>,
+    /// String keys for `badges` (e.g. "badge.perfect"), for localized display
+    #[serde(default)]
+    pub badge_keys: Vec<String>,
+}
+
+impl CreateMatch {
+    /// Date the game from when it started, which stays right however late
+    /// it's finalized. Without a start, the game is taken to have ended at
+    /// `played_at`.
+    pub fn set_game_times(&mut self, started_at: Option<DateTime<Utc>>) {
+        let length = chrono::Duration::seconds(self.duration_secs as i64);
+        let started_at = started_at.unwrap_or(self.played_at - length);
+        self.started_at = Some(started_at);
+        self.ended_at = Some(started_at + length);
+        self.played_at = started_at + length;
+        if let Some(ref mut arena) = self.arena {
+            arena.played_at = self.played_at;
+        }
+    }
+
+    /// Drop what a game against the computer doesn't have: LP, a map side
+    /// and an enemy team
+    pub fn mark_pve(&mut self) {
+        self.lp_change = None;
+        self.rank_change = None;
+        self.side = None;
+        let team = self
+            .participants
+            .iter()
+            .find(|p| riot_ids_match(&p.summoner_name, &self.summoner_name))
+            .map(|p| p.team);
+        if let Some(team) = team {
+            self.participants.retain(|p| p.team == team);
+        }
+    }
+
+    /// Record the game as remade. A remake gives or takes no LP, so any
+    /// rank change measured across it is dropped.
+    pub fn mark_remake(&mut self) {
+        self.result = MatchResult::Remake;
+        self.lp_change = None;
+        self.rank_change = None;
+    }
+}
@@ -0,0 +1,779 @@
+//! Protocol data types
+//!
+//! Serializable types exchanged between the League pack and the main daemon.
+//! Runtime message types (`GameEvent`, commands, responses) come from
+//! `gamepack-runtime` and are re-exported by the daemon's `protocol` module.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    AssetCacheSettings, BufferSettings, LcuRateLimitSettings, LiveField, LocaleStrings,
+    NotificationSettings, PollingConfig, QueueEstimate, RemoteHostSettings, ReplayRecording,
+    ReplayRender, SpectatorSettings, StatsSettings, TriggerConfig, TriggerKind,
+};
+
+// ============================================================================
+// League-Specific Data Types
+// ============================================================================
+
+/// Live match data for League UI display
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiveMatchData {
+    /// Game ID
+    pub game_id: i32,
+    /// Current game time in seconds
+    pub game_time_secs: f64,
+    /// Game-specific live data as JSON
+    pub data: Value,
+}
+
+/// Match result for League.
+///
+/// Sent to the daemon as a plain string (`Display`, same as the serde form):
+/// `"win"` and `"loss"` for finished games, plus League-specific values the
+/// daemon should not count towards a win rate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MatchResult {
+    /// `"win"`
+    Win,
+    /// `"loss"`
+    Loss,
+    /// `"remake"`: ended by an early vote after a player never connected;
+    /// no LP change
+    Remake,
+    /// `"aborted"`: the game never started or crashed (see `abortReason` in
+    /// the details); no end-of-game stats
+    Aborted,
+    /// `"spectated"`: a game the user watched (see `spectated_player` in the
+    /// details); not the user's own result
+    Spectated,
+}
+
+impl std::fmt::Display for MatchResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MatchResult::Win => write!(f, "win"),
+            MatchResult::Loss => write!(f, "loss"),
+            MatchResult::Remake => write!(f, "remake"),
+            MatchResult::Aborted => write!(f, "aborted"),
+            MatchResult::Spectated => write!(f, "spectated"),
+        }
+    }
+}
+
+/// League-specific match data returned when a match ends
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchData {
+    /// Game slug (e.g., "league")
+    pub game_slug: String,
+    /// Game ID
+    pub game_id: i32,
+    /// When the match was played (when it ended)
+    pub played_at: DateTime<Utc>,
+    /// When the game clock started, from the session clock or the
+    /// end-of-game stats
+    #[serde(default)]
+    pub started_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub ended_at: Option<DateTime<Utc>>,
+    /// Match duration in seconds
+    pub duration_secs: i32,
+    /// Match result
+    pub result: MatchResult,
+    /// A spectated game, kept out of personal stats
+    #[serde(default)]
+    pub spectated: bool,
+    /// Game-specific details as JSON
+    pub details: Value,
+}
+
+/// Session context for tracking game session state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionContext {
+    /// Session start time
+    pub started_at: DateTime<Utc>,
+    /// Monotonic clock anchor; session offsets are relative to this
+    #[serde(default)]
+    pub clock: Option<SessionClockAnchor>,
+    /// Game-specific session data
+    pub data: Value,
+}
+
+impl SessionContext {
+    /// Create a new session context
+    pub fn new(data: Value) -> Self {
+        Self {
+            started_at: Utc::now(),
+            clock: None,
+            data,
+        }
+    }
+
+    /// Create a session context anchored to a session clock
+    pub fn anchored(data: Value, anchor: SessionClockAnchor) -> Self {
+        Self {
+            started_at: anchor.started_at,
+            clock: Some(anchor),
+            data,
+        }
+    }
+}
+
+/// A running League client and the account logged in to it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientConnection {
+    /// Client API port (changes every time the client starts)
+    pub port: u16,
+    /// Empty while the client is on the login screen
+    pub puuid: String,
+    /// Riot ID ("name#tag")
+    pub riot_id: String,
+    /// Whether the integration follows this client
+    pub active: bool,
+}
+
+/// Player whose game is being spectated (stored in the session context)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpectatedPlayer {
+    pub puuid: String,
+    /// Riot ID ("name#tag")
+    pub riot_id: String,
+    pub champion_id: i32,
+}
+
+/// Game family an event type occurs in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CatalogMode {
+    /// Summoner's Rift, ARAM and the other team modes
+    League,
+    Tft,
+    Arena,
+}
+
+/// An event type the pack can emit, with its default clip capture
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventCatalogEntry {
+    /// Event type as emitted in `GameEvent`s
+    pub event_type: String,
+    /// English name for settings screens
+    pub name: String,
+    pub modes: Vec<CatalogMode>,
+    /// Trigger rule that decides whether the event is clipped, if any
+    pub trigger: Option<TriggerKind>,
+    /// Whether the default trigger rules clip it
+    pub enabled_by_default: bool,
+    /// Default seconds captured before the event
+    pub pre_secs: f64,
+    /// Default seconds captured after the event
+    pub post_secs: f64,
+}
+
+/// League-specific IPC commands. A command that fails replies
+/// `{ "error": CommandError }`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum LeagueCommand {
+    /// Run a synthetic game event through the trigger evaluator and emit it
+    /// like a real one (for testing the clip pipeline without a game)
+    SimulateEvent {
+        event_type: String,
+        #[serde(default)]
+        data: Value,
+    },
+    /// Get live data, optionally restricted to a set of field groups (an
+    /// empty list means every field, like no list)
+    GetLiveData {
+        #[serde(default)]
+        fields: Option<Vec<LiveField>>,
+    },
+    /// Full `IntegrationStatus`, with what the runtime's status call leaves
+    /// out: supervised task health, buffer metrics, running processes,
+    /// profile and queue estimate
+    GetStatus,
+    /// Get average queue times per queue type
+    GetQueueStats,
+    /// Get dodge and remake counts
+    GetNonGameStats,
+    /// Get how often the enemy banned the champion the player was hovering
+    GetHoverBans,
+    /// Get recorded Arena games (placement, duo partner, augments)
+    GetArenaMatches,
+    /// Get the stored event timeline of a match
+    GetMatchEvents { match_id: String },
+    /// Parse a match's stored end-of-game payload again (to backfill fields
+    /// added since it was played)
+    ReprocessMatch { match_id: String },
+    /// Run match finalization against the current session's data without
+    /// ending the session or storing anything, returning what would be saved
+    PreviewFinalization,
+    /// Get a match's scoreboard snapshots (gold, CS and KDA per player over time)
+    GetMatchSnapshots { match_id: String },
+    /// Backfill game results from the client's match history (games played
+    /// before the companion was installed). Progress is pushed as
+    /// `ImportProgress` lines. Over the protocol channel the import runs in
+    /// the background and the reply is the initial progress; the last line
+    /// is `done` (with an `error` if it failed). On the debug socket the
+    /// reply is the final progress.
+    ImportMatchHistory {
+        /// Most games to read (default 200)
+        #[serde(default)]
+        limit: Option<u32>,
+    },
+    /// Get raw event payloads kept for discovery (see `rawEventRetention`)
+    GetDiscoveredEvents,
+    /// Get the size and record count of every store in the data directory
+    GetStorageReport,
+    /// Check that every stored record still parses
+    CheckStorageIntegrity,
+    /// Drop unreadable log lines and temp files left in the data directory
+    /// (for a "maintain database" action when history views get slow)
+    CompactStorage,
+    /// Get every event type the pack can emit, with names and default capture
+    GetEventCatalog,
+    /// Mark the current moment of the game by hand (hotkey), stored with the
+    /// automatic events
+    BookmarkMoment {
+        /// Echoed back so the companion can match the reply
+        request_id: String,
+        #[serde(default)]
+        note: Option<String>,
+    },
+    /// Get head-to-head records against opponents met more than once
+    GetRivals,
+    /// Get winrate and combined KDA per recurring duo partner
+    GetDuoStats,
+    /// Get winrate and KDA by hour of day and by game number within a session
+    GetPerformanceAnalytics,
+    /// Get the known ranked splits and the current one, for per-split stats
+    GetRankedSplits,
+    /// Get time played today and per day before it
+    GetPlaytime {
+        /// Days to include, today included (default 7)
+        #[serde(default)]
+        days: Option<u32>,
+    },
+    /// Get finished games on a champion, optionally by game mode and date
+    GetChampionGames {
+        champion: String,
+        #[serde(default)]
+        game_mode: Option<String>,
+        #[serde(default)]
+        since: Option<DateTime<Utc>>,
+        #[serde(default)]
+        until: Option<DateTime<Utc>>,
+        /// Leave out games with an AFK or leaver
+        #[serde(default)]
+        exclude_leavers: bool,
+    },
+    /// Client FPS measured by the daemon, added to the match timeline
+    ReportFps {
+        fps: f32,
+        /// Game time of the sample (defaults to the latest live snapshot)
+        #[serde(default)]
+        game_time_secs: Option<f64>,
+    },
+    /// Get the loaded replay's playback, camera/render and recording state
+    GetReplayState,
+    /// Seek, pause/resume or change the speed of the loaded replay
+    SetReplayPlayback {
+        #[serde(default)]
+        time: Option<f64>,
+        #[serde(default)]
+        paused: Option<bool>,
+        #[serde(default)]
+        speed: Option<f64>,
+    },
+    /// Move the replay camera or change render settings
+    SetReplayRender { render: ReplayRender },
+    /// Render a range of the loaded replay to a file with the game's recorder
+    RecordReplay { recording: ReplayRecording },
+    /// Get every running League client and the account logged in to it
+    ListConnections,
+    /// Follow the client logged in to this account (`None` follows the lockfile)
+    SelectConnection {
+        #[serde(default)]
+        puuid: Option<String>,
+    },
+    /// Tear down and re-establish the LCU connection, the WebSockets and the
+    /// Live Client API check (for a "reconnect" button when things look stuck)
+    Reconnect {
+        /// Echoed back so the companion can match the reply
+        request_id: String,
+    },
+    /// Change pack settings at runtime; settings left out are kept. The
+    /// reply has the settings in effect.
+    Configure {
+        /// Poll intervals of the Live Client API and the gameflow fallback
+        #[serde(default)]
+        polling: Option<PollingConfig>,
+        /// Batching of pushed notifications
+        #[serde(default)]
+        notifications: Option<NotificationSettings>,
+        /// PC the League client and game run on (an empty host reads the
+        /// local machine)
+        #[serde(default)]
+        remote_host: Option<RemoteHostSettings>,
+        /// Local icon cache
+        #[serde(default)]
+        asset_cache: Option<AssetCacheSettings>,
+        /// Recording of spectated games
+        #[serde(default)]
+        spectator: Option<SpectatorSettings>,
+        /// Clip trigger rules (the flat on/off settings are accepted too)
+        #[serde(default)]
+        triggers: Option<TriggerConfig>,
+        /// In-memory buffer capacities
+        #[serde(default)]
+        buffers: Option<BufferSettings>,
+        /// Request limits for the League client
+        #[serde(default)]
+        lcu_rate_limit: Option<LcuRateLimitSettings>,
+        /// Translations for the labels in emitted events
+        #[serde(default)]
+        strings: Option<LocaleStrings>,
+        /// Data directory, snapshots and raw event retention (stores are
+        /// reopened; settings left out take their defaults)
+        #[serde(default)]
+        stats: Option<StatsSettings>,
+        /// Soft daily playtime limit in minutes, 0 turns it off (kept in
+        /// `stats`)
+        #[serde(default)]
+        daily_playtime_limit_mins: Option<u32>,
+        /// Start and end sessions from the client's gameflow instead of the
+        /// daemon's session calls, reported as `SessionNotification`s
+        #[serde(default)]
+        gameflow_sessions: Option<bool>,
+    },
+    /// Push the given topics as `EventNotification`s as they happen
+    Subscribe { topics: Vec<EventTopic> },
+    /// Stop pushing the given topics
+    Unsubscribe { topics: Vec<EventTopic> },
+}
+
+/// Cause of a failed command, so the daemon can tell errors worth retrying
+/// from ones that won't go away
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum LeagueErrorCode {
+    /// No League client is running
+    LcuNotRunning,
+    /// The client is running but the request to it failed
+    LcuConnectionFailed,
+    /// The lockfile isn't in the expected format
+    LockfileParse,
+    /// The Live Client API didn't answer in time
+    LiveClientTimeout,
+    /// The Live Client API isn't up (no game loaded)
+    LiveClientUnavailable,
+    /// End-of-game stats aren't available (yet)
+    EogUnavailable,
+    /// The LCU WebSocket failed
+    WebsocketFailed,
+    /// No replay is loaded or the Replay API is disabled
+    ReplayUnavailable,
+    /// The requested match isn't stored
+    NotFound,
+    /// The command's arguments can't be acted on
+    InvalidRequest,
+    /// A response or stored file couldn't be parsed
+    ParseFailed,
+    /// Reading or writing local files failed
+    IoFailed,
+    Internal,
+    /// `ImportMatchHistory` stopped (the `cause` says why)
+    ImportFailed,
+    /// `ReprocessMatch` couldn't rebuild the stored end-of-game payload
+    ReprocessFailed,
+    /// The Replay API rejected `SetReplayPlayback`, `SetReplayRender` or
+    /// `RecordReplay` (the `cause` says why)
+    ReplayRequestFailed,
+    /// `GetStorageReport`, `CheckStorageIntegrity` or `CompactStorage`
+    /// failed (the `cause` says why)
+    StorageFailed,
+}
+
+impl LeagueErrorCode {
+    /// Whether the same command may succeed when sent again later. Command
+    /// codes aren't retryable by themselves; their cause decides.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            LeagueErrorCode::LcuNotRunning
+                | LeagueErrorCode::LcuConnectionFailed
+                | LeagueErrorCode::LiveClientTimeout
+                | LeagueErrorCode::LiveClientUnavailable
+                | LeagueErrorCode::EogUnavailable
+                | LeagueErrorCode::WebsocketFailed
+                | LeagueErrorCode::ReplayUnavailable
+                | LeagueErrorCode::IoFailed
+        )
+    }
+}
+
+/// Reply of a failed command, sent as `{ "error": CommandError }`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandError {
+    pub code: LeagueErrorCode,
+    pub message: String,
+    pub retryable: bool,
+    /// Underlying error of a command code (e.g. `LCU_NOT_RUNNING` for an
+    /// `IMPORT_FAILED`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cause: Option<LeagueErrorCode>,
+}
+
+/// Event stream the daemon can subscribe to instead of polling
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventTopic {
+    /// Client connection and gameflow phase changes
+    Gameflow,
+    /// Game events as the pack's event poll emits them (kills, objectives,
+    /// synthesized streaks), with their trigger decisions
+    GameEvents,
+}
+
+/// What the capture side should do after a gameflow change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordingAction {
+    Start,
+    Stop,
+    /// Keep the recording open but skip footage (e.g. the stats screen)
+    Pause,
+}
+
+/// Capture quality suited to the content on screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordingQuality {
+    /// Fast action: full frame rate and bitrate
+    High,
+    /// Slower gameplay (e.g. TFT)
+    Standard,
+    /// Mostly static client screens
+    Low,
+}
+
+/// Recording advice derived from the gameflow phase, game mode and map,
+/// emitted as a `RecordingHint` event on every phase change
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingHint {
+    pub action: RecordingAction,
+    /// Typical remaining length of what is being recorded
+    pub expected_duration_secs: Option<u32>,
+    pub suggested_quality: Option<RecordingQuality>,
+    pub phase: String,
+    /// Game mode API key (e.g. "ARAM"), when known
+    pub game_mode: Option<String>,
+    pub map_id: Option<i32>,
+}
+
+/// One champion on the matchup card
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntroChampion {
+    pub summoner_name: String,
+    pub champion: String,
+    pub champion_icon: Option<String>,
+    /// Position ("top", "jungle", ...), empty outside role queues
+    pub role: String,
+}
+
+/// The local player's spells and runes
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntroLoadout {
+    pub spell1: Option<String>,
+    pub spell1_icon: Option<String>,
+    pub spell2: Option<String>,
+    pub spell2_icon: Option<String>,
+    pub keystone: Option<String>,
+    pub keystone_icon: Option<String>,
+    pub secondary_tree: Option<String>,
+    pub secondary_tree_icon: Option<String>,
+}
+
+/// Everything an overlay needs for a matchup card, emitted once per game as
+/// a `MatchIntro` event when the game starts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchIntro {
+    pub queue_name: Option<String>,
+    /// Solo queue rank before the game (e.g. "GOLD II")
+    pub rank: Option<String>,
+    pub player: IntroChampion,
+    pub loadout: IntroLoadout,
+    /// The player's team first
+    pub allies: Vec<IntroChampion>,
+    pub enemies: Vec<IntroChampion>,
+}
+
+/// Connection status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionStatus {
+    #[default]
+    Disconnected,
+    Connected,
+    InGame,
+}
+
+impl std::fmt::Display for ConnectionStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConnectionStatus::Disconnected => write!(f, "disconnected"),
+            ConnectionStatus::Connected => write!(f, "connected"),
+            ConnectionStatus::InGame => write!(f, "in_game"),
+        }
+    }
+}
+
+/// Which parts of League are running
+///
+/// The client (LCU) and the game process are separate: the game keeps running
+/// if the client crashes mid-match, and the client runs without a game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RunningStatus {
+    #[default]
+    NotRunning,
+    /// Only the client is running
+    Client,
+    /// Only the game is running
+    InGame,
+    /// Client and game are both running
+    Both,
+}
+
+impl RunningStatus {
+    /// Combine client and game detection
+    pub fn from_parts(client: bool, game: bool) -> Self {
+        match (client, game) {
+            (false, false) => RunningStatus::NotRunning,
+            (true, false) => RunningStatus::Client,
+            (false, true) => RunningStatus::InGame,
+            (true, true) => RunningStatus::Both,
+        }
+    }
+
+    /// Whether anything League-related is running
+    pub fn is_running(&self) -> bool {
+        *self != RunningStatus::NotRunning
+    }
+
+    /// Whether the client is running
+    pub fn has_client(&self) -> bool {
+        matches!(self, RunningStatus::Client | RunningStatus::Both)
+    }
+
+    /// Whether the game is running
+    pub fn has_game(&self) -> bool {
+        matches!(self, RunningStatus::InGame | RunningStatus::Both)
+    }
+}
+
+impl std::fmt::Display for RunningStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RunningStatus::NotRunning => write!(f, "not_running"),
+            RunningStatus::Client => write!(f, "client"),
+            RunningStatus::InGame => write!(f, "in_game"),
+            RunningStatus::Both => write!(f, "both"),
+        }
+    }
+}
+
+/// Integration status
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrationStatus {
+    /// Game slug
+    pub game_slug: String,
+    /// Whether connected to the game client
+    pub connected: bool,
+    /// Detailed connection status
+    pub connection_status: ConnectionStatus,
+    /// Current game phase
+    pub game_phase: Option<String>,
+    /// Whether actively in a match
+    pub is_in_game: bool,
+    /// Whether the active match is a spectated game (only when recording spectated games)
+    #[serde(default)]
+    pub is_spectating: bool,
+    /// In-memory buffer metrics
+    #[serde(default)]
+    pub metrics: IntegrationMetrics,
+    /// Health of supervised background tasks
+    #[serde(default)]
+    pub tasks: Vec<TaskHealth>,
+    /// Profile of the logged-in account, once fetched for a game session
+    #[serde(default)]
+    pub profile: Option<ProfileMetadata>,
+    /// Expected wait while matchmaking, once the queue has recorded waits
+    #[serde(default)]
+    pub queue_estimate: Option<QueueEstimate>,
+    /// Which of the client and the game process are running
+    #[serde(default)]
+    pub running: RunningStatus,
+}
+
+/// Profile header details of the logged-in account, as shown by the client
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProfileMetadata {
+    /// Account level
+    pub summoner_level: i32,
+    pub profile_icon_id: i32,
+    /// Tier of the ranked border (e.g. "GOLD"); `None` while unranked
+    pub ranked_border: Option<String>,
+    /// Honor level (`None` when the client doesn't report it)
+    pub honor_level: Option<i32>,
+}
+
+/// Buffer fill levels and drop counters reported with the status
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IntegrationMetrics {
+    /// Raw game events waiting to be polled
+    pub pending_events: BufferMetrics,
+    /// Live match snapshots for the current session
+    pub live_snapshots: BufferMetrics,
+    /// LCU WebSocket events waiting to be handled, summed over the daemon's
+    /// sockets (each socket counts its own drops since it was opened)
+    #[serde(default)]
+    pub ws_backlog: BufferMetrics,
+    /// Seconds since the integration started
+    #[serde(default)]
+    pub uptime_secs: u64,
+    /// Seconds since the client last connected (`None` while disconnected)
+    #[serde(default)]
+    pub connected_secs: Option<u64>,
+    /// Most recent connection status changes, oldest first
+    #[serde(default)]
+    pub connection_history: Vec<ConnectionTransition>,
+}
+
+/// A change of the client connection status and what caused it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConnectionTransition {
+    pub at: DateTime<Utc>,
+    pub from: ConnectionStatus,
+    pub to: ConnectionStatus,
+    pub cause: String,
+}
+
+impl IntegrationStatus {
+    /// Create a disconnected status
+    pub fn disconnected(slug: &str) -> Self {
+        Self {
+            game_slug: slug.to_string(),
+            connected: false,
+            connection_status: ConnectionStatus::Disconnected,
+            game_phase: None,
+            is_in_game: false,
+            is_spectating: false,
+            metrics: IntegrationMetrics::default(),
+            tasks: Vec::new(),
+            profile: None,
+            queue_estimate: None,
+            running: RunningStatus::NotRunning,
+        }
+    }
+}
+
+// ============================================================================
+// Status and Timing
+// ============================================================================
+
+/// Fill level and drop count of a single buffer
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BufferMetrics {
+    pub len: usize,
+    pub capacity: usize,
+    pub dropped: u64,
+}
+
+/// Lifecycle state of a supervised task
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskState {
+    /// Task is running
+    Running,
+    /// Task crashed and is waiting to be restarted
+    Restarting,
+    /// Task returned on its own (e.g. after a shutdown signal)
+    Finished,
+    /// Task was stopped or aborted by the supervisor
+    Stopped,
+}
+
+/// Health snapshot of a supervised task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskHealth {
+    pub name: String,
+    pub state: TaskState,
+    /// Number of times the task was restarted after a crash
+    pub restarts: u32,
+    /// Panic message of the most recent crash
+    pub last_error: Option<String>,
+}
+
+/// Serializable record of a session clock anchor (stored in `SessionContext`)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionClockAnchor {
+    /// Wall-clock time at the anchor
+    pub started_at: DateTime<Utc>,
+    /// Session seconds at which game time 0 occurred (loading screen length)
+    pub game_time_offset_secs: Option<f64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_result_string_matches_serde() {
+        let results = [
+            MatchResult::Win,
+            MatchResult::Loss,
+            MatchResult::Remake,
+            MatchResult::Aborted,
+            MatchResult::Spectated,
+        ];
+        for result in results {
+            assert_eq!(serde_json::to_value(result).unwrap(), result.to_string());
+        }
+        assert_eq!(MatchResult::Aborted.to_string(), "aborted");
+    }
+
+    #[test]
+    fn test_running_status_from_parts() {
+        assert_eq!(RunningStatus::from_parts(false, false), RunningStatus::NotRunning);
+        assert_eq!(RunningStatus::from_parts(true, false), RunningStatus::Client);
+        assert_eq!(RunningStatus::from_parts(false, true), RunningStatus::InGame);
+        assert_eq!(RunningStatus::from_parts(true, true), RunningStatus::Both);
+    }
+
+    #[test]
+    fn test_running_status_queries() {
+        let cases = [
+            (RunningStatus::NotRunning, false, false, false),
+            (RunningStatus::Client, true, true, false),
+            (RunningStatus::InGame, true, false, true),
+            (RunningStatus::Both, true, true, true),
+        ];
+        for (status, running, client, game) in cases {
+            assert_eq!(status.is_running(), running, "{}", status);
+            assert_eq!(status.has_client(), client, "{}", status);
+            assert_eq!(status.has_game(), game, "{}", status);
+        }
+    }
+}
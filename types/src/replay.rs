@@ -0,0 +1,120 @@
+//! Replay API data types
+//!
+//! Mirrors the game's Replay API (`/replay/*` on port 2999), available while
+//! a replay is loaded and `EnableReplayApi=1` is set in `game.cfg`. Update
+//! types leave unset fields unchanged.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Playback state (`/replay/playback`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ReplayPlayback {
+    /// Current position in game seconds
+    pub time: f64,
+    /// Replay length in seconds
+    pub length: f64,
+    pub paused: bool,
+    pub seeking: bool,
+    pub speed: f64,
+}
+
+/// Playback change
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ReplayPlaybackUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paused: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speed: Option<f64>,
+}
+
+/// Point or rotation in the replay camera's space
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReplayVector {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+/// Camera and render settings (`/replay/render`).
+///
+/// Only the camera fields are typed; other render options (fog, particles,
+/// interface toggles, ...) pass through `other` unchanged.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ReplayRender {
+    /// "top", "fps", "tpc" or "focus"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera_mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera_position: Option<ReplayVector>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera_rotation: Option<ReplayVector>,
+    /// Keep the camera on the selected unit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera_attached: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field_of_view: Option<f64>,
+    /// Unit the camera follows (a champion's summoner name)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selection_name: Option<String>,
+    /// Show the game HUD
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_all: Option<bool>,
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+/// Recording of a replay range by the game itself (`/replay/recording`),
+/// at the game's render quality instead of a screen capture
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ReplayRecording {
+    pub recording: bool,
+    pub start_time: f64,
+    pub end_time: f64,
+    /// Output file (defaults to the game's Highlights folder)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// "webm" (default) or "png" frames
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub codec: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frames_per_second: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lossless: Option<bool>,
+    /// Position while recording (reported by the game)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_time: Option<f64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_render_update_keeps_unknown_options() {
+        let render: ReplayRender = serde_json::from_value(json!({
+            "cameraMode": "fps",
+            "fieldOfView": 50.0,
+            "fogOfWar": false,
+        }))
+        .unwrap();
+        assert_eq!(render.camera_mode.as_deref(), Some("fps"));
+
+        let value = serde_json::to_value(&render).unwrap();
+        assert_eq!(
+            value,
+            json!({ "cameraMode": "fps", "fieldOfView": 50.0, "fogOfWar": false })
+        );
+    }
+}
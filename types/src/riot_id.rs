@@ -0,0 +1,43 @@
+//! Riot ID comparison
+//!
+//! Riot IDs are free-form Unicode and arrive with different casing (and
+//! sometimes decomposed accents) depending on the API that reported them.
+//! Names are compared through a collation key (NFC, lowercased), and a bare
+//! game name, as in Live Client events, matches the full "name#tag".
+
+use unicode_normalization::UnicodeNormalization;
+
+/// Collation key of a Riot ID, for comparisons and map keys
+pub fn riot_id_key(riot_id: &str) -> String {
+    riot_id.trim().to_lowercase().nfc().collect()
+}
+
+/// Collation key of the game name (the part before `#`)
+pub fn game_name_key(riot_id: &str) -> String {
+    riot_id_key(riot_id.split('#').next().unwrap_or_default())
+}
+
+/// Whether two names refer to the same player. Tags are compared when both
+/// names have one.
+pub fn riot_ids_match(a: &str, b: &str) -> bool {
+    if a.contains('#') && b.contains('#') {
+        riot_id_key(a) == riot_id_key(b)
+    } else {
+        game_name_key(a) == game_name_key(b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_riot_ids_match_across_case_and_composition() {
+        // Precomposed "ë" against "e" + combining diaeresis
+        assert!(riot_ids_match("Zoë#EUW", "ZOE\u{308}#euw"));
+        assert_eq!(riot_id_key("Zoë#EUW"), riot_id_key("zoe\u{308}#EUW"));
+        assert!(riot_ids_match("Faker", "faker#KR1"));
+        assert!(!riot_ids_match("Faker#KR1", "Faker#NA1"));
+        assert!(!riot_ids_match("Faker", "Fakerr#KR1"));
+    }
+}
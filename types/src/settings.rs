@@ -0,0 +1,402 @@
+//! League-specific settings types
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// The original flat on/off trigger settings.
+///
+/// Still accepted wherever a `TriggerConfig` is deserialized, and converted
+/// to per-event rules with the default capture windows.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TriggerSettings {
+    pub on_kill: bool,
+    pub on_death: bool,
+    pub on_assist: bool,
+    pub on_multikill: bool,
+    pub on_tower_kill: bool,
+    pub on_dragon: bool,
+    pub on_baron: bool,
+    pub on_ace: bool,
+}
+
+impl Default for TriggerSettings {
+    fn default() -> Self {
+        Self {
+            on_kill: true,
+            on_death: true,
+            on_assist: false,
+            on_multikill: true,
+            on_tower_kill: false,
+            on_dragon: true,
+            on_baron: true,
+            on_ace: true,
+        }
+    }
+}
+
+/// Default seconds captured before an event
+pub const DEFAULT_PRE_SECS: f64 = 15.0;
+
+/// Default seconds captured after an event
+pub const DEFAULT_POST_SECS: f64 = 5.0;
+
+/// Default seconds captured after an objective steal (the reactions are
+/// part of the clip)
+pub const STEAL_POST_SECS: f64 = 10.0;
+
+/// Events a trigger rule can be configured for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TriggerKind {
+    Kill,
+    Death,
+    Assist,
+    Multikill,
+    TowerKill,
+    Dragon,
+    Baron,
+    Ace,
+    /// Dragon, Baron or Herald taken in a fight, or stolen
+    ObjectiveContested,
+    /// Dragon or Baron stolen
+    ObjectiveSteal,
+    /// Control ward placed or bought (for support players)
+    Ward,
+}
+
+/// Whose events a rule fires for
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TriggerScope {
+    /// Only events the player takes part in
+    #[default]
+    Player,
+    /// Events involving the player or any teammate
+    Team,
+}
+
+/// Clip capture settings for one kind of event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TriggerRule {
+    pub enabled: bool,
+    /// Seconds captured before the event
+    pub pre_secs: f64,
+    /// Seconds captured after the event
+    pub post_secs: f64,
+    /// Minimum seconds of game time between two clips of this rule (0 = none)
+    pub cooldown_secs: f64,
+    pub scope: TriggerScope,
+}
+
+impl Default for TriggerRule {
+    fn default() -> Self {
+        Self::enabled(false)
+    }
+}
+
+impl TriggerRule {
+    /// Rule with the default capture window and no cooldown
+    pub fn enabled(enabled: bool) -> Self {
+        Self {
+            enabled,
+            pre_secs: DEFAULT_PRE_SECS,
+            post_secs: DEFAULT_POST_SECS,
+            cooldown_secs: 0.0,
+            scope: TriggerScope::Player,
+        }
+    }
+}
+
+/// Per-event clip triggers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", from = "TriggerConfigShape")]
+pub struct TriggerConfig {
+    /// Rules per event kind; kinds without a rule never trigger
+    pub rules: BTreeMap<TriggerKind, TriggerRule>,
+    /// Smallest multikill that triggers (2 = double kill)
+    pub min_multikill: u32,
+}
+
+impl Default for TriggerConfig {
+    fn default() -> Self {
+        TriggerSettings::default().into()
+    }
+}
+
+impl TriggerConfig {
+    /// The rule for `kind` (disabled if not configured)
+    pub fn rule(&self, kind: TriggerKind) -> TriggerRule {
+        self.rules.get(&kind).cloned().unwrap_or_default()
+    }
+}
+
+impl From<TriggerSettings> for TriggerConfig {
+    fn from(settings: TriggerSettings) -> Self {
+        let rules = [
+            (TriggerKind::Kill, settings.on_kill),
+            (TriggerKind::Death, settings.on_death),
+            (TriggerKind::Assist, settings.on_assist),
+            (TriggerKind::Multikill, settings.on_multikill),
+            (TriggerKind::TowerKill, settings.on_tower_kill),
+            (TriggerKind::Dragon, settings.on_dragon),
+            (TriggerKind::Baron, settings.on_baron),
+            (TriggerKind::Ace, settings.on_ace),
+        ];
+        let mut rules: BTreeMap<_, _> = rules
+            .into_iter()
+            .map(|(kind, enabled)| (kind, TriggerRule::enabled(enabled)))
+            .collect();
+        rules.insert(
+            TriggerKind::ObjectiveSteal,
+            TriggerRule {
+                post_secs: STEAL_POST_SECS,
+                ..TriggerRule::enabled(settings.on_dragon || settings.on_baron)
+            },
+        );
+        Self {
+            rules,
+            min_multikill: 2,
+        }
+    }
+}
+
+/// Accepted JSON shapes of `TriggerConfig`
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TriggerConfigShape {
+    Legacy(TriggerSettings),
+    #[serde(rename_all = "camelCase")]
+    Current {
+        #[serde(default = "default_trigger_rules")]
+        rules: BTreeMap<TriggerKind, TriggerRule>,
+        #[serde(default = "default_min_multikill")]
+        min_multikill: u32,
+    },
+}
+
+fn default_trigger_rules() -> BTreeMap<TriggerKind, TriggerRule> {
+    TriggerConfig::default().rules
+}
+
+fn default_min_multikill() -> u32 {
+    2
+}
+
+impl From<TriggerConfigShape> for TriggerConfig {
+    fn from(shape: TriggerConfigShape) -> Self {
+        match shape {
+            TriggerConfigShape::Legacy(settings) => settings.into(),
+            TriggerConfigShape::Current {
+                rules,
+                min_multikill,
+            } => Self {
+                rules,
+                min_multikill,
+            },
+        }
+    }
+}
+
+/// Capacities for the in-memory buffers kept during a session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BufferSettings {
+    /// Max raw game events waiting to be polled
+    pub max_pending_events: usize,
+    /// Max live match snapshots kept for the current session
+    pub max_live_snapshots: usize,
+    /// Max LCU WebSocket events queued for the consumer
+    pub max_ws_backlog: usize,
+}
+
+impl Default for BufferSettings {
+    fn default() -> Self {
+        Self {
+            max_pending_events: 1024,
+            max_live_snapshots: 120,
+            max_ws_backlog: 256,
+        }
+    }
+}
+
+/// How often the Live Client API and the League client are polled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PollingConfig {
+    /// Game event polling (while events are pushed)
+    pub event_poll_ms: u64,
+    /// Live match data polling
+    pub live_data_ms: u64,
+    /// Gameflow phase polling when the LCU WebSocket is unavailable
+    pub gameflow_fallback_ms: u64,
+    /// Poll at `idle_ms` while there's no game, at the intervals above in one
+    pub adaptive: bool,
+    /// Interval while idle (never below 10s)
+    pub idle_ms: u64,
+}
+
+impl Default for PollingConfig {
+    fn default() -> Self {
+        Self {
+            event_poll_ms: 250,
+            live_data_ms: 1000,
+            gameflow_fallback_ms: 1000,
+            adaptive: true,
+            idle_ms: 10_000,
+        }
+    }
+}
+
+impl PollingConfig {
+    /// Shortest idle interval
+    pub const MIN_IDLE_MS: u64 = 10_000;
+
+    /// Shortest interval of any poll, so a zero can't spin
+    pub const MIN_POLL_MS: u64 = 50;
+
+    /// Interval of a poll configured to `base_ms`, given whether a game is
+    /// active
+    pub fn interval(&self, base_ms: u64, active: bool) -> std::time::Duration {
+        let ms = if self.adaptive && !active {
+            base_ms.max(self.idle_ms).max(Self::MIN_IDLE_MS)
+        } else {
+            base_ms
+        };
+        std::time::Duration::from_millis(ms.max(Self::MIN_POLL_MS))
+    }
+}
+
+/// Token-bucket limits for requests to the League client (LCU)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LcuRateLimitSettings {
+    /// Sustained requests per second
+    pub requests_per_second: u32,
+    /// Maximum burst size
+    pub burst: u32,
+}
+
+impl Default for LcuRateLimitSettings {
+    fn default() -> Self {
+        Self {
+            requests_per_second: 10,
+            burst: 20,
+        }
+    }
+}
+
+/// Local cache for CDN icon assets (champions, items, spells, runes)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AssetCacheSettings {
+    /// Download icons referenced by the live game and finished matches
+    pub enabled: bool,
+    /// Cache directory (defaults to a `pack-league-assets` dir under the OS temp dir)
+    pub cache_dir: Option<String>,
+}
+
+/// Recording of games the user spectates
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SpectatorSettings {
+    /// Start sessions for spectated games (flagged `spectated`, kept out of personal stats)
+    pub record_spectated_games: bool,
+}
+
+/// Output of pushed `EventNotification`s
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NotificationSettings {
+    /// Coalesce routine notifications written within this many milliseconds
+    /// into one write (0 writes each one immediately)
+    pub batch_window_ms: u64,
+}
+
+/// Which raw Live Client event payloads are kept, for finding new event
+/// formats
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RawEventRetention {
+    /// Only the parsed fields are kept
+    #[default]
+    None,
+    /// Payloads of event types the pack doesn't know
+    Unknown,
+    /// Every payload
+    All,
+}
+
+/// Where persisted statistics are stored
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct StatsSettings {
+    /// Data directory (defaults to the platform data dir)
+    pub data_dir: Option<String>,
+    /// Seconds between scoreboard snapshots during a game (default 30, 0
+    /// turns them off)
+    pub snapshot_interval_secs: Option<u64>,
+    /// Raw event payloads kept in the discovery log
+    pub raw_event_retention: RawEventRetention,
+    /// Soft daily playtime limit in minutes; crossing it emits
+    /// `PlaytimeLimitReached` (nothing is blocked)
+    pub daily_playtime_limit_mins: Option<u32>,
+}
+
+impl StatsSettings {
+    /// Configured data directory, else the platform default (`None` when
+    /// neither is known, which keeps stores in memory)
+    pub fn data_dir(&self) -> Option<PathBuf> {
+        self.data_dir.as_ref().map(PathBuf::from).or_else(default_data_dir)
+    }
+}
+
+/// Default data directory for the pack
+///
+/// `%APPDATA%` on Windows, `~/Library/Application Support` on macOS,
+/// `$XDG_DATA_HOME` or `~/.local/share` elsewhere.
+pub fn default_data_dir() -> Option<PathBuf> {
+    let base = if cfg!(windows) {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        std::env::var_os("HOME").map(|h| PathBuf::from(h).join("Library/Application Support"))
+    } else {
+        std::env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".local/share")))
+    }?;
+    Some(base.join("clip-companion").join("pack-league"))
+}
+
+/// Translations of display strings, layered over the built-in English
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LocaleStrings {
+    /// Locale name (e.g. "fr_FR")
+    pub locale: String,
+    /// Translated text per string key (`"phase.in_game": "En jeu"`); keys
+    /// left out stay English
+    pub strings: BTreeMap<String, String>,
+}
+
+/// Reading the League client and game from another PC (dual-PC streaming setups)
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RemoteHostSettings {
+    /// Address of the gaming PC (`None` reads the local machine)
+    pub host: Option<String>,
+    /// Contents of `<League install>/lockfile` on the gaming PC (changes on every client restart)
+    pub lockfile: Option<String>,
+}
+
+impl std::fmt::Debug for RemoteHostSettings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // The lockfile holds the client password
+        f.debug_struct("RemoteHostSettings")
+            .field("host", &self.host)
+            .field("lockfile", &self.lockfile.as_ref().map(|_| "<redacted>"))
+            .finish()
+    }
+}
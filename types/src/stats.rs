@@ -0,0 +1,1311 @@
+//! Persisted pack statistics (queue times, dodges, remakes, Arena results,
+//! champions played, personal bests, repeat opponents, duo partners, results
+//! by time of day, playtime per day)
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+
+use crate::{
+    riot_id_key, riot_ids_match, ArenaMatchDetails, Badge, CreateMatch, MatchResult, Participant,
+    Team,
+};
+
+/// Opponents kept in the rivalry table (least recently seen are dropped first)
+pub const MAX_RIVALS: usize = 200;
+
+/// Losses against an opponent before they can count as a nemesis
+pub const NEMESIS_MIN_LOSSES: u32 = 3;
+
+/// Games with a partner before they show up in the duo summary
+pub const MIN_DUO_GAMES: usize = 2;
+
+/// Idle time between games that starts a new play session
+pub const SESSION_BREAK_SECS: i64 = 60 * 60;
+
+/// Games later in a session than this share the last bucket
+pub const MAX_SESSION_GAME: u32 = 5;
+
+/// Most recent waits in a queue an estimate is based on
+pub const QUEUE_ESTIMATE_SAMPLES: usize = 20;
+
+/// Waits from around the same hour needed to estimate by time of day
+pub const QUEUE_ESTIMATE_MIN_HOUR_SAMPLES: usize = 3;
+
+/// One completed queue: time from entering matchmaking to the ready check
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueTimeRecord {
+    pub queue_id: i32,
+    pub queue_name: String,
+    /// When matchmaking started
+    pub started_at: DateTime<Utc>,
+    /// Seconds spent in queue before the match was found
+    pub wait_secs: f64,
+    /// Hour (0-23) in the player's local time when matchmaking started
+    /// (`None` for queues recorded before it was kept)
+    #[serde(default)]
+    pub local_hour: Option<u32>,
+}
+
+/// Expected wait in the queue the player is searching in
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueEstimate {
+    pub queue_id: i32,
+    /// Median of the waits the estimate is based on
+    pub estimated_secs: f64,
+    /// Number of those waits
+    pub samples: usize,
+    /// Whether the waits are from within an hour of the current time of day
+    pub same_time_of_day: bool,
+    /// Seconds in queue so far
+    pub elapsed_secs: f64,
+}
+
+/// Aggregated queue times for one queue type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueTimeSummary {
+    pub queue_id: i32,
+    pub queue_name: String,
+    pub count: usize,
+    pub average_secs: f64,
+    pub min_secs: f64,
+    pub max_secs: f64,
+}
+
+/// Why a match found in queue didn't turn into a played game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NonGameKind {
+    /// The local player left champ select
+    SelfDodge,
+    /// Another player left champ select (the client re-queued)
+    OtherDodge,
+    /// Champ select ended without a game and the cause isn't known
+    Dodge,
+    /// The game ended in an early surrender vote
+    Remake,
+}
+
+/// One dodged lobby or remade game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NonGameRecord {
+    pub kind: NonGameKind,
+    pub queue_id: i32,
+    pub occurred_at: DateTime<Utc>,
+    /// Seconds spent in champ select (dodges) or in game (remakes)
+    pub lost_secs: f64,
+}
+
+/// Dodge and remake counts
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NonGameSummary {
+    pub self_dodges: usize,
+    pub other_dodges: usize,
+    /// Dodges that couldn't be attributed
+    pub unknown_dodges: usize,
+    pub remakes: usize,
+    /// Total seconds lost to all of the above
+    pub total_lost_secs: f64,
+}
+
+/// A personal record a match can break
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PersonalRecord {
+    Kills,
+    Kda,
+    CsPerMin,
+    ShortestWin,
+}
+
+impl PersonalRecord {
+    /// Badge awarded for breaking the record
+    pub fn badge(&self) -> Badge {
+        match self {
+            PersonalRecord::Kills => Badge::PersonalBestKills,
+            PersonalRecord::Kda => Badge::PersonalBestKda,
+            PersonalRecord::CsPerMin => Badge::PersonalBestCsPerMin,
+            PersonalRecord::ShortestWin => Badge::PersonalBestShortestWin,
+        }
+    }
+}
+
+/// Best values across recorded games
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PersonalBests {
+    pub most_kills: Option<i32>,
+    pub highest_kda: Option<f64>,
+    pub highest_cs_per_min: Option<f64>,
+    pub shortest_win_secs: Option<i32>,
+}
+
+impl PersonalBests {
+    /// Fold a finished game into the bests and return the records it broke.
+    ///
+    /// The first value of each record only sets the baseline, so a fresh
+    /// history doesn't turn every stat of the first game into a record.
+    /// Remakes and aborted games are ignored.
+    pub fn update(&mut self, game: &CreateMatch) -> Vec<PersonalRecord> {
+        let mut broken = Vec::new();
+        if !matches!(game.result, MatchResult::Win | MatchResult::Loss) {
+            return broken;
+        }
+
+        let kda = (game.kills + game.assists) as f64 / game.deaths.max(1) as f64;
+
+        if beats(&mut self.most_kills, game.kills, |new, best| new > best) {
+            broken.push(PersonalRecord::Kills);
+        }
+        if beats(&mut self.highest_kda, kda, |new, best| new > best) {
+            broken.push(PersonalRecord::Kda);
+        }
+        if beats(&mut self.highest_cs_per_min, game.cs_per_min, |new, best| new > best) {
+            broken.push(PersonalRecord::CsPerMin);
+        }
+        if game.result == MatchResult::Win
+            && game.duration_secs > 0
+            && beats(&mut self.shortest_win_secs, game.duration_secs, |new, best| new < best)
+        {
+            broken.push(PersonalRecord::ShortestWin);
+        }
+
+        broken
+    }
+}
+
+/// Store `value` if it beats `best`; true if an existing record was beaten
+fn beats<T: Copy>(best: &mut Option<T>, value: T, better: impl Fn(T, T) -> bool) -> bool {
+    match *best {
+        Some(current) if better(value, current) => {
+            *best = Some(value);
+            true
+        }
+        Some(_) => false,
+        None => {
+            *best = Some(value);
+            false
+        }
+    }
+}
+
+/// Head-to-head record against one opponent
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RivalRecord {
+    /// Riot ID ("name#tag")
+    pub riot_id: String,
+    pub games: u32,
+    /// Games the player won against them
+    pub wins: u32,
+    pub losses: u32,
+    /// Champion they played in the last shared game
+    pub last_champion: String,
+    pub last_seen: DateTime<Utc>,
+}
+
+impl RivalRecord {
+    /// Beat the player at least `NEMESIS_MIN_LOSSES` times and more often than not
+    pub fn is_nemesis(&self) -> bool {
+        self.losses >= NEMESIS_MIN_LOSSES && self.losses > self.wins
+    }
+}
+
+/// Opponents met across recorded games, keyed by Riot ID collation key
+/// (see `riot_id_key`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Rivalries {
+    pub opponents: BTreeMap<String, RivalRecord>,
+}
+
+impl Rivalries {
+    /// Count a finished game against every opponent in it and return the
+    /// records of opponents the player had already met.
+    ///
+    /// Only wins and losses with a known enemy team count; Arena games are
+    /// skipped since their teams aren't two sides of the same game.
+    pub fn update(&mut self, game: &CreateMatch) -> Vec<RivalRecord> {
+        let won = match game.result {
+            MatchResult::Win => true,
+            MatchResult::Loss => false,
+            MatchResult::Remake => return Vec::new(),
+        };
+        if game.arena.is_some() {
+            return Vec::new();
+        }
+        let Some(local) = local_participant(game) else {
+            return Vec::new();
+        };
+
+        let mut repeat = Vec::new();
+        for opponent in game
+            .participants
+            .iter()
+            .filter(|p| p.team != local.team && !p.summoner_name.is_empty())
+        {
+            let record = self
+                .opponents
+                .entry(riot_id_key(&opponent.summoner_name))
+                .or_insert_with(|| RivalRecord {
+                    riot_id: opponent.summoner_name.clone(),
+                    games: 0,
+                    wins: 0,
+                    losses: 0,
+                    last_champion: String::new(),
+                    last_seen: game.played_at,
+                });
+            record.games += 1;
+            if won {
+                record.wins += 1;
+            } else {
+                record.losses += 1;
+            }
+            record.riot_id = opponent.summoner_name.clone();
+            record.last_champion = opponent.champion.clone();
+            record.last_seen = game.played_at;
+
+            if record.games > 1 {
+                repeat.push(record.clone());
+            }
+        }
+
+        self.evict();
+        repeat
+    }
+
+    /// Opponents met more than once, most games first
+    pub fn repeat_opponents(&self) -> Vec<RivalRecord> {
+        let mut rivals: Vec<RivalRecord> = self
+            .opponents
+            .values()
+            .filter(|r| r.games > 1)
+            .cloned()
+            .collect();
+        rivals.sort_by(|a, b| b.games.cmp(&a.games).then(b.last_seen.cmp(&a.last_seen)));
+        rivals
+    }
+
+    /// Merge records stored under raw Riot IDs (before keys were collated)
+    pub fn rekey(&mut self) {
+        let opponents = std::mem::take(&mut self.opponents);
+        for (riot_id, record) in opponents {
+            match self.opponents.entry(riot_id_key(&riot_id)) {
+                Entry::Vacant(entry) => {
+                    entry.insert(record);
+                }
+                Entry::Occupied(mut entry) => {
+                    let merged = entry.get_mut();
+                    merged.games += record.games;
+                    merged.wins += record.wins;
+                    merged.losses += record.losses;
+                    if record.last_seen > merged.last_seen {
+                        merged.riot_id = record.riot_id;
+                        merged.last_champion = record.last_champion;
+                        merged.last_seen = record.last_seen;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Drop the least recently seen one-off opponents, then the least
+    /// recently seen rivals, until the table fits `MAX_RIVALS`
+    fn evict(&mut self) {
+        while self.opponents.len() > MAX_RIVALS {
+            let oldest = self
+                .opponents
+                .iter()
+                .min_by_key(|(_, r)| (r.games > 1, r.last_seen))
+                .map(|(key, _)| key.clone());
+            match oldest {
+                Some(key) => self.opponents.remove(&key),
+                None => break,
+            };
+        }
+    }
+}
+
+/// One game played in a premade with a partner
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuoGameRecord {
+    /// Partner's Riot ID ("name#tag")
+    pub partner: String,
+    pub game_id: i64,
+    pub won: bool,
+    pub kills: i32,
+    pub deaths: i32,
+    pub assists: i32,
+    pub partner_champion: String,
+    pub partner_kills: i32,
+    pub partner_deaths: i32,
+    pub partner_assists: i32,
+    pub played_at: DateTime<Utc>,
+}
+
+impl DuoGameRecord {
+    /// One record per party member found on the player's team. Remakes and
+    /// Arena games (where the duo is assigned, not queued) are skipped.
+    pub fn from_match(game: &CreateMatch, premades: &[String]) -> Vec<DuoGameRecord> {
+        let won = match game.result {
+            MatchResult::Win => true,
+            MatchResult::Loss => false,
+            MatchResult::Remake => return Vec::new(),
+        };
+        if game.arena.is_some() {
+            return Vec::new();
+        }
+        let Some(local) = local_participant(game) else {
+            return Vec::new();
+        };
+
+        game.participants
+            .iter()
+            .filter(|p| {
+                p.team == local.team && premades.iter().any(|m| riot_ids_match(m, &p.summoner_name))
+            })
+            .map(|partner| DuoGameRecord {
+                partner: partner.summoner_name.clone(),
+                game_id: game.game_id,
+                won,
+                kills: game.kills,
+                deaths: game.deaths,
+                assists: game.assists,
+                partner_champion: partner.champion.clone(),
+                partner_kills: partner.kills,
+                partner_deaths: partner.deaths,
+                partner_assists: partner.assists,
+                played_at: game.played_at,
+            })
+            .collect()
+    }
+}
+
+/// Aggregated results with one duo partner
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuoSummary {
+    pub partner: String,
+    pub games: usize,
+    pub wins: usize,
+    /// Wins / games (0.0-1.0)
+    pub win_rate: f64,
+    /// (kills + assists) / deaths of both players together
+    pub combined_kda: f64,
+}
+
+/// Result of a finished game, kept for time-of-day and session analytics
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameResultRecord {
+    pub game_id: i64,
+    pub won: bool,
+    pub kills: i32,
+    pub deaths: i32,
+    pub assists: i32,
+    pub duration_secs: i32,
+    /// When the game ended
+    pub played_at: DateTime<Utc>,
+    /// Hour (0-23) in the player's local time when the game ended
+    pub local_hour: u32,
+    /// Map side the player was on
+    #[serde(default)]
+    pub side: Option<Team>,
+    /// Position played ("TOP", "JUNGLE", ...)
+    #[serde(default)]
+    pub role: Option<String>,
+    /// Empty for games recorded before champions were tracked
+    #[serde(default)]
+    pub champion: String,
+    /// Game mode ("CLASSIC", "ARAM", ...)
+    #[serde(default)]
+    pub game_mode: String,
+    /// Someone went AFK or left
+    #[serde(default)]
+    pub had_leaver: bool,
+    /// Dragons, Barons and Heralds the team took (0 where not tracked)
+    #[serde(default)]
+    pub objectives_taken: u32,
+    /// Of those, objectives the player killed or assisted
+    #[serde(default)]
+    pub objectives_participated: u32,
+}
+
+impl GameResultRecord {
+    /// Record for a finished game; `None` for remakes
+    pub fn from_match(game: &CreateMatch, local_hour: u32) -> Option<Self> {
+        let won = match game.result {
+            MatchResult::Win => true,
+            MatchResult::Loss => false,
+            MatchResult::Remake => return None,
+        };
+        Some(Self {
+            game_id: game.game_id,
+            won,
+            kills: game.kills,
+            deaths: game.deaths,
+            assists: game.assists,
+            duration_secs: game.duration_secs,
+            played_at: game.played_at,
+            local_hour,
+            side: game.side,
+            role: game.role.clone(),
+            champion: game.champion.clone(),
+            game_mode: game.game_mode.clone(),
+            had_leaver: !game.leavers.is_empty(),
+            objectives_taken: game
+                .objective_participation
+                .as_ref()
+                .map_or(0, |p| p.taken()),
+            objectives_participated: game
+                .objective_participation
+                .as_ref()
+                .map_or(0, |p| p.participated()),
+        })
+    }
+
+    /// When the game started
+    fn started_at(&self) -> DateTime<Utc> {
+        self.played_at - chrono::Duration::seconds(self.duration_secs as i64)
+    }
+}
+
+/// Lookup of finished games by champion and game mode.
+///
+/// Not persisted: it's rebuilt from `PackStats::game_results` on load and
+/// kept up to date as games are recorded. Entries are sequence numbers
+/// (position counted from the first game ever recorded), so dropping the
+/// oldest games doesn't shift the others. Games are appended as they end, so
+/// each list is ordered by date and a date range is two binary searches.
+#[derive(Debug, Clone, Default)]
+pub struct GameResultIndex {
+    /// Sequence numbers per (lowercased champion, uppercased game mode)
+    by_champion_mode: BTreeMap<(String, String), Vec<u64>>,
+    /// Sequence number of the first stored game
+    base: u64,
+    /// Sequence number the next game gets
+    next: u64,
+}
+
+impl GameResultIndex {
+    /// Index all stored games
+    pub fn build(records: &[GameResultRecord]) -> Self {
+        let mut index = Self::default();
+        for record in records {
+            index.push(record);
+        }
+        index
+    }
+
+    /// Index a game appended to the stored games
+    pub fn push(&mut self, record: &GameResultRecord) {
+        if !record.champion.is_empty() {
+            let key = (
+                record.champion.to_lowercase(),
+                record.game_mode.to_uppercase(),
+            );
+            self.by_champion_mode.entry(key).or_default().push(self.next);
+        }
+        self.next += 1;
+    }
+
+    /// Forget the `count` oldest games after they were dropped
+    pub fn drop_oldest(&mut self, count: usize) {
+        if count == 0 {
+            return;
+        }
+        self.base += count as u64;
+        let base = self.base;
+        self.by_champion_mode.retain(|_, seqs| {
+            seqs.retain(|&seq| seq >= base);
+            !seqs.is_empty()
+        });
+    }
+
+    /// Games on `champion` (in `game_mode`, if given) that ended in
+    /// `since..until`, oldest first. `records` are the indexed games.
+    pub fn query<'a>(
+        &self,
+        records: &'a [GameResultRecord],
+        champion: &str,
+        game_mode: Option<&str>,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+    ) -> Vec<&'a GameResultRecord> {
+        let champion = champion.to_lowercase();
+        let game_mode = game_mode.map(str::to_uppercase);
+        let played_at = |seq: &u64| records.get((seq - self.base) as usize).map(|r| r.played_at);
+
+        let mut seqs: Vec<u64> = self
+            .by_champion_mode
+            .range((champion.clone(), String::new())..)
+            .take_while(|((c, _), _)| *c == champion)
+            .filter(|((_, mode), _)| game_mode.as_ref().is_none_or(|m| m == mode))
+            .flat_map(|(_, seqs)| {
+                let start = since.map_or(0, |since| {
+                    seqs.partition_point(|s| played_at(s).is_some_and(|t| t < since))
+                });
+                let end = until.map_or(seqs.len(), |until| {
+                    seqs.partition_point(|s| played_at(s).is_some_and(|t| t < until))
+                });
+                seqs[start..end.max(start)].iter().copied()
+            })
+            .collect();
+        seqs.sort_unstable();
+
+        seqs.into_iter()
+            .filter_map(|seq| records.get((seq - self.base) as usize))
+            .collect()
+    }
+}
+
+/// Winrate and KDA of the games in one bucket
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerformanceBucket {
+    /// Hour of day (0-23) or game number within the session (1-based)
+    pub key: u32,
+    pub games: usize,
+    pub wins: usize,
+    pub win_rate: f64,
+    /// (kills + assists) / deaths over all games in the bucket
+    pub kda: f64,
+}
+
+/// Performance by time of day and by position within a play session
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerformanceAnalytics {
+    /// Only hours with games, by hour
+    pub by_hour: Vec<PerformanceBucket>,
+    /// First game of a session, second, ... up to `MAX_SESSION_GAME` (and later)
+    pub by_session_game: Vec<PerformanceBucket>,
+    /// Blue side, then red side (only sides with games)
+    #[serde(default)]
+    pub by_side: Vec<SidePerformance>,
+    /// Share of the team's objectives the player killed or assisted, in
+    /// percent (`None` until a game with tracked objectives)
+    #[serde(default)]
+    pub objective_participation: Option<f64>,
+}
+
+/// Winrate and KDA on one map side
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SidePerformance {
+    pub side: Team,
+    pub games: usize,
+    pub wins: usize,
+    pub win_rate: f64,
+    pub kda: f64,
+}
+
+/// Hours between two hours of the day, across midnight (23 and 0 are 1 apart)
+fn hours_apart(a: u32, b: u32) -> u32 {
+    let diff = a.abs_diff(b) % 24;
+    diff.min(24 - diff)
+}
+
+/// Group games by key into sorted buckets
+fn performance_buckets<'a>(
+    games: impl Iterator<Item = (u32, &'a GameResultRecord)>,
+) -> Vec<PerformanceBucket> {
+    // Per key: games, wins, kills + assists, deaths
+    let mut totals: BTreeMap<u32, (usize, usize, i32, i32)> = BTreeMap::new();
+    for (key, game) in games {
+        let total = totals.entry(key).or_default();
+        total.0 += 1;
+        total.1 += game.won as usize;
+        total.2 += game.kills + game.assists;
+        total.3 += game.deaths;
+    }
+
+    totals
+        .into_iter()
+        .map(|(key, (games, wins, takedowns, deaths))| PerformanceBucket {
+            key,
+            games,
+            wins,
+            win_rate: wins as f64 / games as f64,
+            kda: takedowns as f64 / deaths.max(1) as f64,
+        })
+        .collect()
+}
+
+/// A ranked split and its boundaries
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RankedSplit {
+    pub season_id: i32,
+    pub split_id: i32,
+    pub start: Option<DateTime<Utc>>,
+    /// `None` while the end date isn't announced
+    pub end: Option<DateTime<Utc>>,
+}
+
+impl RankedSplit {
+    /// Whether `at` falls within the split (open-ended bounds always match)
+    pub fn contains(&self, at: DateTime<Utc>) -> bool {
+        self.start.filter(|&start| at < start).is_none() && self.end.filter(|&end| at >= end).is_none()
+    }
+
+    fn same_split(&self, other: &RankedSplit) -> bool {
+        self.season_id == other.season_id && self.split_id == other.split_id
+    }
+}
+
+/// Time spent in game on one calendar day (local time)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyPlaytime {
+    pub date: NaiveDate,
+    pub played_secs: u64,
+    /// Sessions that were (at least partly) played on the day
+    pub sessions: u32,
+}
+
+/// Playtime today and on recent days, for "you played 4h12m today" summaries
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaytimeSummary {
+    pub today_secs: u64,
+    /// Soft daily limit from the settings, if any
+    pub daily_limit_secs: Option<u64>,
+    pub over_limit: bool,
+    /// Days with playtime, oldest first
+    pub days: Vec<DailyPlaytime>,
+}
+
+/// The player's own entry in the participant list (by name, then champion)
+fn local_participant(game: &CreateMatch) -> Option<&Participant> {
+    game.participants
+        .iter()
+        .find(|p| riot_ids_match(&p.summoner_name, &game.summoner_name))
+        .or_else(|| game.participants.iter().find(|p| p.champion == game.champion))
+}
+
+/// Statistics persisted across sessions
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PackStats {
+    /// Most recent queue times, oldest first
+    pub queue_times: Vec<QueueTimeRecord>,
+    /// Most recent dodges and remakes, oldest first
+    pub non_games: Vec<NonGameRecord>,
+    /// Most recent Arena games, oldest first
+    pub arena_match_details: Vec<ArenaMatchDetails>,
+    /// Finished games per champion
+    pub champion_games: BTreeMap<String, u32>,
+    /// Best kills, KDA, CS/min and shortest win so far
+    pub personal_bests: PersonalBests,
+    /// Head-to-head results against opponents
+    pub rivalries: Rivalries,
+    /// Most recent games played with a premade partner, oldest first
+    pub duo_games: Vec<DuoGameRecord>,
+    /// Most recent finished games, oldest first
+    pub game_results: Vec<GameResultRecord>,
+    /// Ranked splits reported by the client, oldest first
+    pub ranked_splits: Vec<RankedSplit>,
+    /// In-game time per local calendar day, oldest first
+    pub daily_playtime: Vec<DailyPlaytime>,
+    /// Times the enemy banned the champion the player was hovering, per champion
+    pub hover_bans: BTreeMap<String, u32>,
+}
+
+impl PackStats {
+    /// Average, min and max queue time per queue, sorted by queue ID
+    pub fn queue_time_summary(&self) -> Vec<QueueTimeSummary> {
+        let mut summaries: Vec<QueueTimeSummary> = Vec::new();
+
+        for record in &self.queue_times {
+            match summaries.iter_mut().find(|s| s.queue_id == record.queue_id) {
+                Some(summary) => {
+                    summary.average_secs += record.wait_secs;
+                    summary.min_secs = summary.min_secs.min(record.wait_secs);
+                    summary.max_secs = summary.max_secs.max(record.wait_secs);
+                    summary.count += 1;
+                }
+                None => summaries.push(QueueTimeSummary {
+                    queue_id: record.queue_id,
+                    queue_name: record.queue_name.clone(),
+                    count: 1,
+                    average_secs: record.wait_secs,
+                    min_secs: record.wait_secs,
+                    max_secs: record.wait_secs,
+                }),
+            }
+        }
+
+        // `average_secs` holds the running total until here
+        for summary in &mut summaries {
+            summary.average_secs /= summary.count as f64;
+        }
+        summaries.sort_by_key(|s| s.queue_id);
+        summaries
+    }
+
+    /// Winrate and combined KDA per recurring duo partner, most games first
+    pub fn duo_summary(&self) -> Vec<DuoSummary> {
+        // Per partner (by collation key, named as last seen): games, wins,
+        // combined kills + assists, combined deaths
+        let mut totals: BTreeMap<String, (&str, usize, usize, i32, i32)> = BTreeMap::new();
+        for record in &self.duo_games {
+            let total = totals.entry(riot_id_key(&record.partner)).or_default();
+            total.0 = record.partner.as_str();
+            total.1 += 1;
+            total.2 += record.won as usize;
+            total.3 += record.kills + record.assists + record.partner_kills + record.partner_assists;
+            total.4 += record.deaths + record.partner_deaths;
+        }
+
+        let mut summaries: Vec<DuoSummary> = totals
+            .into_values()
+            .filter(|(_, games, ..)| *games >= MIN_DUO_GAMES)
+            .map(|(partner, games, wins, takedowns, deaths)| DuoSummary {
+                partner: partner.to_string(),
+                games,
+                wins,
+                win_rate: wins as f64 / games as f64,
+                combined_kda: takedowns as f64 / deaths.max(1) as f64,
+            })
+            .collect();
+        summaries.sort_by(|a, b| b.games.cmp(&a.games));
+        summaries
+    }
+
+    /// Winrate and KDA by hour of day and by game number within a session.
+    /// A session ends after `SESSION_BREAK_SECS` without a game.
+    pub fn performance_analytics(&self) -> PerformanceAnalytics {
+        let mut games: Vec<&GameResultRecord> = self.game_results.iter().collect();
+        games.sort_by_key(|g| g.played_at);
+
+        let mut session_game = 0;
+        let mut previous_end: Option<DateTime<Utc>> = None;
+        let numbered: Vec<(u32, &GameResultRecord)> = games
+            .iter()
+            .map(|game| {
+                let new_session = previous_end.is_none_or(|end| {
+                    (game.started_at() - end).num_seconds() > SESSION_BREAK_SECS
+                });
+                session_game = if new_session { 1 } else { session_game + 1 };
+                previous_end = Some(game.played_at);
+                (session_game.min(MAX_SESSION_GAME), *game)
+            })
+            .collect();
+
+        // Keyed by position in `sides` so blue sorts first
+        let sides = [Team::Blue, Team::Red];
+        let by_side = performance_buckets(games.iter().filter_map(|g| {
+            let side = sides.iter().position(|&s| Some(s) == g.side)?;
+            Some((side as u32, *g))
+        }))
+        .into_iter()
+        .map(|bucket| SidePerformance {
+            side: sides[bucket.key as usize],
+            games: bucket.games,
+            wins: bucket.wins,
+            win_rate: bucket.win_rate,
+            kda: bucket.kda,
+        })
+        .collect();
+
+        let taken: u32 = games.iter().map(|g| g.objectives_taken).sum();
+        let participated: u32 = games.iter().map(|g| g.objectives_participated).sum();
+
+        PerformanceAnalytics {
+            by_hour: performance_buckets(games.iter().map(|g| (g.local_hour, *g))),
+            by_session_game: performance_buckets(numbered.into_iter()),
+            by_side,
+            objective_participation: (taken > 0)
+                .then(|| participated as f64 / taken as f64 * 100.0),
+        }
+    }
+
+    /// Estimated wait in a queue entered at `local_hour`, `elapsed_secs`
+    /// ago. Recent waits from within an hour of that time of day are used
+    /// when there are enough of them, else the queue's most recent waits.
+    /// `None` without any recorded wait in the queue.
+    pub fn queue_estimate(
+        &self,
+        queue_id: i32,
+        local_hour: u32,
+        elapsed_secs: f64,
+    ) -> Option<QueueEstimate> {
+        let near = |hour: Option<u32>| hour.is_some_and(|h| hours_apart(h, local_hour) <= 1);
+        let waits = |same_hour: bool| -> Vec<f64> {
+            self.queue_times
+                .iter()
+                .rev()
+                .filter(|r| r.queue_id == queue_id && (!same_hour || near(r.local_hour)))
+                .take(QUEUE_ESTIMATE_SAMPLES)
+                .map(|r| r.wait_secs)
+                .collect()
+        };
+
+        let same_hour = waits(true);
+        let same_time_of_day = same_hour.len() >= QUEUE_ESTIMATE_MIN_HOUR_SAMPLES;
+        let mut waits = if same_time_of_day { same_hour } else { waits(false) };
+        if waits.is_empty() {
+            return None;
+        }
+
+        waits.sort_by(f64::total_cmp);
+        let mid = waits.len() / 2;
+        let estimated_secs = if waits.len() % 2 == 0 {
+            (waits[mid - 1] + waits[mid]) / 2.0
+        } else {
+            waits[mid]
+        };
+        Some(QueueEstimate {
+            queue_id,
+            estimated_secs,
+            samples: waits.len(),
+            same_time_of_day,
+            elapsed_secs,
+        })
+    }
+
+    /// Dodge and remake counts across all recorded queues
+    pub fn non_game_summary(&self) -> NonGameSummary {
+        let mut summary = NonGameSummary::default();
+        for record in &self.non_games {
+            match record.kind {
+                NonGameKind::SelfDodge => summary.self_dodges += 1,
+                NonGameKind::OtherDodge => summary.other_dodges += 1,
+                NonGameKind::Dodge => summary.unknown_dodges += 1,
+                NonGameKind::Remake => summary.remakes += 1,
+            }
+            summary.total_lost_secs += record.lost_secs;
+        }
+        summary
+    }
+
+    /// Add or update splits from the client; `true` if anything changed
+    pub fn update_ranked_splits(&mut self, splits: Vec<RankedSplit>) -> bool {
+        let mut changed = false;
+        for split in splits {
+            match self.ranked_splits.iter_mut().find(|s| s.same_split(&split)) {
+                Some(known) if *known == split => {}
+                Some(known) => {
+                    *known = split;
+                    changed = true;
+                }
+                None => {
+                    self.ranked_splits.push(split);
+                    changed = true;
+                }
+            }
+        }
+        if changed {
+            self.ranked_splits.sort_by_key(|s| (s.season_id, s.split_id));
+        }
+        changed
+    }
+
+    /// The split `at` falls in, if known
+    pub fn ranked_split_at(&self, at: DateTime<Utc>) -> Option<&RankedSplit> {
+        self.ranked_splits.iter().rev().find(|s| s.contains(at))
+    }
+
+    /// Add a session played from `start` to `end` (local times), split at
+    /// midnight when it spans several days
+    pub fn add_playtime(&mut self, start: NaiveDateTime, end: NaiveDateTime) {
+        let mut from = start;
+        while from < end {
+            let date = from.date();
+            let midnight = date
+                .succ_opt()
+                .map_or(end, |next| next.and_time(NaiveTime::MIN))
+                .min(end);
+            let secs = (midnight - from).num_seconds().max(0) as u64;
+
+            match self.daily_playtime.iter_mut().find(|d| d.date == date) {
+                Some(day) => {
+                    day.played_secs += secs;
+                    day.sessions += 1;
+                }
+                None => {
+                    self.daily_playtime.push(DailyPlaytime {
+                        date,
+                        played_secs: secs,
+                        sessions: 1,
+                    });
+                    self.daily_playtime.sort_by_key(|d| d.date);
+                }
+            }
+            from = midnight;
+        }
+    }
+
+    /// Seconds played on a day
+    pub fn playtime_on(&self, date: NaiveDate) -> u64 {
+        self.daily_playtime
+            .iter()
+            .find(|d| d.date == date)
+            .map_or(0, |d| d.played_secs)
+    }
+
+    /// Playtime on `today` and the `days - 1` days before it
+    pub fn playtime_summary(
+        &self,
+        today: NaiveDate,
+        days: u32,
+        daily_limit_secs: Option<u64>,
+    ) -> PlaytimeSummary {
+        let first = today - chrono::Duration::days(days.saturating_sub(1) as i64);
+        let today_secs = self.playtime_on(today);
+        PlaytimeSummary {
+            today_secs,
+            daily_limit_secs,
+            over_limit: daily_limit_secs.is_some_and(|limit| today_secs >= limit),
+            days: self
+                .daily_playtime
+                .iter()
+                .filter(|d| d.date >= first && d.date <= today)
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(queue_id: i32, wait_secs: f64) -> QueueTimeRecord {
+        QueueTimeRecord {
+            queue_id,
+            queue_name: format!("Queue {}", queue_id),
+            started_at: Utc::now(),
+            wait_secs,
+            local_hour: None,
+        }
+    }
+
+    #[test]
+    fn test_queue_time_summary() {
+        let stats = PackStats {
+            queue_times: vec![record(420, 60.0), record(450, 30.0), record(420, 120.0)],
+            ..Default::default()
+        };
+
+        let summary = stats.queue_time_summary();
+        assert_eq!(summary.len(), 2);
+        assert_eq!(summary[0].queue_id, 420);
+        assert_eq!(summary[0].count, 2);
+        assert_eq!(summary[0].average_secs, 90.0);
+        assert_eq!(summary[0].min_secs, 60.0);
+        assert_eq!(summary[0].max_secs, 120.0);
+        assert_eq!(summary[1].average_secs, 30.0);
+    }
+
+    #[test]
+    fn test_queue_estimate_prefers_same_time_of_day() {
+        let at_hour = |wait_secs: f64, hour: u32| QueueTimeRecord {
+            local_hour: Some(hour),
+            ..record(420, wait_secs)
+        };
+        let stats = PackStats {
+            queue_times: vec![
+                record(420, 60.0),
+                at_hour(300.0, 23),
+                at_hour(400.0, 1),
+                at_hour(90.0, 12),
+                at_hour(500.0, 0),
+                record(450, 30.0),
+            ],
+            ..Default::default()
+        };
+
+        let late = stats.queue_estimate(420, 0, 45.0).unwrap();
+        assert!(late.same_time_of_day);
+        assert_eq!((late.samples, late.estimated_secs), (3, 400.0));
+        assert_eq!(late.elapsed_secs, 45.0);
+
+        // Too few waits around noon: all of the queue's waits
+        let noon = stats.queue_estimate(420, 12, 0.0).unwrap();
+        assert!(!noon.same_time_of_day);
+        assert_eq!((noon.samples, noon.estimated_secs), (5, 300.0));
+
+        assert!(stats.queue_estimate(440, 0, 0.0).is_none());
+    }
+
+    #[test]
+    fn test_non_game_summary() {
+        let non_game = |kind, lost_secs| NonGameRecord {
+            kind,
+            queue_id: 420,
+            occurred_at: Utc::now(),
+            lost_secs,
+        };
+        let stats = PackStats {
+            non_games: vec![
+                non_game(NonGameKind::OtherDodge, 45.0),
+                non_game(NonGameKind::SelfDodge, 80.0),
+                non_game(NonGameKind::OtherDodge, 20.0),
+                non_game(NonGameKind::Remake, 200.0),
+            ],
+            ..Default::default()
+        };
+
+        let summary = stats.non_game_summary();
+        assert_eq!(summary.self_dodges, 1);
+        assert_eq!(summary.other_dodges, 2);
+        assert_eq!(summary.unknown_dodges, 0);
+        assert_eq!(summary.remakes, 1);
+        assert_eq!(summary.total_lost_secs, 345.0);
+    }
+
+    fn game(result: &str, kills: i32, deaths: i32, cs_per_min: f64, duration_secs: i32) -> CreateMatch {
+        serde_json::from_value(serde_json::json!({
+            "gameId": 1, "summonerName": "Me", "champion": "Ahri", "championLevel": 16,
+            "result": result, "kills": kills, "deaths": deaths, "assists": 4, "cs": 200,
+            "csPerMin": cs_per_min, "visionScore": 20, "killParticipation": 50, "damageDealt": 20000,
+            "gameMode": "CLASSIC", "playedAt": Utc::now(), "durationSecs": duration_secs,
+            "lpChange": null, "rank": null, "summonerSpell1": "Flash", "summonerSpell2": "Ignite",
+            "keystoneRune": "Electrocute", "secondaryTree": "Sorcery", "items": [], "trinket": null,
+            "participants": [], "badges": []
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_personal_bests() {
+        let mut bests = PersonalBests::default();
+
+        // The first game only sets the baseline
+        assert!(bests.update(&game("win", 5, 2, 7.0, 1800)).is_empty());
+
+        let broken = bests.update(&game("win", 9, 2, 6.0, 1500));
+        assert_eq!(
+            broken,
+            vec![PersonalRecord::Kills, PersonalRecord::Kda, PersonalRecord::ShortestWin]
+        );
+        assert_eq!(bests.most_kills, Some(9));
+
+        // Losses can't be the shortest win, remakes count for nothing
+        assert_eq!(bests.update(&game("loss", 1, 5, 8.0, 900)), vec![PersonalRecord::CsPerMin]);
+        assert!(bests.update(&game("remake", 20, 0, 10.0, 200)).is_empty());
+    }
+
+    #[test]
+    fn test_duo_summary() {
+        let mut game = game("win", 6, 2, 7.0, 1800);
+        game.participants = serde_json::from_value(serde_json::json!([
+            { "summonerName": "Me", "champion": "Ahri", "team": "blue" },
+            { "summonerName": "Duo#EUW", "champion": "Lee Sin", "team": "blue",
+              "kills": 4, "deaths": 2, "assists": 10 },
+            { "summonerName": "Enemy#EUW", "champion": "Zed", "team": "red" },
+        ]))
+        .unwrap();
+        let premades = ["Duo#EUW".to_string(), "Enemy#EUW".to_string()];
+
+        let records = DuoGameRecord::from_match(&game, &premades);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].partner_champion, "Lee Sin");
+
+        let mut stats = PackStats {
+            duo_games: records.clone(),
+            ..Default::default()
+        };
+        // A single game isn't a recurring duo yet
+        assert!(stats.duo_summary().is_empty());
+
+        game.result = MatchResult::Loss;
+        stats.duo_games.extend(DuoGameRecord::from_match(&game, &premades));
+        let summary = stats.duo_summary();
+        assert_eq!(summary.len(), 1);
+        assert_eq!((summary[0].games, summary[0].wins), (2, 1));
+        assert_eq!(summary[0].win_rate, 0.5);
+        assert_eq!(summary[0].combined_kda, 6.0);
+    }
+
+    #[test]
+    fn test_game_result_index() {
+        let start = Utc::now() - chrono::Duration::days(1);
+        let result = |hours: i64, champion: &str, game_mode: &str| GameResultRecord {
+            game_id: hours,
+            won: true,
+            kills: 4,
+            deaths: 2,
+            assists: 6,
+            duration_secs: 1800,
+            played_at: start + chrono::Duration::hours(hours),
+            local_hour: 0,
+            side: None,
+            role: None,
+            champion: champion.to_string(),
+            game_mode: game_mode.to_string(),
+            had_leaver: false,
+            objectives_taken: 0,
+            objectives_participated: 0,
+        };
+        let mut records = vec![
+            result(1, "Ahri", "CLASSIC"),
+            result(2, "Ahri", "ARAM"),
+            result(3, "Zed", "CLASSIC"),
+            result(4, "Ahri", "CLASSIC"),
+        ];
+        let mut index = GameResultIndex::build(&records);
+        let ids = |games: Vec<&GameResultRecord>| {
+            games.iter().map(|g| g.game_id).collect::<Vec<_>>()
+        };
+
+        assert_eq!(ids(index.query(&records, "ahri", None, None, None)), vec![1, 2, 4]);
+        assert_eq!(ids(index.query(&records, "Ahri", Some("classic"), None, None)), vec![1, 4]);
+        let since = Some(start + chrono::Duration::minutes(90));
+        assert_eq!(ids(index.query(&records, "Ahri", None, since, None)), vec![2, 4]);
+
+        // Dropping the oldest game doesn't shift the others
+        records.remove(0);
+        index.drop_oldest(1);
+        records.push(result(5, "Ahri", "ARAM"));
+        index.push(records.last().unwrap());
+        let until = Some(start + chrono::Duration::hours(5));
+        assert_eq!(ids(index.query(&records, "Ahri", None, None, until)), vec![2, 4]);
+    }
+
+    #[test]
+    fn test_performance_analytics() {
+        let start = Utc::now() - chrono::Duration::days(1);
+        let result = |minutes_after: i64, won: bool, local_hour: u32| GameResultRecord {
+            game_id: minutes_after,
+            won,
+            kills: 4,
+            deaths: 2,
+            assists: 6,
+            duration_secs: 1800,
+            played_at: start + chrono::Duration::minutes(minutes_after),
+            local_hour,
+            side: Some(if local_hour < 22 { Team::Red } else { Team::Blue }),
+            role: None,
+            champion: String::new(),
+            game_mode: String::new(),
+            had_leaver: false,
+            objectives_taken: 0,
+            objectives_participated: 0,
+        };
+        let stats = PackStats {
+            // Two back-to-back games, then a new session after a long break
+            game_results: vec![
+                result(35, true, 21),
+                result(70, false, 22),
+                result(400, true, 2),
+            ],
+            ..Default::default()
+        };
+
+        let analytics = stats.performance_analytics();
+        let hours: Vec<u32> = analytics.by_hour.iter().map(|b| b.key).collect();
+        assert_eq!(hours, vec![2, 21, 22]);
+        assert_eq!(analytics.by_hour[0].kda, 5.0);
+        assert_eq!(analytics.objective_participation, None);
+
+        assert_eq!(analytics.by_session_game.len(), 2);
+        assert_eq!(analytics.by_session_game[0].key, 1);
+        assert_eq!(analytics.by_session_game[0].games, 2);
+        assert_eq!(analytics.by_session_game[0].win_rate, 1.0);
+        assert_eq!(analytics.by_session_game[1].wins, 0);
+
+        assert_eq!(analytics.by_side.len(), 2);
+        assert_eq!(analytics.by_side[0].side, Team::Blue);
+        assert_eq!(analytics.by_side[0].wins, 0);
+        assert_eq!(analytics.by_side[1].games, 2);
+        assert_eq!(analytics.by_side[1].win_rate, 1.0);
+    }
+
+    #[test]
+    fn test_rivalries_head_to_head() {
+        let with_enemies = |result: &str, enemies: &[&str]| {
+            let mut game = game(result, 5, 2, 7.0, 1800);
+            let participant = |name: &str, champion: &str, team: &str| {
+                serde_json::from_value::<Participant>(serde_json::json!({
+                    "summonerName": name, "champion": champion, "team": team
+                }))
+                .unwrap()
+            };
+            game.participants.push(participant("Me", "Ahri", "blue"));
+            game.participants.push(participant("Ally#EUW", "Lux", "blue"));
+            for enemy in enemies {
+                game.participants.push(participant(enemy, "Zed", "red"));
+            }
+            game
+        };
+
+        let mut rivalries = Rivalries::default();
+        assert!(rivalries.update(&with_enemies("win", &["Zed#1", "Yone#2"])).is_empty());
+
+        // The same player, reported with different casing
+        let repeat = rivalries.update(&with_enemies("loss", &["ZED#1"]));
+        assert_eq!(repeat.len(), 1);
+        assert_eq!(repeat[0].riot_id, "ZED#1");
+        assert_eq!((repeat[0].games, repeat[0].wins, repeat[0].losses), (2, 1, 1));
+
+        // Allies are never opponents, remakes don't count
+        assert!(!rivalries.opponents.contains_key(&riot_id_key("Ally#EUW")));
+        assert!(rivalries.update(&with_enemies("remake", &["Zed#1"])).is_empty());
+
+        for _ in 0..2 {
+            rivalries.update(&with_enemies("loss", &["Zed#1"]));
+        }
+        let rivals = rivalries.repeat_opponents();
+        assert_eq!(rivals.len(), 1);
+        assert!(rivals[0].is_nemesis());
+    }
+
+    #[test]
+    fn test_ranked_splits_update_and_lookup() {
+        let at = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+        let split = |split_id: i32, start: &str, end: Option<&str>| RankedSplit {
+            season_id: 2025,
+            split_id,
+            start: Some(at(start)),
+            end: end.map(at),
+        };
+
+        let mut stats = PackStats::default();
+        assert!(stats.update_ranked_splits(vec![split(2, "2025-05-01T00:00:00Z", None)]));
+        assert!(stats.update_ranked_splits(vec![
+            split(1, "2025-01-09T00:00:00Z", Some("2025-05-01T00:00:00Z")),
+            split(2, "2025-05-01T00:00:00Z", Some("2025-08-27T00:00:00Z")),
+        ]));
+        assert!(!stats.update_ranked_splits(vec![split(
+            1,
+            "2025-01-09T00:00:00Z",
+            Some("2025-05-01T00:00:00Z")
+        )]));
+
+        assert_eq!(stats.ranked_splits.len(), 2);
+        assert_eq!(stats.ranked_splits[0].split_id, 1);
+        assert_eq!(stats.ranked_split_at(at("2025-05-01T00:00:00Z")).unwrap().split_id, 2);
+        assert_eq!(stats.ranked_split_at(at("2025-03-01T12:00:00Z")).unwrap().split_id, 1);
+        assert!(stats.ranked_split_at(at("2025-09-01T00:00:00Z")).is_none());
+    }
+
+    #[test]
+    fn test_playtime_is_split_at_midnight() {
+        let at = |s: &str| s.parse::<NaiveDateTime>().unwrap();
+        let day = |s: &str| s.parse::<NaiveDate>().unwrap();
+
+        let mut stats = PackStats::default();
+        stats.add_playtime(at("2025-03-01T20:00:00"), at("2025-03-01T20:35:00"));
+        stats.add_playtime(at("2025-03-01T23:30:00"), at("2025-03-02T00:10:00"));
+
+        assert_eq!(stats.playtime_on(day("2025-03-01")), 35 * 60 + 30 * 60);
+        assert_eq!(stats.playtime_on(day("2025-03-02")), 10 * 60);
+        assert_eq!(stats.daily_playtime[0].sessions, 2);
+
+        let summary = stats.playtime_summary(day("2025-03-02"), 1, Some(3600));
+        assert_eq!(summary.today_secs, 600);
+        assert!(!summary.over_limit);
+        assert_eq!(summary.days.len(), 1);
+        assert_eq!(stats.playtime_summary(day("2025-03-02"), 7, None).days.len(), 2);
+    }
+}